@@ -1,6 +1,6 @@
 use nimiq_block::{Block, BlockError, EquivocationProofError, ForkProof};
 use nimiq_hash::Blake2bHash;
-use nimiq_primitives::{account::AccountError, networks::NetworkId};
+use nimiq_primitives::{account::AccountError, networks::NetworkId, transaction::TransactionError};
 use nimiq_transaction::EquivocationLocator;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -64,6 +64,8 @@ pub enum PushError {
     Orphan,
     #[error("Invalid zk proof")]
     InvalidZKP,
+    #[error("Block does not match the trusted checkpoint")]
+    InvalidCheckpoint,
     #[error("Invalid block: {0}")]
     InvalidBlock(#[from] BlockError),
     #[error("Invalid successor")]
@@ -88,6 +90,21 @@ pub enum PushError {
     IncompleteAccountsTrie,
 }
 
+/// Errors that prevent a transaction from being simulated on top of the current head.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SimulationError {
+    #[error("Transaction is invalid: {0}")]
+    InvalidTransaction(#[from] TransactionError),
+    #[error("Transaction not valid at next block number")]
+    InvalidBlockNumber,
+    #[error("Transaction already included in chain")]
+    AlreadyIncluded,
+    #[error("Transaction cannot be applied: {0}")]
+    AccountsError(#[from] AccountError),
+    #[error("Accounts trie is incomplete and thus the transaction cannot be simulated.")]
+    IncompleteAccountsTrie,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
 #[repr(u8)]
 pub enum Direction {
@@ -0,0 +1,100 @@
+use nimiq_database::{
+    traits::{Database, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableProxy, TransactionProxy,
+};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::trie::{error::MerkleRadixTrieError, trie_chunk::TrieChunkPushResult};
+use nimiq_trie::{trie::MerkleRadixTrie, WriteTransactionProxy};
+
+/// A read-only copy of the accounts trie at the latest macro block.
+///
+/// Unlike the accounts trie, a complete snapshot only changes when a macro block is pushed. Peers that
+/// sync the state from the snapshot therefore sync against a fixed root for a whole batch, instead
+/// of chasing the head of the chain.
+///
+/// The snapshot can be incomplete, either while it is being built from chunks of our peers or
+/// while it is rebuilt from the accounts trie, after it couldn't be advanced to the latest macro
+/// block. An incomplete snapshot isn't served to our peers.
+#[derive(Debug)]
+pub struct AccountsSnapshot {
+    /// The trie holding the snapshot.
+    pub tree: MerkleRadixTrie,
+    /// A database containing the hash of the block the snapshot belongs to. This is the latest
+    /// macro block, unless the snapshot is being rebuilt.
+    meta_table: TableProxy,
+}
+
+impl AccountsSnapshot {
+    const TRIE_DB_NAME: &'static str = "AccountsSnapshot";
+    const META_DB_NAME: &'static str = "AccountsSnapshotMeta";
+
+    const BLOCK_KEY: &'static str = "block";
+
+    /// The maximum number of items copied at once when a trie is copied.
+    const COPY_CHUNK_SIZE: usize = 1000;
+
+    pub fn new(db: DatabaseProxy) -> Self {
+        let tree = MerkleRadixTrie::new_incomplete(db.clone(), Self::TRIE_DB_NAME);
+        let meta_table = db.open_table(Self::META_DB_NAME.to_string());
+        AccountsSnapshot { tree, meta_table }
+    }
+
+    /// Returns the hash of the block the snapshot belongs to, if any.
+    pub fn block_hash(&self, txn: &TransactionProxy) -> Option<Blake2bHash> {
+        txn.get(&self.meta_table, AccountsSnapshot::BLOCK_KEY)
+    }
+
+    /// Moves the snapshot to the given block. The trie must already be at the state of
+    /// the block.
+    pub fn set_block_hash(&self, txn: &mut WriteTransactionProxy, hash: &Blake2bHash) {
+        txn.raw()
+            .put(&self.meta_table, AccountsSnapshot::BLOCK_KEY, hash);
+    }
+
+    /// Returns whether the snapshot contains all accounts.
+    pub fn is_complete(&self, txn: &TransactionProxy) -> bool {
+        self.tree.is_complete(txn)
+    }
+
+    /// Clears the snapshot and starts a new, empty one for the given block.
+    pub fn reset(&self, txn: &mut WriteTransactionProxy, hash: &Blake2bHash) {
+        self.tree.reinitialize_as_incomplete(txn);
+        self.set_block_hash(txn, hash);
+    }
+
+    /// Replaces the contents of `target` with the contents of `source`. Both tries must be in
+    /// the same database. `source` must be complete and its root hash must be `expected_hash`.
+    ///
+    /// `target` is reinitialized first, so any previous contents, complete or not, are discarded.
+    pub(crate) fn copy_trie(
+        txn: &mut WriteTransactionProxy,
+        source: &MerkleRadixTrie,
+        target: &MerkleRadixTrie,
+        expected_hash: &Blake2bHash,
+    ) -> Result<(), MerkleRadixTrieError> {
+        target.reinitialize_as_incomplete(txn);
+        while !Self::copy_chunk(txn, source, target, expected_hash)? {}
+        Ok(())
+    }
+
+    /// Copies the next chunk of `source` into the missing range of the incomplete `target`. Both
+    /// tries must be in the same database. `source` must be complete and its root hash must be
+    /// `expected_hash`, while the complete part of `target` must be at the same state.
+    /// Returns whether `target` is complete afterwards.
+    pub(crate) fn copy_chunk(
+        txn: &mut WriteTransactionProxy,
+        source: &MerkleRadixTrie,
+        target: &MerkleRadixTrie,
+        expected_hash: &Blake2bHash,
+    ) -> Result<bool, MerkleRadixTrieError> {
+        let start_key = target
+            .get_missing_range(txn)
+            .ok_or(MerkleRadixTrieError::TrieAlreadyComplete)?
+            .start;
+        let chunk = source.get_chunk_with_proof(txn, start_key.clone().., Self::COPY_CHUNK_SIZE);
+        match target.put_chunk(txn, start_key, chunk, expected_hash.clone())? {
+            TrieChunkPushResult::Applied => Ok(target.is_complete(txn)),
+            TrieChunkPushResult::Ignored => Err(MerkleRadixTrieError::NonMatchingChunk),
+        }
+    }
+}
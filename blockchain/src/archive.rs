@@ -0,0 +1,339 @@
+//! A compact file format to move chain data between nodes without using the network.
+//!
+//! An archive starts with a magic number and a format version, followed by a sequence of records.
+//! Each record is the length of its payload (a big-endian `u32`), the serialized payload and the
+//! Blake2b hash of the payload as checksum. The first record is the [`ArchiveHeader`], all
+//! following records are [`ArchiveItem`]s, the last of which is always [`ArchiveItem::End`].
+
+use std::io::{self, Read, Write};
+
+use nimiq_block::Block;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, PushError, PushResult};
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use nimiq_transaction::historic_transaction::HistoricTransaction;
+use parking_lot::RwLock;
+use thiserror::Error;
+
+use crate::{interface::HistoryInterface, Blockchain};
+
+/// The magic number chain archives start with.
+const MAGIC: [u8; 8] = *b"NIMQARCH";
+/// The version of the archive format.
+const VERSION: u8 = 1;
+/// The maximum size of a single record. Protects against huge allocations for corrupted archives.
+const MAX_RECORD_SIZE: u32 = 256 * 1024 * 1024;
+
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Not a chain archive")]
+    InvalidMagic,
+    #[error("Unsupported archive version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Archive is for network {archive}, but the blockchain is on network {blockchain}")]
+    NetworkMismatch {
+        archive: NetworkId,
+        blockchain: NetworkId,
+    },
+    #[error("Record {0} exceeds the maximum record size")]
+    RecordTooLarge(u64),
+    #[error("Checksum mismatch in record {0}")]
+    ChecksumMismatch(u64),
+    #[error("Malformed record {0}: {1}")]
+    Malformed(u64, DeserializeError),
+    #[error("Archive is truncated")]
+    Truncated,
+    #[error("Invalid block range: {0}..={1}")]
+    InvalidRange(u32, u32),
+    #[error("Failed to read block {0}: {1}")]
+    Blockchain(u32, BlockchainError),
+    #[error("Failed to push block {0}: {1}")]
+    Push(u32, PushError),
+}
+
+/// Describes the contents of an archive.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveHeader {
+    /// The network the archived blocks belong to.
+    pub network_id: NetworkId,
+    /// The first block number of the archived range.
+    pub first_block: u32,
+    /// The last block number of the archived range.
+    pub last_block: u32,
+    /// Whether the archive only contains the macro blocks of the range, each preceded by the
+    /// history of its batch. Otherwise, it contains all blocks including their bodies.
+    pub macro_blocks_only: bool,
+}
+
+/// An item stored in an archive.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ArchiveItem {
+    /// The historic transactions of the batch that ends with the next macro block.
+    History(Vec<HistoricTransaction>),
+    /// A block including its body.
+    Block(Block),
+    /// Marks the end of the archive. Contains the number of items before it.
+    End(u64),
+}
+
+/// Writes an archive to the underlying writer.
+pub struct ArchiveWriter<W> {
+    writer: W,
+    num_records: u64,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    pub fn new(mut writer: W, header: &ArchiveHeader) -> Result<Self, ArchiveError> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION])?;
+
+        let mut archive_writer = Self {
+            writer,
+            num_records: 0,
+        };
+        archive_writer.write_record(header)?;
+        Ok(archive_writer)
+    }
+
+    pub fn write_item(&mut self, item: &ArchiveItem) -> Result<(), ArchiveError> {
+        self.write_record(item)
+    }
+
+    /// Writes the end marker and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, ArchiveError> {
+        // The header is not counted as an item.
+        self.write_record(&ArchiveItem::End(self.num_records - 1))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_record<T: Serialize>(&mut self, value: &T) -> Result<(), ArchiveError> {
+        let payload = value.serialize_to_vec();
+        let len = u32::try_from(payload.len())
+            .ok()
+            .filter(|len| *len <= MAX_RECORD_SIZE)
+            .ok_or(ArchiveError::RecordTooLarge(self.num_records))?;
+        let checksum: Blake2bHash = Blake2bHasher::default().digest(&payload);
+
+        self.writer.write_all(&len.to_be_bytes())?;
+        self.writer.write_all(&payload)?;
+        self.writer.write_all(checksum.as_bytes())?;
+        self.num_records += 1;
+        Ok(())
+    }
+}
+
+/// Reads an archive from the underlying reader. Iterating over the reader yields the items of the
+/// archive, verifying their checksums. The end marker is checked, but not returned.
+pub struct ArchiveReader<R> {
+    reader: R,
+    header: ArchiveHeader,
+    num_records: u64,
+    finished: bool,
+}
+
+impl<R: Read> ArchiveReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, ArchiveError> {
+        let mut magic = [0u8; MAGIC.len() + 1];
+        reader.read_exact(&mut magic).map_err(truncated)?;
+        if magic[..MAGIC.len()] != MAGIC {
+            return Err(ArchiveError::InvalidMagic);
+        }
+        if magic[MAGIC.len()] != VERSION {
+            return Err(ArchiveError::UnsupportedVersion(magic[MAGIC.len()]));
+        }
+
+        let header = Self::read_record(&mut reader, 0)?;
+        Ok(Self {
+            reader,
+            header,
+            num_records: 1,
+            finished: false,
+        })
+    }
+
+    pub fn header(&self) -> &ArchiveHeader {
+        &self.header
+    }
+
+    fn read_record<T: Deserialize>(reader: &mut R, record: u64) -> Result<T, ArchiveError> {
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len).map_err(truncated)?;
+        let len = u32::from_be_bytes(len);
+        if len > MAX_RECORD_SIZE {
+            return Err(ArchiveError::RecordTooLarge(record));
+        }
+
+        let mut payload = vec![0u8; len as usize];
+        reader.read_exact(&mut payload).map_err(truncated)?;
+        let mut checksum = [0u8; Blake2bHash::SIZE];
+        reader.read_exact(&mut checksum).map_err(truncated)?;
+        if Blake2bHasher::default().digest(&payload) != Blake2bHash::from(checksum) {
+            return Err(ArchiveError::ChecksumMismatch(record));
+        }
+
+        T::deserialize_from_vec(&payload).map_err(|e| ArchiveError::Malformed(record, e))
+    }
+}
+
+impl<R: Read> Iterator for ArchiveReader<R> {
+    type Item = Result<ArchiveItem, ArchiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let record = Self::read_record(&mut self.reader, self.num_records);
+        self.num_records += 1;
+
+        let result = match record {
+            Ok(ArchiveItem::End(num_items)) => {
+                self.finished = true;
+                // The header and the end marker are not counted as items.
+                if num_items != self.num_records - 2 {
+                    return Some(Err(ArchiveError::Truncated));
+                }
+                return None;
+            }
+            Ok(item) => Ok(item),
+            Err(e) => {
+                self.finished = true;
+                Err(e)
+            }
+        };
+        Some(result)
+    }
+}
+
+fn truncated(error: io::Error) -> ArchiveError {
+    if error.kind() == io::ErrorKind::UnexpectedEof {
+        ArchiveError::Truncated
+    } else {
+        ArchiveError::Io(error)
+    }
+}
+
+/// The outcome of importing an archive.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArchiveImport {
+    /// The number of blocks that were applied to the chain.
+    pub num_applied: u64,
+    /// The number of blocks that were already known or not part of the main chain.
+    pub num_ignored: u64,
+}
+
+impl Blockchain {
+    /// Writes the blocks in the given range to an archive. If `macro_blocks_only` is set, only the
+    /// macro blocks are written, each preceded by the history of its batch. Such archives can be
+    /// imported by history nodes. Otherwise, all blocks are written including their bodies.
+    /// Returns the number of blocks written.
+    pub fn export_archive<W: Write>(
+        &self,
+        writer: W,
+        first_block: u32,
+        last_block: u32,
+        macro_blocks_only: bool,
+    ) -> Result<u64, ArchiveError> {
+        if first_block > last_block || last_block > self.block_number() {
+            return Err(ArchiveError::InvalidRange(first_block, last_block));
+        }
+
+        let header = ArchiveHeader {
+            network_id: self.network_id,
+            first_block,
+            last_block,
+            macro_blocks_only,
+        };
+        let mut writer = ArchiveWriter::new(writer, &header)?;
+        let mut num_blocks = 0;
+
+        for block_number in first_block..=last_block {
+            if macro_blocks_only {
+                if !Policy::is_macro_block_at(block_number) {
+                    continue;
+                }
+
+                let batch = Policy::batch_at(block_number);
+                let first_block_of_batch = Policy::first_block_of_batch(batch)
+                    .ok_or(ArchiveError::InvalidRange(first_block, last_block))?;
+                let history = (first_block_of_batch..=block_number)
+                    .flat_map(|block_number| {
+                        self.history_store
+                            .get_block_transactions(block_number, None)
+                    })
+                    .collect();
+                writer.write_item(&ArchiveItem::History(history))?;
+            }
+
+            let block = self
+                .get_block_at(block_number, true, None)
+                .map_err(|e| ArchiveError::Blockchain(block_number, e))?;
+            writer.write_item(&ArchiveItem::Block(block))?;
+            num_blocks += 1;
+        }
+
+        writer.finish()?;
+        Ok(num_blocks)
+    }
+
+    /// Imports an archive, fully verifying all blocks. Blocks that directly succeed the head are
+    /// pushed with [`Blockchain::push`]. Macro blocks that are preceded by the history of their
+    /// batch and don't directly succeed the head are pushed with [`Blockchain::push_history_sync`].
+    pub fn import_archive<R: Read>(
+        blockchain: &RwLock<Self>,
+        reader: R,
+    ) -> Result<ArchiveImport, ArchiveError> {
+        let reader = ArchiveReader::new(reader)?;
+
+        let network_id = blockchain.read().network_id;
+        if reader.header().network_id != network_id {
+            return Err(ArchiveError::NetworkMismatch {
+                archive: reader.header().network_id,
+                blockchain: network_id,
+            });
+        }
+
+        let mut import = ArchiveImport::default();
+        let mut batch_history = None;
+
+        for item in reader {
+            let block = match item? {
+                ArchiveItem::History(history) => {
+                    batch_history = Some(history);
+                    continue;
+                }
+                ArchiveItem::Block(block) => block,
+                ArchiveItem::End(_) => unreachable!("The end marker is consumed by the reader"),
+            };
+            let block_number = block.block_number();
+            let history = batch_history.take();
+
+            let this = blockchain.upgradable_read();
+            let result = match history {
+                Some(history) if block.is_macro() && block_number != this.block_number() + 1 => {
+                    // The history sync expects the history of the whole epoch up to the block.
+                    let mut epoch_history = this
+                        .history_store
+                        .get_final_epoch_transactions(block.epoch_number(), None);
+                    epoch_history.extend(history);
+                    Blockchain::push_history_sync(this, block, &epoch_history)
+                }
+                _ => Blockchain::push(this, block),
+            };
+
+            match result {
+                Ok(PushResult::Extended | PushResult::Rebranched) => import.num_applied += 1,
+                Ok(PushResult::Known | PushResult::Forked | PushResult::Ignored) => {
+                    import.num_ignored += 1
+                }
+                Err(e) => return Err(ArchiveError::Push(block_number, e)),
+            }
+        }
+
+        Ok(import)
+    }
+}
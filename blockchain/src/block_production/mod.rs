@@ -1,3 +1,5 @@
+use std::convert::Infallible;
+
 use nimiq_account::BlockState;
 use nimiq_block::{
     EquivocationProof, MacroBlock, MacroBody, MacroHeader, MicroBlock, MicroBody, MicroHeader,
@@ -7,11 +9,12 @@ use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_bls::KeyPair as BlsKeyPair;
 use nimiq_database::{traits::WriteTransaction, TransactionProxy as DBTransaction};
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
-use nimiq_keys::KeyPair as SchnorrKeyPair;
+use nimiq_keys::{Ed25519Signature, KeyPair as SchnorrKeyPair};
 use nimiq_primitives::policy::Policy;
 use nimiq_transaction::{
     historic_transaction::HistoricTransaction, inherent::Inherent, Transaction,
 };
+use nimiq_vrf::VrfSeed;
 use rand::{CryptoRng, Rng, RngCore};
 
 use crate::{interface::HistoryInterface, Blockchain};
@@ -82,6 +85,43 @@ impl BlockProducer {
         // The rng seed. We need this parameterized in order to have determinism when running unit tests.
         rng: &mut R,
     ) -> MicroBlock {
+        let result: Result<_, Infallible> = Self::next_micro_block_with_signer(
+            blockchain,
+            timestamp,
+            equivocation_proofs,
+            transactions,
+            extra_data,
+            skip_block_proof,
+            |prev_seed| Ok(prev_seed.sign_next_with_rng(&self.signing_key, rng)),
+            |header| {
+                Ok(self
+                    .signing_key
+                    .sign(header.hash::<Blake2bHash>().as_slice()))
+            },
+        );
+        result.unwrap_or_else(|never| match never {})
+    }
+
+    /// Creates the next micro block without requiring the signing key to be available locally.
+    /// The VRF seed and the block header are signed by the given closures instead, which can fail.
+    pub fn next_micro_block_with_signer<E>(
+        // The (upgradable) read locked guard to the blockchain.
+        blockchain: &Blockchain,
+        // The timestamp for the block.
+        timestamp: u64,
+        // Proofs of any misbehavior by malicious validators.
+        equivocation_proofs: Vec<EquivocationProof>,
+        // The transactions to be included in the block body.
+        transactions: Vec<Transaction>,
+        // Extra data for this block.
+        extra_data: Vec<u8>,
+        // Skip block proof.
+        skip_block_proof: Option<SkipBlockProof>,
+        // Signs the seed of the previous block, producing the seed of the new block.
+        sign_seed: impl FnOnce(&VrfSeed) -> Result<VrfSeed, E>,
+        // Signs the header of the new block.
+        sign_header: impl FnOnce(&MicroHeader) -> Result<Ed25519Signature, E>,
+    ) -> Result<MicroBlock, E> {
         // The network ID stays unchanged for the whole blockchain.
         let network = blockchain.head().network();
 
@@ -113,7 +153,7 @@ impl BlockProducer {
             // leader.
             prev_seed
         } else {
-            prev_seed.sign_next_with_rng(&self.signing_key, rng)
+            sign_seed(&prev_seed)?
         };
 
         // Create the inherents from the equivocation proofs or skip block info.
@@ -183,17 +223,15 @@ impl BlockProducer {
             MicroJustification::Skip(skip_block_proof)
         } else {
             // Signs the block header using the signing key.
-            let hash = header.hash::<Blake2bHash>();
-            let signature = self.signing_key.sign(hash.as_slice());
-            MicroJustification::Micro(signature)
+            MicroJustification::Micro(sign_header(&header)?)
         };
 
         // Returns the micro block.
-        MicroBlock {
+        Ok(MicroBlock {
             header,
             body: Some(body),
             justification: Some(justification),
-        }
+        })
     }
 
     /// Creates a proposal for the next macro block (checkpoint or election). It is just a proposal,
@@ -237,6 +275,31 @@ impl BlockProducer {
         // The rng seed. We need this parameterized in order to have determinism when running unit tests.
         rng: &mut R,
     ) -> MacroBlock {
+        let result: Result<_, Infallible> = Self::next_macro_block_proposal_with_signer(
+            blockchain,
+            timestamp,
+            round,
+            extra_data,
+            |prev_seed| Ok(prev_seed.sign_next_with_rng(&self.signing_key, rng)),
+        );
+        result.unwrap_or_else(|never| match never {})
+    }
+
+    /// Creates a proposal for the next macro block without requiring the signing key to be
+    /// available locally. The VRF seed is signed by the given closure instead, which can fail.
+    // Note: Needs to be called with the Blockchain lock held.
+    pub fn next_macro_block_proposal_with_signer<E>(
+        // The (upgradable) read locked guard to the blockchain.
+        blockchain: &Blockchain,
+        // The timestamp for the block proposal.
+        timestamp: u64,
+        // The round for the block proposal.
+        round: u32,
+        // Extra data for this block.
+        extra_data: Vec<u8>,
+        // Signs the seed of the previous block, producing the seed of the new block.
+        sign_seed: impl FnOnce(&VrfSeed) -> Result<VrfSeed, E>,
+    ) -> Result<MacroBlock, E> {
         // The network ID stays unchanged for the whole blockchain.
         let network = blockchain.head().network();
 
@@ -261,10 +324,7 @@ impl BlockProducer {
 
         // Calculate the seed for this block by signing the previous block seed with the validator
         // key.
-        let seed = blockchain
-            .head()
-            .seed()
-            .sign_next_with_rng(&self.signing_key, rng);
+        let seed = sign_seed(blockchain.head().seed())?;
 
         // Create the header for the macro block without the state root and the transactions root.
         // We need several fields of this header in order to calculate the transactions and the
@@ -332,7 +392,7 @@ impl BlockProducer {
             .0;
 
         txn.abort();
-        macro_block
+        Ok(macro_block)
     }
 
     pub fn next_macro_body(
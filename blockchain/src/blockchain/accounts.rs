@@ -36,7 +36,8 @@ impl Blockchain {
     ) -> Result<u64, PushError> {
         // Get the accounts from the state.
         let accounts = &self.state.accounts;
-        let block_state = BlockState::new(block.block_number(), block.timestamp());
+        let block_state =
+            BlockState::with_version(block.block_number(), block.timestamp(), block.version());
 
         // Check the type of the block.
         match block {
@@ -186,7 +187,11 @@ impl Blockchain {
             .expect("Failed to revert - missing revert info");
 
         // Revert the block from AccountsTree.
-        let block_state = BlockState::new(block.block_number(), block.header.timestamp);
+        let block_state = BlockState::with_version(
+            block.block_number(),
+            block.header.timestamp,
+            block.header.version,
+        );
         let result = accounts.revert(
             txn,
             &body.get_raw_transactions(),
@@ -215,6 +220,22 @@ impl Blockchain {
         self.state.accounts.get_proof(Some(&txn), keys)
     }
 
+    /// Gets the account at the given address together with a proof against the state root of
+    /// the head.
+    pub fn get_account_proof(
+        &self,
+        address: &Address,
+    ) -> Result<(Account, TrieProof), IncompleteTrie> {
+        let txn = self.env.read_transaction();
+
+        let account = self.state.accounts.get(address, Some(&txn))?;
+        let proof = self
+            .state
+            .accounts
+            .get_proof(Some(&txn), vec![&KeyNibbles::from(address)])?;
+        Ok((account, proof))
+    }
+
     /// Gets an accounts chunk given a start key and a limit
     pub fn get_accounts_chunk(
         &self,
@@ -18,9 +18,15 @@ use tokio::sync::broadcast::{channel as broadcast, Sender as BroadcastSender};
 #[cfg(feature = "metrics")]
 use crate::chain_metrics::BlockchainMetrics;
 use crate::{
-    blockchain_state::BlockchainState, chain_store::ChainStore, history::HistoryStore,
-    history_store_proxy::HistoryStoreProxy, interface::HistoryInterface,
-    reward::genesis_parameters, HistoryStoreIndex,
+    accounts_snapshot::AccountsSnapshot,
+    blockchain_state::BlockchainState,
+    chain_store::ChainStore,
+    history::HistoryStore,
+    history_store_proxy::HistoryStoreProxy,
+    interface::HistoryInterface,
+    rebranch_log::{RebranchLog, RebranchRecord},
+    reward::genesis_parameters,
+    HistoryRetention, HistoryStoreIndex,
 };
 
 const BROADCAST_MAX_CAPACITY: usize = 256;
@@ -42,12 +48,18 @@ pub struct Blockchain {
     pub fork_notifier: BroadcastSender<ForkEvent>,
     /// The log notifier processes all events regarding accounts changes.
     pub log_notifier: BroadcastSender<BlockLog>,
+    /// The rebranch notifier publishes a record of every rebranch of the main chain.
+    pub rebranch_notifier: BroadcastSender<RebranchRecord>,
     /// The chain store is a database containing all of the chain infos, blocks and receipts.
     pub chain_store: ChainStore,
     /// The history store is a database containing all of the history trees and transactions.
     pub history_store: HistoryStoreProxy,
+    /// The rebranch log is a database containing the most recent rebranches of the main chain.
+    pub rebranch_log: RebranchLog,
     /// The current state of the blockchain.
     pub state: BlockchainState,
+    /// A snapshot of the accounts trie at the latest macro block.
+    pub accounts_snapshot: AccountsSnapshot,
     /// A reference to a "function" to test whether a given transaction is known and valid.
     pub tx_verification_cache: Arc<dyn TransactionVerificationCache>,
     /// The metrics for the blockchain. Needed for analysis.
@@ -72,6 +84,11 @@ pub struct BlockchainConfig {
     pub max_epochs_stored: u32,
     /// Enables/Disables indices in the history store.
     pub index_history: bool,
+    /// How long the history is kept if `keep_history` is set. The full history is kept if `None`.
+    pub history_retention: Option<HistoryRetention>,
+    /// Flag indicating if a snapshot of the accounts trie at the latest macro block should be kept.
+    /// Accounts chunks requested by our peers are served from the snapshot.
+    pub accounts_snapshot: bool,
 }
 
 impl Default for BlockchainConfig {
@@ -80,6 +97,8 @@ impl Default for BlockchainConfig {
             keep_history: true,
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
             index_history: true,
+            history_retention: None,
+            accounts_snapshot: true,
         }
     }
 }
@@ -265,6 +284,9 @@ impl Blockchain {
         let (tx, _rx) = broadcast(BROADCAST_MAX_CAPACITY);
         let (tx_fork, _rx_fork) = broadcast(BROADCAST_MAX_CAPACITY);
         let (tx_log, _rx_log) = broadcast(BROADCAST_MAX_CAPACITY);
+        let (tx_rebranch, _rx_rebranch) = broadcast(BROADCAST_MAX_CAPACITY);
+        let rebranch_log = RebranchLog::new(env.clone());
+        let accounts_snapshot = AccountsSnapshot::new(env.clone());
 
         let history_store = if config.index_history {
             HistoryStoreProxy::WithIndex(HistoryStoreIndex::new(env.clone(), network_id))
@@ -281,6 +303,8 @@ impl Blockchain {
             notifier: tx,
             fork_notifier: tx_fork,
             log_notifier: tx_log,
+            rebranch_notifier: tx_rebranch,
+            rebranch_log,
             chain_store,
             history_store,
             state: BlockchainState {
@@ -294,6 +318,7 @@ impl Blockchain {
                 current_slots: Some(current_slots),
                 previous_slots: last_slots,
             },
+            accounts_snapshot,
             tx_verification_cache: Arc::new(DEFAULT_TX_VERIFICATION_CACHE),
             #[cfg(feature = "metrics")]
             metrics: Arc::new(BlockchainMetrics::default()),
@@ -339,6 +364,9 @@ impl Blockchain {
         let (tx, _rx) = broadcast(BROADCAST_MAX_CAPACITY);
         let (tx_fork, _rx_fork) = broadcast(BROADCAST_MAX_CAPACITY);
         let (tx_log, _rx_log) = broadcast(BROADCAST_MAX_CAPACITY);
+        let (tx_rebranch, _rx_rebranch) = broadcast(BROADCAST_MAX_CAPACITY);
+        let rebranch_log = RebranchLog::new(env.clone());
+        let accounts_snapshot = AccountsSnapshot::new(env.clone());
 
         let history_store = if config.index_history {
             HistoryStoreProxy::WithIndex(HistoryStoreIndex::new(env.clone(), network_id))
@@ -355,6 +383,8 @@ impl Blockchain {
             notifier: tx,
             fork_notifier: tx_fork,
             log_notifier: tx_log,
+            rebranch_notifier: tx_rebranch,
+            rebranch_log,
             chain_store,
             history_store,
             state: BlockchainState {
@@ -368,6 +398,7 @@ impl Blockchain {
                 current_slots: Some(current_slots),
                 previous_slots: Some(Validators::default()),
             },
+            accounts_snapshot,
             tx_verification_cache: Arc::new(DEFAULT_TX_VERIFICATION_CACHE),
             #[cfg(feature = "metrics")]
             metrics: Arc::new(BlockchainMetrics::default()),
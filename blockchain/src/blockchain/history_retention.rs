@@ -0,0 +1,78 @@
+use std::cmp;
+
+use nimiq_database::{TransactionProxy, WriteTransactionProxy};
+use nimiq_primitives::policy::Policy;
+
+use crate::{interface::HistoryInterface, Blockchain};
+
+/// Determines how long a node that keeps the history stores it before pruning it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryRetention {
+    /// Keeps the history of the given number of epochs before the current one.
+    Epochs(u32),
+    /// Keeps the history of all epochs that ended within the given number of days.
+    Days(u32),
+}
+
+impl HistoryRetention {
+    const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+}
+
+impl Blockchain {
+    /// Prunes the history of all epochs that fall out of the configured history retention once the
+    /// election block with the given number and timestamp is applied. The epoch ending with this
+    /// election block is always kept, since it is still needed to enforce the validity window.
+    pub(crate) fn prune_history(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        block_number: u32,
+        timestamp: u64,
+    ) {
+        let Some(retention) = self.config.history_retention else {
+            return;
+        };
+
+        let read_txn: &TransactionProxy = txn;
+        let (first_block, _) = self.history_store.history_store_range(Some(read_txn));
+        if first_block == 0 {
+            // The history store is empty.
+            return;
+        }
+
+        let current_epoch = Policy::epoch_at(block_number);
+        let first_epoch = Policy::epoch_at(first_block);
+        let last_epoch = match retention {
+            HistoryRetention::Epochs(num_epochs) => {
+                current_epoch.saturating_sub(cmp::max(num_epochs, 1))
+            }
+            HistoryRetention::Days(num_days) => {
+                let cutoff = timestamp
+                    .saturating_sub(u64::from(num_days) * HistoryRetention::MILLIS_PER_DAY);
+                // Find the last epoch that ended before the cutoff. Stop at the first election
+                // block we don't know, we can't tell when that epoch ended.
+                let last_epoch = (first_epoch..current_epoch)
+                    .take_while(|&epoch| {
+                        Policy::election_block_of(epoch)
+                            .and_then(|election_block| {
+                                self.chain_store
+                                    .get_block_at(election_block, false, Some(read_txn))
+                                    .ok()
+                            })
+                            .is_some_and(|block| block.timestamp() < cutoff)
+                    })
+                    .last();
+                match last_epoch {
+                    Some(last_epoch) => last_epoch,
+                    // No epoch ended before the cutoff, so there is nothing to prune.
+                    None => return,
+                }
+            }
+        };
+
+        for epoch in first_epoch..=last_epoch {
+            if self.history_store.remove_history(txn, epoch).is_some() {
+                info!(epoch, ?retention, "Pruned history");
+            }
+        }
+    }
+}
@@ -320,7 +320,10 @@ impl Blockchain {
                 .collect();
 
             // Commit block to AccountsTree and create the receipts.
-            let block_state = BlockState::new(block_numbers[i], block_timestamps[i]);
+            // The history doesn't carry the block headers, so we use the version of the macro block
+            // we're proving against. Blocks are only accepted with the version of their chain.
+            let block_state =
+                BlockState::with_version(block_numbers[i], block_timestamps[i], block.version());
             let receipts = this.state.accounts.commit_batch(
                 &mut (&mut txn).into(),
                 &txns,
@@ -412,6 +415,11 @@ impl Blockchain {
             return Err(PushError::InvalidBlock(BlockError::AccountsHashMismatch));
         }
 
+        // Prune the history according to the history retention.
+        if macro_block.is_election() {
+            this.prune_history(&mut txn, block.block_number(), block.timestamp());
+        }
+
         // Give up database transactions and push lock before creating notifications.
         txn.commit();
 
@@ -2,11 +2,15 @@ mod abstract_blockchain;
 pub mod accounts;
 #[allow(clippy::module_inception)]
 pub mod blockchain;
+pub mod history_retention;
 pub mod history_sync;
 pub mod inherents;
 pub mod push;
 pub(super) mod rebranch_utils;
+pub mod receipt_proof;
+pub mod simulation;
 pub mod slots;
+pub mod snapshot;
 pub mod verify;
 pub mod wrappers;
 pub mod zkp_sync;
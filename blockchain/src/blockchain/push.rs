@@ -332,6 +332,9 @@ impl Blockchain {
                 // Prune the History Store.
                 this.history_store
                     .remove_history(&mut txn, Policy::epoch_at(block_number).saturating_sub(1));
+            } else {
+                // Prune the History Store according to the history retention.
+                this.prune_history(&mut txn, block_number, chain_info.head.timestamp());
             }
         }
 
@@ -445,6 +448,26 @@ impl Blockchain {
                 }
             };
 
+        // Keep a record of the rebranch for operators, within the same transaction.
+        let reverted = revert_chain
+            .iter()
+            .rev()
+            .map(|(hash, chain_info)| this.rebranched_block(hash, &chain_info.head, &write_txn))
+            .collect();
+        let adopted = fork_chain
+            .iter()
+            .rev()
+            .map(|(hash, chain_info, _)| this.rebranched_block(hash, &chain_info.head, &write_txn))
+            .collect();
+        let record = this.rebranch_log.push(
+            &mut write_txn,
+            this.time.now(),
+            ancestor.0.clone(),
+            ancestor.1.head.block_number(),
+            reverted,
+            adopted,
+        );
+
         // Commit transaction & update head.
         let new_head_hash = &fork_chain[0].0;
         let new_head_info = &fork_chain[0].1;
@@ -507,7 +530,17 @@ impl Blockchain {
         this.metrics
             .note_rebranch(&reverted_blocks, &adopted_blocks);
 
+        if record.depth() > 0 {
+            info!(
+                depth = record.depth(),
+                common_ancestor = %record.common_ancestor,
+                involves_skip_blocks = record.involves_skip_blocks(),
+                "Reorganized the main chain",
+            );
+        }
+
         // We do not log errors if there are no listeners.
+        this.rebranch_notifier.send(record).ok();
         this.notifier
             .send(BlockchainEvent::Rebranched(reverted_blocks, adopted_blocks))
             .ok();
@@ -566,6 +599,9 @@ impl Blockchain {
         let total_tx_size;
         {
             let is_complete = self.state.accounts.is_complete(Some(txn));
+            // Keep the received diff if we can't record one. The diffs since the latest macro
+            // block are needed to restore the accounts trie from the accounts snapshot.
+            let received_diff = if is_complete { None } else { diff.clone() };
             let mut txn: TrieWriteTransactionProxy = txn.into();
             if is_complete {
                 txn.start_recording();
@@ -580,6 +616,9 @@ impl Blockchain {
                 let recorded_diff = txn.stop_recording().into_forward_diff();
                 self.chain_store
                     .put_accounts_diff(txn.raw(), &block.hash(), &recorded_diff);
+            } else if let Some(received_diff) = received_diff {
+                self.chain_store
+                    .put_accounts_diff(txn.raw(), &block.hash(), &received_diff);
             }
         }
 
@@ -589,6 +628,11 @@ impl Blockchain {
             return Err(e);
         }
 
+        // Move the accounts snapshot along with the new block.
+        if self.config.accounts_snapshot {
+            self.update_accounts_snapshot(block, &mut txn.into());
+        }
+
         Ok(total_tx_size)
     }
 
@@ -1,12 +1,13 @@
 use std::error::Error;
 
 use nimiq_account::{BlockLog, BlockLogger};
+use nimiq_block::Block;
 use nimiq_blockchain_interface::{ChainInfo, PushError};
 use nimiq_database::{TransactionProxy, WriteTransactionProxy};
 use nimiq_hash::Blake2bHash;
 use nimiq_primitives::trie::trie_diff::TrieDiff;
 
-use crate::Blockchain;
+use crate::{rebranch_log::RebranchedBlock, Blockchain};
 
 impl Blockchain {
     /// Finds the common ancestor between the current main chain in the context of `txn` and the fork chain given by
@@ -257,4 +258,34 @@ impl Blockchain {
 
         Ok((revert_chain, block_logs))
     }
+
+    /// Describes a block that was reverted or adopted during a rebranch for the rebranch log.
+    pub(super) fn rebranched_block(
+        &self,
+        hash: &Blake2bHash,
+        block: &Block,
+        txn: &TransactionProxy,
+    ) -> RebranchedBlock {
+        // Skip blocks are not proposed by a single validator.
+        let proposer = if block.is_skip() {
+            None
+        } else {
+            self.get_proposer_of(hash, Some(txn))
+                .ok()
+                .map(|slot| slot.validator.address)
+        };
+
+        RebranchedBlock {
+            hash: hash.clone(),
+            block_number: block.block_number(),
+            proposer,
+            is_skip: block.is_skip(),
+            transactions: block
+                .transactions()
+                .unwrap_or_default()
+                .iter()
+                .map(|transaction| transaction.raw_tx_hash().into())
+                .collect(),
+        }
+    }
 }
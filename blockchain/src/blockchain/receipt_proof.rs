@@ -0,0 +1,97 @@
+use nimiq_block::TransactionReceiptProof;
+use nimiq_blockchain_interface::BlockchainError;
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+use thiserror::Error;
+
+use crate::{interface::HistoryIndexInterface, Blockchain};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ProveReceiptError {
+    #[error("Proving transactions requires a history index")]
+    RequiresHistoryIndex,
+    #[error("Transaction not found: {0}")]
+    TransactionNotFound(Blake2bHash),
+    #[error("Transaction is not finalized yet")]
+    NotFinalized,
+    #[error("Block {0} is not an election block")]
+    NotAnElectionBlock(u32),
+    #[error("The trusted election block must not be after the transaction")]
+    TrustedBlockAfterTransaction,
+    #[error("Couldn't prove the inclusion of the transaction")]
+    CouldntProveInclusion,
+    #[error("{0}")]
+    Blockchain(#[from] BlockchainError),
+}
+
+impl Blockchain {
+    /// Creates a self-contained proof that the transaction with the given hash was included in the
+    /// chain. The proof can be verified offline by anyone trusting the election block with the
+    /// given number. Only transactions in finalized batches can be proven.
+    pub fn prove_transaction_receipt(
+        &self,
+        hash: &Blake2bHash,
+        trusted_block_number: u32,
+    ) -> Result<TransactionReceiptProof, ProveReceiptError> {
+        let history_index = self
+            .history_store
+            .history_index()
+            .ok_or(ProveReceiptError::RequiresHistoryIndex)?;
+        let transaction = history_index
+            .get_hist_tx_by_hash(hash, None)
+            .ok_or_else(|| ProveReceiptError::TransactionNotFound(hash.clone()))?;
+
+        // Transactions in finalized epochs are proven by the election block of their epoch,
+        // transactions in the current epoch by the latest checkpoint block.
+        let election_head = self.state.election_head.block_number();
+        let macro_head = self.state.macro_info.head.block_number();
+        let (block_number, verifier_state) = if transaction.block_number <= election_head {
+            (Policy::election_block_after(transaction.block_number), None)
+        } else if transaction.block_number <= macro_head {
+            (
+                macro_head,
+                Some(self.state.macro_info.history_tree_len as usize),
+            )
+        } else {
+            return Err(ProveReceiptError::NotFinalized);
+        };
+
+        if !Policy::is_election_block_at(trusted_block_number) {
+            return Err(ProveReceiptError::NotAnElectionBlock(trusted_block_number));
+        }
+        if trusted_block_number > block_number {
+            return Err(ProveReceiptError::TrustedBlockAfterTransaction);
+        }
+
+        let trusted_block = self
+            .get_block_at(trusted_block_number, true, None)?
+            .unwrap_macro();
+        let election_blocks = (Policy::epoch_at(trusted_block_number) + 1
+            ..Policy::epoch_at(block_number))
+            .map(|epoch| {
+                let election_block = Policy::election_block_of(epoch).unwrap();
+                Ok(self
+                    .get_block_at(election_block, true, None)?
+                    .unwrap_macro())
+            })
+            .collect::<Result<_, BlockchainError>>()?;
+        // The body of the proving block isn't needed to verify it.
+        let block = self.get_block_at(block_number, false, None)?.unwrap_macro();
+
+        let history_proof = history_index
+            .prove(
+                Policy::epoch_at(block_number),
+                vec![hash],
+                verifier_state,
+                None,
+            )
+            .ok_or(ProveReceiptError::CouldntProveInclusion)?;
+
+        Ok(TransactionReceiptProof {
+            trusted_block,
+            election_blocks,
+            block,
+            history_proof,
+        })
+    }
+}
@@ -0,0 +1,133 @@
+use nimiq_account::{BlockLogger, BlockState, Log, TransactionOperationReceipt};
+use nimiq_blockchain_interface::{AbstractBlockchain, SimulationError};
+use nimiq_database::traits::WriteTransaction;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
+use nimiq_primitives::{account::FailReason, coin::Coin};
+use nimiq_transaction::Transaction;
+use nimiq_trie::WriteTransactionProxy;
+
+use crate::Blockchain;
+
+/// The balance of an account before and after a simulated transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub address: Address,
+    pub balance_before: Coin,
+    pub balance_after: Coin,
+}
+
+/// The outcome of applying a transaction on top of the current head without persisting it.
+#[derive(Clone, Debug)]
+pub struct TransactionSimulation {
+    /// The hash of the simulated transaction.
+    pub tx_hash: Blake2bHash,
+    /// The block number the transaction was simulated at, i.e. the successor of the head.
+    pub block_number: u32,
+    /// The block timestamp used for the simulation.
+    pub timestamp: u64,
+    /// The receipt the accounts tree produced for the transaction.
+    pub receipt: TransactionOperationReceipt,
+    /// The logs the transaction produced. A failed transaction only produces the fee logs.
+    pub logs: Vec<Log>,
+    /// The balance changes of the sender and the recipient.
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+impl TransactionSimulation {
+    /// Returns the reason for the failure if the transaction would fail, but still pay the fee.
+    pub fn fail_reason(&self) -> Option<FailReason> {
+        match self.receipt {
+            TransactionOperationReceipt::Ok(_) => None,
+            TransactionOperationReceipt::Err(_, fail_reason) => Some(fail_reason),
+        }
+    }
+}
+
+/// Implements methods to simulate transactions.
+impl Blockchain {
+    /// Runs the full verification path for the given transaction and applies it to the accounts
+    /// tree as if it was included in the next block. The accounts are modified on a write
+    /// transaction that is always aborted, so the state of the blockchain is never changed.
+    pub fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<TransactionSimulation, SimulationError> {
+        // Intrinsic verification (signature, network, data).
+        transaction.verify(self.network_id)?;
+
+        // Check the validity window and whether the transaction was already included.
+        let block_number = self.block_number() + 1;
+        if !transaction.is_valid_at(block_number) {
+            return Err(SimulationError::InvalidBlockNumber);
+        }
+
+        let tx_hash = transaction.hash();
+        if self.contains_tx_in_validity_window(&tx_hash, None) {
+            return Err(SimulationError::AlreadyIncluded);
+        }
+
+        let accounts = &self.state.accounts;
+        let timestamp = self.time.now().max(self.timestamp());
+        let block_state = BlockState::new(block_number, timestamp);
+
+        let mut raw_txn = self.write_transaction();
+        let mut txn: WriteTransactionProxy = (&mut raw_txn).into();
+
+        if !accounts.is_complete(Some(&txn)) {
+            return Err(SimulationError::IncompleteAccountsTrie);
+        }
+
+        let mut addresses = vec![transaction.sender.clone()];
+        if transaction.recipient != transaction.sender {
+            addresses.push(transaction.recipient.clone());
+        }
+        let balances_before: Vec<Coin> = addresses
+            .iter()
+            .map(|address| accounts.get_complete(address, Some(&txn)).balance())
+            .collect();
+
+        let mut block_logger = BlockLogger::empty();
+        let result = accounts.commit(
+            &mut txn,
+            &[transaction.clone()],
+            &[],
+            &block_state,
+            &mut block_logger,
+        );
+
+        let balance_changes = addresses
+            .into_iter()
+            .zip(balances_before)
+            .map(|(address, balance_before)| BalanceChange {
+                balance_after: accounts.get_complete(&address, Some(&txn)).balance(),
+                address,
+                balance_before,
+            })
+            .collect();
+
+        raw_txn.abort();
+
+        let mut receipts = result?;
+        let receipt = receipts
+            .transactions
+            .pop()
+            .expect("Simulation must produce exactly one receipt");
+
+        let logs = block_logger
+            .build(0)
+            .transaction_logs()
+            .first()
+            .map(|tx_log| tx_log.logs.clone())
+            .unwrap_or_default();
+
+        Ok(TransactionSimulation {
+            tx_hash,
+            block_number,
+            timestamp,
+            receipt,
+            logs,
+            balance_changes,
+        })
+    }
+}
@@ -0,0 +1,410 @@
+use std::ops::RangeFrom;
+
+use nimiq_account::Account;
+use nimiq_block::Block;
+use nimiq_blockchain_interface::{BlockchainError, ChunksPushError, ChunksPushResult};
+use nimiq_database::{traits::WriteTransaction, TransactionProxy};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::{
+    account::AccountError,
+    key_nibbles::KeyNibbles,
+    policy::Policy,
+    trie::{
+        error::IncompleteTrie,
+        trie_chunk::{TrieChunk, TrieChunkPushResult, TrieChunkWithStart},
+        trie_proof::TrieProof,
+    },
+};
+use nimiq_trie::WriteTransactionProxy;
+
+use crate::{accounts_snapshot::AccountsSnapshot, Blockchain};
+
+/// Implements methods to maintain the accounts snapshot and to sync the accounts from it.
+impl Blockchain {
+    /// Moves the accounts snapshot along with the given block. Expects the block to be committed
+    /// already, i.e. the accounts trie is at the state of the block.
+    ///
+    /// At macro blocks, the snapshot is advanced by applying the accounts diffs of the batch. If
+    /// that isn't possible, it is reset. An empty snapshot is then either synced from our peers
+    /// or, if the accounts trie is complete, rebuilt from it by
+    /// [`rebuild_accounts_snapshot_chunk`](Self::rebuild_accounts_snapshot_chunk). While it is
+    /// rebuilt, the snapshot also follows the micro blocks, such that it stays at the state of
+    /// the accounts trie.
+    pub(super) fn update_accounts_snapshot(&self, block: &Block, txn: &mut WriteTransactionProxy) {
+        if block.is_micro() && !self.is_rebuilding_accounts_snapshot(txn) {
+            return;
+        }
+
+        let block_hash = block.hash();
+        if !self.advance_accounts_snapshot(block, &block_hash, txn) {
+            debug!(%block, "Resetting accounts snapshot");
+            self.accounts_snapshot.reset(txn, &block_hash);
+        }
+    }
+
+    /// Private function that returns whether the accounts snapshot is rebuilt from the accounts
+    /// trie, i.e. the snapshot is incomplete while the accounts trie is complete.
+    fn is_rebuilding_accounts_snapshot(&self, txn: &TransactionProxy) -> bool {
+        !self.accounts_snapshot.is_complete(txn) && self.state.accounts.is_complete(Some(txn))
+    }
+
+    /// Copies the next chunk of the accounts trie into the accounts snapshot while the snapshot
+    /// is being rebuilt. Each chunk is copied in its own database transaction, such that blocks
+    /// can be pushed in between.
+    /// Returns `true` if there are more chunks to copy.
+    pub fn rebuild_accounts_snapshot_chunk(&self) -> bool {
+        // Avoid a write transaction if there is nothing to rebuild.
+        if !self.config.accounts_snapshot
+            || !self.is_rebuilding_accounts_snapshot(&self.read_transaction())
+        {
+            return false;
+        }
+
+        let mut txn = self.write_transaction();
+        let more_chunks = self.copy_accounts_snapshot_chunk(&mut (&mut txn).into());
+        txn.commit();
+        more_chunks
+    }
+
+    /// Private function that copies the next chunk of the accounts trie into the accounts
+    /// snapshot. If that fails, the snapshot is reset to be rebuilt from scratch.
+    /// Returns `true` if there are more chunks to copy.
+    fn copy_accounts_snapshot_chunk(&self, txn: &mut WriteTransactionProxy) -> bool {
+        let snapshot = &self.accounts_snapshot;
+        if !self.is_rebuilding_accounts_snapshot(txn) {
+            return false;
+        }
+
+        // The snapshot follows the main chain, so it must be at the head to be rebuilt.
+        let Some(head_hash) = self.chain_store.get_head(Some(txn)) else {
+            return false;
+        };
+        if snapshot.block_hash(txn).as_ref() != Some(&head_hash) {
+            return false;
+        }
+
+        let state_root = self.state.accounts.get_root_hash_assert(Some(txn));
+        match AccountsSnapshot::copy_chunk(
+            txn,
+            &self.state.accounts.tree,
+            &snapshot.tree,
+            &state_root,
+        ) {
+            Ok(complete) => !complete,
+            Err(error) => {
+                warn!(%error, block_hash = %head_hash, "Failed to rebuild accounts snapshot");
+                snapshot.reset(txn, &head_hash);
+                false
+            }
+        }
+    }
+
+    /// Private function that applies the accounts diffs of all blocks since the macro block of
+    /// the snapshot to the snapshot. Returns `false` if the snapshot couldn't be advanced.
+    fn advance_accounts_snapshot(
+        &self,
+        block: &Block,
+        block_hash: &Blake2bHash,
+        txn: &mut WriteTransactionProxy,
+    ) -> bool {
+        let snapshot = &self.accounts_snapshot;
+        let Some(snapshot_hash) = snapshot.block_hash(txn) else {
+            return false;
+        };
+        let Some(hashes) = self.blocks_since(
+            &snapshot_hash,
+            block_hash.clone(),
+            block.parent_hash().clone(),
+            txn,
+        ) else {
+            return false;
+        };
+
+        for hash in hashes {
+            let Ok(diff) = self.chain_store.get_accounts_diff(&hash, Some(txn)) else {
+                return false;
+            };
+            if snapshot.tree.apply_diff(txn, diff).is_err() {
+                return false;
+            }
+        }
+
+        // It is fine to have an incomplete snapshot here.
+        snapshot.tree.update_root(txn).ok();
+        if let Some(root_hash) = snapshot.tree.root_hash(txn) {
+            if root_hash != *block.state_root() {
+                return false;
+            }
+        }
+
+        snapshot.set_block_hash(txn, block_hash);
+        true
+    }
+
+    /// Private function that returns the hashes of the blocks after `ancestor` up to and
+    /// including the block with the given hash and parent hash, in ascending order.
+    /// Returns `None` if `ancestor` is not one of the predecessors within a batch.
+    fn blocks_since(
+        &self,
+        ancestor: &Blake2bHash,
+        hash: Blake2bHash,
+        parent_hash: Blake2bHash,
+        txn: &TransactionProxy,
+    ) -> Option<Vec<Blake2bHash>> {
+        if hash == *ancestor {
+            return Some(vec![]);
+        }
+
+        let mut hashes = vec![hash];
+        let mut current = parent_hash;
+        while current != *ancestor {
+            if hashes.len() >= Policy::blocks_per_batch() as usize {
+                return None;
+            }
+            let chain_info = self
+                .chain_store
+                .get_chain_info(&current, false, Some(txn))
+                .ok()?;
+            let parent_hash = chain_info.head.parent_hash().clone();
+            hashes.push(current);
+            current = parent_hash;
+        }
+
+        hashes.reverse();
+        Some(hashes)
+    }
+
+    /// Gets a chunk of the accounts snapshot given a start key and a limit.
+    /// Returns `None` if there is no complete snapshot at the latest macro block.
+    pub fn get_accounts_snapshot_chunk(
+        &self,
+        start: KeyNibbles,
+        limit: usize,
+        txn: &TransactionProxy,
+    ) -> Option<TrieChunk> {
+        if !self.has_complete_snapshot(txn) {
+            return None;
+        }
+
+        Some(
+            self.accounts_snapshot
+                .tree
+                .get_chunk_with_proof(txn, start.., limit),
+        )
+    }
+
+    /// Gets the account at the given address together with a proof against the state root of
+    /// the latest macro block.
+    /// Returns `Ok(None)` if there is no complete snapshot at the latest macro block.
+    pub fn get_account_snapshot_proof(
+        &self,
+        address: &Address,
+    ) -> Result<Option<(Account, TrieProof)>, IncompleteTrie> {
+        let txn = self.read_transaction();
+        if !self.has_complete_snapshot(&txn) {
+            return Ok(None);
+        }
+
+        let tree = &self.accounts_snapshot.tree;
+        let key = KeyNibbles::from(address);
+        let account = tree.get(&txn, &key)?.unwrap_or_default();
+        let proof = tree.get_proof(&txn, vec![&key])?;
+        Ok(Some((account, proof)))
+    }
+
+    /// Private function that returns whether the accounts snapshot is enabled and complete at the
+    /// latest macro block.
+    fn has_complete_snapshot(&self, txn: &TransactionProxy) -> bool {
+        let snapshot = &self.accounts_snapshot;
+        self.config.accounts_snapshot
+            && snapshot.is_complete(txn)
+            && snapshot.block_hash(txn).as_ref() == Some(&self.state.macro_head_hash)
+    }
+
+    /// Retrieves the missing range of the accounts snapshot at the latest macro block. If the
+    /// snapshot belongs to an older macro block, the whole range is missing.
+    /// This function returns `None` when the snapshot is complete or when it is rebuilt from the
+    /// complete accounts trie instead of being synced from our peers.
+    pub fn get_missing_snapshot_range(
+        &self,
+        txn_opt: Option<&TransactionProxy>,
+    ) -> Option<RangeFrom<KeyNibbles>> {
+        let read_txn: TransactionProxy;
+        let txn = match txn_opt {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.read_transaction();
+                &read_txn
+            }
+        };
+
+        if self.state.accounts.is_complete(Some(txn)) {
+            return None;
+        }
+
+        let snapshot = &self.accounts_snapshot;
+        if snapshot.block_hash(txn).as_ref() != Some(&self.state.macro_head_hash) {
+            return Some(KeyNibbles::ROOT..);
+        }
+        snapshot.tree.get_missing_range(txn)
+    }
+
+    /// Commits a set of chunks of the accounts snapshot at the given macro block.
+    /// Once the snapshot is complete, the accounts trie is restored from it.
+    pub fn commit_snapshot_chunks(
+        &self,
+        chunks: Vec<TrieChunkWithStart>,
+        block_hash: &Blake2bHash,
+    ) -> Result<ChunksPushResult, ChunksPushError> {
+        if chunks.is_empty() {
+            return Ok(ChunksPushResult::EmptyChunks);
+        }
+
+        // Chunks of any other block than our latest macro block don't belong to the snapshot.
+        // If our accounts trie is complete, the snapshot is rebuilt from it instead.
+        if *block_hash != self.state.macro_head_hash || self.state.accounts.is_complete(None) {
+            log::debug!(
+                "Ignoring {} snapshot chunks for block {}",
+                chunks.len(),
+                block_hash
+            );
+            return Ok(ChunksPushResult::Chunks(0, chunks.len()));
+        }
+
+        let snapshot = &self.accounts_snapshot;
+        let state_root = self.state.macro_info.head.state_root();
+        let mut chunk_result = Ok(ChunksPushResult::EmptyChunks);
+        let mut chunks_committed = 0;
+        let mut chunks_ignored = 0;
+        for (i, chunk_data) in chunks.into_iter().enumerate() {
+            let mut txn = self.write_transaction();
+            log::trace!(
+                "Committing snapshot chunk for block: {} chunk: {} start_key: {}",
+                block_hash,
+                chunk_data.chunk,
+                chunk_data.start_key
+            );
+            let result = {
+                let mut txn: WriteTransactionProxy = (&mut txn).into();
+                // Our snapshot belongs to an older macro block, start a new one.
+                if snapshot.block_hash(&txn).as_ref() != Some(block_hash) {
+                    snapshot.reset(&mut txn, block_hash);
+                }
+                snapshot
+                    .tree
+                    .put_chunk(
+                        &mut txn,
+                        chunk_data.start_key,
+                        chunk_data.chunk,
+                        state_root.clone(),
+                    )
+                    .map_err(AccountError::from)
+            };
+            match result {
+                Err(e) => {
+                    txn.abort();
+                    log::warn!(
+                        "Commit snapshot chunk for block {} failed: {}",
+                        block_hash,
+                        e
+                    );
+                    chunk_result = Err(ChunksPushError::AccountsError(i, e));
+                    break;
+                }
+                Ok(TrieChunkPushResult::Applied) => {
+                    chunks_committed += 1;
+                    chunk_result = Ok(ChunksPushResult::Chunks(chunks_committed, chunks_ignored));
+                }
+                Ok(TrieChunkPushResult::Ignored) => {
+                    // The chunk has been ignored, but might still have been valid.
+                    log::debug!(
+                        "Commit snapshot chunk for block {} was ignored.",
+                        block_hash
+                    );
+                    chunks_ignored += 1;
+                    chunk_result = Ok(ChunksPushResult::Chunks(chunks_committed, chunks_ignored));
+                }
+            };
+
+            txn.commit();
+        }
+
+        let txn = self.read_transaction();
+        if chunks_committed > 0
+            && snapshot.is_complete(&txn)
+            && !self.state.accounts.is_complete(Some(&txn))
+        {
+            drop(txn);
+            self.restore_accounts_from_snapshot();
+        }
+
+        chunk_result
+    }
+
+    /// Private function that restores the incomplete accounts trie from the complete snapshot.
+    /// The accounts diffs of the blocks since the macro block of the snapshot are applied on top
+    /// of it. If this fails, the accounts trie is left untouched.
+    fn restore_accounts_from_snapshot(&self) {
+        let head = &self.state.main_chain.head;
+        let mut txn = self.write_transaction();
+        let result = self.apply_snapshot_to_accounts(&mut (&mut txn).into());
+
+        match result {
+            Ok(()) => {
+                txn.commit();
+                info!(block = %head, "Restored accounts trie from snapshot");
+            }
+            Err(error) => {
+                txn.abort();
+                warn!(block = %head, %error, "Failed to restore accounts trie from snapshot");
+            }
+        }
+    }
+
+    /// Private function that replaces the accounts trie with the snapshot and applies the
+    /// accounts diffs of the blocks since the macro block of the snapshot.
+    fn apply_snapshot_to_accounts(
+        &self,
+        txn: &mut WriteTransactionProxy,
+    ) -> Result<(), BlockchainError> {
+        let head = &self.state.main_chain.head;
+        let snapshot_hash = self
+            .accounts_snapshot
+            .block_hash(txn)
+            .ok_or(BlockchainError::InconsistentState)?;
+
+        // Get the diffs first, there is no point in copying the snapshot if one is missing.
+        let hashes = self
+            .blocks_since(
+                &snapshot_hash,
+                self.state.head_hash.clone(),
+                head.parent_hash().clone(),
+                txn,
+            )
+            .ok_or(BlockchainError::AccountsDiffNotFound)?;
+        let diffs = hashes
+            .iter()
+            .map(|hash| self.chain_store.get_accounts_diff(hash, Some(txn)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let tree = &self.state.accounts.tree;
+        let snapshot_root = self.state.macro_info.head.state_root();
+        AccountsSnapshot::copy_trie(txn, &self.accounts_snapshot.tree, tree, snapshot_root)
+            .map_err(|error| {
+                debug!(%error, "Failed to copy accounts snapshot");
+                BlockchainError::InconsistentState
+            })?;
+        for diff in diffs {
+            tree.apply_diff(txn, diff)
+                .map_err(|_| BlockchainError::InconsistentState)?;
+        }
+        tree.update_root(txn)
+            .map_err(|_| BlockchainError::InconsistentState)?;
+
+        if tree.root_hash(txn).as_ref() != Some(head.state_root()) {
+            return Err(BlockchainError::InconsistentState);
+        }
+        Ok(())
+    }
+}
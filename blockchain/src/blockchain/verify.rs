@@ -1,12 +1,15 @@
 use nimiq_account::BlockLogger;
-use nimiq_block::{Block, BlockError, MacroBlock, MacroBody};
+use nimiq_block::{
+    Block, BlockError, EquivocationProof, EquivocationProofError, MacroBlock, MacroBody,
+};
 use nimiq_blockchain_interface::{AbstractBlockchain, ChainInfo, PushError};
+use nimiq_bls::{batch::BatchVerifier, pool::VerificationPool};
 use nimiq_database::{
     traits::{ReadTransaction, WriteTransaction},
     TransactionProxy as DBTransaction, WriteTransactionProxy,
 };
 use nimiq_hash::Hash;
-use nimiq_primitives::policy::Policy;
+use nimiq_primitives::{policy::Policy, slots_allocation::Validators};
 
 use crate::{interface::HistoryInterface, BlockProducer, Blockchain};
 
@@ -87,14 +90,22 @@ impl Blockchain {
             // Verify that the block is valid for the given proposer.
             block.verify_proposer(&proposer.signing_key, predecessor.seed())?;
 
-            // Verify that the block is valid for the current validators.
-            block.verify_validators(&self.current_validators().unwrap())?;
+            // Verify that the block is valid for the current validators and that the equivocation
+            // proofs are valid. Their BLS signatures are verified together in a single batch.
+            let validators = self.current_validators().unwrap();
+            let mut batch = BatchVerifier::new();
+            block.verify_validators_batched(&validators, &mut batch)?;
+            self.verify_equivocation_proofs_batched(block, txn, &mut batch)?;
 
             // Verify that the transactions in the block are valid.
             self.verify_transactions(block)?;
 
-            // Verify that the equivocation proofs are valid.
-            self.verify_equivocation_proofs(block, txn)?;
+            if !VerificationPool::global().verify(batch) {
+                // A failed batch doesn't tell which signature is invalid, so verify them one by
+                // one to return the right error.
+                block.verify_validators(&validators)?;
+                self.verify_equivocation_proofs(block, txn)?;
+            }
         }
 
         Ok(())
@@ -200,6 +211,33 @@ impl Blockchain {
         block: &Block,
         txn: &DBTransaction,
     ) -> Result<(), PushError> {
+        self.verify_equivocation_proofs_with(block, txn, |equivocation_proof, validators| {
+            equivocation_proof.verify(block.network(), validators)
+        })
+    }
+
+    /// Verifies the equivocation proofs of a block like `verify_equivocation_proofs`, but only
+    /// adds their BLS signatures to `batch` instead of verifying them.
+    fn verify_equivocation_proofs_batched(
+        &self,
+        block: &Block,
+        txn: &DBTransaction,
+        batch: &mut BatchVerifier,
+    ) -> Result<(), PushError> {
+        self.verify_equivocation_proofs_with(block, txn, |equivocation_proof, validators| {
+            equivocation_proof.verify_batched(block.network(), validators, batch)
+        })
+    }
+
+    fn verify_equivocation_proofs_with<F>(
+        &self,
+        block: &Block,
+        txn: &DBTransaction,
+        mut verify: F,
+    ) -> Result<(), PushError>
+    where
+        F: FnMut(&EquivocationProof, &Validators) -> Result<(), EquivocationProofError>,
+    {
         // We don't need to perform any checks if the given block is not a
         // micro block as only micro blocks contain equivocation proofs.
         let micro_block = match block {
@@ -227,7 +265,7 @@ impl Blockchain {
                     Some(txn),
                 )
                 .expect("Couldn't calculate validators");
-            equivocation_proof.verify(block.network(), &validators)?;
+            verify(equivocation_proof, &validators)?;
         }
         Ok(())
     }
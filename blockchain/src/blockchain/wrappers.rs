@@ -230,6 +230,15 @@ impl Blockchain {
         self.state.accounts.tree.get_missing_range(txn)
     }
 
+    /// Returns the first epoch whose history is still stored, or `None` if the history store is
+    /// empty. The history of all older epochs has been pruned.
+    pub fn first_epoch_with_history(&self) -> Option<u32> {
+        match self.history_store.history_store_range(None) {
+            (0, _) => None,
+            (first_block, _) => Some(Policy::epoch_at(first_block)),
+        }
+    }
+
     /// Removes the history of a given epoch
     pub fn remove_epoch_history(&mut self, epoch_number: u32) {
         let mut txn = self.write_transaction();
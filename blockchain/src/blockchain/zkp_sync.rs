@@ -5,6 +5,7 @@ use nimiq_blockchain_interface::{
     AbstractBlockchain, BlockchainEvent, ChainInfo, PushError, PushResult,
 };
 use nimiq_database::traits::{ReadTransaction, WriteTransaction};
+use nimiq_hash::Blake2bHash;
 use nimiq_primitives::policy::Policy;
 use nimiq_zkp::{verify::verify, NanoProof, ZKP_VERIFYING_DATA};
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
@@ -25,6 +26,31 @@ impl Blockchain {
         block: Block,
         proof: NanoProof,
         trusted_proof: bool,
+    ) -> Result<PushResult, PushError> {
+        Self::push_election_head(this, block, (!trusted_proof).then_some(proof))
+    }
+
+    /// Syncs from a trusted checkpoint. It receives the election block with the hash the operator
+    /// configured as trusted and adopts it without any proof that it descends from genesis.
+    /// Like `push_zkp`, this brings the node from the genesis block to the checkpoint.
+    pub fn push_trusted_checkpoint(
+        this: RwLockUpgradableReadGuard<Self>,
+        block: Block,
+        checkpoint: &Blake2bHash,
+    ) -> Result<PushResult, PushError> {
+        if !block.is_election() || block.hash() != *checkpoint {
+            return Err(PushError::InvalidCheckpoint);
+        }
+
+        Self::push_election_head(this, block, None)
+    }
+
+    /// Adopts the given election block as the new head, clearing the chain and history stores.
+    /// If a proof is given, it must prove that the block descends from the genesis block.
+    fn push_election_head(
+        this: RwLockUpgradableReadGuard<Self>,
+        block: Block,
+        proof: Option<NanoProof>,
     ) -> Result<PushResult, PushError> {
         // Must be an election block.
         assert!(block.is_election());
@@ -64,7 +90,7 @@ impl Blockchain {
         let genesis_hash_blake2b = genesis_macro_block.hash();
 
         // Verify the zk proof.
-        if !trusted_proof {
+        if let Some(proof) = proof {
             let verify_result = verify(
                 genesis_hash_blake2s,
                 block.unwrap_macro_ref().hash_blake2s(),
@@ -296,6 +322,9 @@ impl Blockchain {
                 // Prune the History Store.
                 this.history_store
                     .remove_history(&mut txn, Policy::epoch_at(block_number).saturating_sub(1));
+            } else {
+                // Prune the History Store according to the history retention.
+                this.prune_history(&mut txn, block_number, chain_info.head.timestamp());
             }
         }
 
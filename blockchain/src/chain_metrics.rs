@@ -3,14 +3,28 @@ use nimiq_blockchain_interface::{ChunksPushError, ChunksPushResult, PushError, P
 use nimiq_hash::Blake2bHash;
 use prometheus_client::{
     encoding::{EncodeLabelSet, EncodeLabelValue},
-    metrics::{counter::Counter, family::Family},
+    metrics::{counter::Counter, family::Family, histogram::Histogram},
     registry::Registry,
 };
 
-#[derive(Default)]
 pub struct BlockchainMetrics {
     block_push_counts: Family<PushResultLabels, Counter>,
     transactions_counts: Family<TransactionProcessedLabels, Counter>,
+    rebranch_depths: Histogram,
+    rebranched_skip_blocks: Counter,
+}
+
+impl Default for BlockchainMetrics {
+    fn default() -> Self {
+        BlockchainMetrics {
+            block_push_counts: Default::default(),
+            transactions_counts: Default::default(),
+            rebranch_depths: Histogram::new(
+                [0.0, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0].into_iter(),
+            ),
+            rebranched_skip_blocks: Default::default(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
@@ -53,6 +67,18 @@ impl BlockchainMetrics {
             "Count of transactions applied/reverted",
             self.transactions_counts.clone(),
         );
+
+        registry.register(
+            "rebranch_depths",
+            "Number of blocks reverted per rebranch",
+            self.rebranch_depths.clone(),
+        );
+
+        registry.register(
+            "rebranched_skip_blocks",
+            "Count of skip blocks reverted or adopted during rebranches",
+            self.rebranched_skip_blocks.clone(),
+        );
     }
 
     #[inline]
@@ -101,6 +127,15 @@ impl BlockchainMetrics {
         reverted_blocks: &[(Blake2bHash, Block)],
         adopted_blocks: &[(Blake2bHash, Block)],
     ) {
+        self.rebranch_depths.observe(reverted_blocks.len() as f64);
+        self.rebranched_skip_blocks.inc_by(
+            reverted_blocks
+                .iter()
+                .chain(adopted_blocks)
+                .filter(|(_, block)| block.is_skip())
+                .count() as u64,
+        );
+
         for (_, micro_block) in reverted_blocks {
             if let Some(Micro(micro_body)) = micro_block.body() {
                 self.transactions_counts
@@ -2,9 +2,16 @@
 extern crate log;
 
 pub use block_production::BlockProducer;
-pub use blockchain::blockchain::{Blockchain, BlockchainConfig, TransactionVerificationCache};
+pub use blockchain::{
+    blockchain::{Blockchain, BlockchainConfig, TransactionVerificationCache},
+    history_retention::HistoryRetention,
+    receipt_proof::ProveReceiptError,
+    simulation::{BalanceChange, TransactionSimulation},
+};
 pub use history::*;
 
+pub mod accounts_snapshot;
+pub mod archive;
 pub(crate) mod block_production;
 pub(crate) mod blockchain;
 pub(crate) mod blockchain_state;
@@ -12,4 +19,5 @@ pub(crate) mod blockchain_state;
 pub mod chain_metrics;
 pub(crate) mod chain_store;
 pub(crate) mod history;
+pub mod rebranch_log;
 pub mod reward;
@@ -0,0 +1,152 @@
+use std::io;
+
+use nimiq_database::{
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableFlags, TableProxy, WriteTransactionProxy,
+};
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_serde::{Deserialize, Serialize};
+
+/// A block that was reverted or adopted during a rebranch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RebranchedBlock {
+    pub hash: Blake2bHash,
+    pub block_number: u32,
+    /// The validator that proposed the block, if it could be determined.
+    pub proposer: Option<Address>,
+    /// Whether the block is a skip block.
+    pub is_skip: bool,
+    /// The hashes of the transactions in the block.
+    pub transactions: Vec<Blake2bHash>,
+}
+
+/// A record of a rebranch of the main chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RebranchRecord {
+    /// The sequence number of the record. It is increasing over the lifetime of the database.
+    pub id: u64,
+    /// The local time of the rebranch in milliseconds.
+    pub timestamp: u64,
+    /// The hash of the last block both branches have in common.
+    pub common_ancestor: Blake2bHash,
+    /// The block number of the common ancestor.
+    pub common_ancestor_number: u32,
+    /// The blocks of the previous main chain that were reverted, in ascending order.
+    pub reverted_blocks: Vec<RebranchedBlock>,
+    /// The blocks of the new main chain that were adopted, in ascending order.
+    pub adopted_blocks: Vec<RebranchedBlock>,
+}
+
+impl RebranchRecord {
+    /// The depth of the rebranch, i.e. the number of blocks that were reverted.
+    pub fn depth(&self) -> u32 {
+        self.reverted_blocks.len() as u32
+    }
+
+    /// Whether any of the reverted or adopted blocks is a skip block.
+    pub fn involves_skip_blocks(&self) -> bool {
+        self.reverted_blocks
+            .iter()
+            .chain(self.adopted_blocks.iter())
+            .any(|block| block.is_skip)
+    }
+}
+
+impl IntoDatabaseValue for RebranchRecord {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize_to_writer(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for RebranchRecord {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::deserialize_from_vec(bytes).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+/// A bounded, persistent log of the most recent rebranches. Once the log is full, the oldest
+/// records are dropped.
+#[derive(Debug)]
+pub struct RebranchLog {
+    db: DatabaseProxy,
+    rebranch_table: TableProxy,
+}
+
+impl RebranchLog {
+    const REBRANCH_DB_NAME: &'static str = "Rebranches";
+
+    /// The maximum number of records kept in the log.
+    pub const MAX_RECORDS: u64 = 1024;
+
+    pub fn new(db: DatabaseProxy) -> Self {
+        let rebranch_table =
+            db.open_table_with_flags(Self::REBRANCH_DB_NAME.to_string(), TableFlags::UINT_KEYS);
+        RebranchLog { db, rebranch_table }
+    }
+
+    /// Appends a new record to the log within the given transaction, assigning it the next
+    /// sequence number. Returns the record.
+    pub fn push(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        timestamp: u64,
+        common_ancestor: Blake2bHash,
+        common_ancestor_number: u32,
+        reverted_blocks: Vec<RebranchedBlock>,
+        adopted_blocks: Vec<RebranchedBlock>,
+    ) -> RebranchRecord {
+        let (first_id, id) = {
+            let mut cursor = ReadTransaction::cursor(&*txn, &self.rebranch_table);
+            let first_id = cursor.first::<u64, RebranchRecord>().map(|(id, _)| id);
+            let id = cursor
+                .last::<u64, RebranchRecord>()
+                .map_or(0, |(id, _)| id + 1);
+            (first_id, id)
+        };
+
+        let record = RebranchRecord {
+            id,
+            timestamp,
+            common_ancestor,
+            common_ancestor_number,
+            reverted_blocks,
+            adopted_blocks,
+        };
+        txn.put_reserve(&self.rebranch_table, &id, &record);
+
+        // Drop the oldest records if the log is full.
+        if let Some(first_id) = first_id {
+            for old_id in first_id..(id + 1).saturating_sub(Self::MAX_RECORDS) {
+                txn.remove(&self.rebranch_table, &old_id);
+            }
+        }
+
+        record
+    }
+
+    /// Returns up to `max` of the most recent records, newest first.
+    pub fn recent(&self, max: usize) -> Vec<RebranchRecord> {
+        let txn = self.db.read_transaction();
+        let mut cursor = txn.cursor(&self.rebranch_table);
+
+        let mut records = vec![];
+        let mut record = cursor.last::<u64, RebranchRecord>();
+        while let Some((_, rebranch)) = record {
+            if records.len() >= max {
+                break;
+            }
+            records.push(rebranch);
+            record = cursor.prev::<u64, RebranchRecord>();
+        }
+        records
+    }
+}
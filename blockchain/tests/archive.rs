@@ -0,0 +1,115 @@
+use nimiq_blockchain::{
+    archive::{ArchiveError, ArchiveImport},
+    interface::HistoryInterface,
+    Blockchain,
+};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    block_production::TemporaryBlockProducer, blockchain::produce_macro_blocks,
+};
+
+fn export(temp_producer: &TemporaryBlockProducer, macro_blocks_only: bool) -> Vec<u8> {
+    let blockchain = temp_producer.blockchain.read();
+    let mut archive = vec![];
+    blockchain
+        .export_archive(
+            &mut archive,
+            Policy::genesis_block_number() + 1,
+            blockchain.block_number(),
+            macro_blocks_only,
+        )
+        .unwrap();
+    archive
+}
+
+#[test]
+fn it_exports_and_imports_all_blocks() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    produce_macro_blocks(
+        &temp_producer1.producer,
+        &temp_producer1.blockchain,
+        Policy::batches_per_epoch() as usize + 1,
+    );
+    temp_producer1.next_block(vec![], false);
+    let archive = export(&temp_producer1, false);
+
+    let temp_producer2 = TemporaryBlockProducer::new();
+    let import = Blockchain::import_archive(&temp_producer2.blockchain, &archive[..]).unwrap();
+
+    let head = temp_producer1.blockchain.read().head();
+    let num_blocks = (head.block_number() - Policy::genesis_block_number()) as u64;
+    assert_eq!(
+        import,
+        ArchiveImport {
+            num_applied: num_blocks,
+            num_ignored: 0,
+        }
+    );
+    assert_eq!(temp_producer2.blockchain.read().head(), head);
+
+    // Importing the archive again doesn't change anything.
+    let import = Blockchain::import_archive(&temp_producer2.blockchain, &archive[..]).unwrap();
+    assert_eq!(
+        import,
+        ArchiveImport {
+            num_applied: 0,
+            num_ignored: num_blocks,
+        }
+    );
+}
+
+#[test]
+fn it_exports_and_imports_macro_blocks_with_history() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    produce_macro_blocks(
+        &temp_producer1.producer,
+        &temp_producer1.blockchain,
+        Policy::batches_per_epoch() as usize + 2,
+    );
+    let archive = export(&temp_producer1, true);
+
+    let temp_producer2 = TemporaryBlockProducer::new();
+    let import = Blockchain::import_archive(&temp_producer2.blockchain, &archive[..]).unwrap();
+
+    assert_eq!(import.num_applied, Policy::batches_per_epoch() as u64 + 2);
+    let blockchain1 = temp_producer1.blockchain.read();
+    let blockchain2 = temp_producer2.blockchain.read();
+    assert_eq!(blockchain2.head(), blockchain1.head());
+    assert_eq!(
+        blockchain2
+            .history_store
+            .get_epoch_transactions(blockchain2.epoch_number(), None),
+        blockchain1
+            .history_store
+            .get_epoch_transactions(blockchain1.epoch_number(), None)
+    );
+}
+
+#[test]
+fn it_rejects_corrupted_archives() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    produce_macro_blocks(&temp_producer1.producer, &temp_producer1.blockchain, 1);
+    let archive = export(&temp_producer1, false);
+
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    // Corrupt the checksum of the end marker.
+    let mut corrupted = archive.clone();
+    *corrupted.last_mut().unwrap() ^= 0xff;
+    assert!(matches!(
+        Blockchain::import_archive(&temp_producer2.blockchain, &corrupted[..]),
+        Err(ArchiveError::ChecksumMismatch(_))
+    ));
+
+    assert!(matches!(
+        Blockchain::import_archive(&temp_producer2.blockchain, &archive[..archive.len() - 1]),
+        Err(ArchiveError::Truncated)
+    ));
+
+    assert!(matches!(
+        Blockchain::import_archive(&temp_producer2.blockchain, &b"not an archive"[..]),
+        Err(ArchiveError::InvalidMagic)
+    ));
+}
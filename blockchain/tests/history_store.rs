@@ -2,7 +2,7 @@ use nimiq_block::{
     Block, DoubleProposalProof, DoubleVoteProof, EquivocationProof, ForkProof, MacroHeader,
     MicroHeader,
 };
-use nimiq_blockchain::interface::HistoryInterface;
+use nimiq_blockchain::{interface::HistoryInterface, BlockchainConfig, HistoryRetention};
 use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
 use nimiq_bls::AggregateSignature;
 use nimiq_database::traits::WriteTransaction;
@@ -418,3 +418,36 @@ fn it_pushes_macro_block_with_rewards() {
         i += 1;
     }
 }
+
+#[test]
+fn it_prunes_history_according_to_retention() {
+    let num_epochs = 3;
+    let producers = [
+        (None, Some(1)),
+        (Some(HistoryRetention::Epochs(1)), Some(num_epochs)),
+        (Some(HistoryRetention::Epochs(2)), Some(num_epochs - 1)),
+        // All blocks are produced within the same day.
+        (Some(HistoryRetention::Days(1)), Some(1)),
+    ];
+
+    for (history_retention, expected_first_epoch) in producers {
+        let temp_producer = TemporaryBlockProducer::with_config(BlockchainConfig {
+            history_retention,
+            ..Default::default()
+        });
+
+        produce_macro_blocks(
+            &temp_producer.producer,
+            &temp_producer.blockchain,
+            (num_epochs * Policy::batches_per_epoch() as u32) as usize,
+        );
+
+        let blockchain = temp_producer.blockchain.read();
+        assert_eq!(blockchain.epoch_number(), num_epochs);
+        assert_eq!(
+            blockchain.first_epoch_with_history(),
+            expected_first_epoch,
+            "{history_retention:?}"
+        );
+    }
+}
@@ -612,3 +612,46 @@ fn can_push_zkps() {
         );
     }
 }
+
+#[test]
+fn can_push_trusted_checkpoints() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    // Produce a full epoch of blocks.
+    for _ in 0..Policy::blocks_per_epoch() - 1 {
+        temp_producer1.next_block(vec![], false);
+    }
+    let election_block = temp_producer1.next_block(vec![], false);
+    let block_number = election_block.block_number();
+
+    // A block not matching the trusted checkpoint is rejected.
+    let blockchain2 = temp_producer2.blockchain.upgradable_read();
+    let result = Blockchain::push_trusted_checkpoint(
+        blockchain2,
+        election_block.clone(),
+        &Blake2bHash::default(),
+    );
+
+    assert_eq!(result, Err(PushError::InvalidCheckpoint));
+    assert_eq!(
+        temp_producer2.blockchain.read().block_number(),
+        Policy::genesis_block_number()
+    );
+
+    // The trusted checkpoint is adopted without a proof.
+    let checkpoint = election_block.hash();
+    let blockchain2 = temp_producer2.blockchain.upgradable_read();
+    let result = Blockchain::push_trusted_checkpoint(blockchain2, election_block, &checkpoint);
+
+    assert_eq!(result, Ok(PushResult::Extended));
+    {
+        let blockchain2_rg = temp_producer2.blockchain.read();
+        assert_eq!(blockchain2_rg.block_number(), block_number);
+        assert_eq!(blockchain2_rg.election_head_hash(), checkpoint);
+        assert_eq!(
+            blockchain2_rg.get_missing_accounts_range(None),
+            Some(KeyNibbles::ROOT..)
+        );
+    }
+}
@@ -656,3 +656,127 @@ fn can_detect_invalid_chunks() {
         ))
     );
 }
+
+#[test]
+fn can_restore_accounts_from_snapshot() {
+    let mut rng = test_rng(false);
+    let key_pair = key_pair_with_funds();
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new_incomplete();
+
+    // Produce a batch and one more block with a transaction, such that the head moved past the
+    // snapshot.
+    for _ in 0..Policy::blocks_per_batch() {
+        let (block, diff) = temp_producer1.next_block_and_diff_with_txs(vec![], false, vec![]);
+        assert_eq!(
+            temp_producer2.push_with_chunks(block, diff, vec![]),
+            Ok((PushResult::Extended, Ok(ChunksPushResult::EmptyChunks)))
+        );
+    }
+    // The snapshot of the first blockchain is rebuilt from its accounts trie.
+    while temp_producer1
+        .blockchain
+        .read()
+        .rebuild_accounts_snapshot_chunk()
+    {}
+    let tx = TransactionBuilder::new_basic(
+        &key_pair,
+        Address::from(&KeyPair::generate(&mut rng).public),
+        100.try_into().unwrap(),
+        Coin::ZERO,
+        temp_producer1.blockchain.read().block_number() + 1,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+    let (block, diff) = temp_producer1.next_block_and_diff_with_txs(vec![], false, vec![tx]);
+    assert_eq!(
+        temp_producer2.push_with_chunks(block, diff, vec![]),
+        Ok((PushResult::Extended, Ok(ChunksPushResult::EmptyChunks)))
+    );
+
+    let blockchain1 = temp_producer1.blockchain.read();
+    let blockchain2 = temp_producer2.blockchain.read();
+    let macro_head_hash = blockchain1.state.macro_head_hash.clone();
+    assert_eq!(blockchain2.state.macro_head_hash, macro_head_hash);
+
+    // Sync the snapshot in multiple chunks.
+    let mut start_key = KeyNibbles::ROOT;
+    loop {
+        let chunk = blockchain1
+            .get_accounts_snapshot_chunk(start_key.clone(), 3, &blockchain1.read_transaction())
+            .expect("Snapshot should be complete");
+        let end_key = chunk.end_key.clone();
+        assert_eq!(
+            blockchain2.commit_snapshot_chunks(
+                vec![TrieChunkWithStart { chunk, start_key }],
+                &macro_head_hash
+            ),
+            Ok(ChunksPushResult::Chunks(1, 0))
+        );
+        match end_key {
+            Some(key) => start_key = key,
+            None => break,
+        }
+    }
+
+    // The accounts trie was restored and is at the head.
+    assert!(blockchain2.state.accounts.is_complete(None));
+    assert_eq!(
+        blockchain2.state.accounts.get_root_hash(None),
+        blockchain1.state.accounts.get_root_hash(None),
+    );
+
+    // Chunks for other blocks are ignored.
+    let chunk = TrieChunkWithStart {
+        chunk: blockchain1
+            .state
+            .accounts
+            .get_chunk(KeyNibbles::ROOT, 3, None),
+        start_key: KeyNibbles::ROOT,
+    };
+    assert_eq!(
+        blockchain2.commit_snapshot_chunks(vec![chunk], &blockchain1.head_hash()),
+        Ok(ChunksPushResult::Chunks(0, 1))
+    );
+}
+
+#[test]
+fn can_rebuild_accounts_snapshot() {
+    let temp_producer = TemporaryBlockProducer::new();
+    let has_snapshot = || {
+        let blockchain = temp_producer.blockchain.read();
+        blockchain
+            .get_accounts_snapshot_chunk(KeyNibbles::ROOT, 3, &blockchain.read_transaction())
+            .is_some()
+    };
+
+    // The snapshot couldn't be advanced to the first macro block and isn't served while it is
+    // incomplete.
+    for _ in 0..Policy::blocks_per_batch() {
+        temp_producer.next_block(vec![], false);
+    }
+    assert!(!has_snapshot());
+
+    // The snapshot follows the head while it is rebuilt.
+    temp_producer.next_block(vec![], false);
+    while temp_producer
+        .blockchain
+        .read()
+        .rebuild_accounts_snapshot_chunk()
+    {}
+    assert!(!has_snapshot());
+
+    // The complete snapshot is served once it is advanced to the next macro block.
+    for _ in 1..Policy::blocks_per_batch() {
+        temp_producer.next_block(vec![], false);
+    }
+    assert!(has_snapshot());
+
+    let blockchain = temp_producer.blockchain.read();
+    let txn = blockchain.read_transaction();
+    assert_eq!(
+        blockchain.accounts_snapshot.tree.root_hash(&txn).as_ref(),
+        Some(blockchain.state.macro_info.head.state_root())
+    );
+    assert!(!blockchain.rebuild_accounts_snapshot_chunk());
+}
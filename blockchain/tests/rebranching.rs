@@ -50,6 +50,58 @@ fn it_can_rebranch_skip_block() {
     assert_eq!(temp_producer1.push(fork2), Ok(PushResult::Extended));
 }
 
+#[test]
+fn it_records_rebranches() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    // [0] - [0]
+    //    \- [1]
+    let block = temp_producer1.next_block(vec![], false);
+    temp_producer2.push(block.clone()).unwrap();
+
+    let inferior = temp_producer1.next_block(vec![], false);
+    let fork = temp_producer2.next_block(vec![], true);
+
+    let mut rebranches = temp_producer1
+        .blockchain
+        .read()
+        .rebranch_notifier
+        .subscribe();
+    assert_eq!(
+        temp_producer1.push(fork.clone()),
+        Ok(PushResult::Rebranched)
+    );
+
+    let record = rebranches.try_recv().unwrap();
+    assert_eq!(record.depth(), 1);
+    assert!(record.involves_skip_blocks());
+    assert_eq!(record.common_ancestor, block.hash());
+    assert_eq!(record.common_ancestor_number, block.block_number());
+
+    assert_eq!(record.reverted_blocks.len(), 1);
+    assert_eq!(record.reverted_blocks[0].hash, inferior.hash());
+    assert!(!record.reverted_blocks[0].is_skip);
+    assert!(record.reverted_blocks[0].proposer.is_some());
+
+    assert_eq!(record.adopted_blocks.len(), 1);
+    assert_eq!(record.adopted_blocks[0].hash, fork.hash());
+    assert!(record.adopted_blocks[0].is_skip);
+    assert_eq!(record.adopted_blocks[0].proposer, None);
+
+    // The record is persisted in the rebranch log.
+    assert_eq!(
+        temp_producer1.blockchain.read().rebranch_log.recent(10),
+        vec![record]
+    );
+    assert!(temp_producer2
+        .blockchain
+        .read()
+        .rebranch_log
+        .recent(10)
+        .is_empty());
+}
+
 #[test]
 fn micro_block_works_after_macro_block() {
     let genesis_block_number = Policy::genesis_block_number();
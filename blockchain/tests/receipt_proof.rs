@@ -0,0 +1,107 @@
+use nimiq_block::{ReceiptProofError, TransactionReceiptProof};
+use nimiq_blockchain::{interface::HistoryInterface, ProveReceiptError};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    block_production::TemporaryBlockProducer, blockchain::produce_macro_blocks_with_txns,
+};
+use nimiq_transaction::historic_transaction::{HistoricTransaction, HistoricTransactionData};
+
+fn basic_transaction(transactions: Vec<HistoricTransaction>) -> Blake2bHash {
+    transactions
+        .into_iter()
+        .find(|tx| matches!(tx.data, HistoricTransactionData::Basic(_)))
+        .unwrap()
+        .tx_hash()
+        .into()
+}
+
+#[test]
+fn it_proves_transaction_receipts() {
+    let temp_producer = TemporaryBlockProducer::new();
+    produce_macro_blocks_with_txns(
+        &temp_producer.producer,
+        &temp_producer.blockchain,
+        2 * Policy::batches_per_epoch() as usize + 1,
+        5,
+        0,
+    );
+
+    let blockchain = temp_producer.blockchain.read();
+    let genesis_block = Policy::genesis_block_number();
+    let genesis_hash = blockchain.get_genesis_hash();
+    let election_block_1 = Policy::election_block_of(1).unwrap();
+
+    // A transaction of a finalized epoch, proven against the genesis block and the election block
+    // of the epoch before it.
+    let hash = basic_transaction(blockchain.history_store.get_epoch_transactions(2, None));
+    for trusted_block in [genesis_block, election_block_1] {
+        let proof = blockchain
+            .prove_transaction_receipt(&hash, trusted_block)
+            .unwrap();
+        let trusted_hash = blockchain
+            .get_block_at(trusted_block, false, None)
+            .unwrap()
+            .hash();
+
+        // The proof survives serialization.
+        let proof =
+            TransactionReceiptProof::deserialize_from_vec(&proof.serialize_to_vec()).unwrap();
+        let transactions = proof
+            .verify(&trusted_hash, NetworkId::UnitAlbatross)
+            .unwrap();
+        assert!(transactions
+            .iter()
+            .any(|tx| Blake2bHash::from(tx.tx_hash()) == hash));
+        assert_eq!(
+            proof.block.block_number(),
+            Policy::election_block_of(2).unwrap()
+        );
+    }
+
+    // A transaction of the current epoch is proven by the latest checkpoint block.
+    let hash = basic_transaction(
+        blockchain
+            .history_store
+            .get_epoch_transactions(blockchain.epoch_number(), None),
+    );
+    let proof = blockchain
+        .prove_transaction_receipt(&hash, genesis_block)
+        .unwrap();
+    proof
+        .verify(&genesis_hash, NetworkId::UnitAlbatross)
+        .unwrap();
+    assert_eq!(
+        proof.block.block_number(),
+        blockchain.macro_head().block_number()
+    );
+
+    // The proof can't be verified against a different trusted block or network.
+    assert_eq!(
+        proof.verify(&Blake2bHash::default(), NetworkId::UnitAlbatross),
+        Err(ReceiptProofError::UntrustedBlock)
+    );
+    assert!(matches!(
+        proof.verify(&genesis_hash, NetworkId::TestAlbatross),
+        Err(ReceiptProofError::InvalidBlock(..))
+    ));
+
+    // Only election blocks before the transaction can be trusted.
+    assert_eq!(
+        blockchain
+            .prove_transaction_receipt(&hash, genesis_block + 1)
+            .err(),
+        Some(ProveReceiptError::NotAnElectionBlock(genesis_block + 1))
+    );
+    assert_eq!(
+        blockchain
+            .prove_transaction_receipt(&Blake2bHash::default(), genesis_block)
+            .err(),
+        Some(ProveReceiptError::TransactionNotFound(
+            Blake2bHash::default()
+        ))
+    );
+}
@@ -0,0 +1,143 @@
+use std::{convert::TryInto, sync::Arc};
+
+use nimiq_account::Log;
+use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::{AbstractBlockchain, SimulationError};
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_genesis::NetworkId;
+use nimiq_keys::{Address, KeyPair as SchnorrKeyPair, PrivateKey as SchnorrPrivateKey};
+use nimiq_primitives::{account::FailReason, coin::Coin, transaction::TransactionError};
+use nimiq_serde::Deserialize;
+use nimiq_test_log::test;
+use nimiq_transaction_builder::TransactionBuilder;
+use nimiq_utils::time::OffsetTime;
+
+const ACCOUNT_SECRET_KEY: &str = "6c9320ac201caf1f8eaa5b05f5d67a9e77826f3f6be266a0ecccc20416dc6587";
+
+fn ed25519_key_pair(secret_key: &str) -> SchnorrKeyPair {
+    let priv_key =
+        SchnorrPrivateKey::deserialize_from_vec(&hex::decode(secret_key).unwrap()).unwrap();
+    priv_key.into()
+}
+
+fn blockchain() -> Blockchain {
+    let time = Arc::new(OffsetTime::new());
+    let env = VolatileDatabase::new(20).unwrap();
+    Blockchain::new(
+        env,
+        BlockchainConfig::default(),
+        NetworkId::UnitAlbatross,
+        time,
+    )
+    .unwrap()
+}
+
+#[test]
+fn it_can_simulate_transactions_without_changing_state() {
+    let blockchain = blockchain();
+    let key_pair = ed25519_key_pair(ACCOUNT_SECRET_KEY);
+    let sender = Address::from(&key_pair.public);
+    let recipient = Address::from([1u8; Address::SIZE]);
+
+    let initial_root = blockchain.state.accounts.get_root_hash_assert(None);
+    let initial_balance = blockchain
+        .state
+        .accounts
+        .get_complete(&sender, None)
+        .balance();
+
+    let tx = TransactionBuilder::new_basic(
+        &key_pair,
+        recipient.clone(),
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(100),
+        blockchain.block_number(),
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+
+    let simulation = blockchain.simulate_transaction(&tx).unwrap();
+
+    assert_eq!(simulation.block_number, blockchain.block_number() + 1);
+    assert_eq!(simulation.fail_reason(), None);
+    assert!(simulation
+        .logs
+        .iter()
+        .any(|log| matches!(log, Log::Transfer { .. })));
+
+    assert_eq!(simulation.balance_changes.len(), 2);
+    assert_eq!(simulation.balance_changes[0].address, sender);
+    assert_eq!(
+        simulation.balance_changes[0].balance_before,
+        initial_balance
+    );
+    assert_eq!(
+        simulation.balance_changes[0].balance_after,
+        initial_balance - Coin::from_u64_unchecked(1100)
+    );
+    assert_eq!(simulation.balance_changes[1].address, recipient);
+    assert_eq!(
+        simulation.balance_changes[1].balance_after,
+        simulation.balance_changes[1].balance_before + Coin::from_u64_unchecked(1000)
+    );
+
+    // The simulation must not leave any trace in the accounts tree.
+    assert_eq!(
+        blockchain.state.accounts.get_root_hash_assert(None),
+        initial_root
+    );
+    assert_eq!(
+        blockchain
+            .state
+            .accounts
+            .get_complete(&sender, None)
+            .balance(),
+        initial_balance
+    );
+}
+
+#[test]
+fn it_reports_failed_and_invalid_transactions() {
+    let blockchain = blockchain();
+    let key_pair = ed25519_key_pair(ACCOUNT_SECRET_KEY);
+    let sender = Address::from(&key_pair.public);
+
+    // Delegating to a validator that does not exist fails, but the fee is still paid.
+    let tx = TransactionBuilder::new_create_staker(
+        &key_pair,
+        &key_pair,
+        Some(Address::from([2u8; Address::SIZE])),
+        100_000_000.try_into().unwrap(),
+        200.try_into().unwrap(),
+        blockchain.block_number(),
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap();
+
+    let simulation = blockchain.simulate_transaction(&tx).unwrap();
+    assert_eq!(
+        simulation.fail_reason(),
+        Some(FailReason::NonExistentAddress)
+    );
+    assert_eq!(simulation.balance_changes[0].address, sender);
+    assert_eq!(
+        simulation.balance_changes[0].balance_after,
+        simulation.balance_changes[0].balance_before - Coin::from_u64_unchecked(200)
+    );
+
+    // A transaction for a foreign network is rejected by the intrinsic verification.
+    let tx = TransactionBuilder::new_basic(
+        &key_pair,
+        Address::from([1u8; Address::SIZE]),
+        Coin::from_u64_unchecked(1000),
+        Coin::from_u64_unchecked(100),
+        blockchain.block_number(),
+        NetworkId::TestAlbatross,
+    )
+    .unwrap();
+
+    assert_eq!(
+        blockchain.simulate_transaction(&tx).unwrap_err(),
+        SimulationError::InvalidTransaction(TransactionError::ForeignNetwork)
+    );
+}
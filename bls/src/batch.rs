@@ -0,0 +1,144 @@
+use ark_ec::{pairing::Pairing, Group};
+use ark_ff::Zero;
+use ark_mnt6_753::{Fr, G1Projective, G2Projective, MNT6_753};
+use nimiq_hash::Hash;
+use nimiq_utils::key_rng::{RngCore, SecureRng};
+
+use crate::{AggregatePublicKey, AggregateSignature, PublicKey, SigHash, Signature};
+
+/// A single signature check of a [`BatchVerifier`].
+#[derive(Clone)]
+struct BatchItem {
+    public_key: G2Projective,
+    hash: SigHash,
+    signature: G1Projective,
+}
+
+/// Collects signature checks and verifies all of them at once.
+///
+/// Instead of checking `e(sig_i, g2) == e(H(m_i), pk_i)` for every signature individually, the
+/// batch is verified with a random linear combination of the checks:
+/// `e(sum(r_i * sig_i), g2) == prod(e(r_i * H(m_i), pk_i))`, where the `r_i` are random 128-bit
+/// scalars. This only needs a single final exponentiation for the whole batch. The random
+/// scalars ensure that invalid signatures can't cancel each other out, a batch containing an
+/// invalid signature passes with a probability of at most 2^-128.
+///
+/// A failing batch does not tell which of its signatures is invalid. Callers that need to know
+/// have to verify the signatures individually afterwards.
+#[derive(Clone, Default)]
+pub struct BatchVerifier {
+    items: Vec<BatchItem>,
+}
+
+impl BatchVerifier {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of signatures in the batch.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Adds a signature over the given message to the batch.
+    pub fn add<M: Hash>(&mut self, public_key: &PublicKey, msg: &M, signature: &Signature) {
+        self.add_hash(public_key, msg.hash(), signature);
+    }
+
+    /// Adds a signature over the given hash to the batch.
+    pub fn add_hash(&mut self, public_key: &PublicKey, hash: SigHash, signature: &Signature) {
+        self.items.push(BatchItem {
+            public_key: public_key.public_key,
+            hash,
+            signature: signature.signature,
+        });
+    }
+
+    /// Adds an aggregate signature over the given message to the batch.
+    pub fn add_aggregate<M: Hash>(
+        &mut self,
+        public_key: &AggregatePublicKey,
+        msg: &M,
+        signature: &AggregateSignature,
+    ) {
+        self.add(&public_key.0, msg, &signature.0);
+    }
+
+    /// Adds an aggregate signature over the given hash to the batch.
+    pub fn add_aggregate_hash(
+        &mut self,
+        public_key: &AggregatePublicKey,
+        hash: SigHash,
+        signature: &AggregateSignature,
+    ) {
+        self.add_hash(&public_key.0, hash, &signature.0);
+    }
+
+    /// Moves all signatures of `other` into this batch.
+    pub fn append(&mut self, other: &mut Self) {
+        self.items.append(&mut other.items);
+    }
+
+    /// Splits the batch into at most `count` batches of roughly equal size.
+    pub fn split(self, count: usize) -> Vec<Self> {
+        if self.items.is_empty() {
+            return vec![];
+        }
+        let chunk_size = self.items.len().div_ceil(count.max(1));
+        self.items
+            .chunks(chunk_size)
+            .map(|items| Self {
+                items: items.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Verifies all signatures in the batch on the calling thread. An empty batch is valid. Like
+    /// [`PublicKey::verify_g1`], the batch is invalid if any of its public keys is the point at
+    /// infinity.
+    pub fn verify(&self) -> bool {
+        if self.items.iter().any(|item| item.public_key.is_zero()) {
+            return false;
+        }
+
+        // A single signature doesn't need to be randomized.
+        if let [item] = self.items.as_slice() {
+            return Self::check(
+                item.signature,
+                [Signature::hash_to_g1(item.hash.clone())],
+                [item.public_key],
+            );
+        }
+
+        let mut rng = SecureRng::default();
+        let mut signature = G1Projective::zero();
+        let mut hashes = Vec::with_capacity(self.items.len());
+        let mut public_keys = Vec::with_capacity(self.items.len());
+        for item in &self.items {
+            let r = Fr::from(((rng.next_u64() as u128) << 64) | rng.next_u64() as u128);
+            signature += item.signature * r;
+            hashes.push(Signature::hash_to_g1(item.hash.clone()) * r);
+            public_keys.push(item.public_key);
+        }
+
+        Self::check(signature, hashes, public_keys)
+    }
+
+    /// Checks `e(signature, g2) == prod(e(hashes_i, public_keys_i))` with a single final
+    /// exponentiation.
+    fn check(
+        signature: G1Projective,
+        hashes: impl IntoIterator<Item = G1Projective>,
+        public_keys: impl IntoIterator<Item = G2Projective>,
+    ) -> bool {
+        let g1 = std::iter::once(signature).chain(hashes);
+        let g2 = std::iter::once(-G2Projective::generator()).chain(public_keys);
+        MNT6_753::multi_pairing(g1, g2).is_zero()
+    }
+}
@@ -1,13 +1,138 @@
 use std::collections::HashMap;
 
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_mnt6_753::G2Affine;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(feature = "database-storage")]
+use nimiq_database::{
+    traits::{Database, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableProxy,
+};
+
 use crate::{lazy::LazyPublicKey, CompressedPublicKey, PublicKey};
 
+/// Defines an interface for persisting uncompressed public keys across restarts.
+pub trait PublicKeyStore: Send {
+    /// Gets the uncompressed form of the given compressed public key, if it is stored.
+    fn get(&self, compressed_key: &CompressedPublicKey) -> Option<PublicKey>;
+
+    /// Returns whether the uncompressed form of the given compressed public key is stored.
+    fn contains(&self, compressed_key: &CompressedPublicKey) -> bool {
+        self.get(compressed_key).is_some()
+    }
+
+    /// Stores the uncompressed form of the given compressed public key.
+    fn put(&self, compressed_key: &CompressedPublicKey, public_key: &PublicKey);
+
+    /// Stores the uncompressed forms of all given compressed public keys at once.
+    fn put_all(&self, keys: &[(CompressedPublicKey, PublicKey)]) {
+        for (compressed_key, public_key) in keys {
+            self.put(compressed_key, public_key);
+        }
+    }
+}
+
+#[cfg(feature = "database-storage")]
+/// DB implementation of a PublicKeyStore meant for persistent storage
+#[derive(Debug)]
+pub struct DBPublicKeyStore {
+    /// Environment for the DB creation and transaction handling.
+    env: DatabaseProxy,
+    /// A database of uncompressed public keys, keyed by the compressed public key.
+    public_key_db: TableProxy,
+}
+
+#[cfg(feature = "database-storage")]
+impl DBPublicKeyStore {
+    const PUBLIC_KEY_DB_NAME: &'static str = "BlsPublicKeys";
+
+    pub fn new(env: DatabaseProxy) -> Self {
+        let public_key_db = env.open_table(Self::PUBLIC_KEY_DB_NAME.to_string());
+
+        Self { env, public_key_db }
+    }
+}
+
+#[cfg(feature = "database-storage")]
+impl PublicKeyStore for DBPublicKeyStore {
+    fn get(&self, compressed_key: &CompressedPublicKey) -> Option<PublicKey> {
+        let bytes: Vec<u8> = self
+            .env
+            .read_transaction()
+            .get(&self.public_key_db, compressed_key.as_ref())?;
+        deserialize_public_key(compressed_key, &bytes)
+    }
+
+    fn contains(&self, compressed_key: &CompressedPublicKey) -> bool {
+        self.env
+            .read_transaction()
+            .get::<_, Vec<u8>>(&self.public_key_db, compressed_key.as_ref())
+            .is_some()
+    }
+
+    fn put(&self, compressed_key: &CompressedPublicKey, public_key: &PublicKey) {
+        let mut tx = self.env.write_transaction();
+        tx.put(
+            &self.public_key_db,
+            compressed_key.as_ref(),
+            &serialize_public_key(public_key),
+        );
+        tx.commit();
+    }
+
+    fn put_all(&self, keys: &[(CompressedPublicKey, PublicKey)]) {
+        let mut tx = self.env.write_transaction();
+        for (compressed_key, public_key) in keys {
+            tx.put(
+                &self.public_key_db,
+                compressed_key.as_ref(),
+                &serialize_public_key(public_key),
+            );
+        }
+        tx.commit();
+    }
+}
+
+/// Serializes a public key in its uncompressed form, which is much faster to deserialize than
+/// the compressed form.
+fn serialize_public_key(public_key: &PublicKey) -> Vec<u8> {
+    let mut bytes = vec![];
+    public_key
+        .public_key
+        .into_affine()
+        .serialize_uncompressed(&mut bytes)
+        .expect("Serializing into a vector can't fail");
+    bytes
+}
+
+/// Deserializes a public key serialized with [`serialize_public_key`]. Returns `None` if the
+/// bytes aren't the uncompressed form of `compressed_key`.
+///
+/// The expensive subgroup check is skipped. Instead, the point must be on the curve and have the
+/// same compressed form as `compressed_key`, which determines the point uniquely.
+fn deserialize_public_key(compressed_key: &CompressedPublicKey, bytes: &[u8]) -> Option<PublicKey> {
+    let point = G2Affine::deserialize_uncompressed_unchecked(bytes).ok()?;
+    if !point.is_on_curve() {
+        return None;
+    }
+    let public_key = PublicKey {
+        public_key: point.into_group(),
+    };
+    (public_key.compress() == *compressed_key).then_some(public_key)
+}
+
 /// An implementation of a max capacity cache using a hashmap for the public keys.
 /// The replacement policy in use removes an arbitrary element.
+///
+/// The cache can optionally be backed by a [`PublicKeyStore`], which persists the uncompressed
+/// keys of accepted validator sets so that they don't need to be uncompressed again after a
+/// restart. Only [`warm_up`](Self::warm_up) writes to the store, since other lookups may be
+/// for keys of blocks that haven't been verified yet.
 pub struct PublicKeyCache {
     // FIXME: Change to a map with good caching strategy.
     cache: HashMap<CompressedPublicKey, PublicKey>,
     max_capacity: usize,
+    store: Option<Box<dyn PublicKeyStore>>,
 }
 
 impl PublicKeyCache {
@@ -16,23 +141,64 @@ impl PublicKeyCache {
         PublicKeyCache {
             cache: HashMap::with_capacity(max_capacity),
             max_capacity,
+            store: None,
+        }
+    }
+
+    /// Creates a new cache with the specified maximum capacity that is backed by the given store.
+    pub fn with_store(max_capacity: usize, store: Box<dyn PublicKeyStore>) -> Self {
+        PublicKeyCache {
+            cache: HashMap::with_capacity(max_capacity),
+            max_capacity,
+            store: Some(store),
         }
     }
 
     /// Gets the corresponding uncompressed key by retrieving it from the cache.
-    /// If the value isn't cached, it uncompresses the pk and caches it.
+    /// If the value isn't cached, it is looked up in the store. If it isn't stored either, it
+    /// uncompresses the pk and caches it, without storing it.
     pub fn get_or_uncompress(&mut self, compressed_key: &CompressedPublicKey) -> Option<PublicKey> {
         // First check if we have the uncompressed key cached.
         if let Some(uncompressed_key) = self.cache.get(compressed_key) {
-            Some(*uncompressed_key)
-        } else {
-            // If not, we try uncompressing it.
-            let uncompressed_key = compressed_key.uncompress().ok();
-            if let Some(uncompressed_key) = uncompressed_key {
-                // Upon success, we store the uncompressed key.
-                self.put_if_absent(compressed_key.clone(), uncompressed_key);
+            return Some(*uncompressed_key);
+        }
+
+        // Then check if we have it stored.
+        if let Some(uncompressed_key) = self
+            .store
+            .as_ref()
+            .and_then(|store| store.get(compressed_key))
+        {
+            self.put_if_absent(compressed_key.clone(), uncompressed_key);
+            return Some(uncompressed_key);
+        }
+
+        // If not, we try uncompressing it.
+        let uncompressed_key = compressed_key.uncompress().ok();
+        if let Some(uncompressed_key) = uncompressed_key {
+            // Upon success, we store the uncompressed key in our cache.
+            self.put_if_absent(compressed_key.clone(), uncompressed_key);
+        }
+        uncompressed_key
+    }
+
+    /// Uncompresses the given lazy public keys using the cache and persists them in the store,
+    /// e.g. the voting keys of a validator set at startup or of an accepted election block.
+    /// The keys that aren't stored yet are stored at once.
+    pub fn warm_up<'a, I: IntoIterator<Item = &'a LazyPublicKey>>(&mut self, keys: I) {
+        let mut uncompressed_keys = vec![];
+        for key in keys {
+            self.get_or_uncompress_lazy_public_key(key);
+            if let (Some(store), Some(uncompressed_key)) = (&self.store, *key.cache.read()) {
+                if !store.contains(&key.compressed) {
+                    uncompressed_keys.push((key.compressed.clone(), uncompressed_key));
+                }
+            }
+        }
+        if let Some(store) = &self.store {
+            if !uncompressed_keys.is_empty() {
+                store.put_all(&uncompressed_keys);
             }
-            uncompressed_key
         }
     }
 
@@ -8,6 +8,12 @@ pub mod lazy;
 // Implements all of the types needed to do BLS signatures.
 mod types;
 
+// Implements batched verification of many signatures at once.
+pub mod batch;
+
+// A bounded thread pool verifying signature batches.
+pub mod pool;
+
 // Specifies the hash algorithm used for signatures
 pub type SigHash = Blake2sHash;
 
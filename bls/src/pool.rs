@@ -0,0 +1,148 @@
+use std::{num::NonZeroUsize, sync::OnceLock};
+#[cfg(not(target_family = "wasm"))]
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use crate::batch::BatchVerifier;
+
+/// The minimum number of signatures a part of a batch must have to be verified on a worker
+/// thread. Smaller batches are verified on the calling thread.
+const MIN_SIGNATURES_PER_WORKER: usize = 2;
+
+/// The maximum number of worker threads of the global pool.
+const MAX_GLOBAL_THREADS: usize = 8;
+
+#[cfg(not(target_family = "wasm"))]
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A bounded pool of threads verifying [`BatchVerifier`] batches.
+///
+/// Large batches are split across the worker threads, while the calling thread verifies a part
+/// of the batch itself and then waits for the workers. The queue of pending jobs is bounded,
+/// parts of a batch that don't fit into the queue are verified on the calling thread instead.
+/// On wasm, or if the pool has no worker threads, all batches are verified on the calling
+/// thread.
+pub struct VerificationPool {
+    num_threads: usize,
+    #[cfg(not(target_family = "wasm"))]
+    jobs: Option<SyncSender<Job>>,
+}
+
+impl VerificationPool {
+    /// Creates a pool with `num_threads` worker threads and room for `queue_size` pending jobs.
+    pub fn new(num_threads: usize, queue_size: usize) -> Self {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            if num_threads == 0 {
+                return Self {
+                    num_threads,
+                    jobs: None,
+                };
+            }
+
+            let (sender, receiver) = mpsc::sync_channel::<Job>(queue_size);
+            let receiver = Arc::new(Mutex::new(receiver));
+            for i in 0..num_threads {
+                let receiver = Arc::clone(&receiver);
+                thread::Builder::new()
+                    .name(format!("bls-verify-{}", i))
+                    .spawn(move || loop {
+                        // The lock guard is dropped before the job runs.
+                        let job = receiver.lock().unwrap().recv();
+                        match job {
+                            // Keep the worker alive if a job panics, the job reports its failure.
+                            Ok(job) => {
+                                if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                                    log::error!("BLS verification job panicked");
+                                }
+                            }
+                            Err(_) => break,
+                        }
+                    })
+                    .expect("Failed to spawn BLS verification thread");
+            }
+
+            Self {
+                num_threads,
+                jobs: Some(sender),
+            }
+        }
+
+        #[cfg(target_family = "wasm")]
+        {
+            let _ = queue_size;
+            Self { num_threads: 0 }
+        }
+    }
+
+    /// Returns the pool shared by the whole process. It has one worker thread per available
+    /// CPU core, up to a maximum of 8.
+    pub fn global() -> &'static VerificationPool {
+        static POOL: OnceLock<VerificationPool> = OnceLock::new();
+        POOL.get_or_init(|| {
+            let num_threads = std::thread::available_parallelism()
+                .map_or(1, NonZeroUsize::get)
+                .min(MAX_GLOBAL_THREADS);
+            VerificationPool::new(num_threads, 4 * num_threads)
+        })
+    }
+
+    /// Returns the number of worker threads of the pool.
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Verifies all signatures in the batch, see [`BatchVerifier::verify`]. Blocks until the
+    /// whole batch is verified.
+    pub fn verify(&self, batch: BatchVerifier) -> bool {
+        let num_parts = (batch.len() / MIN_SIGNATURES_PER_WORKER).min(self.num_threads + 1);
+        if num_parts <= 1 {
+            return batch.verify();
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let Some(jobs) = &self.jobs else {
+                return batch.verify();
+            };
+
+            let mut parts = batch.split(num_parts);
+            // The last part is always verified on the calling thread.
+            let own_part = parts.pop().expect("Batch has more than one part");
+
+            let num_jobs = parts.len();
+            let (results_tx, results_rx) = mpsc::channel();
+            for part in parts {
+                let results_tx = results_tx.clone();
+                let job: Job = Box::new(move || {
+                    // A part that panicked while being verified is invalid.
+                    let valid =
+                        panic::catch_unwind(AssertUnwindSafe(|| part.verify())).unwrap_or(false);
+                    results_tx.send(valid).ok();
+                });
+                if let Err(TrySendError::Full(job) | TrySendError::Disconnected(job)) =
+                    jobs.try_send(job)
+                {
+                    // The queue is full, verify the part ourselves.
+                    job();
+                }
+            }
+            drop(results_tx);
+
+            // The results channel is closed once all jobs ran, or were dropped from the queue
+            // without running. A job that didn't send its result makes the batch invalid.
+            let own_result = own_part.verify();
+            let results: Vec<bool> = results_rx.iter().collect();
+            own_result && results.len() == num_jobs && results.into_iter().all(|valid| valid)
+        }
+
+        #[cfg(target_family = "wasm")]
+        batch.verify()
+    }
+}
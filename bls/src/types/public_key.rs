@@ -49,9 +49,12 @@ impl PublicKey {
         if self.public_key.is_zero() {
             return false;
         }
-        let lhs = MNT6_753::pairing(signature.signature, G2Projective::generator());
-        let rhs = MNT6_753::pairing(hash_curve, self.public_key);
-        lhs == rhs
+        // Checks e(signature, g2) == e(hash_curve, public_key) with a single final exponentiation.
+        MNT6_753::multi_pairing(
+            [signature.signature, hash_curve],
+            [-G2Projective::generator(), self.public_key],
+        )
+        .is_zero()
     }
 
     /// Transforms a public key into a serialized compressed form.
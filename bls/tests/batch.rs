@@ -0,0 +1,127 @@
+use nimiq_bls::{batch::BatchVerifier, pool::VerificationPool, *};
+use nimiq_test_log::test;
+use nimiq_test_utils::test_rng::test_rng;
+use nimiq_utils::key_rng::SecureGenerate;
+
+// Warning: You really should run these tests on release mode. Otherwise it will take too long.
+
+fn signed_batch(size: usize) -> (BatchVerifier, Vec<(KeyPair, String, Signature)>) {
+    let rng = &mut test_rng(false);
+    let mut batch = BatchVerifier::new();
+    let mut signatures = vec![];
+
+    for i in 0..size {
+        let keypair = KeyPair::generate(rng);
+        let message = format!("Message {}", i);
+        let sig = keypair.sign(&message);
+        batch.add(&keypair.public_key, &message, &sig);
+        signatures.push((keypair, message, sig));
+    }
+
+    (batch, signatures)
+}
+
+#[test]
+fn batch_verify_valid_signatures() {
+    assert!(BatchVerifier::new().verify());
+
+    for size in [1, 2, 5] {
+        let (batch, _) = signed_batch(size);
+        assert_eq!(batch.len(), size);
+        assert!(batch.verify());
+    }
+}
+
+#[test]
+fn batch_verify_rejects_invalid_signature() {
+    let (mut batch, signatures) = signed_batch(4);
+
+    // A signature of the wrong key.
+    let (_, message, _) = &signatures[0];
+    let (other_keypair, _, _) = &signatures[1];
+    batch.add(
+        &signatures[2].0.public_key,
+        message,
+        &other_keypair.sign(message),
+    );
+    assert!(!batch.verify());
+
+    // A single invalid signature.
+    let mut batch = BatchVerifier::new();
+    batch.add(&other_keypair.public_key, message, &signatures[0].2);
+    assert!(!batch.verify());
+}
+
+#[test]
+fn batch_verify_rejects_cancelling_signatures() {
+    let (_, signatures) = signed_batch(2);
+    let (keypair1, message1, sig1) = &signatures[0];
+    let (keypair2, message2, sig2) = &signatures[1];
+
+    // The sum of the two signatures is unchanged, which would pass a naive aggregated check.
+    let delta = keypair1.sign(&"Delta".to_string()).signature;
+    let forged1 = Signature::from(sig1.signature + delta);
+    let forged2 = Signature::from(sig2.signature - delta);
+
+    let mut batch = BatchVerifier::new();
+    batch.add(&keypair1.public_key, message1, &forged1);
+    batch.add(&keypair2.public_key, message2, &forged2);
+    assert!(!batch.verify());
+}
+
+#[test]
+fn batch_verify_aggregate_signatures() {
+    let (_, signatures) = signed_batch(3);
+    let message = "Same message".to_string();
+
+    let mut agg_pk = AggregatePublicKey::new();
+    let mut agg_sig = AggregateSignature::new();
+    for (keypair, _, _) in &signatures {
+        agg_pk.aggregate(&keypair.public_key);
+        agg_sig.aggregate(&keypair.sign(&message));
+    }
+
+    let mut batch = BatchVerifier::new();
+    batch.add_aggregate(&agg_pk, &message, &agg_sig);
+    let (mut other, _) = signed_batch(2);
+    batch.append(&mut other);
+    assert!(other.is_empty());
+    assert!(batch.verify());
+
+    // An empty aggregate public key is never valid.
+    let mut batch = BatchVerifier::new();
+    batch.add_aggregate(&AggregatePublicKey::new(), &message, &agg_sig);
+    assert!(!batch.verify());
+}
+
+#[test]
+fn pool_verifies_batches() {
+    for num_threads in [0, 1, 3] {
+        let pool = VerificationPool::new(num_threads, 1);
+        assert_eq!(pool.num_threads(), num_threads);
+
+        let (batch, signatures) = signed_batch(8);
+        assert!(pool.verify(batch.clone()));
+        assert!(pool.verify(BatchVerifier::new()));
+
+        let mut invalid = batch;
+        let (keypair, message, _) = &signatures[0];
+        invalid.add(&keypair.public_key, message, &signatures[1].2);
+        assert!(!pool.verify(invalid));
+    }
+
+    let (batch, _) = signed_batch(4);
+    assert!(VerificationPool::global().verify(batch));
+}
+
+#[test]
+fn split_batches() {
+    let (batch, _) = signed_batch(5);
+    let parts = batch.split(2);
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].len(), 3);
+    assert_eq!(parts[1].len(), 2);
+    assert!(parts.iter().all(BatchVerifier::verify));
+
+    assert!(BatchVerifier::new().split(3).is_empty());
+}
@@ -1,6 +1,17 @@
-use std::cmp;
-
-use nimiq_bls::{cache::PublicKeyCache, lazy::LazyPublicKey, KeyPair};
+use std::{
+    cmp,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use nimiq_bls::{
+    cache::{PublicKeyCache, PublicKeyStore},
+    lazy::LazyPublicKey,
+    CompressedPublicKey, KeyPair, PublicKey,
+};
 use nimiq_test_utils::test_rng::test_rng;
 use nimiq_utils::key_rng::SecureGenerate;
 
@@ -74,3 +85,135 @@ fn does_not_store_duplicates() {
     );
     assert_eq!(cache.len(), 1, "should not store duplicates");
 }
+
+#[derive(Clone, Default)]
+struct MemoryStore(
+    Arc<Mutex<HashMap<CompressedPublicKey, PublicKey>>>,
+    Arc<AtomicUsize>,
+);
+
+impl PublicKeyStore for MemoryStore {
+    fn get(&self, compressed_key: &CompressedPublicKey) -> Option<PublicKey> {
+        self.0.lock().unwrap().get(compressed_key).copied()
+    }
+
+    fn put(&self, compressed_key: &CompressedPublicKey, public_key: &PublicKey) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(compressed_key.clone(), *public_key);
+        self.1.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn put_all(&self, keys: &[(CompressedPublicKey, PublicKey)]) {
+        self.0.lock().unwrap().extend(keys.iter().cloned());
+        self.1.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn persists_uncompressed_keys_in_store() {
+    let store = MemoryStore::default();
+    let mut cache = PublicKeyCache::with_store(1, Box::new(store.clone()));
+
+    let rng = &mut test_rng(false);
+
+    let keypairs: Vec<KeyPair> = (0..2).map(|_| KeyPair::generate(rng)).collect();
+    let lazy_keys: Vec<LazyPublicKey> = keypairs
+        .iter()
+        .map(|keypair| LazyPublicKey::from_compressed(&keypair.public_key.compress()))
+        .collect();
+    cache.warm_up(&lazy_keys);
+
+    assert!(lazy_keys.iter().all(LazyPublicKey::has_uncompressed));
+    assert_eq!(cache.len(), 1, "should enforce maximum");
+    assert_eq!(store.0.lock().unwrap().len(), 2, "should store all keys");
+    assert_eq!(
+        store.1.load(Ordering::Relaxed),
+        1,
+        "should store all keys at once"
+    );
+
+    // A new cache, e.g. after a restart, gets the keys from the store.
+    let mut cache = PublicKeyCache::with_store(2, Box::new(store.clone()));
+    for keypair in &keypairs {
+        assert_eq!(
+            cache
+                .get_or_uncompress(&keypair.public_key.compress())
+                .unwrap(),
+            keypair.public_key
+        );
+    }
+    assert_eq!(cache.len(), 2);
+    assert_eq!(store.0.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn only_persists_warmed_up_keys() {
+    let store = MemoryStore::default();
+    let mut cache = PublicKeyCache::with_store(2, Box::new(store.clone()));
+
+    let rng = &mut test_rng(false);
+
+    // Keys that are merely looked up, e.g. for unverified blocks, aren't stored.
+    let keypairs: Vec<KeyPair> = (0..2).map(|_| KeyPair::generate(rng)).collect();
+    let lazy_keys: Vec<LazyPublicKey> = keypairs
+        .iter()
+        .map(|keypair| LazyPublicKey::from_compressed(&keypair.public_key.compress()))
+        .collect();
+    assert_eq!(
+        cache
+            .get_or_uncompress(&keypairs[0].public_key.compress())
+            .unwrap(),
+        keypairs[0].public_key
+    );
+    cache.get_or_uncompress_lazy_public_key(&lazy_keys[1]);
+    assert!(lazy_keys[1].has_uncompressed());
+    assert!(store.0.lock().unwrap().is_empty(), "should not store keys");
+
+    // Warming up stores the keys, even if they are uncompressed already.
+    cache.warm_up(&lazy_keys);
+    assert_eq!(store.0.lock().unwrap().len(), 2, "should store all keys");
+    assert_eq!(store.1.load(Ordering::Relaxed), 1);
+
+    // Keys that are stored already aren't stored again.
+    cache.warm_up(&lazy_keys);
+    assert_eq!(store.1.load(Ordering::Relaxed), 1, "should not store again");
+}
+
+#[cfg(feature = "database-storage")]
+#[test]
+fn persists_uncompressed_keys_in_database() {
+    use nimiq_bls::cache::DBPublicKeyStore;
+    use nimiq_database::volatile::VolatileDatabase;
+
+    let env = VolatileDatabase::new(1).unwrap();
+    let store = DBPublicKeyStore::new(env.clone());
+
+    let rng = &mut test_rng(false);
+    let keypair = KeyPair::generate(rng);
+    let other_keypair = KeyPair::generate(rng);
+    let compressed_key = keypair.public_key.compress();
+
+    assert_eq!(store.get(&compressed_key), None);
+    assert!(!store.contains(&compressed_key));
+    store.put(&compressed_key, &keypair.public_key);
+    assert_eq!(store.get(&compressed_key), Some(keypair.public_key));
+    assert!(store.contains(&compressed_key));
+
+    // A stored key that doesn't match its compressed form is ignored.
+    let other_compressed_key = other_keypair.public_key.compress();
+    store.put(&other_compressed_key, &keypair.public_key);
+    assert_eq!(store.get(&other_compressed_key), None);
+
+    // Keys can be stored together.
+    store.put_all(&[(other_compressed_key.clone(), other_keypair.public_key)]);
+    assert_eq!(
+        store.get(&other_compressed_key),
+        Some(other_keypair.public_key)
+    );
+
+    // The keys survive reopening the store.
+    let store = DBPublicKeyStore::new(env);
+    assert_eq!(store.get(&compressed_key), Some(keypair.public_key));
+}
@@ -6,6 +6,7 @@ use nimiq::client::Client;
 use nimiq::config::{command_line::CommandLine, config::ClientConfig, config_file::ConfigFile};
 use nimiq::error::Error;
 use nimiq::extras::{
+    archive::{export_archive, import_archive},
     logging::{initialize_logging, log_error_cause_chain},
     metrics_server::NimiqTaskMonitor,
     panic::initialize_panic_reporting,
@@ -59,6 +60,20 @@ async fn main_inner(test_interval: Option<u64>) -> Result<(), Error> {
     let config = builder.build()?;
     log::debug!("Final configuration: {:#?}", config);
 
+    // Export or import a chain archive instead of running the client.
+    if let Some(path) = &command_line.export {
+        return export_archive(
+            &config,
+            path,
+            command_line.export_from,
+            command_line.export_to,
+            command_line.export_macro_only,
+        );
+    }
+    if let Some(path) = &command_line.import {
+        return import_archive(&config, path);
+    }
+
     // Initialize the client.
     let client = Client::from_config(config.clone()).await?;
 
@@ -84,6 +99,7 @@ async fn main_inner(test_interval: Option<u64>) -> Result<(), Error> {
             mempool.clone(),
             consensus.clone(),
             client.network(),
+            client.validator_proxy(),
             &[task_monitor], // Pass as a slice.
         );
     }
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use futures::StreamExt;
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use parking_lot::RwLock;
+use tokio::task::spawn_blocking;
+
+/// Rebuilds the accounts snapshot from the accounts trie after the blockchain reset it, because
+/// it couldn't be advanced to the latest macro block.
+///
+/// The snapshot is copied chunk by chunk, each in its own blocking task, such that blocks can be
+/// pushed in between. The snapshot isn't served until it is complete.
+pub(crate) async fn rebuild_accounts_snapshot(blockchain: Arc<RwLock<Blockchain>>) {
+    let mut blockchain_events = blockchain.read().notifier_as_stream();
+    loop {
+        // Copy chunks until the snapshot is complete or can't be rebuilt at the current head.
+        loop {
+            let blockchain = Arc::clone(&blockchain);
+            let result =
+                spawn_blocking(move || blockchain.read().rebuild_accounts_snapshot_chunk()).await;
+            if !matches!(result, Ok(true)) {
+                break;
+            }
+        }
+
+        // Any new block might have reset the snapshot.
+        if blockchain_events.next().await.is_none() {
+            return;
+        }
+    }
+}
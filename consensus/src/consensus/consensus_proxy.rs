@@ -23,6 +23,7 @@ use nimiq_transaction::{
     historic_transaction::HistoricTransaction, ControlTransaction, ControlTransactionTopic,
     Transaction, TransactionTopic,
 };
+use parking_lot::RwLock;
 use tokio::sync::{
     broadcast::Sender as BroadcastSender, mpsc::Sender as MpscSender,
     oneshot::channel as oneshot_channel,
@@ -37,6 +38,7 @@ use crate::{
         RequestBlocksProof, RequestSubscribeToAddress, RequestTransactionReceiptsByAddress,
         RequestTransactionsProof, ResponseBlocksProof,
     },
+    sync::status::SyncStatus,
     ConsensusEvent,
 };
 
@@ -45,6 +47,7 @@ pub struct ConsensusProxy<N: Network> {
     pub network: Arc<N>,
     pub(crate) established_flag: Arc<AtomicBool>,
     pub(crate) synced_validity_window_flag: Arc<AtomicBool>,
+    pub(crate) sync_status: Arc<RwLock<SyncStatus<N::PeerId>>>,
     pub(crate) events: BroadcastSender<ConsensusEvent>,
     pub(crate) request: MpscSender<ConsensusRequest<N>>,
 }
@@ -56,6 +59,7 @@ impl<N: Network> Clone for ConsensusProxy<N> {
             network: Arc::clone(&self.network),
             established_flag: Arc::clone(&self.established_flag),
             synced_validity_window_flag: Arc::clone(&self.synced_validity_window_flag),
+            sync_status: Arc::clone(&self.sync_status),
             events: self.events.clone(),
             request: self.request.clone(),
         }
@@ -88,6 +92,19 @@ impl<N: Network> ConsensusProxy<N> {
         BroadcastStream::new(self.events.subscribe())
     }
 
+    /// Returns the current sync status of the node.
+    pub fn sync_status(&self) -> SyncStatus<N::PeerId> {
+        let status = self.sync_status.read().clone();
+        match &self.blockchain {
+            #[cfg(feature = "full")]
+            BlockchainProxy::Full(blockchain) => SyncStatus {
+                missing_accounts_range: blockchain.read().get_missing_accounts_range(None),
+                ..status
+            },
+            BlockchainProxy::Light(_) => status,
+        }
+    }
+
     /// Subscribe to remote address notification events
     pub async fn subscribe_address_notifications(
         &self,
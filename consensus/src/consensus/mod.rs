@@ -9,15 +9,20 @@ use std::{
     time::Duration,
 };
 
-use futures::{FutureExt, StreamExt};
+use futures::{stream::BoxStream, FutureExt, StreamExt};
 use instant::Instant;
 use nimiq_block::Block;
-use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
 use nimiq_hash::Blake2bHash;
-use nimiq_network_interface::{network::Network, request::request_handler};
+use nimiq_network_interface::{
+    network::{Network, SubscribeEvents},
+    request::request_handler,
+};
+use nimiq_time::{interval, Interval};
 use nimiq_utils::spawn::spawn;
 use nimiq_zkp_component::zkp_component::ZKPComponentProxy;
+use parking_lot::RwLock;
 use tokio::sync::{
     broadcast::{channel as broadcast, Sender as BroadcastSender},
     mpsc::{
@@ -33,17 +38,26 @@ use self::remote_event_dispatcher::RemoteEventDispatcher;
 use crate::{
     consensus::head_requests::{HeadRequests, HeadRequestsResult},
     messages::{RequestBlock, RequestHead, RequestMacroChain, RequestMissingBlocks},
-    sync::{syncer::LiveSyncPushEvent, syncer_proxy::SyncerProxy},
+    sync::{
+        status::{EtaEstimator, SyncPhase, SyncStatus},
+        syncer::LiveSyncPushEvent,
+        syncer_proxy::SyncerProxy,
+    },
 };
 #[cfg(feature = "full")]
 use crate::{
     messages::{
-        RequestBatchSet, RequestBlocksProof, RequestHistoryChunk,
+        RequestBatchSet, RequestBlocksProof, RequestCompactTrieProof, RequestHistoryChunk,
         RequestTransactionReceiptsByAddress, RequestTransactionsProof, RequestTrieProof,
     },
-    sync::live::{diff_queue::RequestTrieDiff, state_queue::RequestChunk},
+    sync::live::{
+        diff_queue::RequestTrieDiff,
+        state_queue::{RequestChunk, RequestSnapshotChunk},
+    },
 };
 
+#[cfg(feature = "full")]
+mod accounts_snapshot_rebuilder;
 pub mod consensus_proxy;
 mod head_requests;
 mod remote_data_store;
@@ -135,6 +149,15 @@ pub struct Consensus<N: Network> {
     head_requests: Option<HeadRequests<N>>,
     head_requests_time: Option<Instant>,
 
+    /// The latest sync status, shared with the `ConsensusProxy`.
+    sync_status: Arc<RwLock<SyncStatus<N::PeerId>>>,
+    eta_estimator: EtaEstimator,
+    /// Head changes and peer events, on which the sync status is refreshed.
+    blockchain_events: BoxStream<'static, BlockchainEvent>,
+    network_events: SubscribeEvents<N::PeerId>,
+    /// Interval to refresh the sync status while the macro sync downloads within an epoch.
+    sync_status_interval: Interval,
+
     min_peers: usize,
 
     /// Sender and Receiver of a consensus request channel used to relay requests from any source
@@ -166,6 +189,9 @@ impl<N: Network> Consensus<N> {
     /// established state and to advance the chain.
     const HEAD_REQUESTS_TIMEOUT: Duration = Duration::from_secs(5);
 
+    /// Interval in which the sync status is refreshed, in addition to head and peer changes.
+    const SYNC_STATUS_INTERVAL: Duration = Duration::from_secs(1);
+
     pub fn from_network(
         blockchain: BlockchainProxy,
         network: Arc<N>,
@@ -195,6 +221,9 @@ impl<N: Network> Consensus<N> {
         #[cfg(feature = "full")]
         Self::init_remote_event_dispatcher(&network, &blockchain);
 
+        #[cfg(feature = "full")]
+        Self::init_accounts_snapshot_rebuilder(&blockchain);
+
         let established_flag = Arc::new(AtomicBool::new(false));
         let mut synced_validity_window_flag = true;
         #[cfg(feature = "full")]
@@ -205,6 +234,9 @@ impl<N: Network> Consensus<N> {
         }
         let synced_validity_window_flag = Arc::new(AtomicBool::new(synced_validity_window_flag));
 
+        let blockchain_events = blockchain.read().notifier_as_stream();
+        let network_events = network.subscribe_events();
+
         Consensus {
             blockchain,
             network,
@@ -215,6 +247,11 @@ impl<N: Network> Consensus<N> {
             synced_validity_window_flag,
             head_requests: None,
             head_requests_time: None,
+            sync_status: Arc::new(RwLock::new(SyncStatus::default())),
+            eta_estimator: EtaEstimator::default(),
+            blockchain_events,
+            network_events,
+            sync_status_interval: interval(Self::SYNC_STATUS_INTERVAL),
             min_peers,
             // Choose a small buffer as having a lot of items buffered here indicates a bigger problem.
             requests: mpsc_channel(10),
@@ -240,6 +277,16 @@ impl<N: Network> Consensus<N> {
         }
     }
 
+    #[cfg(feature = "full")]
+    fn init_accounts_snapshot_rebuilder(blockchain: &BlockchainProxy) {
+        // We rebuild the accounts snapshot in its own task (this is only available for full nodes and history nodes)
+        if let BlockchainProxy::Full(blockchain) = blockchain {
+            spawn(accounts_snapshot_rebuilder::rebuild_accounts_snapshot(
+                Arc::clone(blockchain),
+            ));
+        }
+    }
+
     fn init_network_request_receivers(network: &Arc<N>, blockchain: &BlockchainProxy) {
         let stream = network.receive_requests::<RequestMacroChain>();
         spawn(Box::pin(request_handler(network, stream, blockchain)));
@@ -267,6 +314,9 @@ impl<N: Network> Consensus<N> {
                 let stream = network.receive_requests::<RequestChunk>();
                 spawn(Box::pin(request_handler(network, stream, blockchain)));
 
+                let stream = network.receive_requests::<RequestSnapshotChunk>();
+                spawn(Box::pin(request_handler(network, stream, blockchain)));
+
                 let supports_history_index = blockchain.read().history_store.supports_index();
 
                 // Only spawn these handlers if the history index is enabled.
@@ -281,6 +331,9 @@ impl<N: Network> Consensus<N> {
                 let stream = network.receive_requests::<RequestTrieProof>();
                 spawn(Box::pin(request_handler(network, stream, blockchain)));
 
+                let stream = network.receive_requests::<RequestCompactTrieProof>();
+                spawn(Box::pin(request_handler(network, stream, blockchain)));
+
                 let stream = network.receive_requests::<RequestBlocksProof>();
                 spawn(Box::pin(request_handler(network, stream, blockchain)));
             }
@@ -306,11 +359,29 @@ impl<N: Network> Consensus<N> {
             network: Arc::clone(&self.network),
             established_flag: Arc::clone(&self.established_flag),
             synced_validity_window_flag: Arc::clone(&self.synced_validity_window_flag),
+            sync_status: Arc::clone(&self.sync_status),
             events: self.events.clone(),
             request: self.requests.0.clone(),
         }
     }
 
+    /// Refreshes the sync status shared with the `ConsensusProxy`.
+    fn update_sync_status(&mut self) {
+        let (mut phase, progress) = self.sync.progress();
+        let eta = if phase == SyncPhase::MacroSync {
+            self.eta_estimator.estimate(&progress)
+        } else {
+            self.eta_estimator.reset();
+            None
+        };
+        if self.is_established() {
+            phase = SyncPhase::Synced;
+        }
+
+        let block_number = self.blockchain.read().block_number();
+        *self.sync_status.write() = SyncStatus::new(phase, block_number, progress, eta);
+    }
+
     /// Forcefully sets consensus established, should be used for tests only.
     pub fn force_established(&mut self) {
         trace!("Consensus forcefully established.");
@@ -327,6 +398,8 @@ impl<N: Network> Consensus<N> {
                 synced_validity_window,
             })
             .ok();
+
+        self.update_sync_status();
     }
 
     /// Checks if the validity window is available.
@@ -513,9 +586,23 @@ impl<N: Network> Future for Consensus<N> {
             }
         }
 
+        // The sync status changes when our head or our peers change. The download progress of
+        // the macro sync changes in between, so it is refreshed regularly as well.
+        let mut refresh_sync_status = false;
+        while let Poll::Ready(Some(_)) = self.blockchain_events.poll_next_unpin(cx) {
+            refresh_sync_status = true;
+        }
+        while let Poll::Ready(Some(_)) = self.network_events.poll_next_unpin(cx) {
+            refresh_sync_status = true;
+        }
+        while self.sync_status_interval.poll_next_unpin(cx).is_ready() {
+            refresh_sync_status = true;
+        }
+
         // Check consensus established state on changes.
         if let Some(event) = self.check_established(None) {
             self.events.send(event).ok();
+            refresh_sync_status = true;
         }
 
         // Poll any head requests if active.
@@ -532,6 +619,7 @@ impl<N: Network> Future for Consensus<N> {
                 // Update established state using the result.
                 if let Some(event) = self.check_established(Some(result)) {
                     self.events.send(event).ok();
+                    refresh_sync_status = true;
                 }
             }
         }
@@ -546,6 +634,10 @@ impl<N: Network> Future for Consensus<N> {
         // Advance consensus and catch-up through head requests.
         self.request_heads();
 
+        if refresh_sync_status {
+            self.update_sync_status();
+        }
+
         Poll::Pending
     }
 }
@@ -8,16 +8,24 @@ use nimiq_account::{
 };
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 use nimiq_network_interface::{
     network::{CloseReason, Network},
     peer_info::Services,
-    request::{OutboundRequestError, RequestError},
+    request::{InboundRequestError, OutboundRequestError, RequestError},
+};
+use nimiq_primitives::{
+    key_nibbles::KeyNibbles,
+    policy::Policy,
+    trie::{
+        compact_trie_proof::CompactTrieProof,
+        trie_proof::{Error as TrieProofError, TrieProof},
+    },
 };
-use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
 use nimiq_serde::Deserialize;
 
-use crate::messages::RequestTrieProof;
+use crate::messages::{RequestCompactTrieProof, RequestTrieProof, ResponseTrieProofError};
 
 /// The Remote Data Store is a component to remotely request data from the staking
 /// contract such as:
@@ -35,6 +43,25 @@ pub(crate) struct RemoteDataStore<N: Network> {
     pub(crate) min_peers: usize,
 }
 
+/// An accounts trie proof received from a peer, in one of the supported formats.
+enum ReceivedTrieProof {
+    Full(TrieProof),
+    Compact(CompactTrieProof),
+}
+
+impl ReceivedTrieProof {
+    fn verify_values(
+        self,
+        root_hash: &Blake2bHash,
+        keys: &[&KeyNibbles],
+    ) -> Result<BTreeMap<KeyNibbles, Option<Vec<u8>>>, TrieProofError> {
+        match self {
+            ReceivedTrieProof::Full(proof) => proof.verify_values(root_hash, keys),
+            ReceivedTrieProof::Compact(proof) => proof.verify_values(root_hash, keys),
+        }
+    }
+}
+
 /// Internal Remote operations the Remote Data Store can perform over addresses on
 /// wasm
 enum RemoteDataStoreOps {
@@ -74,25 +101,17 @@ impl<N: Network> RemoteDataStore<N> {
                 "Performing accounts by address request to peer",
             );
             log::debug!("Getting accounts for {:?}", keys.iter());
-            let response = network
-                .request::<RequestTrieProof>(
-                    RequestTrieProof {
-                        keys: keys.to_vec(),
-                    },
-                    peer_id,
-                )
-                .await;
+            let response = Self::request_trie_proof(&network, keys, peer_id).await;
 
             match response {
-                Ok(Ok(response)) => {
+                Ok(Ok((block_hash, proof))) => {
                     let blockchain = blockchain.read();
                     // First try to obtain, from our chain store, the block that was used to generate the proof
-                    let block = blockchain.get_block(&response.block_hash, false).ok();
+                    let block = blockchain.get_block(&block_hash, false).ok();
 
                     if let Some(block) = block {
                         // Now we need to verify the proof
-                        if let Ok(values) = response
-                            .proof
+                        if let Ok(values) = proof
                             .verify_values(block.state_root(), &keys.iter().collect::<Vec<_>>())
                         {
                             return Ok(values
@@ -112,7 +131,7 @@ impl<N: Network> RemoteDataStore<N> {
                     } else {
                         // If we couldn't find the block, then we cannot verify the proof
                         // A malicious peer could just send random hashes.
-                        log::debug!(block_hash = %response.block_hash, "Received an accounts proof, but we could not find the block that was used to generate the proof");
+                        log::debug!(%block_hash, "Received an accounts proof, but we could not find the block that was used to generate the proof");
                     }
                 }
                 Ok(Err(error)) => {
@@ -131,6 +150,47 @@ impl<N: Network> RemoteDataStore<N> {
         ))
     }
 
+    /// Requests a proof for the given keys from a peer. Uses compact proofs if the peer supports
+    /// them and falls back to regular proofs otherwise.
+    async fn request_trie_proof(
+        network: &Arc<N>,
+        keys: &[KeyNibbles],
+        peer_id: N::PeerId,
+    ) -> Result<Result<(Blake2bHash, ReceivedTrieProof), ResponseTrieProofError>, RequestError>
+    {
+        let response = network
+            .request::<RequestCompactTrieProof>(
+                RequestCompactTrieProof {
+                    keys: keys.to_vec(),
+                },
+                peer_id,
+            )
+            .await;
+
+        match response {
+            // Peers that don't support compact proofs yet have no receiver for the request.
+            Err(RequestError::InboundRequest(InboundRequestError::NoReceiver)) => {
+                log::debug!(%peer_id, "Peer doesn't support compact accounts proofs");
+                let response = network
+                    .request::<RequestTrieProof>(
+                        RequestTrieProof {
+                            keys: keys.to_vec(),
+                        },
+                        peer_id,
+                    )
+                    .await?;
+                Ok(response
+                    .map(|response| (response.block_hash, ReceivedTrieProof::Full(response.proof))))
+            }
+            response => Ok(response?.map(|response| {
+                (
+                    response.block_hash,
+                    ReceivedTrieProof::Compact(response.proof),
+                )
+            })),
+        }
+    }
+
     async fn get_staking_contract(&self) -> Result<StakingContract, RequestError> {
         let key = KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS);
         let accounts = Self::get_trie(
@@ -22,7 +22,7 @@ use crate::messages::*;
 #[cfg(feature = "full")]
 use crate::sync::live::{
     diff_queue::{RequestTrieDiff, ResponseTrieDiff},
-    state_queue::{Chunk, RequestChunk, ResponseChunk},
+    state_queue::{Chunk, RequestChunk, RequestSnapshotChunk, ResponseChunk},
 };
 
 impl<N: Network> Handle<N, BlockchainProxy> for RequestMacroChain {
@@ -426,6 +426,36 @@ impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestChunk {
     }
 }
 
+#[cfg(feature = "full")]
+impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestSnapshotChunk {
+    fn handle(&self, peer_id: N::PeerId, blockchain: &Arc<RwLock<Blockchain>>) -> ResponseChunk {
+        let blockchain_rg = blockchain.read();
+
+        // Serve the chunk from the snapshot at the latest macro block if we have one, such that
+        // the peer syncs against a fixed state root.
+        let txn = blockchain_rg.read_transaction();
+        let limit = cmp::min(self.limit, Policy::state_chunks_max_size()) as usize;
+        if let Some(chunk) =
+            blockchain_rg.get_accounts_snapshot_chunk(self.start_key.clone(), limit, &txn)
+        {
+            return ResponseChunk::Chunk(Chunk {
+                block_number: blockchain_rg.state.macro_info.head.block_number(),
+                block_hash: blockchain_rg.state.macro_head_hash.clone(),
+                chunk,
+            });
+        }
+        drop(txn);
+        drop(blockchain_rg);
+
+        // Otherwise, serve a chunk of our head.
+        let request = RequestChunk {
+            start_key: self.start_key.clone(),
+            limit: self.limit,
+        };
+        <RequestChunk as Handle<N, Arc<RwLock<Blockchain>>>>::handle(&request, peer_id, blockchain)
+    }
+}
+
 #[cfg(feature = "full")]
 impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestTrieDiff {
     fn handle(&self, _peer_id: N::PeerId, context: &Arc<RwLock<Blockchain>>) -> ResponseTrieDiff {
@@ -714,6 +744,26 @@ impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestTrieProof {
     }
 }
 
+#[cfg(feature = "full")]
+impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestCompactTrieProof {
+    fn handle(
+        &self,
+        _peer_id: N::PeerId,
+        blockchain: &Arc<RwLock<Blockchain>>,
+    ) -> Result<ResponseCompactTrieProof, ResponseTrieProofError> {
+        let blockchain = blockchain.read();
+
+        // We only prove accounts that exist in our current state
+        match blockchain.get_accounts_proof(self.keys.iter().collect()) {
+            Err(IncompleteTrie) => Err(ResponseTrieProofError::IncompleteTrie),
+            Ok(proof) => Ok(ResponseCompactTrieProof {
+                proof: proof.into(),
+                block_hash: blockchain.head_hash(),
+            }),
+        }
+    }
+}
+
 #[cfg(feature = "full")]
 impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestBlocksProof {
     fn handle(
@@ -12,7 +12,10 @@ use nimiq_network_interface::{
     network::Topic,
     request::{RequestCommon, RequestMarker},
 };
-use nimiq_primitives::{key_nibbles::KeyNibbles, trie::trie_proof::TrieProof};
+use nimiq_primitives::{
+    key_nibbles::KeyNibbles,
+    trie::{compact_trie_proof::CompactTrieProof, trie_proof::TrieProof},
+};
 use nimiq_serde::{Deserialize, Serialize, SerializedMaxSize};
 use nimiq_transaction::{
     historic_transaction::HistoricTransaction, history_proof::HistoryTreeProof,
@@ -457,6 +460,32 @@ pub enum ResponseTrieProofError {
     Other,
 }
 
+/// Version 2 of [`RequestTrieProof`], answered with a [`CompactTrieProof`].
+///
+/// Peers that don't support this version yet reply with a `NoReceiver` error, in which case
+/// [`RequestTrieProof`] should be used instead.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestCompactTrieProof {
+    /// Addresses for which the accounts trie proof is requested for
+    pub keys: Vec<KeyNibbles>, //-> Accounts
+}
+
+impl RequestCommon for RequestCompactTrieProof {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 219;
+    type Response = Result<ResponseCompactTrieProof, ResponseTrieProofError>;
+    const MAX_REQUESTS: u32 = MAX_REQUEST_TRIE_PROOF;
+}
+
+/// Response to [`RequestCompactTrieProof`].
+#[derive(Serialize, Deserialize)]
+pub struct ResponseCompactTrieProof {
+    // The accounts proof
+    pub proof: CompactTrieProof,
+    // The hash of the block that was used to create the proof
+    pub block_hash: Blake2bHash,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RequestBlocksProof {
     pub election_head: u32,
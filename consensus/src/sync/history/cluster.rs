@@ -24,7 +24,10 @@ use crate::{
     messages::{
         BatchSetError, BatchSetInfo, HistoryChunkError, RequestBatchSet, RequestHistoryChunk,
     },
-    sync::{peer_list::PeerList, sync_queue::SyncQueue},
+    sync::{
+        history::HistoryStagingStore, peer_list::PeerList, peer_scheduler::PeerScheduler,
+        sync_queue::SyncQueue,
+    },
 };
 
 /// Error enumeration for history sync request
@@ -111,6 +114,13 @@ pub struct BatchSetVerifyState {
     validators: Validators,
 }
 
+#[derive(Clone, Debug)]
+pub struct BatchSetRequest {
+    hash: Blake2bHash,
+    /// Batch set info that was already downloaded and verified before, if any.
+    staged: Option<BatchSetInfo>,
+}
+
 #[derive(Clone, Debug)]
 pub struct HistoryChunkRequest {
     epoch_number: u32,
@@ -139,8 +149,13 @@ pub struct SyncCluster<TNetwork: Network> {
     pub first_block_number: usize,
 
     // Both batch_set_queue and the history_queue share the same peers.
-    pub(crate) batch_set_queue:
-        SyncQueue<TNetwork, Blake2bHash, BatchSetInfo, HistoryRequestError, BatchSetVerifyState>,
+    pub(crate) batch_set_queue: SyncQueue<
+        TNetwork,
+        BatchSetRequest,
+        BatchSetInfo,
+        HistoryRequestError,
+        BatchSetVerifyState,
+    >,
     history_queue: SyncQueue<
         TNetwork,
         HistoryChunkRequest,
@@ -154,15 +169,21 @@ pub struct SyncCluster<TNetwork: Network> {
 
     blockchain: Arc<RwLock<Blockchain>>,
     network: Arc<TNetwork>,
+    staging: Option<Arc<HistoryStagingStore>>,
+    chunk_scheduler: Arc<RwLock<PeerScheduler<TNetwork::PeerId>>>,
 }
 
 impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
     const NUM_PENDING_BATCH_SETS: usize = 5;
-    const NUM_PENDING_CHUNKS: usize = 12;
+    pub(crate) const NUM_PENDING_CHUNKS: usize = 12;
+    pub(crate) const MIN_PENDING_CHUNKS: usize = 4;
+    pub(crate) const MAX_PENDING_CHUNKS: usize = 48;
 
     pub(crate) fn for_epoch(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TNetwork>,
+        staging: Option<Arc<HistoryStagingStore>>,
+        chunk_scheduler: Arc<RwLock<PeerScheduler<TNetwork::PeerId>>>,
         peers: PeerList<TNetwork>,
         epoch_ids: Vec<Blake2bHash>,
         first_epoch_number: usize,
@@ -170,6 +191,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
         Self::new(
             blockchain,
             network,
+            staging,
+            chunk_scheduler,
             Arc::new(RwLock::new(peers)),
             epoch_ids,
             first_epoch_number,
@@ -181,6 +204,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
     pub(crate) fn for_checkpoint(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TNetwork>,
+        staging: Option<Arc<HistoryStagingStore>>,
+        chunk_scheduler: Arc<RwLock<PeerScheduler<TNetwork::PeerId>>>,
         peers: PeerList<TNetwork>,
         checkpoint_id: Blake2bHash,
         epoch_number: usize,
@@ -189,6 +214,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
         Self::new(
             blockchain,
             network,
+            staging,
+            chunk_scheduler,
             Arc::new(RwLock::new(peers)),
             vec![checkpoint_id],
             epoch_number,
@@ -199,6 +226,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
     fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TNetwork>,
+        staging: Option<Arc<HistoryStagingStore>>,
+        chunk_scheduler: Arc<RwLock<PeerScheduler<TNetwork::PeerId>>>,
         peers: Arc<RwLock<PeerList<TNetwork>>>,
         epoch_ids: Vec<Blake2bHash>,
         first_epoch_number: usize,
@@ -216,15 +245,30 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
         };
         let epoch_ids_queue = epoch_ids
             .iter()
-            .map(|epoch_id| (epoch_id.clone(), None))
+            .map(|epoch_id| {
+                let request = BatchSetRequest {
+                    hash: epoch_id.clone(),
+                    staged: staging
+                        .as_ref()
+                        .and_then(|staging| staging.get_batch_set_info(epoch_id)),
+                };
+                (request, None)
+            })
             .collect();
         let batch_set_queue = SyncQueue::with_verification(
             Arc::clone(&network),
             epoch_ids_queue,
             peers.clone(),
             Self::NUM_PENDING_BATCH_SETS,
-            |id, network, peer_id| {
-                async move { Self::request_epoch(network, peer_id, id).await }.boxed()
+            |request, network, peer_id| {
+                async move {
+                    // Batch set infos we staged before don't need to be downloaded again.
+                    if let Some(batch_set_info) = request.staged {
+                        return Ok(batch_set_info);
+                    }
+                    Self::request_epoch(network, peer_id, request.hash).await
+                }
+                .boxed()
             },
             |_, batch_set_info, verify_state| {
                 if let Err(e) = Self::verify_batch_set_info(
@@ -256,6 +300,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
             batch_verify_state,
         );
 
+        // History chunks are requested concurrently from the fastest peers, as determined by
+        // the scheduler shared by all clusters.
         let history_queue = SyncQueue::new(
             Arc::clone(&network),
             Vec::<(HistoryChunkRequest, Option<_>)>::new(),
@@ -269,7 +315,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
                 }
                 .boxed()
             },
-        );
+        )
+        .with_scheduler(Arc::clone(&chunk_scheduler));
         Self {
             id,
             epoch_ids,
@@ -281,6 +328,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
             num_epochs_finished: 0,
             blockchain,
             network,
+            staging,
+            chunk_scheduler,
         }
     }
 
@@ -375,6 +424,11 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
         // Release the blockchain lock
         drop(blockchain);
 
+        // Stage the verified batch set info, so it doesn't need to be downloaded again after a restart.
+        if let Some(staging) = &self.staging {
+            staging.put_batch_set_info(&block.hash(), &epoch);
+        }
+
         info!(
             "Syncing epoch #{}/{} ({} checkpoints, {} total history items)",
             epoch_number,
@@ -408,12 +462,21 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
             // Now compute how many history items we need to download
             let history_len = batch_set.history_len.size() - start_txn;
 
+            // Resume from the history chunks we staged before (if any).
+            let first_chunk = start_txn / CHUNK_SIZE as u64;
+            let (history, num_staged_chunks) = self
+                .staging
+                .as_ref()
+                .map(|staging| staging.get_history(&batch_set.macro_block.hash(), first_chunk))
+                .filter(|(history, _)| history.len() as u64 <= history_len)
+                .unwrap_or_default();
+
             // Prepare pending info.
             let pending_batch_set = PendingBatchSet {
                 macro_block: batch_set.macro_block.clone(),
                 history_len,
                 batch_set_index: index,
-                history: Vec::new(),
+                history,
             };
 
             log::debug!(
@@ -421,12 +484,13 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
                 block = %batch_set.macro_block,
                 history_len,
                 batch_set_index = index,
+                num_staged_chunks,
                 "Adding pending batch set",
             );
 
-            // Queue history chunks for the given batch set for download.
-            let history_chunk_ids: Vec<(HistoryChunkRequest, Option<_>)> = (start_txn
-                / CHUNK_SIZE as u64
+            // Queue the missing history chunks for the given batch set for download.
+            let history_chunk_ids: Vec<(HistoryChunkRequest, Option<_>)> = (first_chunk
+                + num_staged_chunks
                 ..((batch_set.history_len.size()).div_ceil(CHUNK_SIZE as u64)))
                 .map(|i| {
                     (
@@ -451,6 +515,7 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
         &mut self,
         epoch_number: u32,
         block_number: u32,
+        chunk_index: u64,
         mut history_chunk: HistoryTreeChunk,
     ) -> Result<(), SyncClusterResult> {
         // Find batch set in pending_batch_sets.
@@ -469,10 +534,25 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
 
         let batch_set = &mut self.pending_batch_sets[*batch_set_idx];
 
+        // Stage the verified history chunk, so it doesn't need to be downloaded again after a restart.
+        if let Some(staging) = &self.staging {
+            staging.put_chunk(
+                &batch_set.macro_block.hash(),
+                chunk_index,
+                &history_chunk.history,
+            );
+        }
+
         // Add the received history chunk to the pending epoch.
         batch_set.history.append(&mut history_chunk.history);
 
+        let (concurrency, throughput) = {
+            let scheduler = self.chunk_scheduler.read();
+            (scheduler.concurrency(), scheduler.throughput())
+        };
         log::info!(
+            concurrency,
+            chunks_per_second = throughput,
             "Downloading history for epoch #{}, batch set #{}: {}/{} ({:.2}%)",
             batch_set.epoch_number(),
             batch_set.batch_set_index,
@@ -517,6 +597,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
         Self::for_epoch(
             Arc::clone(&self.blockchain),
             Arc::clone(&self.network),
+            self.staging.clone(),
+            Arc::clone(&self.chunk_scheduler),
             self.batch_set_queue.peers.read().clone(), // makes sure we have a hard copy
             ids,
             first_epoch_number,
@@ -570,6 +652,37 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
         self.num_epochs_finished
     }
 
+    /// Returns the epoch and block number of the last macro block this cluster syncs to.
+    pub(crate) fn target(&self) -> (u32, u32) {
+        if Policy::is_election_block_at(self.first_block_number as u32) {
+            // Epoch cluster
+            let num_epochs = self.len().saturating_sub(1);
+            (
+                (self.first_epoch_number + num_epochs) as u32,
+                (self.first_block_number + num_epochs * Policy::blocks_per_epoch() as usize) as u32,
+            )
+        } else {
+            // Checkpoint cluster
+            (
+                self.first_epoch_number as u32,
+                self.first_block_number as u32,
+            )
+        }
+    }
+
+    /// Returns the number of history chunks downloaded and the total number of history chunks
+    /// of the batch sets currently being downloaded.
+    pub(crate) fn chunk_progress(&self) -> (u64, u64) {
+        self.pending_batch_sets
+            .iter()
+            .fold((0, 0), |(done, total), batch_set| {
+                (
+                    done + (batch_set.history.len() as u64).div_ceil(CHUNK_SIZE as u64),
+                    total + batch_set.history_len.div_ceil(CHUNK_SIZE as u64),
+                )
+            })
+    }
+
     pub(crate) fn reset_verify_state(&mut self) {
         let blockchain = self.blockchain.read();
         let verify_state = BatchSetVerifyState {
@@ -687,6 +800,7 @@ impl<TNetwork: Network + 'static> Stream for SyncCluster<TNetwork> {
                     if let Err(e) = self.on_history_chunk_received(
                         request.epoch_number,
                         request.block_number,
+                        request.chunk_index,
                         history_chunk,
                     ) {
                         return Poll::Ready(Some(Err(e)));
@@ -709,6 +823,11 @@ impl<TNetwork: Network + 'static> Stream for SyncCluster<TNetwork> {
             }
         }
 
+        // Emit batch sets that were completed from the staged history.
+        if let Some(batch_set) = self.pop_complete_epoch() {
+            return Poll::Ready(Some(Ok(batch_set.into())));
+        }
+
         // We're done if there are no more epochs to process.
         if self.batch_set_queue.is_empty() && self.pending_batch_sets.is_empty() {
             return Poll::Ready(None);
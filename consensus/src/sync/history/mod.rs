@@ -1,6 +1,8 @@
 pub mod cluster;
+mod staging;
 mod sync;
 mod sync_clustering;
 mod sync_stream;
 
+pub use staging::HistoryStagingStore;
 pub use sync::HistoryMacroSync;
@@ -0,0 +1,188 @@
+use std::io;
+
+use nimiq_database::{
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableProxy, WriteTransactionProxy,
+};
+use nimiq_database_value::FromDatabaseValue;
+use nimiq_hash::Blake2bHash;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::historic_transaction::HistoricTransaction;
+
+use crate::messages::BatchSetInfo;
+
+/// History items of a chunk that was downloaded and verified, but not applied yet.
+#[derive(Serialize, Deserialize)]
+struct StagedChunk(Vec<HistoricTransaction>);
+
+impl FromDatabaseValue for StagedChunk {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::deserialize_from_vec(bytes).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+impl FromDatabaseValue for BatchSetInfo {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::deserialize_from_vec(bytes).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+/// Persistent staging area for the history sync.
+///
+/// Batch set infos and history chunks are stored here once they have been verified, so that a
+/// restarted history node resumes downloading an epoch where it stopped instead of refetching it.
+/// Staged data is removed as soon as the corresponding batch set was pushed to the blockchain or
+/// was superseded by the blockchain, and all of it is discarded if pushing a batch set fails.
+#[derive(Debug)]
+pub struct HistoryStagingStore {
+    /// Environment for the DB creation and transaction handling.
+    env: DatabaseProxy,
+    /// Verified batch set infos by the hash of their final macro block.
+    batch_sets: TableProxy,
+    /// Verified history chunks by the hash of their batch set's macro block and chunk index.
+    chunks: TableProxy,
+}
+
+impl HistoryStagingStore {
+    const BATCH_SET_DB_NAME: &'static str = "HistoryStagingBatchSets";
+    const CHUNK_DB_NAME: &'static str = "HistoryStagingChunks";
+
+    pub fn new(env: DatabaseProxy) -> Self {
+        let batch_sets = env.open_table(Self::BATCH_SET_DB_NAME.to_string());
+        let chunks = env.open_table(Self::CHUNK_DB_NAME.to_string());
+
+        Self {
+            env,
+            batch_sets,
+            chunks,
+        }
+    }
+
+    fn chunk_key(block_hash: &Blake2bHash, chunk_index: u64) -> Vec<u8> {
+        let mut key = block_hash.as_slice().to_vec();
+        key.extend_from_slice(&chunk_index.to_be_bytes());
+        key
+    }
+
+    /// Returns the staged batch set info whose final macro block has the given hash.
+    pub fn get_batch_set_info(&self, hash: &Blake2bHash) -> Option<BatchSetInfo> {
+        self.env.read_transaction().get(&self.batch_sets, hash)
+    }
+
+    /// Stages a verified batch set info under the hash of its final macro block.
+    pub fn put_batch_set_info(&self, hash: &Blake2bHash, batch_set_info: &BatchSetInfo) {
+        let mut txn = self.env.write_transaction();
+        txn.put::<_, Vec<u8>>(&self.batch_sets, hash, &batch_set_info.serialize_to_vec());
+        txn.commit();
+    }
+
+    /// Returns the history of the consecutive staged chunks of the batch set with the given macro
+    /// block hash, starting at `first_chunk`, together with the number of chunks found.
+    pub fn get_history(
+        &self,
+        block_hash: &Blake2bHash,
+        first_chunk: u64,
+    ) -> (Vec<HistoricTransaction>, u64) {
+        let txn = self.env.read_transaction();
+        let mut history = Vec::new();
+        let mut chunk_index = first_chunk;
+        while let Some(StagedChunk(mut items)) =
+            txn.get(&self.chunks, &Self::chunk_key(block_hash, chunk_index))
+        {
+            history.append(&mut items);
+            chunk_index += 1;
+        }
+        (history, chunk_index - first_chunk)
+    }
+
+    /// Stages a verified history chunk of the batch set with the given macro block hash.
+    pub fn put_chunk(
+        &self,
+        block_hash: &Blake2bHash,
+        chunk_index: u64,
+        history: &[HistoricTransaction],
+    ) {
+        let mut txn = self.env.write_transaction();
+        txn.put::<_, Vec<u8>>(
+            &self.chunks,
+            &Self::chunk_key(block_hash, chunk_index),
+            &history.serialize_to_vec(),
+        );
+        txn.commit();
+    }
+
+    /// Removes all staged data of the batch set with the given macro block hash. This includes
+    /// the batch set info if the block is the final macro block of its epoch's batch set info.
+    pub fn remove_batch_set(&self, block_hash: &Blake2bHash) {
+        let mut txn = self.env.write_transaction();
+        txn.remove(&self.batch_sets, block_hash);
+        self.remove_chunks(&mut txn, block_hash);
+        txn.commit();
+    }
+
+    /// Removes the staged epochs that end at or below the given block number, e.g. because the
+    /// blockchain already contains them or adopted a different fork.
+    pub fn remove_superseded(&self, block_number: u32) {
+        let mut txn = self.env.write_transaction();
+
+        let superseded: Vec<(Blake2bHash, BatchSetInfo)> = {
+            let mut cursor = ReadTransaction::cursor(&txn, &self.batch_sets);
+            let mut superseded = Vec::new();
+            let mut entry: Option<(Blake2bHash, BatchSetInfo)> = cursor.first();
+            while let Some((hash, batch_set_info)) = entry {
+                let final_block_number = batch_set_info
+                    .batch_sets
+                    .last()
+                    .map_or(0, |batch_set| batch_set.macro_block.block_number());
+                if final_block_number <= block_number {
+                    superseded.push((hash, batch_set_info));
+                }
+                entry = cursor.next();
+            }
+            superseded
+        };
+
+        for (hash, batch_set_info) in superseded {
+            txn.remove(&self.batch_sets, &hash);
+            for batch_set in &batch_set_info.batch_sets {
+                self.remove_chunks(&mut txn, &batch_set.macro_block.hash());
+            }
+        }
+        txn.commit();
+    }
+
+    /// Removes all staged data.
+    pub fn clear(&self) {
+        let mut txn = self.env.write_transaction();
+        txn.clear_database(&self.batch_sets);
+        txn.clear_database(&self.chunks);
+        txn.commit();
+    }
+
+    fn remove_chunks(&self, txn: &mut WriteTransactionProxy, block_hash: &Blake2bHash) {
+        let prefix = block_hash.as_slice();
+        let keys: Vec<Vec<u8>> = {
+            let mut cursor = ReadTransaction::cursor(&*txn, &self.chunks);
+            let mut keys = Vec::new();
+            let mut entry: Option<(Vec<u8>, Vec<u8>)> =
+                cursor.seek_range_key(&Self::chunk_key(block_hash, 0));
+            while let Some((key, _)) = entry {
+                if !key.starts_with(prefix) {
+                    break;
+                }
+                keys.push(key);
+                entry = cursor.next();
+            }
+            keys
+        };
+        for key in keys {
+            txn.remove(&self.chunks, &key);
+        }
+    }
+}
@@ -6,6 +6,7 @@ use std::{
 
 use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt};
 use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::network::{Network, SubscribeEvents};
 use parking_lot::RwLock;
@@ -13,8 +14,12 @@ use parking_lot::RwLock;
 use crate::{
     messages::Checkpoint,
     sync::{
-        history::cluster::{SyncCluster, SyncClusterResult},
-        syncer::MacroSync,
+        history::{
+            cluster::{SyncCluster, SyncClusterResult},
+            HistoryStagingStore,
+        },
+        peer_scheduler::PeerScheduler,
+        syncer::{MacroSync, MacroSyncProgress},
     },
 };
 
@@ -48,6 +53,10 @@ pub struct HistoryMacroSync<TNetwork: Network> {
     pub(crate) blockchain: Arc<RwLock<Blockchain>>,
     pub(crate) network: Arc<TNetwork>,
     pub(crate) network_event_rx: SubscribeEvents<TNetwork::PeerId>,
+    pub(crate) staging: Option<Arc<HistoryStagingStore>>,
+    /// The epoch number of our blockchain when the sync started
+    pub(crate) start_epoch: u32,
+    pub(crate) chunk_scheduler: Arc<RwLock<PeerScheduler<TNetwork::PeerId>>>,
     pub(crate) peers: HashMap<TNetwork::PeerId, usize>,
     pub(crate) epoch_ids_stream:
         FuturesUnordered<BoxFuture<'static, Option<EpochIds<TNetwork::PeerId>>>>,
@@ -62,15 +71,35 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
     pub(crate) const MAX_CLUSTERS: usize = 100;
     pub(crate) const MAX_QUEUED_JOBS: usize = 4;
 
+    /// Creates a new history macro sync. If a staging store is given, downloaded history is
+    /// persisted there until it is applied, so that the sync can resume after a restart.
     pub fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TNetwork>,
         network_event_rx: SubscribeEvents<TNetwork::PeerId>,
+        staging: Option<HistoryStagingStore>,
     ) -> Self {
+        let (start_epoch, block_number) = {
+            let blockchain = blockchain.read();
+            (blockchain.epoch_number(), blockchain.block_number())
+        };
+
+        // Discard what was staged for epochs the blockchain already moved past.
+        if let Some(staging) = &staging {
+            staging.remove_superseded(block_number);
+        }
+
         Self {
             blockchain,
             network,
             network_event_rx,
+            staging: staging.map(Arc::new),
+            start_epoch,
+            chunk_scheduler: Arc::new(RwLock::new(PeerScheduler::new(
+                SyncCluster::<TNetwork>::NUM_PENDING_CHUNKS,
+                SyncCluster::<TNetwork>::MIN_PENDING_CHUNKS,
+                SyncCluster::<TNetwork>::MAX_PENDING_CHUNKS,
+            ))),
             peers: HashMap::new(),
             epoch_ids_stream: FuturesUnordered::new(),
             epoch_clusters: VecDeque::new(),
@@ -81,7 +110,14 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
         }
     }
 
+    /// The scheduler of the history chunk requests, which keeps the download statistics of
+    /// all peers.
+    pub fn chunk_scheduler(&self) -> &RwLock<PeerScheduler<TNetwork::PeerId>> {
+        &self.chunk_scheduler
+    }
+
     pub fn remove_peer(&mut self, peer_id: TNetwork::PeerId) {
+        self.chunk_scheduler.write().remove_peer(&peer_id);
         for cluster in self.epoch_clusters.iter_mut() {
             cluster.remove_peer(&peer_id);
         }
@@ -123,4 +159,34 @@ impl<TNetwork: Network> MacroSync<TNetwork::PeerId> for HistoryMacroSync<TNetwor
             waker.wake_by_ref();
         }
     }
+
+    fn progress(&self) -> MacroSyncProgress<TNetwork::PeerId> {
+        let mut progress = MacroSyncProgress::new(self.peers.keys().copied().collect());
+
+        // The target is the furthest macro block any of our clusters syncs to.
+        let target = self
+            .epoch_clusters
+            .iter()
+            .chain(&self.checkpoint_clusters)
+            .chain(&self.active_cluster)
+            .map(|cluster| cluster.target())
+            .max();
+        if let Some((target_epoch, target_block)) = target {
+            let current_epoch = self.blockchain.read().epoch_number();
+            progress.target_epoch = Some(target_epoch);
+            progress.target_block = Some(target_block);
+            progress.epochs_done = current_epoch.saturating_sub(self.start_epoch);
+            progress.epochs_total = target_epoch.saturating_sub(self.start_epoch);
+        }
+
+        if let Some(cluster) = &self.active_cluster {
+            (progress.chunks_done, progress.chunks_total) = cluster.chunk_progress();
+        }
+
+        let scheduler = self.chunk_scheduler.read();
+        progress.chunks_per_second = scheduler.throughput();
+        progress.chunk_concurrency = scheduler.concurrency();
+
+        progress
+    }
 }
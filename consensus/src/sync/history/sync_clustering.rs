@@ -367,6 +367,8 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
             new_clusters.push_back(SyncCluster::for_epoch(
                 Arc::clone(&self.blockchain),
                 Arc::clone(&self.network),
+                self.staging.clone(),
+                Arc::clone(&self.chunk_scheduler),
                 peers,
                 Vec::from(&epoch_ids.ids[id_index..]),
                 epoch_ids.first_epoch_number + id_index,
@@ -410,6 +412,8 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
                 let cluster = SyncCluster::for_checkpoint(
                     Arc::clone(&self.blockchain),
                     Arc::clone(&self.network),
+                    self.staging.clone(),
+                    Arc::clone(&self.chunk_scheduler),
                     peers,
                     checkpoint.hash,
                     checkpoint_epoch,
@@ -668,6 +672,7 @@ mod tests {
             Arc::clone(blockchain),
             Arc::clone(net),
             net.subscribe_events(),
+            None,
         );
         sync.cluster_epoch_ids(epoch_ids1.clone());
         sync.cluster_epoch_ids(epoch_ids2.clone());
@@ -679,6 +684,7 @@ mod tests {
                 Arc::clone(blockchain),
                 Arc::clone(net),
                 net.subscribe_events(),
+                None,
             );
             sync.cluster_epoch_ids(epoch_ids2);
             sync.cluster_epoch_ids(epoch_ids1);
@@ -1159,6 +1165,7 @@ mod tests {
             Arc::clone(&blockchain),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            None,
         );
 
         let epoch_ids1 = generate_epoch_ids(net2.peer_id(), 10, 1, None, false);
@@ -1182,6 +1189,7 @@ mod tests {
             blockchain,
             Arc::clone(&net1),
             net1.subscribe_events(),
+            None,
         );
 
         let epoch_ids1 = generate_epoch_ids(net2.peer_id(), 10, 1, None, false);
@@ -181,7 +181,7 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
             let job = self.job_queue.pop_front().unwrap();
 
             match job {
-                Job::PushBatchSet(cluster_id, ..) => {
+                Job::PushBatchSet(cluster_id, hash, _) => {
                     let result = result.unwrap();
 
                     log::debug!(
@@ -190,6 +190,19 @@ impl<TNetwork: Network> HistoryMacroSync<TNetwork> {
                         result
                     );
 
+                    if let Some(staging) = &self.staging {
+                        if result == SyncClusterResult::EpochSuccessful {
+                            // The batch set is applied now, so its staged data is not needed
+                            // anymore. Neither is the data of epochs the blockchain moved past,
+                            // e.g. those of a fork that was not adopted.
+                            staging.remove_batch_set(&hash);
+                            staging.remove_superseded(self.blockchain.read().block_number());
+                        } else {
+                            // The staged data led to a failed push, don't resume from it.
+                            staging.clear();
+                        }
+                    }
+
                     if result != SyncClusterResult::EpochSuccessful {
                         // The push operation failed, therefore the whole cluster is invalid.
                         // Clean out any jobs originating from the failed cluster from the job_queue.
@@ -264,11 +277,16 @@ mod tests {
     use std::{sync::Arc, task::Poll};
 
     use futures::{Stream, StreamExt};
-    use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
+    use nimiq_blockchain::{
+        interface::HistoryInterface, BlockProducer, Blockchain, BlockchainConfig, CHUNK_SIZE,
+    };
     use nimiq_blockchain_interface::AbstractBlockchain;
     use nimiq_blockchain_proxy::BlockchainProxy;
     use nimiq_database::volatile::VolatileDatabase;
-    use nimiq_network_interface::{network::Network, request::request_handler};
+    use nimiq_network_interface::{
+        network::Network,
+        request::{request_handler, Handle},
+    };
     use nimiq_network_mock::{MockHub, MockNetwork};
     use nimiq_primitives::{networks::NetworkId, policy::Policy};
     use nimiq_test_log::test;
@@ -278,7 +296,10 @@ mod tests {
 
     use crate::{
         messages::{RequestBatchSet, RequestHistoryChunk, RequestMacroChain},
-        sync::{history::HistoryMacroSync, syncer::MacroSyncReturn},
+        sync::{
+            history::{HistoryMacroSync, HistoryStagingStore},
+            syncer::MacroSyncReturn,
+        },
     };
 
     fn blockchain() -> Arc<RwLock<Blockchain>> {
@@ -366,6 +387,7 @@ mod tests {
             Arc::clone(&chain),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            None,
         );
 
         spawn_request_handlers(&net2, &chain);
@@ -405,6 +427,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            None,
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -445,6 +468,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            None,
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -478,6 +502,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            None,
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -512,6 +537,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            None,
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -553,6 +579,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            None,
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -604,6 +631,7 @@ mod tests {
             Arc::clone(&chain1),
             Arc::clone(&net1),
             net1.subscribe_events(),
+            None,
         );
 
         spawn_request_handlers(&net2, &chain2);
@@ -667,6 +695,7 @@ mod tests {
                 Arc::clone(&chain_sync),
                 Arc::clone(&net_sync),
                 net_sync.subscribe_events(),
+                None,
             );
 
             spawn_request_handlers(&net_sync, &chain_sync);
@@ -734,4 +763,130 @@ mod tests {
             DisconnectDuringSyncStream::new(res.current_i / 3, Some(res.current_i / 3 * 2));
         assert!(res.next().await.unwrap());
     }
+
+    #[test(tokio::test)]
+    async fn it_resumes_from_staged_history() {
+        let mut hub = MockHub::default();
+        let net1 = Arc::new(hub.new_network());
+        let net2 = Arc::new(hub.new_network());
+
+        let chain1 = blockchain();
+        let chain2 = blockchain();
+
+        let producer = BlockProducer::new(signing_key(), voting_key());
+        produce_macro_blocks_with_txns(
+            &producer,
+            &chain2,
+            Policy::batches_per_epoch() as usize,
+            1,
+            0,
+        );
+
+        // Stage the history of the epoch as if it had been downloaded before a restart.
+        let staging = HistoryStagingStore::new(VolatileDatabase::new(20).unwrap());
+        let election_block = chain2.read().election_head();
+        let election_hash = election_block.hash();
+        let mut chunk_index = 0;
+        while let Some(chunk) = chain2.read().history_store.prove_chunk(
+            election_block.epoch_number(),
+            election_block.block_number(),
+            CHUNK_SIZE,
+            chunk_index,
+            None,
+        ) {
+            if chunk.history.is_empty() {
+                break;
+            }
+            staging.put_chunk(&election_hash, chunk_index as u64, &chunk.history);
+            chunk_index += 1;
+        }
+        assert!(chunk_index > 0);
+
+        let mut sync = HistoryMacroSync::<MockNetwork>::new(
+            Arc::clone(&chain1),
+            Arc::clone(&net1),
+            net1.subscribe_events(),
+            Some(staging),
+        );
+
+        // The peer doesn't serve any history chunks, so the history can only come from staging.
+        spawn(request_handler(
+            &net2,
+            net2.receive_requests::<RequestMacroChain>(),
+            &BlockchainProxy::from(&chain2),
+        ));
+        spawn(request_handler(
+            &net2,
+            net2.receive_requests::<RequestBatchSet>(),
+            &chain2,
+        ));
+        net1.dial_mock(&net2);
+
+        match sync.next().await {
+            Some(MacroSyncReturn::Good(_)) => {
+                assert_eq!(chain1.read().head(), chain2.read().head());
+            }
+            res => panic!("Unexpected HistorySyncReturn: {res:?}"),
+        }
+
+        // The staged history was removed once it was applied.
+        let staging = sync.staging.as_ref().unwrap();
+        assert_eq!(staging.get_history(&election_hash, 0).1, 0);
+        assert!(staging.get_batch_set_info(&election_hash).is_none());
+    }
+
+    #[test(tokio::test)]
+    async fn it_discards_staged_history_the_chain_moved_past() {
+        let mut hub = MockHub::default();
+        let net = Arc::new(hub.new_network());
+
+        let chain = blockchain();
+        let producer = BlockProducer::new(signing_key(), voting_key());
+        produce_macro_blocks_with_txns(
+            &producer,
+            &chain,
+            Policy::batches_per_epoch() as usize,
+            1,
+            0,
+        );
+
+        // Stage the epoch the chain already contains, e.g. left over from a restart.
+        let staging = HistoryStagingStore::new(VolatileDatabase::new(20).unwrap());
+        let election_block = chain.read().election_head();
+        let election_hash = election_block.hash();
+        let batch_set_info =
+            <RequestBatchSet as Handle<MockNetwork, Arc<RwLock<Blockchain>>>>::handle(
+                &RequestBatchSet {
+                    hash: election_hash.clone(),
+                },
+                net.get_local_peer_id(),
+                &chain,
+            )
+            .unwrap();
+        staging.put_batch_set_info(&election_hash, &batch_set_info);
+        let history = chain
+            .read()
+            .history_store
+            .prove_chunk(
+                election_block.epoch_number(),
+                election_block.block_number(),
+                CHUNK_SIZE,
+                0,
+                None,
+            )
+            .unwrap()
+            .history;
+        staging.put_chunk(&election_hash, 0, &history);
+
+        let sync = HistoryMacroSync::<MockNetwork>::new(
+            Arc::clone(&chain),
+            Arc::clone(&net),
+            net.subscribe_events(),
+            Some(staging),
+        );
+
+        let staging = sync.staging.as_ref().unwrap();
+        assert!(staging.get_batch_set_info(&election_hash).is_none());
+        assert_eq!(staging.get_history(&election_hash, 0).1, 0);
+    }
 }
@@ -6,12 +6,14 @@ use std::{
 
 use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt};
 use nimiq_block::Block;
+use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::{
     network::{CloseReason, Network, SubscribeEvents},
     request::RequestError,
 };
+use nimiq_primitives::policy::Policy;
 use nimiq_utils::spawn::spawn;
 use nimiq_zkp_component::{
     types::{Error, ZKPRequestEvent},
@@ -23,7 +25,11 @@ use parking_lot::RwLock;
 use crate::messages::{HistoryChunk, HistoryChunkError, RequestHistoryChunk};
 use crate::{
     messages::{BlockError, Checkpoint},
-    sync::{peer_list::PeerList, sync_queue::SyncQueue, syncer::MacroSync},
+    sync::{
+        peer_list::PeerList,
+        sync_queue::SyncQueue,
+        syncer::{MacroSync, MacroSyncProgress},
+    },
 };
 
 #[derive(Clone)]
@@ -52,6 +58,22 @@ impl<T> EpochIds<T> {
     pub(crate) fn last_epoch_number(&self) -> usize {
         self.checkpoint_epoch_number().saturating_sub(1)
     }
+
+    /// Returns the epoch and block number of the latest macro block announced by the sender
+    /// (if any)
+    pub(crate) fn target(&self) -> Option<(u32, u32)> {
+        if let Some(checkpoint) = &self.checkpoint {
+            return Some((
+                self.checkpoint_epoch_number() as u32,
+                checkpoint.block_number,
+            ));
+        }
+        if self.ids.is_empty() {
+            return None;
+        }
+        let epoch_number = self.last_epoch_number() as u32;
+        Policy::election_block_of(epoch_number).map(|block_number| (epoch_number, block_number))
+    }
 }
 
 /// Struct used to track the progress of the validity window chunk process.
@@ -130,7 +152,7 @@ const PENDING_SIZE: usize = 5;
 /// The LightMacroSync is one type of MacroSync and it is essentially a stream,
 /// that operates on a per peer basis, emitting peers either as Outdated or Good.
 /// To do this, it will:
-///   1. Request the latest ZKP from a peer
+///   1. Request the latest ZKP from a peer, or the trusted checkpoint block if one is configured
 ///   2. Request epoch IDs from the peer
 ///   3. Request the last (if any) election or checkpoint blocks
 /// If during the process, a peer is deemed as outdated, then it is emitted
@@ -151,6 +173,18 @@ pub struct LightMacroSync<TNetwork: Network> {
     /// ZKP related requests (proofs)
     pub(crate) zkp_requests:
         FuturesUnordered<BoxFuture<'static, (Result<ZKPRequestEvent, Error>, TNetwork::PeerId)>>,
+    /// Hash of the trusted election block to sync from instead of requesting ZKPs
+    pub(crate) trusted_checkpoint: Option<Blake2bHash>,
+    /// Trusted checkpoint block requests
+    pub(crate) checkpoint_requests: FuturesUnordered<
+        BoxFuture<
+            'static,
+            (
+                Result<Result<Block, BlockError>, RequestError>,
+                TNetwork::PeerId,
+            ),
+        >,
+    >,
     /// Block requests
     pub(crate) block_headers: FuturesUnordered<
         BoxFuture<
@@ -184,6 +218,10 @@ pub struct LightMacroSync<TNetwork: Network> {
     pub(crate) synced_validity_peers: Vec<TNetwork::PeerId>,
     /// Minimum distance to light sync in #blocks from the peers head.
     pub(crate) full_sync_threshold: u32,
+    /// The epoch number of our blockchain when the sync started
+    pub(crate) start_epoch: u32,
+    /// The epoch and block number of the latest macro block announced by our peers
+    pub(crate) target: Option<(u32, u32)>,
     /// Waker used for the poll next function
     pub(crate) waker: Option<Waker>,
 }
@@ -195,6 +233,7 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
         network_event_rx: SubscribeEvents<TNetwork::PeerId>,
         zkp_component_proxy: ZKPComponentProxy<TNetwork>,
         full_sync_threshold: u32,
+        trusted_checkpoint: Option<Blake2bHash>,
     ) -> Self {
         #[cfg(feature = "full")]
         let peers = Arc::new(RwLock::new(PeerList::default()));
@@ -221,6 +260,8 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
             },
         );
 
+        let start_epoch = blockchain.read().epoch_number();
+
         Self {
             blockchain,
             network,
@@ -229,8 +270,12 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
             epoch_ids_stream: FuturesUnordered::new(),
             zkp_component_proxy,
             zkp_requests: FuturesUnordered::new(),
+            trusted_checkpoint,
+            checkpoint_requests: FuturesUnordered::new(),
             waker: None,
             full_sync_threshold,
+            start_epoch,
+            target: None,
             block_headers: Default::default(),
             validity_requests: None,
             syncing_peers: HashSet::new(),
@@ -261,10 +306,26 @@ impl<TNetwork: Network> MacroSync<TNetwork::PeerId> for LightMacroSync<TNetwork>
     const MAX_REQUEST_EPOCHS: u16 = 1000; // TODO: Use other value
 
     fn add_peer(&mut self, peer_id: TNetwork::PeerId) {
-        info!(%peer_id, "Requesting zkp from peer");
+        if let Some(checkpoint) = &self.trusted_checkpoint {
+            info!(%peer_id, %checkpoint, "Requesting trusted checkpoint from peer");
 
-        self.zkp_requests
-            .push(Self::request_zkps(self.zkp_component_proxy.clone(), peer_id).boxed());
+            let network = Arc::clone(&self.network);
+            let block_hash = checkpoint.clone();
+            self.checkpoint_requests.push(
+                async move {
+                    (
+                        Self::request_macro_block(network, peer_id, block_hash).await,
+                        peer_id,
+                    )
+                }
+                .boxed(),
+            );
+        } else {
+            info!(%peer_id, "Requesting zkp from peer");
+
+            self.zkp_requests
+                .push(Self::request_zkps(self.zkp_component_proxy.clone(), peer_id).boxed());
+        }
 
         // Pushing the future to FuturesUnordered above does not wake the task that
         // polls `epoch_ids_stream`. Therefore, we need to wake the task manually.
@@ -272,4 +333,20 @@ impl<TNetwork: Network> MacroSync<TNetwork::PeerId> for LightMacroSync<TNetwork>
             waker.wake_by_ref();
         }
     }
+
+    fn progress(&self) -> MacroSyncProgress<TNetwork::PeerId> {
+        let mut progress = MacroSyncProgress::new(self.syncing_peers.iter().copied().collect());
+
+        // The light sync adopts the latest election block in a single step, so there are no
+        // chunks to count.
+        if let Some((target_epoch, target_block)) = self.target {
+            let current_epoch = self.blockchain.read().epoch_number();
+            progress.target_epoch = Some(target_epoch);
+            progress.target_block = Some(target_block);
+            progress.epochs_done = current_epoch.saturating_sub(self.start_epoch);
+            progress.epochs_total = target_epoch.saturating_sub(self.start_epoch);
+        }
+
+        progress
+    }
 }
@@ -9,6 +9,8 @@ use nimiq_block::Block;
 #[cfg(feature = "full")]
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::AbstractBlockchain;
+#[cfg(feature = "full")]
+use nimiq_blockchain_interface::PushResult;
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_light_blockchain::LightBlockchain;
 use nimiq_network_interface::network::{CloseReason, Network, NetworkEvent};
@@ -164,6 +166,102 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
         Poll::Pending
     }
 
+    // Function that polls the trusted checkpoint block requests:
+    //   A) The request fails:
+    //         In this case we disconnect the peer
+    //   B) The peer does not know the checkpoint:
+    //         In this case we can't sync from it and emit it as outdated
+    //   C) The peer sends the checkpoint block:
+    //         In this case we adopt it (unless we are already past it) and request epoch ids from this peer
+    fn poll_checkpoints(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<MacroSyncReturn<TNetwork::PeerId>>> {
+        while let Poll::Ready(Some(checkpoint_result)) =
+            self.checkpoint_requests.poll_next_unpin(cx)
+        {
+            let checkpoint = self
+                .trusted_checkpoint
+                .clone()
+                .expect("Checkpoint requests require a trusted checkpoint");
+
+            match checkpoint_result {
+                (Ok(Ok(block)), peer_id) => {
+                    let result = match self.blockchain {
+                        #[cfg(feature = "full")]
+                        BlockchainProxy::Full(ref full_blockchain) => {
+                            let blockchain_urg = full_blockchain.upgradable_read();
+                            if block.hash() == checkpoint
+                                && block
+                                    .block_number()
+                                    .saturating_sub(blockchain_urg.block_number())
+                                    <= self.full_sync_threshold
+                            {
+                                // We are already past the checkpoint or close enough to it not to clear
+                                // our state. Instead, we request the epoch ids from this peer.
+                                log::debug!(
+                                    peer_id = %peer_id,
+                                    "Not applying the trusted checkpoint, our chain is sufficiently close."
+                                );
+                                Ok(PushResult::Ignored)
+                            } else {
+                                Blockchain::push_trusted_checkpoint(
+                                    blockchain_urg,
+                                    block,
+                                    &checkpoint,
+                                )
+                            }
+                        }
+                        BlockchainProxy::Light(ref light_blockchain) => {
+                            LightBlockchain::push_trusted_checkpoint(
+                                light_blockchain.upgradable_read(),
+                                block,
+                                &checkpoint,
+                            )
+                        }
+                    };
+
+                    match result {
+                        Ok(result) => {
+                            log::debug!(result = ?result, "Applied trusted checkpoint to the blockchain");
+                            // Request epoch ids with our updated state from this peer
+                            let future = Self::request_epoch_ids(
+                                self.blockchain.clone(),
+                                Arc::clone(&self.network),
+                                peer_id,
+                            )
+                            .boxed();
+                            self.epoch_ids_stream.push(future);
+                        }
+                        Err(result) => {
+                            log::warn!(?result, %peer_id, "Banning peer because it sent a block not matching the trusted checkpoint");
+
+                            self.disconnect_peer(peer_id, CloseReason::MaliciousPeer);
+
+                            return Poll::Ready(None);
+                        }
+                    }
+                }
+                (Ok(Err(error)), peer_id) => {
+                    // The peer doesn't know the checkpoint, so we can't sync from it.
+                    log::debug!(%error, %peer_id, "Peer doesn't know the trusted checkpoint");
+                    return Poll::Ready(Some(MacroSyncReturn::Outdated(peer_id)));
+                }
+                (Err(error), peer_id) => {
+                    log::debug!(
+                        ?error,
+                        %peer_id,
+                        "Error requesting trusted checkpoint from peer",
+                    );
+                    self.disconnect_peer(peer_id, CloseReason::Error);
+                    return Poll::Ready(None);
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+
     fn poll_epoch_ids(
         &mut self,
         cx: &mut Context<'_>,
@@ -182,7 +280,14 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
                 );
 
                 return Poll::Ready(Some(MacroSyncReturn::Outdated(epoch_ids.sender)));
-            } else if epoch_ids.ids.is_empty() && epoch_ids.checkpoint.is_none() {
+            }
+
+            // Remember the furthest macro block any peer announced as the target of the sync.
+            if let Some(target) = epoch_ids.target() {
+                self.target = self.target.max(Some(target));
+            }
+
+            if epoch_ids.ids.is_empty() && epoch_ids.checkpoint.is_none() {
                 match self.blockchain {
                     #[cfg(feature = "full")]
                     BlockchainProxy::Full(ref blockchain) => {
@@ -427,6 +532,10 @@ impl<TNetwork: Network> Stream for LightMacroSync<TNetwork> {
             return Poll::Ready(o);
         }
 
+        if let Poll::Ready(o) = self.poll_checkpoints(cx) {
+            return Poll::Ready(o);
+        }
+
         if let Poll::Ready(o) = self.poll_epoch_ids(cx) {
             return Poll::Ready(o);
         }
@@ -455,9 +564,12 @@ mod tests {
 
     use futures::StreamExt;
     use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
-    use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+    use nimiq_blockchain_interface::AbstractBlockchain;
+    #[cfg(feature = "full")]
+    use nimiq_blockchain_interface::PushResult;
     use nimiq_blockchain_proxy::BlockchainProxy;
     use nimiq_database::{traits::WriteTransaction, volatile::VolatileDatabase};
+    use nimiq_hash::Blake2bHash;
     use nimiq_light_blockchain::LightBlockchain;
     use nimiq_network_interface::{network::Network, request::request_handler};
     use nimiq_network_mock::{MockHub, MockNetwork};
@@ -469,7 +581,10 @@ mod tests {
 
     use crate::{
         messages::{RequestBlock, RequestHistoryChunk, RequestMacroChain},
-        sync::{light::LightMacroSync, syncer::MacroSyncReturn},
+        sync::{
+            light::LightMacroSync,
+            syncer::{MacroSync, MacroSyncReturn},
+        },
     };
 
     fn blockchain() -> BlockchainProxy {
@@ -541,6 +656,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                None,
             );
 
             spawn_request_handlers(&net2, &chain2.clone());
@@ -584,6 +700,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                None,
             );
 
             let zkp_component2 =
@@ -648,6 +765,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                None,
             );
 
             let zkp_component2 =
@@ -711,6 +829,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                None,
             );
 
             let zkp_component2 =
@@ -728,6 +847,11 @@ mod tests {
                 }
                 res => panic!("Unexpected HistorySyncReturn: {res:?}"),
             }
+
+            // The checkpoint announced by the peer is reported as the target.
+            let progress = sync.progress();
+            assert_eq!(progress.target_epoch, Some(chain2.read().epoch_number()));
+            assert_eq!(progress.target_block, Some(chain2.read().block_number()));
         }
 
         test(light_blockchain()).await;
@@ -767,6 +891,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                None,
             );
 
             let zkp_component2 =
@@ -867,6 +992,7 @@ mod tests {
                 net1.subscribe_events(),
                 zkp_component_proxy,
                 0,
+                None,
             );
 
             let zkp_component2 =
@@ -894,4 +1020,106 @@ mod tests {
         test(0).await;
         test(1).await;
     }
+
+    #[test(tokio::test)]
+    async fn it_can_sync_from_a_trusted_checkpoint() {
+        async fn test(chain1: BlockchainProxy) {
+            let mut hub = MockHub::default();
+            let net1 = Arc::new(hub.new_network());
+            let net2 = Arc::new(hub.new_network());
+
+            let chain2 = blockchain();
+            let producer = BlockProducer::new(signing_key(), voting_key());
+            let mut checkpoint = None;
+            if let BlockchainProxy::Full(ref chain2) = chain2 {
+                produce_macro_blocks_with_txns(
+                    &producer,
+                    chain2,
+                    Policy::batches_per_epoch() as usize,
+                    1,
+                    0,
+                );
+                checkpoint = Some(chain2.read().election_head_hash());
+                produce_macro_blocks_with_txns(
+                    &producer,
+                    chain2,
+                    Policy::batches_per_epoch() as usize,
+                    1,
+                    0,
+                );
+            }
+
+            let zkp_component =
+                nimiq_zkp_component::ZKPComponent::new(chain1.clone(), Arc::clone(&net1), None)
+                    .await;
+
+            let zkp_component_proxy = zkp_component.proxy();
+
+            spawn(zkp_component);
+
+            let mut sync = LightMacroSync::<MockNetwork>::new(
+                chain1.clone(),
+                Arc::clone(&net1),
+                net1.subscribe_events(),
+                zkp_component_proxy,
+                0,
+                checkpoint,
+            );
+
+            // The peer doesn't need to provide any proofs.
+            spawn_request_handlers(&net2, &chain2.clone());
+            net1.dial_mock(&net2);
+
+            match sync.next().await {
+                Some(MacroSyncReturn::Good(_)) => {
+                    assert_eq!(chain1.read().head(), chain2.read().head());
+                }
+                res => panic!("Unexpected MacroSyncReturn: {res:?}"),
+            }
+        }
+
+        test(light_blockchain()).await;
+        test(blockchain()).await;
+    }
+
+    #[test(tokio::test)]
+    async fn it_emits_peers_not_knowing_the_trusted_checkpoint() {
+        async fn test(chain1: BlockchainProxy) {
+            let mut hub = MockHub::default();
+            let net1 = Arc::new(hub.new_network());
+            let net2 = Arc::new(hub.new_network());
+
+            let chain2 = blockchain();
+
+            let zkp_component =
+                nimiq_zkp_component::ZKPComponent::new(chain1.clone(), Arc::clone(&net1), None)
+                    .await;
+
+            let zkp_component_proxy = zkp_component.proxy();
+
+            spawn(zkp_component);
+
+            let mut sync = LightMacroSync::<MockNetwork>::new(
+                chain1.clone(),
+                Arc::clone(&net1),
+                net1.subscribe_events(),
+                zkp_component_proxy,
+                0,
+                Some(Blake2bHash::default()),
+            );
+
+            spawn_request_handlers(&net2, &chain2.clone());
+            net1.dial_mock(&net2);
+
+            match sync.next().await {
+                Some(MacroSyncReturn::Outdated(_)) => {
+                    assert_eq!(chain1.read().block_number(), Policy::genesis_block_number());
+                }
+                res => panic!("Unexpected MacroSyncReturn: {res:?}"),
+            }
+        }
+
+        test(light_blockchain()).await;
+        test(blockchain()).await;
+    }
 }
@@ -393,6 +393,35 @@ pub async fn push_chunks_only<N: Network>(
     (push_results.push_chunks_result, push_results.block_hash)
 }
 
+/// Pushes chunks of the accounts snapshot at the given macro block into the blockchain.
+#[cfg(feature = "full")]
+pub async fn push_snapshot_chunks<N: Network>(
+    blockchain: BlockchainProxy,
+    block_hash: Blake2bHash,
+    chunks: Vec<ChunkAndId<N>>,
+) -> (Result<ChunksPushResult, ChunksPushError>, Blake2bHash) {
+    let chunks: Vec<_> = chunks
+        .into_iter()
+        .map(|chunk| ChunkAndId::into_pair(chunk).0)
+        .collect();
+
+    let push_result = {
+        let block_hash = block_hash.clone();
+        spawn_blocking(move || match blockchain {
+            BlockchainProxy::Full(ref blockchain) => blockchain
+                .upgradable_read()
+                .commit_snapshot_chunks(chunks, &block_hash),
+            BlockchainProxy::Light(ref _blockchain) => {
+                // We do not push chunks into a light blockchain.
+                unreachable!()
+            }
+        })
+        .await
+    };
+
+    (push_result, block_hash)
+}
+
 /// Pushes the a single block and the respective chunks into the blockchain. If a light
 /// blockchain was supplied, no chunks are committed.
 /// The return value consists of the result of pushing the block, the error of pushing
@@ -414,8 +443,10 @@ fn blockchain_push<N: Network>(
     let blockchain_push_result;
     if let Some(block) = block {
         let block_hash = block.hash();
-        // Update validator keys from BLS public key cache.
+        // Update validator keys from BLS public key cache. They are only persisted once the
+        // block has been accepted, since the block hasn't been verified yet.
         block.update_validator_keys(&mut bls_cache.lock());
+        let validators = block.validators();
         match blockchain {
             #[cfg(feature = "full")]
             BlockchainProxy::Full(ref blockchain) => {
@@ -444,6 +475,15 @@ fn blockchain_push<N: Network>(
                     BlockchainPushResult::with_light_block_result(push_result, block_hash);
             }
         }
+
+        // Persist the voting keys of the validator set that was just accepted.
+        if let (Some(validators), Some(Ok(PushResult::Extended | PushResult::Rebranched))) =
+            (validators, &blockchain_push_result.block_push_result)
+        {
+            bls_cache
+                .lock()
+                .warm_up(validators.iter().map(|validator| &validator.voting_key));
+        }
     } else {
         match blockchain {
             #[cfg(feature = "full")]
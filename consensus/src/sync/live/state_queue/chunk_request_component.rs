@@ -5,11 +5,14 @@ use std::{
 };
 
 use futures::{FutureExt, Stream, StreamExt};
-use nimiq_network_interface::{network::Network, request::RequestError};
+use nimiq_network_interface::{
+    network::Network,
+    request::{InboundRequestError, RequestError},
+};
 use nimiq_primitives::key_nibbles::KeyNibbles;
 use parking_lot::RwLock;
 
-use super::{RequestChunk, ResponseChunk};
+use super::{RequestChunk, RequestSnapshotChunk, ResponseChunk};
 use crate::sync::{peer_list::PeerList, sync_queue::SyncQueue};
 
 /// Peer Tracking & Chunk Request Component.
@@ -65,12 +68,25 @@ impl<N: Network> ChunkRequestComponent<N> {
         self.sync_queue.add_ids(vec![(request, None)]);
     }
 
+    /// Requests a chunk of the peer's accounts snapshot, or of its head if the peer has no
+    /// complete snapshot.
     async fn request_missing_chunks_from_peer(
         network: Arc<N>,
         peer_id: N::PeerId,
         request: RequestChunk,
     ) -> Result<ResponseChunk, RequestError> {
-        network.request::<RequestChunk>(request, peer_id).await
+        let response = network
+            .request::<RequestSnapshotChunk>(request.clone().into(), peer_id)
+            .await;
+
+        match response {
+            // Peers that don't support snapshot chunks yet have no receiver for the request.
+            Err(RequestError::InboundRequest(InboundRequestError::NoReceiver)) => {
+                debug!(%peer_id, "Peer doesn't support accounts snapshot chunks");
+                network.request::<RequestChunk>(request, peer_id).await
+            }
+            response => response,
+        }
     }
 }
 
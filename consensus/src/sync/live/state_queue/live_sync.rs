@@ -167,6 +167,16 @@ impl<N: Network> LiveSyncQueue<N> for StateQueue<N> {
                         .boxed(),
                 );
             }
+            QueuedStateChunks::SnapshotStateChunk(block_hash, chunks) => {
+                // Chunks of the accounts snapshot at our latest macro block.
+                future_results.push_back(
+                    queue::push_snapshot_chunks::<N>(blockchain, block_hash, chunks)
+                        .map(|(push_chunk_error, block_hash)| {
+                            PushOpResult::HeadChunk(push_chunk_error, block_hash)
+                        })
+                        .boxed(),
+                );
+            }
             QueuedStateChunks::TooFarFutureBlock(peer_id)
             | QueuedStateChunks::TooFarFutureChunk(ChunkAndId { peer_id, .. }) => {
                 // Peer is too far ahead.
@@ -89,11 +89,41 @@ impl RequestCommon for RequestChunk {
     const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_CHUNKS;
 }
 
+/// The request of a trie chunk of the accounts snapshot at the latest macro block.
+/// Peers without a complete snapshot respond with a chunk of their head instead, like for
+/// [`RequestChunk`].
+///
+/// Peers that don't support this request yet reply with a `NoReceiver` error, in which case
+/// [`RequestChunk`] should be used instead.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestSnapshotChunk {
+    pub start_key: KeyNibbles,
+    pub limit: u32,
+}
+
+impl From<RequestChunk> for RequestSnapshotChunk {
+    fn from(request: RequestChunk) -> Self {
+        RequestSnapshotChunk {
+            start_key: request.start_key,
+            limit: request.limit,
+        }
+    }
+}
+
+impl RequestCommon for RequestSnapshotChunk {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 220;
+    type Response = ResponseChunk;
+
+    const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_CHUNKS;
+}
+
 pub enum QueuedStateChunks<N: Network> {
     Head(BlockAndId<N>, Option<TrieDiff>, Vec<ChunkAndId<N>>),
     Buffered(Vec<(BlockAndId<N>, Option<TrieDiff>, Vec<ChunkAndId<N>>)>),
     Missing(Vec<(Block, Option<TrieDiff>, Vec<ChunkAndId<N>>)>),
     HeadStateChunk(Vec<ChunkAndId<N>>),
+    SnapshotStateChunk(Blake2bHash, Vec<ChunkAndId<N>>),
     TooFarFutureBlock(N::PeerId),
     TooDistantPastBlock(N::PeerId),
     TooFarFutureChunk(ChunkAndId<N>),
@@ -171,6 +201,11 @@ pub struct StateQueue<N: Network> {
     /// or a rebranch event.
     start_key: ChunkRequestState,
 
+    /// Whether we sync against the accounts snapshot at the latest macro block. Peers serve
+    /// chunks of their snapshot if they have one, otherwise chunks of their head. We follow the
+    /// kind of chunks we last received, such that a reset continues the right chain of chunks.
+    snapshot_sync: bool,
+
     /// The blockchain event stream.
     blockchain_rx: BoxStream<'static, BlockchainEvent>,
 }
@@ -210,6 +245,7 @@ impl<N: Network> StateQueue<N> {
             buffer_size: 0,
             current_macro_height,
             start_key,
+            snapshot_sync: true,
             blockchain_rx,
         }
     }
@@ -248,11 +284,17 @@ impl<N: Network> StateQueue<N> {
     fn request_chunk(&mut self) -> bool {
         let start_key = match self.start_key {
             ChunkRequestState::Complete | ChunkRequestState::Paused(_) => None,
-            ChunkRequestState::Reset => self
-                .blockchain
-                .read()
-                .get_missing_accounts_range(None)
-                .map(|v| v.start),
+            ChunkRequestState::Reset => {
+                let blockchain = self.blockchain.read();
+                let snapshot_range = if self.snapshot_sync {
+                    blockchain.get_missing_snapshot_range(None)
+                } else {
+                    None
+                };
+                snapshot_range
+                    .or_else(|| blockchain.get_missing_accounts_range(None))
+                    .map(|v| v.start)
+            }
             ChunkRequestState::Continue(ref key) => Some(key.clone()),
         };
 
@@ -322,6 +364,7 @@ impl<N: Network> StateQueue<N> {
         let blockchain = self.blockchain.read();
         let current_block_height = blockchain.block_number();
         let current_block_hash = blockchain.head_hash();
+        let macro_head_hash = blockchain.macro_head_hash();
         let contains_block = blockchain.contains(&chunk.block_hash, true);
         drop(blockchain);
 
@@ -332,7 +375,18 @@ impl<N: Network> StateQueue<N> {
             self.prune_buffer();
         }
 
-        if chunk.block_number < current_block_height.saturating_sub(self.config.tolerate_past_max) {
+        if chunk.block_hash == macro_head_hash && chunk.block_hash != current_block_hash {
+            // Chunks of the snapshot at our latest macro block are applied to our snapshot. They
+            // are valid for the whole batch, so the toleration window doesn't apply.
+            self.snapshot_sync = true;
+            self.set_start_key(&chunk.chunk.end_key, current_block_height);
+            return Some(QueuedStateChunks::SnapshotStateChunk(
+                chunk.block_hash,
+                vec![ChunkAndId::new(chunk.chunk, start_key, peer_id)],
+            ));
+        } else if chunk.block_number
+            < current_block_height.saturating_sub(self.config.tolerate_past_max)
+        {
             log::warn!(
                 "Discarding chunk {} earlier than toleration window (max {})",
                 chunk,
@@ -370,6 +424,7 @@ impl<N: Network> StateQueue<N> {
             );
         } else if chunk.block_hash == current_block_hash {
             // Immediately return chunks for the current head blockchain.
+            self.snapshot_sync = false;
             self.set_start_key(&chunk.chunk.end_key, chunk.block_number);
             return Some(QueuedStateChunks::HeadStateChunk(vec![ChunkAndId::new(
                 chunk.chunk,
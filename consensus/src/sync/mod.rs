@@ -3,6 +3,8 @@ pub mod history;
 pub mod light;
 pub mod live;
 pub mod peer_list;
+pub mod peer_scheduler;
+pub mod status;
 mod sync_queue;
 pub mod syncer;
 pub mod syncer_proxy;
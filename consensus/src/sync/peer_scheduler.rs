@@ -0,0 +1,255 @@
+use std::{collections::HashMap, hash::Hash, time::Duration};
+
+use instant::Instant;
+
+/// Request statistics of a single peer.
+#[derive(Clone, Debug, Default)]
+pub struct PeerStats {
+    /// The number of requests that completed, successfully or not.
+    pub num_requests: u64,
+    /// The number of requests that failed.
+    pub num_failures: u64,
+    /// The smoothed latency of the successful requests.
+    pub latency: Option<Duration>,
+    /// The number of requests currently in flight.
+    pub in_flight: usize,
+}
+
+impl PeerStats {
+    /// The fraction of requests that failed. Peers without any completed requests are assumed
+    /// to be reliable.
+    pub fn failure_rate(&self) -> f64 {
+        if self.num_requests == 0 {
+            return 0.0;
+        }
+        self.num_failures as f64 / self.num_requests as f64
+    }
+}
+
+/// Schedules requests over a set of peers based on their observed latency and reliability.
+///
+/// Peers are picked by their expected time to answer one more request, which grows with their
+/// latency, the number of requests already in flight to them and their failure rate. This way,
+/// fast peers get several requests concurrently while slow or unreliable peers are used less.
+///
+/// The number of requests that should be in flight overall (the concurrency) is adapted to the
+/// observed bandwidth: it grows by one for every window of successful requests and is halved
+/// whenever a request fails or takes much longer than the peer usually needs.
+#[derive(Debug)]
+pub struct PeerScheduler<TPeerId> {
+    peers: HashMap<TPeerId, PeerStats>,
+    concurrency: usize,
+    min_concurrency: usize,
+    max_concurrency: usize,
+    num_successes_in_window: usize,
+    num_completed: u64,
+    started_at: Instant,
+}
+
+impl<TPeerId: Copy + Eq + Hash> PeerScheduler<TPeerId> {
+    /// The maximum number of requests in flight to a single peer.
+    const MAX_IN_FLIGHT_PER_PEER: usize = 8;
+    /// The weight of a new latency sample in the smoothed latency.
+    const LATENCY_SMOOTHING: f64 = 0.2;
+    /// A request taking longer than this factor times the smoothed latency counts as congestion.
+    const CONGESTION_FACTOR: u32 = 3;
+    /// The latency assumed for peers when no latency was observed at all yet.
+    const DEFAULT_LATENCY: Duration = Duration::from_secs(1);
+
+    pub fn new(initial_concurrency: usize, min_concurrency: usize, max_concurrency: usize) -> Self {
+        assert!(min_concurrency > 0 && min_concurrency <= max_concurrency);
+        Self {
+            peers: HashMap::new(),
+            concurrency: initial_concurrency.clamp(min_concurrency, max_concurrency),
+            min_concurrency,
+            max_concurrency,
+            num_successes_in_window: 0,
+            num_completed: 0,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// The number of requests that should currently be in flight.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// The number of successful requests per second since the scheduler was created.
+    pub fn throughput(&self) -> f64 {
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        if elapsed == 0.0 {
+            return 0.0;
+        }
+        self.num_completed as f64 / elapsed
+    }
+
+    /// The statistics of all peers requests were sent to.
+    pub fn peer_stats(&self) -> impl Iterator<Item = (&TPeerId, &PeerStats)> {
+        self.peers.iter()
+    }
+
+    /// Returns the peer out of `candidates` that is expected to answer the next request the
+    /// fastest. `exclude` is only picked if there is no other candidate, e.g. to retry a failed
+    /// request with a different peer.
+    pub fn select_peer(
+        &self,
+        candidates: &[TPeerId],
+        exclude: Option<&TPeerId>,
+    ) -> Option<TPeerId> {
+        // Peers we know nothing about yet are assumed to be as fast as the fastest known peer,
+        // so that they are tried early on.
+        let best_latency = self
+            .peers
+            .values()
+            .filter_map(|stats| stats.latency)
+            .min()
+            .unwrap_or(Self::DEFAULT_LATENCY);
+
+        let default_stats = PeerStats::default();
+        let cost = |peer_id: &TPeerId| {
+            let stats = self.peers.get(peer_id).unwrap_or(&default_stats);
+            let latency = stats.latency.unwrap_or(best_latency).as_secs_f64();
+            // Keep unreliable peers usable, but make them increasingly expensive.
+            let reliability = (1.0 - stats.failure_rate()).max(0.05);
+            latency * (stats.in_flight + 1) as f64 / reliability
+        };
+        let in_flight =
+            |peer_id: &TPeerId| self.peers.get(peer_id).map_or(0, |stats| stats.in_flight);
+
+        let select = |allow_excluded: bool| {
+            candidates
+                .iter()
+                .filter(|peer_id| allow_excluded || Some(*peer_id) != exclude)
+                .filter(|peer_id| in_flight(peer_id) < Self::MAX_IN_FLIGHT_PER_PEER)
+                .min_by(|a, b| cost(a).total_cmp(&cost(b)))
+                .copied()
+        };
+
+        select(false)
+            .or_else(|| select(true))
+            // All peers are saturated, so fall back to the least busy one.
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .min_by_key(|peer_id| in_flight(peer_id))
+                    .copied()
+            })
+    }
+
+    /// Records that a request was sent to the given peer.
+    pub fn on_request(&mut self, peer_id: &TPeerId) {
+        self.peers.entry(*peer_id).or_default().in_flight += 1;
+    }
+
+    /// Records the outcome of a request to the given peer that took `latency` to complete.
+    pub fn on_response(&mut self, peer_id: &TPeerId, latency: Duration, success: bool) {
+        let stats = self.peers.entry(*peer_id).or_default();
+        stats.in_flight = stats.in_flight.saturating_sub(1);
+        stats.num_requests += 1;
+
+        if !success {
+            stats.num_failures += 1;
+            self.decrease_concurrency();
+            return;
+        }
+
+        let congested = stats
+            .latency
+            .is_some_and(|smoothed| latency > smoothed * Self::CONGESTION_FACTOR);
+        stats.latency = Some(match stats.latency {
+            Some(smoothed) => {
+                smoothed.mul_f64(1.0 - Self::LATENCY_SMOOTHING)
+                    + latency.mul_f64(Self::LATENCY_SMOOTHING)
+            }
+            None => latency,
+        });
+        self.num_completed += 1;
+
+        if congested {
+            self.decrease_concurrency();
+        } else {
+            self.num_successes_in_window += 1;
+            if self.num_successes_in_window >= self.concurrency {
+                self.num_successes_in_window = 0;
+                self.concurrency = (self.concurrency + 1).min(self.max_concurrency);
+            }
+        }
+    }
+
+    /// Records that a request to the given peer was abandoned before it completed.
+    pub fn on_cancel(&mut self, peer_id: &TPeerId) {
+        if let Some(stats) = self.peers.get_mut(peer_id) {
+            stats.in_flight = stats.in_flight.saturating_sub(1);
+        }
+    }
+
+    /// Forgets everything about the given peer.
+    pub fn remove_peer(&mut self, peer_id: &TPeerId) {
+        self.peers.remove(peer_id);
+    }
+
+    fn decrease_concurrency(&mut self) {
+        self.num_successes_in_window = 0;
+        self.concurrency = (self.concurrency / 2).max(self.min_concurrency);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::PeerScheduler;
+
+    #[test]
+    fn it_prefers_fast_and_reliable_peers() {
+        let mut scheduler = PeerScheduler::new(4, 1, 16);
+        let peers = [1, 2, 3];
+
+        for (peer_id, latency) in [(1, 100), (2, 10), (3, 10)] {
+            scheduler.on_request(&peer_id);
+            scheduler.on_response(&peer_id, Duration::from_millis(latency), true);
+        }
+        scheduler.on_request(&3);
+        scheduler.on_response(&3, Duration::from_millis(10), false);
+
+        assert_eq!(scheduler.select_peer(&peers, None), Some(2));
+        // Failed requests are retried with another peer.
+        assert_eq!(scheduler.select_peer(&peers, Some(&2)), Some(3));
+        assert_eq!(scheduler.select_peer(&[2], Some(&2)), Some(2));
+
+        // Requests in flight make a peer more expensive.
+        for _ in 0..3 {
+            scheduler.on_request(&2);
+        }
+        assert_eq!(scheduler.select_peer(&peers, None), Some(3));
+
+        // Unknown peers are tried early.
+        assert_eq!(scheduler.select_peer(&[1, 4], None), Some(4));
+    }
+
+    #[test]
+    fn it_adapts_the_concurrency() {
+        let mut scheduler = PeerScheduler::new(4, 2, 6);
+
+        for _ in 0..4 {
+            scheduler.on_request(&1);
+            scheduler.on_response(&1, Duration::from_millis(10), true);
+        }
+        assert_eq!(scheduler.concurrency(), 5);
+
+        for _ in 0..20 {
+            scheduler.on_request(&1);
+            scheduler.on_response(&1, Duration::from_millis(10), true);
+        }
+        assert_eq!(scheduler.concurrency(), 6);
+
+        scheduler.on_request(&1);
+        scheduler.on_response(&1, Duration::from_millis(10), false);
+        assert_eq!(scheduler.concurrency(), 3);
+
+        // A response that is much slower than usual indicates congestion.
+        scheduler.on_request(&1);
+        scheduler.on_response(&1, Duration::from_millis(100), true);
+        assert_eq!(scheduler.concurrency(), 2);
+    }
+}
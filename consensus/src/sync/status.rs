@@ -0,0 +1,180 @@
+use std::{ops::RangeFrom, time::Duration};
+
+use instant::Instant;
+use nimiq_primitives::key_nibbles::KeyNibbles;
+
+use crate::sync::syncer::MacroSyncProgress;
+
+/// The phases a node goes through while syncing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncPhase {
+    /// Syncing macro blocks (and history) up to the latest macro block of the peers.
+    MacroSync,
+    /// Downloading the accounts trie.
+    StateSync,
+    /// Catching up with the blocks the peers are currently processing.
+    LiveSync,
+    /// Consensus is established.
+    Synced,
+}
+
+impl SyncPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncPhase::MacroSync => "macro-sync",
+            SyncPhase::StateSync => "state-sync",
+            SyncPhase::LiveSync => "live-sync",
+            SyncPhase::Synced => "synced",
+        }
+    }
+}
+
+/// A snapshot of the sync state of the node.
+#[derive(Clone, Debug)]
+pub struct SyncStatus<TPeerId> {
+    /// The current phase of the sync.
+    pub phase: SyncPhase,
+    /// The block number of our head.
+    pub block_number: u32,
+    /// The latest epoch known to be available from the peers.
+    pub target_epoch: Option<u32>,
+    /// The latest macro block known to be available from the peers.
+    pub target_block: Option<u32>,
+    /// The number of epochs synced so far.
+    pub epochs_done: u32,
+    /// The total number of epochs to sync.
+    pub epochs_total: u32,
+    /// The number of chunks downloaded for the epoch currently being synced.
+    pub chunks_done: u64,
+    /// The total number of chunks to download for the epoch currently being synced.
+    pub chunks_total: u64,
+    /// The number of chunks downloaded per second.
+    pub chunks_per_second: f64,
+    /// The number of chunk requests kept in flight.
+    pub chunk_concurrency: usize,
+    /// The range of the accounts trie that is still missing, if the trie is incomplete.
+    pub missing_accounts_range: Option<RangeFrom<KeyNibbles>>,
+    /// The estimated time until the macro sync is finished.
+    pub eta: Option<Duration>,
+    /// The peers we are syncing with.
+    pub peers: Vec<TPeerId>,
+}
+
+impl<TPeerId> SyncStatus<TPeerId> {
+    pub(crate) fn new(
+        phase: SyncPhase,
+        block_number: u32,
+        progress: MacroSyncProgress<TPeerId>,
+        eta: Option<Duration>,
+    ) -> Self {
+        Self {
+            phase,
+            block_number,
+            target_epoch: progress.target_epoch,
+            target_block: progress.target_block,
+            epochs_done: progress.epochs_done,
+            epochs_total: progress.epochs_total,
+            chunks_done: progress.chunks_done,
+            chunks_total: progress.chunks_total,
+            chunks_per_second: progress.chunks_per_second,
+            chunk_concurrency: progress.chunk_concurrency,
+            missing_accounts_range: None,
+            eta,
+            peers: progress.peers,
+        }
+    }
+}
+
+impl<TPeerId> Default for SyncStatus<TPeerId> {
+    fn default() -> Self {
+        Self::new(
+            SyncPhase::MacroSync,
+            0,
+            MacroSyncProgress::new(vec![]),
+            None,
+        )
+    }
+}
+
+/// Estimates the remaining time of the macro sync from the rate at which it progressed since
+/// it was first observed.
+#[derive(Default)]
+pub(crate) struct EtaEstimator {
+    start: Option<(Instant, f64)>,
+}
+
+impl EtaEstimator {
+    pub(crate) fn estimate<TPeerId>(
+        &mut self,
+        progress: &MacroSyncProgress<TPeerId>,
+    ) -> Option<Duration> {
+        let fraction = Self::fraction(progress)?;
+        let (start, start_fraction) = *self.start.get_or_insert((Instant::now(), fraction));
+        if fraction <= start_fraction {
+            return None;
+        }
+
+        let rate = (fraction - start_fraction) / start.elapsed().as_secs_f64();
+        Duration::try_from_secs_f64((1.0 - fraction) / rate).ok()
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.start = None;
+    }
+
+    /// The fraction of the macro sync that is done. Chunks count towards the current epoch.
+    fn fraction<TPeerId>(progress: &MacroSyncProgress<TPeerId>) -> Option<f64> {
+        let chunk_fraction = if progress.chunks_total > 0 {
+            progress.chunks_done as f64 / progress.chunks_total as f64
+        } else {
+            0.0
+        };
+
+        if progress.epochs_total > 0 {
+            let epochs_done = progress.epochs_done as f64 + chunk_fraction;
+            Some((epochs_done / progress.epochs_total as f64).min(1.0))
+        } else if progress.chunks_total > 0 {
+            Some(chunk_fraction)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EtaEstimator;
+    use crate::sync::syncer::MacroSyncProgress;
+
+    fn progress(epochs: (u32, u32), chunks: (u64, u64)) -> MacroSyncProgress<u32> {
+        let mut progress = MacroSyncProgress::new(vec![]);
+        (progress.epochs_done, progress.epochs_total) = epochs;
+        (progress.chunks_done, progress.chunks_total) = chunks;
+        progress
+    }
+
+    #[test]
+    fn it_computes_the_sync_fraction() {
+        assert_eq!(EtaEstimator::fraction(&progress((0, 0), (0, 0))), None);
+        assert_eq!(
+            EtaEstimator::fraction(&progress((0, 0), (1, 4))),
+            Some(0.25)
+        );
+        assert_eq!(EtaEstimator::fraction(&progress((1, 2), (0, 0))), Some(0.5));
+        assert_eq!(
+            EtaEstimator::fraction(&progress((1, 2), (2, 4))),
+            Some(0.75)
+        );
+    }
+
+    #[test]
+    fn it_only_estimates_after_progress() {
+        let mut estimator = EtaEstimator::default();
+        assert_eq!(estimator.estimate(&progress((1, 4), (0, 0))), None);
+        assert_eq!(estimator.estimate(&progress((1, 4), (0, 0))), None);
+        assert!(estimator.estimate(&progress((2, 4), (0, 0))).is_some());
+
+        estimator.reset();
+        assert_eq!(estimator.estimate(&progress((2, 4), (0, 0))), None);
+    }
+}
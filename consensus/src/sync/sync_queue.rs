@@ -4,6 +4,7 @@ use std::{
     collections::{BinaryHeap, VecDeque},
     fmt::{Debug, Display, Formatter},
     future::Future,
+    hash::Hash,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll, Waker},
@@ -12,12 +13,13 @@ use std::{
 use futures::{
     future, future::BoxFuture, ready, stream::FuturesUnordered, FutureExt, Stream, StreamExt,
 };
+use instant::Instant;
 use nimiq_network_interface::network::{Network, PubsubId};
 use nimiq_utils::WakerExt as _;
 use parking_lot::RwLock;
 use pin_project::pin_project;
 
-use super::peer_list::PeerList;
+use super::{peer_list::PeerList, peer_scheduler::PeerScheduler};
 use crate::sync::peer_list::PeerListIndex;
 
 #[pin_project]
@@ -67,6 +69,42 @@ impl<TId, TOutput> Ord for OrderWrapper<TId, TOutput> {
     }
 }
 
+/// Reports the outcome of a scheduled request to its [`PeerScheduler`]. Requests that are
+/// dropped before they complete are reported as cancelled.
+struct RequestTracker<TPeerId: Copy + Eq + Hash> {
+    scheduler: Arc<RwLock<PeerScheduler<TPeerId>>>,
+    peer_id: TPeerId,
+    start: Instant,
+    completed: bool,
+}
+
+impl<TPeerId: Copy + Eq + Hash> RequestTracker<TPeerId> {
+    fn new(scheduler: Arc<RwLock<PeerScheduler<TPeerId>>>, peer_id: TPeerId) -> Self {
+        scheduler.write().on_request(&peer_id);
+        Self {
+            scheduler,
+            peer_id,
+            start: Instant::now(),
+            completed: false,
+        }
+    }
+
+    fn complete(&mut self, success: bool) {
+        self.completed = true;
+        self.scheduler
+            .write()
+            .on_response(&self.peer_id, self.start.elapsed(), success);
+    }
+}
+
+impl<TPeerId: Copy + Eq + Hash> Drop for RequestTracker<TPeerId> {
+    fn drop(&mut self) {
+        if !self.completed {
+            self.scheduler.write().on_cancel(&self.peer_id);
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Error;
 
@@ -106,6 +144,7 @@ pub struct SyncQueue<
     request_fn: RequestFn<TId, TNetwork, TOutput, TError>,
     verify_fn: VerifyFn<TId, TOutput, TVerifyState>,
     verify_state: TVerifyState,
+    scheduler: Option<Arc<RwLock<PeerScheduler<TNetwork::PeerId>>>>,
     waker: Option<Waker>,
 }
 
@@ -175,17 +214,74 @@ where
             request_fn,
             verify_fn,
             verify_state: initial_verify_state,
+            scheduler: None,
             waker: None,
         }
     }
 
+    /// Schedules the requests of this queue with the given scheduler instead of round-robin.
+    /// The scheduler then picks the peers to request from and determines how many requests are
+    /// kept in flight. It can be shared between several queues that request from the same peers.
+    pub fn with_scheduler(
+        mut self,
+        scheduler: Arc<RwLock<PeerScheduler<TNetwork::PeerId>>>,
+    ) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    fn desired_pending_size(&self) -> usize {
+        match &self.scheduler {
+            Some(scheduler) => scheduler.read().concurrency(),
+            None => self.desired_pending_size,
+        }
+    }
+
+    /// Picks the next peer to request from and updates `peer_index` to point to it. Without a
+    /// scheduler, this is the peer after `peer_index`. Otherwise, it is the best peer according
+    /// to the scheduler, which avoids the peer at `peer_index` when retrying a failed request.
+    fn next_peer(&self, peer_index: &mut PeerListIndex, retry: bool) -> Option<TNetwork::PeerId> {
+        let peers = self.peers.read();
+        match &self.scheduler {
+            Some(scheduler) => {
+                let exclude = if retry { peers.get(peer_index) } else { None };
+                let peer_id = scheduler
+                    .read()
+                    .select_peer(peers.peers(), exclude.as_ref())?;
+                *peer_index = peers.index_of(&peer_id)?;
+                Some(peer_id)
+            }
+            None => peers.increment_and_get(peer_index),
+        }
+    }
+
+    fn request(
+        &self,
+        id: TId,
+        peer_id: TNetwork::PeerId,
+    ) -> BoxFuture<'static, Option<Result<TOutput, TError>>> {
+        let request = (self.request_fn)(id, Arc::clone(&self.network), peer_id);
+        match &self.scheduler {
+            Some(scheduler) => {
+                let mut tracker = RequestTracker::new(Arc::clone(scheduler), peer_id);
+                async move {
+                    let result = request.await;
+                    tracker.complete(result.is_ok());
+                    Some(result)
+                }
+                .boxed()
+            }
+            None => request.map(Some).boxed(),
+        }
+    }
+
     fn try_push_futures(&mut self) {
         // Determine number of new futures required to maintain desired_pending_size.
         let num_ids_to_request = cmp::min(
             self.ids_to_request.len(), // At most all of the ids
             // The number of pending futures can be higher than the desired pending size
             // (e.g., if there is an error and we re-request)
-            self.desired_pending_size
+            self.desired_pending_size()
                 .saturating_sub(self.pending_futures.len() + self.queued_outputs.len()),
         );
 
@@ -207,11 +303,14 @@ where
             // If we know the peer that sent us this block, we ask them first.
             let peer = match pubsub_peer {
                 Some(pubsub_peer) => Some(pubsub_peer),
-                None => self
-                    .peers
-                    .read()
-                    .increment_and_get(&mut self.current_peer_index)
-                    .map(|peer_id| (peer_id, self.current_peer_index.clone())),
+                None => {
+                    let mut peer_index = self.current_peer_index.clone();
+                    let peer = self
+                        .next_peer(&mut peer_index, false)
+                        .map(|peer_id| (peer_id, peer_index.clone()));
+                    self.current_peer_index = peer_index;
+                    peer
+                }
             };
 
             let wrapper = match peer {
@@ -225,9 +324,7 @@ where
                     );
 
                     OrderWrapper {
-                        data: (self.request_fn)(id.clone(), Arc::clone(&self.network), peer_id)
-                            .map(Some)
-                            .boxed(),
+                        data: self.request(id.clone(), peer_id),
                         id,
                         index: self.next_incoming_index,
                         peer: peer_index,
@@ -253,7 +350,7 @@ where
                 "Requesting {} ids (ids_to_request={}, remaining_until_limit={}, pending_futures={}, queued_outputs={}, num_peers={})",
                 num_ids_to_request,
                 self.ids_to_request.len(),
-                self.desired_pending_size
+                self.desired_pending_size()
                     .saturating_sub(self.pending_futures.len() + self.queued_outputs.len()),
                 self.pending_futures.len(),
                 self.queued_outputs.len(),
@@ -287,7 +384,7 @@ where
         }
 
         // Re-request from different peer. Return an error if there are no more peers.
-        let peer = match self.peers.read().increment_and_get(&mut peer_index) {
+        let peer = match self.next_peer(&mut peer_index, true) {
             Some(peer) => peer,
             None => return false,
         };
@@ -301,9 +398,7 @@ where
         );
 
         let wrapper = OrderWrapper {
-            data: (self.request_fn)(id.clone(), Arc::clone(&self.network), peer)
-                .map(Some)
-                .boxed(),
+            data: self.request(id.clone(), peer),
             id,
             index,
             peer: peer_index,
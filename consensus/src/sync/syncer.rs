@@ -15,7 +15,7 @@ use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::network::{CloseReason, Network, NetworkEvent, SubscribeEvents};
 use nimiq_time::{interval, Interval};
 
-use crate::{consensus::ResolveBlockRequest, messages::RequestHead};
+use crate::{consensus::ResolveBlockRequest, messages::RequestHead, sync::status::SyncPhase};
 
 /// Trait that defines how a node synchronizes macro blocks
 /// The expected functionality is that there could be different methods of syncing but they
@@ -28,6 +28,48 @@ pub trait MacroSync<TPeerId>: Stream<Item = MacroSyncReturn<TPeerId>> + Unpin +
     const MAX_REQUEST_EPOCHS: u16;
     /// Adds a peer to synchronize macro blocks
     fn add_peer(&mut self, peer_id: TPeerId);
+    /// Returns the progress of the synchronization
+    fn progress(&self) -> MacroSyncProgress<TPeerId>;
+}
+
+#[derive(Clone, Debug)]
+/// Progress of a `MacroSync` towards the macro state of its peers
+pub struct MacroSyncProgress<TPeerId> {
+    /// The latest epoch known to be available from the peers
+    pub target_epoch: Option<u32>,
+    /// The latest macro block known to be available from the peers
+    pub target_block: Option<u32>,
+    /// The number of epochs synced so far
+    pub epochs_done: u32,
+    /// The total number of epochs to sync
+    pub epochs_total: u32,
+    /// The number of chunks downloaded for the epoch currently being synced
+    pub chunks_done: u64,
+    /// The total number of chunks to download for the epoch currently being synced
+    pub chunks_total: u64,
+    /// The number of chunks downloaded per second
+    pub chunks_per_second: f64,
+    /// The number of chunk requests kept in flight
+    pub chunk_concurrency: usize,
+    /// The peers we are syncing with
+    pub peers: Vec<TPeerId>,
+}
+
+impl<TPeerId> MacroSyncProgress<TPeerId> {
+    /// Creates the progress of a sync that has no target (yet).
+    pub fn new(peers: Vec<TPeerId>) -> Self {
+        Self {
+            target_epoch: None,
+            target_block: None,
+            epochs_done: 0,
+            epochs_total: 0,
+            chunks_done: 0,
+            chunks_total: 0,
+            chunks_per_second: 0.0,
+            chunk_concurrency: 0,
+            peers,
+        }
+    }
 }
 
 /// Trait that defines how a node synchronizes receiving the blocks the peers are currently
@@ -201,6 +243,26 @@ impl<N: Network, M: MacroSync<N::PeerId>, L: LiveSync<N>> Syncer<N, M, L> {
         self.live_sync.state_complete()
     }
 
+    /// Returns the current sync phase and the progress of the macro sync. The peers of the
+    /// progress include the peers of both macro and live sync.
+    pub fn progress(&self) -> (SyncPhase, MacroSyncProgress<N::PeerId>) {
+        let phase = if self.live_sync.num_peers() == 0 {
+            SyncPhase::MacroSync
+        } else if !self.live_sync.state_complete() {
+            SyncPhase::StateSync
+        } else {
+            SyncPhase::LiveSync
+        };
+
+        let mut progress = self.macro_sync.progress();
+        for peer_id in self.live_sync.peers() {
+            if !progress.peers.contains(&peer_id) {
+                progress.peers.push(peer_id);
+            }
+        }
+        (phase, progress)
+    }
+
     /// Initiates an attempt to resolve a ResolveBlockRequest.
     pub fn resolve_block(&mut self, request: ResolveBlockRequest<N>) {
         self.live_sync.resolve_block(request)
@@ -10,6 +10,7 @@ use futures::{Stream, StreamExt};
 use nimiq_block::Block;
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_bls::cache::PublicKeyCache;
+use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::network::{Network, SubscribeEvents};
 use nimiq_primitives::policy::Policy;
 use nimiq_zkp_component::zkp_component::ZKPComponentProxy;
@@ -18,7 +19,7 @@ use pin_project::pin_project;
 
 #[cfg(feature = "full")]
 use crate::sync::{
-    history::HistoryMacroSync,
+    history::{HistoryMacroSync, HistoryStagingStore},
     live::{diff_queue::DiffQueue, state_queue::StateQueue, StateLiveSync},
 };
 use crate::{
@@ -26,7 +27,8 @@ use crate::{
     sync::{
         light::LightMacroSync,
         live::{block_queue::BlockQueue, queue::QueueConfig, BlockLiveSync},
-        syncer::{LiveSyncPushEvent, Syncer},
+        status::SyncPhase,
+        syncer::{LiveSyncPushEvent, MacroSyncProgress, Syncer},
     },
 };
 
@@ -63,6 +65,7 @@ impl<N: Network> SyncerProxy<N> {
         network: Arc<N>,
         bls_cache: Arc<Mutex<PublicKeyCache>>,
         network_event_rx: SubscribeEvents<N::PeerId>,
+        history_staging: Option<HistoryStagingStore>,
     ) -> Self {
         assert!(
             matches!(blockchain_proxy, BlockchainProxy::Full(_)),
@@ -88,7 +91,12 @@ impl<N: Network> SyncerProxy<N> {
             bls_cache,
         );
 
-        let macro_sync = HistoryMacroSync::new(blockchain, Arc::clone(&network), network_event_rx);
+        let macro_sync = HistoryMacroSync::new(
+            blockchain,
+            Arc::clone(&network),
+            network_event_rx,
+            history_staging,
+        );
 
         Self::History(Syncer::new(
             blockchain_proxy,
@@ -107,6 +115,7 @@ impl<N: Network> SyncerProxy<N> {
         zkp_component_proxy: ZKPComponentProxy<N>,
         network_event_rx: SubscribeEvents<N::PeerId>,
         full_sync_threshold: u32,
+        trusted_checkpoint: Option<Blake2bHash>,
     ) -> Self {
         let mut queue_config = QueueConfig::default();
         let min_queue_size = full_sync_threshold + Policy::blocks_per_batch() * 2;
@@ -149,6 +158,7 @@ impl<N: Network> SyncerProxy<N> {
             network_event_rx,
             zkp_component_proxy,
             full_sync_threshold,
+            trusted_checkpoint,
         );
 
         Self::Full(Syncer::new(
@@ -166,6 +176,7 @@ impl<N: Network> SyncerProxy<N> {
         bls_cache: Arc<Mutex<PublicKeyCache>>,
         zkp_component_proxy: ZKPComponentProxy<N>,
         network_event_rx: SubscribeEvents<N::PeerId>,
+        trusted_checkpoint: Option<Blake2bHash>,
     ) -> Self {
         let block_queue_config = QueueConfig {
             include_micro_bodies: false,
@@ -192,6 +203,7 @@ impl<N: Network> SyncerProxy<N> {
             network_event_rx,
             zkp_component_proxy,
             0, // Since the light sync does not keep state, we ignore the threshold.
+            trusted_checkpoint,
         );
 
         Self::Light(Syncer::new(
@@ -227,6 +239,11 @@ impl<N: Network> SyncerProxy<N> {
         gen_syncer_match!(self, state_complete)
     }
 
+    /// Returns the current sync phase and the progress of the macro sync
+    pub fn progress(&self) -> (SyncPhase, MacroSyncProgress<N::PeerId>) {
+        gen_syncer_match!(self, progress)
+    }
+
     pub fn resolve_block(&mut self, request: ResolveBlockRequest<N>) {
         gen_syncer_match!(self, resolve_block, request)
     }
@@ -58,6 +58,7 @@ async fn test_request_transactions_by_address() {
             TESTING_BLS_CACHE_MAX_CAPACITY,
         ))),
         net1.subscribe_events(),
+        None,
     )
     .await;
 
@@ -77,6 +78,7 @@ async fn test_request_transactions_by_address() {
             TESTING_BLS_CACHE_MAX_CAPACITY,
         ))),
         net2.subscribe_events(),
+        None,
     )
     .await;
     let consensus2 = Consensus::from_network(
@@ -209,6 +209,7 @@ async fn sync_ingredients() {
             TESTING_BLS_CACHE_MAX_CAPACITY,
         ))),
         net1.subscribe_events(),
+        None,
     )
     .await;
     let consensus1 = Consensus::from_network(
@@ -246,6 +247,7 @@ async fn sync_ingredients() {
             TESTING_BLS_CACHE_MAX_CAPACITY,
         ))),
         net2.subscribe_events(),
+        None,
     )
     .await;
 
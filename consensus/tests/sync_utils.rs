@@ -48,6 +48,7 @@ async fn syncer(
                     TESTING_BLS_CACHE_MAX_CAPACITY,
                 ))),
                 network.subscribe_events(),
+                None,
             )
             .await
         }
@@ -61,6 +62,7 @@ async fn syncer(
                 zkp_prover.proxy(),
                 network.subscribe_events(),
                 0,
+                None,
             )
             .await
         }
@@ -73,6 +75,7 @@ async fn syncer(
                 ))),
                 zkp_prover.proxy(),
                 network.subscribe_events(),
+                None,
             )
             .await
         }
@@ -119,6 +122,7 @@ pub async fn sync_two_peers(
             TESTING_BLS_CACHE_MAX_CAPACITY,
         ))),
         net1.subscribe_events(),
+        None,
     )
     .await;
     let zkp_prover1 =
@@ -1,7 +1,10 @@
 use std::{
+    collections::HashMap,
     fmt::Debug,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures::{
@@ -9,7 +12,8 @@ use futures::{
     ready,
     stream::{BoxStream, Stream, StreamExt},
 };
-use nimiq_time::{interval, Interval};
+use instant::Instant;
+use nimiq_time::{interval, sleep, Interval};
 use tokio::select;
 
 use crate::{
@@ -19,6 +23,7 @@ use crate::{
     level::Level,
     network::{LevelUpdateSender, Network},
     partitioner::Partitioner,
+    peer_quality::PeerQualityTracker,
     protocol::Protocol,
     store::ContributionStore,
     todo::TodoList,
@@ -56,14 +61,24 @@ struct NextAggregation<
     /// Sink used to relay messages
     sender: LevelUpdateSender<N>,
 
-    /// Interval for starting the next level regardless of previous levels completion
-    start_level_interval: Interval,
+    /// Timeout for starting the next level regardless of previous levels completion
+    level_timeout: BoxFuture<'static, ()>,
 
     /// Interval for sending level updates to the corresponding peers regardless of progression
     periodic_update_interval: Interval,
 
     /// the level which needs activation next
     next_level_timeout: usize,
+
+    /// The quality of the peers, shared with other aggregations
+    peer_quality: Arc<PeerQualityTracker>,
+
+    /// The time of the first update sent to each peer that hasn't responded with a valid
+    /// contribution since
+    sent_at: HashMap<usize, Instant>,
+
+    /// The time this aggregation was started
+    started_at: Instant,
 }
 
 impl<
@@ -78,6 +93,7 @@ impl<
         own_contribution: P::Contribution,
         input_stream: LevelUpdateStream<P, TId>,
         sender: LevelUpdateSender<N>,
+        peer_quality: Arc<PeerQualityTracker>,
     ) -> Self {
         // Invoke the partitioner to create the level structure of peers.
         let levels: Vec<Level> = Level::create_levels(protocol.partitioner(), protocol.identify());
@@ -86,11 +102,11 @@ impl<
         let mut todos = TodoList::new(protocol.identify(), protocol.evaluator(), input_stream);
 
         // Add our own contribution to the todo list.
-        todos.add_contribution(own_contribution.clone(), 0);
+        todos.add_contribution(own_contribution.clone(), 0, protocol.node_id());
 
-        // Regardless of level completion consecutive levels need to be activated at some point. Activate Levels every time this interval ticks,
-        // if the level has not already been activated due to level completion
-        let start_level_interval = interval(config.timeout);
+        // Regardless of level completion consecutive levels need to be activated at some point. Activate the next level every time
+        // this timeout triggers, if the level has not already been activated due to level completion
+        let level_timeout = sleep(config.timeout).boxed();
 
         // Every `config.update_interval` send Level updates to corresponding peers no matter the aggregations progression
         // (makes sure other peers can catch up).
@@ -104,12 +120,34 @@ impl<
             levels,
             contribution: own_contribution,
             sender,
-            start_level_interval,
+            level_timeout,
             periodic_update_interval,
             next_level_timeout: 0,
+            peer_quality,
+            sent_at: HashMap::new(),
+            started_at: Instant::now(),
         }
     }
 
+    /// Selects the peers on `level` to send the next update to, preferring responsive peers.
+    fn select_next_peers(&self, level: &Level) -> Vec<usize> {
+        level.select_next_peers_by_score(self.config.peer_count, |peer_id| {
+            self.peer_quality.score(peer_id)
+        })
+    }
+
+    /// The time to wait before activating the level which needs activation next. It adapts to the
+    /// latency of the peers on the level before it, which would complete it otherwise.
+    fn level_timeout_duration(&self) -> Duration {
+        let expected_latency = self
+            .next_level_timeout
+            .checked_sub(1)
+            .and_then(|level| self.levels.get(level))
+            .filter(|level| level.id > 0)
+            .and_then(|level| self.peer_quality.expected_latency(&level.peer_ids));
+        self.config.level_timeout(expected_latency)
+    }
+
     /// Starts level `level`
     fn start_level(&mut self, level: usize) {
         let level = self
@@ -140,7 +178,7 @@ impl<
                         best,
                         level.id,
                         !level.receive_complete(),
-                        level.select_next_peers(self.config.peer_count),
+                        self.select_next_peers(level),
                     );
                 }
             }
@@ -232,7 +270,7 @@ impl<
                         multisig,
                         level.id,
                         !level.receive_complete(),
-                        level.select_next_peers(self.config.peer_count),
+                        self.select_next_peers(level),
                     );
                 }
             }
@@ -268,9 +306,12 @@ impl<
             );
 
             // Send the level update to every peer_id in peer_ids
+            let now = Instant::now();
             for peer_id in peer_ids {
                 // This should always be the case
                 if peer_id < self.protocol.partitioner().size() {
+                    // Remember when the peer was first asked, to measure how long it takes to respond.
+                    self.sent_at.entry(peer_id).or_insert(now);
                     self.sender
                         // `send` is not a future and thus will not block execution.
                         .send((update.clone(), peer_id));
@@ -285,10 +326,7 @@ impl<
         for level_id in 1..self.levels.len() {
             let (receive_complete, next_peers) = {
                 let level = self.levels.get(level_id).unwrap();
-                (
-                    level.receive_complete(),
-                    level.select_next_peers(self.config.peer_count),
-                )
+                (level.receive_complete(), self.select_next_peers(level))
             };
 
             // Get the current best aggregate from store (no clone() needed as that already happens within the store)
@@ -326,6 +364,24 @@ impl<
         }
     }
 
+    /// Records whether the contribution received from `origin` was valid. The origin of the level
+    /// updates must have been authenticated by the network, see [`Aggregation::with_peer_quality`].
+    fn record_peer_quality(&mut self, origin: usize, valid: bool) {
+        if origin == self.protocol.node_id() {
+            return;
+        }
+
+        if valid {
+            let latency = self
+                .sent_at
+                .remove(&origin)
+                .map(|sent_at| sent_at.elapsed());
+            self.peer_quality.record_valid(origin, latency);
+        } else {
+            self.peer_quality.record_invalid(origin);
+        }
+    }
+
     async fn next(mut self) -> (P::Contribution, Option<Self>) {
         // As long as there is no new aggregate to return loop over the select of both intervals and the actual aggregation
         loop {
@@ -334,13 +390,17 @@ impl<
             // Likewise the periodic update will only trigger between todos.
             select! {
                 _ = self.periodic_update_interval.next().fuse() => self.automatic_update(),
-                _ = self.start_level_interval.next().fuse() => self.activate_next_level(),
+                _ = (&mut self.level_timeout).fuse() => {
+                    self.activate_next_level();
+                    self.level_timeout = sleep(self.level_timeout_duration()).boxed();
+                },
                 _ = self.sender.next().fuse() => {},
                 item = self.todos.next().fuse() => {
                     match item {
                         Some(todo) => {
                             // verify the contribution
                             let result = self.protocol.verify(&todo.contribution).await;
+                            self.record_peer_quality(todo.origin, result.is_ok());
 
                             if result.is_ok() {
                                 // special case of full contributions
@@ -477,14 +537,44 @@ impl<
         own_contribution: TProtocol::Contribution,
         input_stream: LevelUpdateStream<TProtocol, TId>,
         network: TNetwork,
+    ) -> Self {
+        Self::with_peer_quality(
+            protocol,
+            config,
+            own_contribution,
+            input_stream,
+            network,
+            Arc::new(PeerQualityTracker::new()),
+        )
+    }
+
+    /// Creates a new aggregation which prioritises peers according to the given `peer_quality`
+    /// and records the responses of the peers in it.
+    ///
+    /// The responses are attributed to the origin of the level updates, so `input_stream` must
+    /// only contain updates whose origin is the peer that actually sent them.
+    pub fn with_peer_quality(
+        protocol: TProtocol,
+        config: Config,
+        own_contribution: TProtocol::Contribution,
+        input_stream: LevelUpdateStream<TProtocol, TId>,
+        network: TNetwork,
+        peer_quality: Arc<PeerQualityTracker>,
     ) -> Self {
         // Create the Sender, buffering a single message per recipient.
         let sender = LevelUpdateSender::new(protocol.partitioner().size(), network);
 
-        let next_aggregation =
-            NextAggregation::new(protocol, config, own_contribution, input_stream, sender)
-                .next()
-                .boxed();
+        peer_quality.aggregation_started();
+        let next_aggregation = NextAggregation::new(
+            protocol,
+            config,
+            own_contribution,
+            input_stream,
+            sender,
+            peer_quality,
+        )
+        .next()
+        .boxed();
 
         Self {
             next_aggregation: Some(next_aggregation),
@@ -515,6 +605,10 @@ impl<
 
         self.next_aggregation = next_aggregation.map(|next_aggregation| {
             if next_aggregation.is_complete_aggregate(&aggregate) {
+                let completion_time = next_aggregation.started_at.elapsed();
+                next_aggregation
+                    .peer_quality
+                    .aggregation_completed(completion_time);
                 FinishedAggregation::from(next_aggregation, aggregate.clone()).boxed()
             } else {
                 next_aggregation.next().boxed()
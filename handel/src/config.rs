@@ -9,9 +9,17 @@ pub struct Config {
     /// Frequency at which updates are sent to peers
     pub update_interval: Duration,
 
-    /// Timeout for levels
+    /// Timeout for levels. This is the upper bound of the adaptive level timeout, and the
+    /// timeout used as long as the latency of the peers on a level is unknown.
     pub timeout: Duration,
 
+    /// Lower bound of the adaptive level timeout
+    pub min_timeout: Duration,
+
+    /// The adaptive level timeout is this multiple of the observed latency of the peers on the
+    /// preceding level
+    pub timeout_latency_factor: u32,
+
     /// How many peers are contacted at each level
     pub peer_count: usize,
 }
@@ -22,7 +30,21 @@ impl Default for Config {
             update_count: 1,
             update_interval: Duration::from_millis(500),
             timeout: Duration::from_millis(400),
+            min_timeout: Duration::from_millis(100),
+            timeout_latency_factor: 3,
             peer_count: 2,
         }
     }
 }
+
+impl Config {
+    /// Returns the timeout for a level given the expected latency of the peers on the preceding
+    /// level, if known.
+    pub fn level_timeout(&self, expected_latency: Option<Duration>) -> Duration {
+        match expected_latency {
+            Some(latency) => (latency * self.timeout_latency_factor)
+                .clamp(self.min_timeout, self.timeout.max(self.min_timeout)),
+            None => self.timeout,
+        }
+    }
+}
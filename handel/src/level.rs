@@ -143,6 +143,47 @@ impl Level {
         }
     }
 
+    /// Selects the set of next peers to send an update to for this level given a count of them,
+    /// preferring peers with a higher `score`.
+    ///
+    /// Half of the peers (rounded down) are the best scoring ones, the others are selected
+    /// round-robin like in [`select_next_peers`](Self::select_next_peers), so that every peer is
+    /// contacted eventually. Peers with the same score are selected in round-robin order.
+    pub fn select_next_peers_by_score<F: Fn(usize) -> f64>(
+        &self,
+        count: usize,
+        score: F,
+    ) -> Vec<usize> {
+        if self.id == 0 || self.is_empty() {
+            return vec![];
+        }
+
+        let num_peers = self.peer_ids.len();
+        let size = min(count, num_peers);
+        let mut state = self.state.write();
+
+        // Rank the peers starting at the round-robin position, the sort is stable.
+        let mut ranked: Vec<usize> = (0..num_peers)
+            .map(|i| self.peer_ids[(state.send_peers_pos + i) % num_peers])
+            .collect();
+        ranked.sort_by(|a, b| score(*b).total_cmp(&score(*a)));
+        let mut selected: Vec<usize> = ranked.into_iter().take(size / 2).collect();
+
+        // Fill up the remaining peers round-robin.
+        for _ in 0..num_peers {
+            if selected.len() >= size {
+                break;
+            }
+            let peer_id = self.peer_ids[state.send_peers_pos];
+            if !selected.contains(&peer_id) {
+                selected.push(peer_id);
+            }
+            state.send_peers_pos = (state.send_peers_pos + 1) % num_peers;
+        }
+
+        selected
+    }
+
     /// Updates the signature to send
     pub fn update_signature_to_send<C: AggregatableContribution>(&self, signature: &C) -> bool {
         let mut state = self.state.write();
@@ -257,6 +298,27 @@ mod test {
         }
     }
 
+    #[test]
+    fn it_prefers_peers_with_higher_scores() {
+        let level = Level::new(1, vec![4, 5, 6, 7], 5);
+        let score = |peer_id: usize| if peer_id == 6 { 1.0 } else { 0.0 };
+
+        // One peer is the best scoring one, the other one is selected round-robin.
+        assert_eq!(level.select_next_peers_by_score(2, score), vec![6, 4]);
+        assert_eq!(level.select_next_peers_by_score(2, score), vec![6, 5]);
+        assert_eq!(level.select_next_peers_by_score(2, score), vec![6, 7]);
+
+        // A single peer is always selected round-robin.
+        assert_eq!(level.select_next_peers_by_score(1, score), vec![4]);
+
+        // Without any scores, the selection is round-robin.
+        let level = Level::new(1, vec![4, 5, 6, 7], 5);
+        assert_eq!(
+            level.select_next_peers_by_score(4, |_| 0.0),
+            vec![4, 5, 6, 7]
+        );
+    }
+
     #[test]
     fn it_updates_signature_to_send() {
         let mut rng = thread_rng();
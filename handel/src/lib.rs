@@ -15,6 +15,7 @@ pub mod identity;
 pub mod level;
 pub mod network;
 pub mod partitioner;
+pub mod peer_quality;
 pub mod protocol;
 pub mod store;
 pub(crate) mod todo;
@@ -0,0 +1,202 @@
+use std::{collections::HashMap, time::Duration};
+
+use parking_lot::RwLock;
+
+/// The weight of a new sample in the exponentially smoothed averages.
+const SMOOTHING_FACTOR: f64 = 0.2;
+
+/// How responsive a single peer was in past aggregations.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PeerQuality {
+    /// The number of valid contributions received from the peer.
+    pub valid_contributions: u32,
+    /// The number of invalid contributions received from the peer.
+    pub invalid_contributions: u32,
+    /// Smoothed share of valid contributions, between -1 (only invalid contributions) and 1 (only
+    /// valid contributions). Recent contributions weigh more.
+    pub reliability: f64,
+    /// Smoothed time between our first update to the peer and the first valid contribution we
+    /// received from it, if known.
+    pub latency: Option<Duration>,
+}
+
+impl PeerQuality {
+    /// Returns the score of the peer, higher is better. Peers we know nothing about score 0,
+    /// peers that sent valid contributions score higher the faster they responded, and peers
+    /// that sent invalid contributions score below 0.
+    pub fn score(&self) -> f64 {
+        self.reliability / (1.0 + self.latency.unwrap_or_default().as_secs_f64())
+    }
+}
+
+/// Statistics about the aggregations that reported to a [`PeerQualityTracker`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AggregationStats {
+    /// The number of aggregations started.
+    pub started: u64,
+    /// The number of aggregations that collected the contributions of all peers.
+    pub completed: u64,
+    /// The time the last completed aggregation took.
+    pub last_completion_time: Option<Duration>,
+    /// The smoothed time aggregations took to complete.
+    pub average_completion_time: Option<Duration>,
+}
+
+/// Keeps track of the [`PeerQuality`] of all peers and of [`AggregationStats`].
+///
+/// A tracker is meant to be shared by all aggregations among the same set of peers, so that
+/// later aggregations can prioritise the peers that responded well in earlier ones. Peers are
+/// identified by their ID in the aggregation, so the peer qualities must be reset whenever the
+/// set of peers changes.
+#[derive(Debug, Default)]
+pub struct PeerQualityTracker {
+    peers: RwLock<HashMap<usize, PeerQuality>>,
+    aggregations: RwLock<AggregationStats>,
+}
+
+impl PeerQualityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the quality of the given peer.
+    pub fn peer(&self, peer_id: usize) -> PeerQuality {
+        self.peers.read().get(&peer_id).cloned().unwrap_or_default()
+    }
+
+    /// Returns the qualities of all peers we received contributions from.
+    pub fn peers(&self) -> HashMap<usize, PeerQuality> {
+        self.peers.read().clone()
+    }
+
+    /// Returns the score of the given peer, see [`PeerQuality::score`].
+    pub fn score(&self, peer_id: usize) -> f64 {
+        self.peers
+            .read()
+            .get(&peer_id)
+            .map_or(0.0, PeerQuality::score)
+    }
+
+    /// Records a valid contribution of the given peer, together with the time it took the peer
+    /// to respond to our first update, if known.
+    pub fn record_valid(&self, peer_id: usize, latency: Option<Duration>) {
+        let mut peers = self.peers.write();
+        let peer = peers.entry(peer_id).or_default();
+        peer.valid_contributions += 1;
+        peer.reliability = smooth(peer.reliability, 1.0);
+        if let Some(latency) = latency {
+            peer.latency = Some(match peer.latency {
+                Some(previous) => smooth_duration(previous, latency),
+                None => latency,
+            });
+        }
+    }
+
+    /// Records an invalid contribution of the given peer.
+    pub fn record_invalid(&self, peer_id: usize) {
+        let mut peers = self.peers.write();
+        let peer = peers.entry(peer_id).or_default();
+        peer.invalid_contributions += 1;
+        peer.reliability = smooth(peer.reliability, -1.0);
+    }
+
+    /// Returns the median latency of those of the given peers whose latency is known.
+    pub fn expected_latency(&self, peer_ids: &[usize]) -> Option<Duration> {
+        let peers = self.peers.read();
+        let mut latencies: Vec<Duration> = peer_ids
+            .iter()
+            .filter_map(|peer_id| peers.get(peer_id)?.latency)
+            .collect();
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort();
+        Some(latencies[latencies.len() / 2])
+    }
+
+    /// Forgets the qualities of all peers, e.g. because the set of peers changed.
+    pub fn reset_peers(&self) {
+        self.peers.write().clear();
+    }
+
+    pub(crate) fn aggregation_started(&self) {
+        self.aggregations.write().started += 1;
+    }
+
+    pub(crate) fn aggregation_completed(&self, completion_time: Duration) {
+        let mut aggregations = self.aggregations.write();
+        aggregations.completed += 1;
+        aggregations.last_completion_time = Some(completion_time);
+        aggregations.average_completion_time = Some(match aggregations.average_completion_time {
+            Some(previous) => smooth_duration(previous, completion_time),
+            None => completion_time,
+        });
+    }
+
+    /// Returns the statistics of the aggregations so far.
+    pub fn aggregation_stats(&self) -> AggregationStats {
+        self.aggregations.read().clone()
+    }
+}
+
+fn smooth(previous: f64, sample: f64) -> f64 {
+    previous + SMOOTHING_FACTOR * (sample - previous)
+}
+
+fn smooth_duration(previous: Duration, sample: Duration) -> Duration {
+    Duration::from_secs_f64(smooth(previous.as_secs_f64(), sample.as_secs_f64()))
+}
+
+#[cfg(test)]
+mod test {
+    use nimiq_test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn it_scores_responsive_peers_higher() {
+        let tracker = PeerQualityTracker::new();
+        tracker.record_valid(1, Some(Duration::from_millis(50)));
+        tracker.record_valid(2, Some(Duration::from_millis(300)));
+        tracker.record_invalid(3);
+
+        assert!(tracker.score(1) > tracker.score(2));
+        assert!(tracker.score(2) > tracker.score(4));
+        assert!(tracker.score(4) > tracker.score(3));
+        assert_eq!(tracker.peer(3).invalid_contributions, 1);
+
+        tracker.reset_peers();
+        assert_eq!(tracker.peer(1), PeerQuality::default());
+    }
+
+    #[test]
+    fn it_computes_the_expected_latency() {
+        let tracker = PeerQualityTracker::new();
+        assert_eq!(tracker.expected_latency(&[1, 2, 3]), None);
+
+        tracker.record_valid(1, Some(Duration::from_millis(10)));
+        tracker.record_valid(2, Some(Duration::from_millis(30)));
+        tracker.record_valid(3, None);
+        tracker.record_valid(4, Some(Duration::from_millis(20)));
+        assert_eq!(
+            tracker.expected_latency(&[1, 2, 3, 4, 5]),
+            Some(Duration::from_millis(20))
+        );
+    }
+
+    #[test]
+    fn it_tracks_completion_times() {
+        let tracker = PeerQualityTracker::new();
+        tracker.aggregation_started();
+        tracker.aggregation_started();
+        tracker.aggregation_completed(Duration::from_millis(100));
+        tracker.aggregation_completed(Duration::from_millis(200));
+
+        let stats = tracker.aggregation_stats();
+        assert_eq!(stats.started, 2);
+        assert_eq!(stats.completed, 2);
+        assert_eq!(stats.last_completion_time, Some(Duration::from_millis(200)));
+        let average = stats.average_completion_time.unwrap().as_secs_f64();
+        assert!((average - 0.12).abs() < 1e-6);
+    }
+}
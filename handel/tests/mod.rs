@@ -16,6 +16,7 @@ use nimiq_handel::{
     identity::{Identity, IdentityRegistry, WeightRegistry},
     network::Network,
     partitioner::BinomialPartitioner,
+    peer_quality::PeerQualityTracker,
     protocol,
     store::ReplaceStore,
     update::LevelUpdate,
@@ -205,6 +206,7 @@ async fn it_can_aggregate() {
         update_interval: Duration::from_millis(500),
         timeout: Duration::from_millis(500),
         peer_count: 1,
+        ..Default::default()
     };
 
     let stopped = Arc::new(RwLock::new(false));
@@ -276,7 +278,8 @@ async fn it_can_aggregate() {
     }
 
     // instead of spawning the aggregation task await its result here.
-    let mut aggregation = Aggregation::new(
+    let peer_quality = Arc::new(PeerQualityTracker::new());
+    let mut aggregation = Aggregation::with_peer_quality(
         protocol,
         config.clone(),
         contribution,
@@ -285,6 +288,7 @@ async fn it_can_aggregate() {
                 .map(move |msg| msg.0 .0),
         ),
         NetworkWrapper(Arc::clone(&net)),
+        Arc::clone(&peer_quality),
     );
 
     // aggregating should not take more than 300 ms per each 7 contributors
@@ -321,6 +325,12 @@ async fn it_can_aggregate() {
         }
     }
 
+    // The completed aggregation and the peers that contributed to it were recorded.
+    let stats = peer_quality.aggregation_stats();
+    assert_eq!(stats.started, 1);
+    assert_eq!(stats.completed, 1);
+    assert!((0..contributor_num).any(|id| peer_quality.peer(id).valid_contributions > 0));
+
     drop(aggregation);
     net.disconnect();
 
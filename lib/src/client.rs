@@ -5,7 +5,11 @@ use nimiq_block::Block;
 use nimiq_blockchain::{Blockchain, BlockchainConfig};
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
+#[cfg(feature = "database-storage")]
+use nimiq_bls::cache::DBPublicKeyStore;
 use nimiq_bls::cache::PublicKeyCache;
+#[cfg(feature = "full-consensus")]
+use nimiq_consensus::sync::history::HistoryStagingStore;
 use nimiq_consensus::{
     sync::syncer_proxy::SyncerProxy, Consensus as AbstractConsensus,
     ConsensusProxy as AbstractConsensusProxy, Error::BlockchainError,
@@ -30,13 +34,15 @@ use nimiq_network_libp2p::{
 use nimiq_primitives::policy::Policy;
 use nimiq_utils::time::OffsetTime;
 #[cfg(feature = "validator")]
+use nimiq_validator::signer::{LocalSigner, RemoteSigner, ValidatorSigner};
+#[cfg(feature = "validator")]
 use nimiq_validator::validator::Validator as AbstractValidator;
 #[cfg(feature = "validator")]
 use nimiq_validator::validator::ValidatorProxy as AbstractValidatorProxy;
 #[cfg(feature = "validator")]
 use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
 #[cfg(feature = "wallet")]
-use nimiq_wallet::WalletStore;
+use nimiq_wallet::{TransactionScheduler, UnlockedWallets, WalletStore};
 use nimiq_zkp::ZKP_VERIFYING_DATA;
 #[cfg(feature = "zkp-prover")]
 use nimiq_zkp_circuits::setup::{all_files_created, load_verifying_data, setup, DEVELOPMENT_SEED};
@@ -103,11 +109,23 @@ pub(crate) struct ClientInner {
     #[cfg(feature = "wallet")]
     wallet_store: Arc<WalletStore>,
 
+    /// Transactions held by the wallet until they are due
+    #[cfg(feature = "wallet")]
+    transaction_scheduler: Arc<TransactionScheduler>,
+
+    /// Wallets that are currently unlocked and can sign transactions
+    #[cfg(feature = "wallet")]
+    unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+
     zkp_component: ZKPComponentProxy,
 }
 
 /// This function is used to generate the services flags (provided, needed) based upon the configured sync mode
-pub fn generate_service_flags(sync_mode: SyncMode, index_history: bool) -> (Services, Services) {
+pub fn generate_service_flags(
+    sync_mode: SyncMode,
+    index_history: bool,
+    prunes_history: bool,
+) -> (Services, Services) {
     let provided_services = match sync_mode {
         // Services provided by history nodes
         SyncMode::History => {
@@ -116,6 +134,10 @@ pub fn generate_service_flags(sync_mode: SyncMode, index_history: bool) -> (Serv
             if index_history {
                 services |= Services::TRANSACTION_INDEX;
             }
+            // A pruning history node can't provide the full transaction history.
+            if prunes_history {
+                services.remove(Services::HISTORY);
+            }
             services
         }
         // Services provided by full nodes
@@ -141,6 +163,25 @@ pub fn generate_service_flags(sync_mode: SyncMode, index_history: bool) -> (Serv
     (provided_services, required_services)
 }
 
+/// Uncompresses the voting keys of the current validators and of the validators that would be
+/// elected next through the BLS cache, so that they are ready when blocks need to be verified.
+#[cfg(feature = "full-consensus")]
+fn warm_up_bls_cache(blockchain: &Blockchain, bls_cache: &Mutex<PublicKeyCache>) {
+    // The next validators can only be determined once we have the complete staking contract.
+    let next_validators = blockchain
+        .accounts_complete()
+        .then(|| blockchain.next_validators(&blockchain.head().seed()));
+
+    // Warm up both validator sets at once, so that the uncompressed keys are stored together.
+    let voting_keys = blockchain
+        .state
+        .current_slots
+        .iter()
+        .chain(next_validators.iter())
+        .flat_map(|validators| validators.iter().map(|validator| &validator.voting_key));
+    bls_cache.lock().warm_up(voting_keys);
+}
+
 impl ClientInner {
     async fn from_config(config: ClientConfig) -> Result<Client, Error> {
         // Get network info (i.e. which specific blockchain we're on)
@@ -221,8 +262,11 @@ impl ClientInner {
             identity_keypair.public().to_peer_id().to_base58()
         );
 
-        let (mut provided_services, required_services) =
-            generate_service_flags(config.consensus.sync_mode, config.consensus.index_history);
+        let (mut provided_services, required_services) = generate_service_flags(
+            config.consensus.sync_mode,
+            config.consensus.index_history,
+            config.consensus.prunes_history(),
+        );
 
         // We update the services flags depending on our validator configuration
         #[cfg(feature = "validator")]
@@ -340,6 +384,13 @@ impl ClientInner {
             config.database,
         )?;
 
+        // Persist uncompressed BLS keys so that they don't need to be uncompressed again on restart.
+        #[cfg(feature = "database-storage")]
+        let bls_cache = Arc::new(Mutex::new(PublicKeyCache::with_store(
+            Policy::BLS_CACHE_MAX_CAPACITY,
+            Box::new(DBPublicKeyStore::new(environment.clone())),
+        )));
+        #[cfg(not(feature = "database-storage"))]
         let bls_cache = Arc::new(Mutex::new(PublicKeyCache::new(
             Policy::BLS_CACHE_MAX_CAPACITY,
         )));
@@ -347,6 +398,7 @@ impl ClientInner {
         #[cfg(feature = "full-consensus")]
         let mut blockchain_config = BlockchainConfig {
             max_epochs_stored: config.consensus.max_epochs_stored,
+            accounts_snapshot: config.consensus.accounts_snapshot,
             ..Default::default()
         };
 
@@ -369,6 +421,7 @@ impl ClientInner {
             SyncMode::History => {
                 blockchain_config.keep_history = true;
                 blockchain_config.index_history = config.consensus.index_history;
+                blockchain_config.history_retention = config.consensus.history_retention();
                 let blockchain = match Blockchain::new(
                     environment.clone(),
                     blockchain_config,
@@ -381,6 +434,8 @@ impl ClientInner {
                     }
                 };
 
+                warm_up_bls_cache(&blockchain.read(), &bls_cache);
+
                 let blockchain_proxy = BlockchainProxy::from(&blockchain);
                 #[cfg(feature = "zkp-prover")]
                 let zkp_component = if config.zkp.prover_active {
@@ -406,6 +461,7 @@ impl ClientInner {
                     Arc::clone(&network),
                     bls_cache,
                     network_events,
+                    Some(HistoryStagingStore::new(environment.clone())),
                 )
                 .await;
                 (blockchain_proxy, syncer, zkp_component)
@@ -427,6 +483,8 @@ impl ClientInner {
                     }
                 };
 
+                warm_up_bls_cache(&blockchain.read(), &bls_cache);
+
                 let blockchain_proxy = BlockchainProxy::from(&blockchain);
                 #[cfg(feature = "zkp-prover")]
                 let zkp_component = if config.zkp.prover_active {
@@ -455,6 +513,7 @@ impl ClientInner {
                     zkp_component.proxy(),
                     network_events,
                     config.consensus.full_sync_threshold,
+                    config.consensus.checkpoint.clone(),
                 )
                 .await;
                 (blockchain_proxy, syncer, zkp_component)
@@ -471,6 +530,7 @@ impl ClientInner {
                     bls_cache,
                     zkp_component.proxy(),
                     network_events,
+                    config.consensus.checkpoint.clone(),
                 )
                 .await;
                 (blockchain_proxy, syncer, zkp_component)
@@ -480,6 +540,10 @@ impl ClientInner {
         // Open wallet
         #[cfg(feature = "wallet")]
         let wallet_store = Arc::new(WalletStore::new(environment.clone()));
+        #[cfg(feature = "wallet")]
+        let transaction_scheduler = Arc::new(TransactionScheduler::new(environment.clone()));
+        #[cfg(feature = "wallet")]
+        let unlocked_wallets = Arc::new(RwLock::new(UnlockedWallets::default()));
 
         // Initialize consensus
         let consensus = Consensus::new(
@@ -503,11 +567,28 @@ impl ClientInner {
                     // Load validator address
                     let automatic_reactivate = validator_config.automatic_reactivate;
 
-                    // Load signing key (before we give away ownership of the storage config)
-                    let signing_key = config.storage.signing_keypair()?;
-
-                    // Load validator key (before we give away ownership of the storage config)
-                    let voting_key = config.storage.voting_keypair()?;
+                    // Load the optional treasury configuration
+                    let treasury = validator_config.treasury;
+
+                    // Load whether to wait for the live head before signing anything
+                    let safe_startup = validator_config.safe_startup;
+
+                    // Connect to the remote signer or load the signing key and the voting key
+                    // (before we give away ownership of the storage config)
+                    let signer: Arc<dyn ValidatorSigner> = match validator_config.remote_signer {
+                        Some(remote_signer) => {
+                            Arc::new(RemoteSigner::connect(remote_signer).map_err(|e| {
+                                Error::config_error(format!(
+                                    "Failed to connect to remote signer: {}",
+                                    e
+                                ))
+                            })?)
+                        }
+                        None => Arc::new(LocalSigner::new(
+                            config.storage.signing_keypair()?,
+                            config.storage.voting_keypair()?,
+                        )),
+                    };
 
                     // Load fee key (before we give away ownership of the storage config)
                     let fee_key = config.storage.fee_keypair()?;
@@ -522,9 +603,10 @@ impl ClientInner {
                         validator_network,
                         validator_address,
                         automatic_reactivate,
-                        signing_key,
-                        voting_key,
+                        treasury,
+                        signer,
                         fee_key,
+                        safe_startup,
                         config.mempool.clone(),
                     );
 
@@ -559,6 +641,14 @@ impl ClientInner {
             }
         }
 
+        // Send scheduled transactions once they are due.
+        #[cfg(feature = "wallet")]
+        crate::extras::transaction_scheduler::start_transaction_scheduler(
+            Arc::clone(&transaction_scheduler),
+            consensus.proxy(),
+            Arc::clone(&unlocked_wallets),
+        );
+
         // Start network.
         network.listen_on(config.network.listen_addresses).await;
         network.start_connecting().await;
@@ -572,6 +662,10 @@ impl ClientInner {
                 validator: validator_proxy,
                 #[cfg(feature = "wallet")]
                 wallet_store,
+                #[cfg(feature = "wallet")]
+                transaction_scheduler,
+                #[cfg(feature = "wallet")]
+                unlocked_wallets,
                 zkp_component: zkp_component.proxy(),
             }),
             consensus: Some(consensus),
@@ -642,6 +736,16 @@ impl Client {
         Arc::clone(&self.inner.wallet_store)
     }
 
+    #[cfg(feature = "wallet")]
+    pub fn transaction_scheduler(&self) -> Arc<TransactionScheduler> {
+        Arc::clone(&self.inner.transaction_scheduler)
+    }
+
+    #[cfg(feature = "wallet")]
+    pub fn unlocked_wallets(&self) -> Arc<RwLock<UnlockedWallets>> {
+        Arc::clone(&self.inner.unlocked_wallets)
+    }
+
     /// Returns the *Validator* or `None`.
     #[cfg(feature = "validator")]
     pub fn take_validator(&mut self) -> Option<Validator> {
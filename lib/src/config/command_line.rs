@@ -67,6 +67,37 @@ pub struct CommandLine {
     /// Internally used flag to start a zero-knowledge prover process.
     #[clap(long, action)]
     pub prove: bool,
+
+    /// Export the local chain to an archive file and exit.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client --export chain.archive --export-from 1 --export-to 43200`
+    ///
+    #[clap(long, value_name = "FILE", conflicts_with = "import")]
+    pub export: Option<PathBuf>,
+
+    /// First block number to export (default: the first block after genesis).
+    #[clap(long, requires = "export")]
+    pub export_from: Option<u32>,
+
+    /// Last block number to export (default: the head of the chain).
+    #[clap(long, requires = "export")]
+    pub export_to: Option<u32>,
+
+    /// Only export macro blocks along with the history of their batches.
+    /// Such archives can only be imported by history nodes.
+    #[clap(long, requires = "export")]
+    pub export_macro_only: bool,
+
+    /// Import an archive file into the local chain, fully verifying all blocks, and exit.
+    ///
+    /// # Examples
+    ///
+    /// * `nimiq-client --import chain.archive`
+    ///
+    #[clap(long, value_name = "FILE")]
+    pub import: Option<PathBuf>,
 }
 
 impl CommandLine {
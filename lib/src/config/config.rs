@@ -10,6 +10,8 @@ use std::{
 };
 
 use derive_builder::Builder;
+#[cfg(feature = "full-consensus")]
+use nimiq_blockchain::HistoryRetention;
 #[cfg(feature = "validator")]
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
 #[cfg(feature = "database-storage")]
@@ -26,6 +28,8 @@ use nimiq_serde::Deserialize;
 #[cfg(feature = "validator")]
 use nimiq_utils::key_rng::SecureGenerate;
 use nimiq_utils::{file_store::FileStore, Sensitive};
+#[cfg(feature = "validator")]
+use nimiq_validator::{signer::RemoteSignerConfig, treasury::TreasuryConfig};
 use nimiq_zkp_circuits::DEFAULT_KEYS_PATH;
 use subtle::ConstantTimeEq;
 
@@ -92,6 +96,37 @@ pub struct ConsensusConfig {
     #[builder(default = "true")]
     /// History indices enabled. Only effective for history nodes (default: `true`)
     pub index_history: bool,
+    #[builder(default)]
+    /// Hash of a trusted election block to sync from instead of using a ZKP.
+    /// Only effective for full and light nodes.
+    pub checkpoint: Option<Blake2bHash>,
+    #[builder(default)]
+    /// Number of past epochs whose history is kept by history nodes. Older history is pruned.
+    pub history_retention_epochs: Option<u32>,
+    #[builder(default)]
+    /// Number of days for which the history is kept by history nodes. Older history is pruned.
+    pub history_retention_days: Option<u32>,
+    #[builder(default = "true")]
+    /// Whether a snapshot of the accounts trie at the latest macro block is kept, from which
+    /// accounts chunks are served to syncing peers. Only effective for full and history nodes.
+    pub accounts_snapshot: bool,
+}
+
+impl ConsensusConfig {
+    /// Whether a history node prunes its history, i.e. doesn't store the full history.
+    pub fn prunes_history(&self) -> bool {
+        self.history_retention_epochs.is_some() || self.history_retention_days.is_some()
+    }
+
+    /// The history retention of the blockchain, if the history is pruned.
+    #[cfg(feature = "full-consensus")]
+    pub fn history_retention(&self) -> Option<HistoryRetention> {
+        match (self.history_retention_epochs, self.history_retention_days) {
+            (Some(epochs), _) => Some(HistoryRetention::Epochs(epochs)),
+            (None, Some(days)) => Some(HistoryRetention::Days(days)),
+            (None, None) => None,
+        }
+    }
 }
 
 impl Default for ConsensusConfig {
@@ -102,6 +137,10 @@ impl Default for ConsensusConfig {
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
             full_sync_threshold: 10800,
             index_history: true,
+            checkpoint: None,
+            history_retention_epochs: None,
+            history_retention_days: None,
+            accounts_snapshot: true,
         }
     }
 }
@@ -510,6 +549,15 @@ pub struct ValidatorConfig {
 
     /// Config if the validator automatically reactivates itself.
     pub automatic_reactivate: bool,
+
+    /// Config if the validator refuses to sign anything until it has observed the live head.
+    pub safe_startup: bool,
+
+    /// Optional treasury managing the rewards paid out to the fee key.
+    pub treasury: Option<TreasuryConfig>,
+
+    /// Optional remote signer holding the signing key and the voting key.
+    pub remote_signer: Option<RemoteSignerConfig>,
 }
 
 /// Credentials for JSON RPC server, metrics server or websocket RPC server
@@ -766,6 +814,7 @@ impl ClientConfigBuilder {
         let mut consensus = ConsensusConfigBuilder::default()
             .sync_mode(config_file.consensus.sync_mode)
             .index_history(config_file.consensus.index_history)
+            .accounts_snapshot(config_file.consensus.accounts_snapshot)
             .build()
             .unwrap();
         if let Some(min_peers) = config_file.consensus.min_peers {
@@ -774,6 +823,45 @@ impl ClientConfigBuilder {
         if let Some(full_sync_threshold) = config_file.consensus.full_sync_threshold {
             consensus.full_sync_threshold = full_sync_threshold;
         }
+        if let Some(checkpoint) = &config_file.consensus.checkpoint {
+            if consensus.sync_mode == SyncMode::History {
+                return Err(Error::config_error(
+                    "A trusted checkpoint can't be used by history nodes",
+                ));
+            }
+            consensus.checkpoint = Some(
+                checkpoint
+                    .parse()
+                    .map_err(|e| Error::config_error(format!("Invalid checkpoint hash: {e}")))?,
+            );
+        }
+        let retention = (
+            config_file.consensus.history_retention_epochs,
+            config_file.consensus.history_retention_days,
+        );
+        if retention != (None, None) {
+            if consensus.sync_mode != SyncMode::History {
+                return Err(Error::config_error(
+                    "A history retention can only be used by history nodes",
+                ));
+            }
+            match retention {
+                (Some(_), Some(_)) => {
+                    return Err(Error::config_error(
+                        "Only one of history_retention_epochs and history_retention_days can be set",
+                    ));
+                }
+                (Some(0), _) | (_, Some(0)) => {
+                    return Err(Error::config_error(
+                        "The history retention must be positive",
+                    ));
+                }
+                (epochs, days) => {
+                    consensus.history_retention_epochs = epochs;
+                    consensus.history_retention_days = days;
+                }
+            }
+        }
         self.consensus(consensus);
 
         // Configure network
@@ -799,6 +887,13 @@ impl ClientConfigBuilder {
             self.validator(ValidatorConfig {
                 validator_address: Address::from_any_str(&validator_config.validator_address)?,
                 automatic_reactivate: validator_config.automatic_reactivate,
+                safe_startup: validator_config.safe_startup,
+                treasury: validator_config.treasury.clone().map(TreasuryConfig::from),
+                remote_signer: validator_config
+                    .remote_signer
+                    .clone()
+                    .map(RemoteSignerConfig::try_from)
+                    .transpose()?,
             });
 
             if let Some(key_path) = &validator_config.voting_key_file {
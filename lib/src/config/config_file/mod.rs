@@ -1,8 +1,10 @@
 use std::{
     collections::HashMap, fmt::Debug, fs::read_to_string, num::NonZeroU8, path::Path, str::FromStr,
+    time::Duration,
 };
 
 use log::level_filters::LevelFilter;
+use nimiq_keys::Address;
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::{
     config::MempoolConfig,
@@ -13,6 +15,8 @@ use nimiq_network_interface::Multiaddr;
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_serde::Deserialize;
 use nimiq_utils::Sensitive;
+#[cfg(feature = "validator")]
+use nimiq_validator::{signer::RemoteSignerConfig, treasury::TreasuryConfig};
 use thiserror::Error;
 use url::Url;
 
@@ -191,6 +195,19 @@ pub struct ConsensusSettings {
     /// History indices enabled. Only effective for history nodes (default: `true`)
     #[serde(default = "default_true")]
     pub index_history: bool,
+    /// Hash of a trusted election block to sync from instead of using a ZKP.
+    /// Only effective for full and light nodes.
+    pub checkpoint: Option<String>,
+    /// Number of past epochs whose history is kept. Older history is pruned.
+    /// Only effective for history nodes. Can't be combined with `history_retention_days`.
+    pub history_retention_epochs: Option<u32>,
+    /// Number of days for which the history is kept. Older history is pruned.
+    /// Only effective for history nodes. Can't be combined with `history_retention_epochs`.
+    pub history_retention_days: Option<u32>,
+    /// Keep a snapshot of the accounts trie at the latest macro block and serve accounts chunks
+    /// from it. Only effective for full and history nodes (default: `true`)
+    #[serde(default = "default_true")]
+    pub accounts_snapshot: bool,
 }
 
 impl Default for ConsensusSettings {
@@ -202,6 +219,10 @@ impl Default for ConsensusSettings {
             min_peers: None,
             full_sync_threshold: None,
             index_history: true,
+            checkpoint: None,
+            history_retention_epochs: None,
+            history_retention_days: None,
+            accounts_snapshot: true,
         }
     }
 }
@@ -434,6 +455,79 @@ pub struct ValidatorSettings {
     pub fee_key: Option<Sensitive<String>>,
     #[serde(default)]
     pub automatic_reactivate: bool,
+    #[serde(default = "default_true")]
+    pub safe_startup: bool,
+    pub treasury: Option<TreasurySettings>,
+    pub remote_signer: Option<RemoteSignerSettings>,
+}
+
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TreasurySettings {
+    #[serde(deserialize_with = "deserialize_coin")]
+    #[serde(default)]
+    pub fee_reserve: Coin,
+    #[serde(deserialize_with = "deserialize_string_option")]
+    #[serde(default)]
+    pub compound_staker: Option<Address>,
+    #[serde(default)]
+    pub compound_percentage: u8,
+    #[serde(deserialize_with = "deserialize_string_option")]
+    #[serde(default)]
+    pub sweep_address: Option<Address>,
+    #[serde(deserialize_with = "deserialize_coin")]
+    #[serde(default)]
+    pub min_transfer: Coin,
+    #[serde(deserialize_with = "deserialize_coin_option")]
+    #[serde(default)]
+    pub max_transfer_per_epoch: Option<Coin>,
+    #[serde(deserialize_with = "deserialize_coin")]
+    #[serde(default)]
+    pub transaction_fee: Coin,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[cfg(feature = "validator")]
+impl From<TreasurySettings> for TreasuryConfig {
+    fn from(treasury: TreasurySettings) -> Self {
+        Self {
+            fee_reserve: treasury.fee_reserve,
+            compound_staker: treasury.compound_staker,
+            compound_percentage: treasury.compound_percentage,
+            sweep_address: treasury.sweep_address,
+            min_transfer: treasury.min_transfer,
+            max_transfer_per_epoch: treasury.max_transfer_per_epoch,
+            transaction_fee: treasury.transaction_fee,
+            dry_run: treasury.dry_run,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerSettings {
+    pub address: String,
+    pub auth_token: Sensitive<String>,
+    pub timeout_ms: Option<u64>,
+}
+
+#[cfg(feature = "validator")]
+impl TryFrom<RemoteSignerSettings> for RemoteSignerConfig {
+    type Error = Error;
+
+    fn try_from(remote_signer: RemoteSignerSettings) -> Result<Self, Self::Error> {
+        Ok(Self {
+            address: remote_signer.address.parse().map_err(|e| {
+                Error::config_error(format!("Invalid remote signer address: {}", e))
+            })?,
+            auth_token: remote_signer.auth_token,
+            timeout: remote_signer
+                .timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(RemoteSignerConfig::DEFAULT_TIMEOUT),
+        })
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Default)]
@@ -11,6 +11,16 @@ where
     Coin::try_from(value).map_err(Error::custom)
 }
 
+pub(crate) fn deserialize_coin_option<'de, D>(deserializer: D) -> Result<Option<Coin>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<u64>::deserialize(deserializer)?;
+    value
+        .map(|value| Coin::try_from(value).map_err(Error::custom))
+        .transpose()
+}
+
 #[allow(dead_code)]
 pub(crate) fn deserialize_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
@@ -23,6 +23,10 @@ pub enum Error {
     #[error("Consensus error: {0}")]
     Consensus(#[from] nimiq_consensus::Error),
 
+    #[cfg(feature = "full-consensus")]
+    #[error("Chain archive error: {0}")]
+    Archive(#[from] nimiq_blockchain::archive::ArchiveError),
+
     #[error("Config file parsing error: {0}")]
     Toml(#[from] toml::de::Error),
 
@@ -0,0 +1,106 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+    sync::Arc,
+};
+
+use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_consensus::Error::BlockchainError;
+use nimiq_genesis::NetworkInfo;
+use nimiq_primitives::policy::Policy;
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
+
+use crate::{
+    config::config::{ClientConfig, SyncMode},
+    error::Error,
+};
+
+/// Opens the blockchain in the database of the client without connecting to the network.
+fn open_blockchain(config: &ClientConfig) -> Result<Blockchain, Error> {
+    if config.consensus.sync_mode == SyncMode::Light {
+        return Err(Error::config_error(
+            "Chain archives can't be used by light nodes",
+        ));
+    }
+
+    let network_info = NetworkInfo::from_network_id(config.network_id);
+    let _ = Policy::get_or_init(Policy {
+        genesis_block_number: network_info.genesis_block().block_number(),
+        ..Default::default()
+    });
+
+    let environment = config.storage.database(
+        config.network_id,
+        config.consensus.sync_mode,
+        config.database.clone(),
+    )?;
+
+    let is_history = config.consensus.sync_mode == SyncMode::History;
+    let blockchain_config = BlockchainConfig {
+        keep_history: is_history,
+        max_epochs_stored: config.consensus.max_epochs_stored,
+        index_history: is_history && config.consensus.index_history,
+        history_retention: config.consensus.history_retention(),
+        accounts_snapshot: false,
+    };
+
+    Blockchain::new(
+        environment,
+        blockchain_config,
+        config.network_id,
+        Arc::new(OffsetTime::new()),
+    )
+    .map_err(|e| Error::Consensus(BlockchainError(e)))
+}
+
+/// Exports the blocks from `first_block` to `last_block` of the local chain to an archive file.
+/// By default, the whole chain after the genesis block is exported.
+pub fn export_archive(
+    config: &ClientConfig,
+    path: &Path,
+    first_block: Option<u32>,
+    last_block: Option<u32>,
+    macro_blocks_only: bool,
+) -> Result<(), Error> {
+    let blockchain = open_blockchain(config)?;
+    let first_block = first_block.unwrap_or(Policy::genesis_block_number() + 1);
+    let last_block = last_block.unwrap_or_else(|| blockchain.block_number());
+
+    log::info!(
+        first_block,
+        last_block,
+        macro_blocks_only,
+        path = %path.display(),
+        "Exporting chain archive"
+    );
+    let writer = BufWriter::new(File::create(path)?);
+    let num_blocks =
+        blockchain.export_archive(writer, first_block, last_block, macro_blocks_only)?;
+    log::info!(num_blocks, "Exported chain archive");
+
+    Ok(())
+}
+
+/// Imports an archive file into the local chain, fully verifying all blocks.
+pub fn import_archive(config: &ClientConfig, path: &Path) -> Result<(), Error> {
+    let blockchain = RwLock::new(open_blockchain(config)?);
+
+    log::info!(
+        head = blockchain.read().block_number(),
+        path = %path.display(),
+        "Importing chain archive"
+    );
+    let reader = BufReader::new(File::open(path)?);
+    let import = Blockchain::import_archive(&blockchain, reader)?;
+    log::info!(
+        num_applied = import.num_applied,
+        num_ignored = import.num_ignored,
+        head = blockchain.read().block_number(),
+        "Imported chain archive"
+    );
+
+    Ok(())
+}
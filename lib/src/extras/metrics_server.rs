@@ -6,6 +6,8 @@ use nimiq_consensus::ConsensusProxy;
 use nimiq_mempool::mempool::Mempool;
 pub use nimiq_metrics_server::NimiqTaskMonitor;
 use nimiq_network_interface::network::Network;
+#[cfg(feature = "validator")]
+use nimiq_validator::validator::ValidatorProxy;
 
 pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
@@ -13,16 +15,25 @@ pub fn start_metrics_server<TNetwork: Network>(
     #[cfg(feature = "nimiq-mempool")] mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    #[cfg(feature = "validator")] validator: Option<ValidatorProxy>,
     task_monitors: &[NimiqTaskMonitor],
 ) {
     #[cfg(not(feature = "nimiq-mempool"))]
     let mempool = None;
+    #[cfg(feature = "validator")]
+    let (validator_performance, handel_peer_quality) = validator
+        .map(|validator| (validator.performance, validator.handel_peer_quality))
+        .unzip();
+    #[cfg(not(feature = "validator"))]
+    let (validator_performance, handel_peer_quality) = (None, None);
     nimiq_metrics_server::start_metrics_server(
         addr,
         blockchain_proxy,
         mempool,
         consensus_proxy,
         network,
+        validator_performance,
+        handel_peer_quality,
         task_monitors,
     );
 }
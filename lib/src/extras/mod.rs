@@ -1,3 +1,5 @@
+#[cfg(feature = "full-consensus")]
+pub mod archive;
 #[cfg(feature = "deadlock")]
 pub mod deadlock;
 #[cfg(feature = "launcher")]
@@ -12,5 +14,7 @@ pub mod panic;
 pub mod rpc_server;
 #[cfg(feature = "signal-handling")]
 pub mod signal_handling;
+#[cfg(feature = "wallet")]
+pub mod transaction_scheduler;
 #[cfg(feature = "web-logging")]
 pub mod web_logging;
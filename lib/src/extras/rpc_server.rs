@@ -4,7 +4,6 @@ use nimiq_jsonrpc_server::{
     AllowListDispatcher, Config, Credentials, ModularDispatcher, Server as _Server,
 };
 use nimiq_rpc_server::dispatchers::*;
-use nimiq_wallet::WalletStore;
 
 #[cfg(feature = "rpc-server")]
 use crate::config::config::RpcServerConfig;
@@ -13,11 +12,7 @@ use crate::{client::Client, config::consts::default_bind, error::Error};
 pub type Server = _Server<AllowListDispatcher<ModularDispatcher>>;
 
 #[cfg(feature = "rpc-server")]
-pub fn initialize_rpc_server(
-    client: &Client,
-    config: RpcServerConfig,
-    wallet_store: Arc<WalletStore>,
-) -> Result<Server, Error> {
+pub fn initialize_rpc_server(client: &Client, config: RpcServerConfig) -> Result<Server, Error> {
     let ip = config.bind_to.unwrap_or_else(default_bind);
     log::info!("Initializing RPC server: {}:{}", ip, config.port);
 
@@ -38,14 +33,16 @@ pub fn initialize_rpc_server(
 
     let mut dispatcher = ModularDispatcher::default();
 
-    let wallet_dispatcher = WalletDispatcher::new(wallet_store);
-    let unlocked_wallets = Arc::clone(&wallet_dispatcher.unlocked_wallets);
+    let unlocked_wallets = client.unlocked_wallets();
+    let wallet_dispatcher =
+        WalletDispatcher::new(client.wallet_store(), Arc::clone(&unlocked_wallets));
 
     dispatcher.add(BlockchainDispatcher::new(client.blockchain()));
 
     dispatcher.add(ConsensusDispatcher::new(
         client.consensus_proxy(),
         Some(unlocked_wallets),
+        Some(client.transaction_scheduler()),
     ));
     dispatcher.add(NetworkDispatcher::new(client.network()));
     if let Some(mempool) = client.mempool() {
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use futures::{future::BoxFuture, FutureExt, StreamExt};
+#[cfg(feature = "full-consensus")]
+use nimiq_account::Account;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
+use nimiq_blockchain_proxy::BlockchainReadProxy;
+use nimiq_keys::{Address, KeyPair};
+use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_transaction::Transaction;
+use nimiq_wallet::{ScheduledTransactionSubmitter, TransactionScheduler, UnlockedWallets};
+use parking_lot::RwLock;
+
+use crate::client::ConsensusProxy;
+
+/// Submits the transactions scheduled in the node wallet through the consensus.
+struct ConsensusSubmitter {
+    consensus: ConsensusProxy,
+    unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+}
+
+impl ScheduledTransactionSubmitter for ConsensusSubmitter {
+    fn network_id(&self) -> NetworkId {
+        self.consensus.blockchain.read().network_id()
+    }
+
+    fn htlc_balance(&self, address: &Address) -> Result<Option<Coin>, String> {
+        match &self.consensus.blockchain.read() {
+            #[cfg(feature = "full-consensus")]
+            BlockchainReadProxy::Full(blockchain) => {
+                match blockchain.get_account_if_complete(address) {
+                    Some(Account::HTLC(contract)) => Ok(Some(contract.balance)),
+                    Some(_) => Err(format!("No HTLC contract at {}", address)),
+                    None => Ok(None),
+                }
+            }
+            BlockchainReadProxy::Light(_) => {
+                Err("HTLC redemption is not supported for a light blockchain".to_string())
+            }
+        }
+    }
+
+    fn key_pair(&self, wallet: &Address) -> Option<KeyPair> {
+        self.unlocked_wallets
+            .read()
+            .get(wallet)
+            .map(|wallet_account| wallet_account.key_pair.clone())
+    }
+
+    fn send_transaction(&self, transaction: Transaction) -> BoxFuture<'_, Result<(), String>> {
+        async move {
+            self.consensus
+                .send_transaction(transaction)
+                .await
+                .map_err(|error| error.to_string())
+        }
+        .boxed()
+    }
+}
+
+/// Spawns the task that follows the chain head and sends the transactions held by the
+/// [`TransactionScheduler`] once they are due.
+pub fn start_transaction_scheduler(
+    scheduler: Arc<TransactionScheduler>,
+    consensus: ConsensusProxy,
+    unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+) {
+    let blockchain_events = consensus.blockchain.read().notifier_as_stream();
+
+    let heads_consensus = consensus.clone();
+    let heads = blockchain_events
+        .filter_map(move |event| {
+            let head = match event {
+                BlockchainEvent::Extended(_)
+                | BlockchainEvent::Rebranched(..)
+                | BlockchainEvent::HistoryAdopted(_)
+                    // Wait until we are in sync to avoid sending transactions based on an
+                    // outdated head.
+                    if heads_consensus.is_established() =>
+                {
+                    let blockchain = heads_consensus.blockchain.read();
+                    Some((blockchain.block_number(), blockchain.timestamp()))
+                }
+                _ => None,
+            };
+            futures::future::ready(head)
+        })
+        .boxed();
+
+    let submitter = ConsensusSubmitter {
+        consensus,
+        unlocked_wallets,
+    };
+
+    nimiq_utils::spawn::spawn(async move { scheduler.run(heads, &submitter).await });
+}
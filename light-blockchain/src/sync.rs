@@ -2,6 +2,7 @@ use nimiq_block::{Block, BlockError};
 use nimiq_blockchain_interface::{
     AbstractBlockchain, BlockchainEvent, ChainInfo, PushError, PushResult,
 };
+use nimiq_hash::Blake2bHash;
 use nimiq_zkp::{verify::verify, NanoProof, ZKP_VERIFYING_DATA};
 use parking_lot::RwLockUpgradableReadGuard;
 
@@ -21,6 +22,30 @@ impl LightBlockchain {
         block: Block,
         proof: NanoProof,
         trusted_proof: bool,
+    ) -> Result<PushResult, PushError> {
+        Self::push_election_head(this, block, (!trusted_proof).then_some(proof))
+    }
+
+    /// Syncs from a trusted checkpoint. It receives the election block with the hash the operator
+    /// configured as trusted and adopts it without any proof that it descends from genesis.
+    pub fn push_trusted_checkpoint(
+        this: RwLockUpgradableReadGuard<Self>,
+        block: Block,
+        checkpoint: &Blake2bHash,
+    ) -> Result<PushResult, PushError> {
+        if !block.is_election() || block.hash() != *checkpoint {
+            return Err(PushError::InvalidCheckpoint);
+        }
+
+        Self::push_election_head(this, block, None)
+    }
+
+    /// Adopts the given election block as the new head. If a proof is given, it must prove that
+    /// the block descends from the genesis block.
+    fn push_election_head(
+        this: RwLockUpgradableReadGuard<Self>,
+        block: Block,
+        proof: Option<NanoProof>,
     ) -> Result<PushResult, PushError> {
         // Must be an election block.
         assert!(block.is_election());
@@ -50,7 +75,7 @@ impl LightBlockchain {
         block.verify(this.network_id)?;
 
         // Verify the zk proof.
-        if !trusted_proof {
+        if let Some(proof) = proof {
             let verify_result = verify(
                 this.genesis_block.unwrap_macro_ref().hash_blake2s(),
                 block_hash_blake2s,
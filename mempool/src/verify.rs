@@ -3,7 +3,9 @@ use std::sync::Arc;
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_hash::Hash;
-use nimiq_primitives::{account::AccountError, networks::NetworkId, transaction::TransactionError};
+use nimiq_primitives::{
+    account::AccountError, networks::NetworkId, policy::Policy, transaction::TransactionError,
+};
 use nimiq_transaction::Transaction;
 use parking_lot::RwLock;
 use thiserror::Error;
@@ -46,6 +48,9 @@ pub(crate) async fn verify_tx(
         .await
         .unwrap()?;
 
+    // The transaction must be supported by the protocol version of the blocks we produce.
+    transaction.verify_version(Policy::VERSION)?;
+
     // 2. Acquire blockchain read lock
     let blockchain = blockchain.read();
 
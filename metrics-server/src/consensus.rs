@@ -1,4 +1,7 @@
-use nimiq_consensus::ConsensusProxy;
+use nimiq_consensus::{
+    sync::status::{SyncPhase, SyncStatus},
+    ConsensusProxy,
+};
 use nimiq_network_interface::network::Network;
 use prometheus_client::registry::Registry;
 
@@ -13,12 +16,84 @@ impl ConsensusMetrics {
     ) {
         let sub_registry = registry.sub_registry_with_prefix("consensus");
 
-        let closure =
-            NumericClosureMetric::new_gauge(Box::new(move || consensus.is_established() as i64));
+        let consensus_proxy = consensus.clone();
+        let closure = NumericClosureMetric::new_gauge(Box::new(move || {
+            consensus_proxy.is_established() as i64
+        }));
         sub_registry.register(
             "is_established",
             "Whether consensus is established",
             closure,
         );
+
+        let sync_registry = sub_registry.sub_registry_with_prefix("sync");
+
+        let metrics: [(&str, &str, fn(&SyncStatus<TNetwork::PeerId>) -> i64); 10] = [
+            (
+                "phase",
+                "Current sync phase (0 = macro sync, 1 = state sync, 2 = live sync, 3 = synced)",
+                |s| match s.phase {
+                    SyncPhase::MacroSync => 0,
+                    SyncPhase::StateSync => 1,
+                    SyncPhase::LiveSync => 2,
+                    SyncPhase::Synced => 3,
+                },
+            ),
+            (
+                "target_epoch",
+                "Latest epoch known to be available from the peers",
+                |s| s.target_epoch.map_or(0, i64::from),
+            ),
+            (
+                "target_block",
+                "Latest macro block known to be available from the peers",
+                |s| s.target_block.map_or(0, i64::from),
+            ),
+            ("epochs_done", "Number of epochs synced so far", |s| {
+                s.epochs_done as i64
+            }),
+            ("epochs_total", "Total number of epochs to sync", |s| {
+                s.epochs_total as i64
+            }),
+            (
+                "chunks_done",
+                "Number of chunks downloaded for the epoch being synced",
+                |s| s.chunks_done as i64,
+            ),
+            (
+                "chunks_total",
+                "Total number of chunks to download for the epoch being synced",
+                |s| s.chunks_total as i64,
+            ),
+            (
+                "chunk_concurrency",
+                "Number of chunk requests kept in flight",
+                |s| s.chunk_concurrency as i64,
+            ),
+            (
+                "eta_seconds",
+                "Estimated number of seconds until the macro sync is finished",
+                |s| s.eta.map_or(0, |eta| eta.as_secs() as i64),
+            ),
+            ("peers", "Number of peers we are syncing with", |s| {
+                s.peers.len() as i64
+            }),
+        ];
+
+        for (name, help, metric) in metrics {
+            let consensus = consensus.clone();
+            let closure =
+                NumericClosureMetric::new_gauge(Box::new(move || metric(&consensus.sync_status())));
+            sync_registry.register(name, help, closure);
+        }
+
+        let closure = NumericClosureMetric::new_gauge(Box::new(move || {
+            consensus.sync_status().chunks_per_second
+        }));
+        sync_registry.register(
+            "chunks_per_second",
+            "Number of chunks downloaded per second",
+            closure,
+        );
     }
 }
@@ -0,0 +1,73 @@
+use std::{sync::Arc, time::Duration};
+
+use nimiq_handel::peer_quality::{AggregationStats, PeerQualityTracker};
+use prometheus_client::registry::Registry;
+
+use crate::NumericClosureMetric;
+
+pub struct HandelMetrics {}
+
+impl HandelMetrics {
+    pub fn register(registry: &mut Registry, peer_quality: Arc<PeerQualityTracker>) {
+        let sub_registry = registry.sub_registry_with_prefix("handel");
+
+        let metrics: [(&str, &str, fn(&AggregationStats) -> i64); 4] = [
+            (
+                "aggregations_started",
+                "Number of skip block and macro block aggregations started",
+                |s| s.started as i64,
+            ),
+            (
+                "aggregations_completed",
+                "Number of aggregations that collected the contributions of all validators",
+                |s| s.completed as i64,
+            ),
+            (
+                "last_completion_time_ms",
+                "Time in milliseconds the last completed aggregation took",
+                |s| millis(s.last_completion_time),
+            ),
+            (
+                "average_completion_time_ms",
+                "Smoothed time in milliseconds completed aggregations took",
+                |s| millis(s.average_completion_time),
+            ),
+        ];
+
+        for (name, help, metric) in metrics {
+            let peer_quality = Arc::clone(&peer_quality);
+            let closure = NumericClosureMetric::new_gauge(Box::new(move || {
+                metric(&peer_quality.aggregation_stats())
+            }));
+            sub_registry.register(name, help, closure);
+        }
+
+        let peers = Arc::clone(&peer_quality);
+        let closure = NumericClosureMetric::new_gauge(Box::new(move || {
+            peers
+                .peers()
+                .values()
+                .map(|peer| peer.invalid_contributions as i64)
+                .sum::<i64>()
+        }));
+        sub_registry.register(
+            "invalid_contributions",
+            "Number of invalid contributions received from other validators in this epoch",
+            closure,
+        );
+
+        let closure = NumericClosureMetric::new_gauge(Box::new(move || {
+            let peer_ids: Vec<usize> = peer_quality.peers().into_keys().collect();
+            millis(peer_quality.expected_latency(&peer_ids))
+        }));
+        sub_registry.register(
+            "peer_latency_ms",
+            "Median time in milliseconds other validators took to respond with a valid contribution",
+            closure,
+        );
+    }
+}
+
+fn millis(duration: Option<Duration>) -> i64 {
+    duration.map_or(0, |duration| duration.as_millis() as i64)
+}
@@ -2,8 +2,10 @@ use std::{fmt::Debug, net::SocketAddr, sync::Arc};
 
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_consensus::ConsensusProxy;
+use nimiq_handel::peer_quality::PeerQualityTracker;
 use nimiq_mempool::mempool::Mempool;
 use nimiq_network_interface::network::Network;
+use nimiq_validator::performance::PerformanceTracker;
 use parking_lot::RwLock;
 use prometheus_client::{
     encoding::{EncodeGaugeValue, EncodeMetric, MetricEncoder},
@@ -17,18 +19,21 @@ use tokio_metrics::TaskMonitor;
 #[cfg(tokio_unstable)]
 use crate::tokio_runtime::TokioRuntimeMetrics;
 use crate::{
-    chain::BlockMetrics, consensus::ConsensusMetrics, mempool::MempoolMetrics,
-    network::NetworkMetrics, server::metrics_server, tokio_task::TokioTaskMetrics,
+    chain::BlockMetrics, consensus::ConsensusMetrics, handel::HandelMetrics,
+    mempool::MempoolMetrics, network::NetworkMetrics, server::metrics_server,
+    tokio_task::TokioTaskMetrics, validator::ValidatorMetrics,
 };
 
 mod chain;
 mod consensus;
+mod handel;
 mod mempool;
 mod network;
 mod server;
 #[cfg(tokio_unstable)]
 mod tokio_runtime;
 mod tokio_task;
+mod validator;
 
 #[derive(Clone)]
 pub struct NimiqTaskMonitor {
@@ -84,6 +89,8 @@ pub fn start_metrics_server<TNetwork: Network>(
     mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    validator_performance: Option<Arc<RwLock<PerformanceTracker>>>,
+    handel_peer_quality: Option<Arc<PeerQualityTracker>>,
     task_monitors: &[NimiqTaskMonitor],
 ) {
     let mut registry = Registry::default();
@@ -97,6 +104,14 @@ pub fn start_metrics_server<TNetwork: Network>(
         MempoolMetrics::register(nimiq_registry, mempool);
     }
 
+    if let Some(validator_performance) = validator_performance {
+        ValidatorMetrics::register(nimiq_registry, validator_performance);
+    }
+
+    if let Some(handel_peer_quality) = handel_peer_quality {
+        HandelMetrics::register(nimiq_registry, handel_peer_quality);
+    }
+
     // Setup the task metrics
     let task_metrics = Arc::new(RwLock::new(TokioTaskMetrics::new()));
     task_metrics.write().register(
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use nimiq_validator::performance::{PerformanceTracker, ValidatorPerformance};
+use parking_lot::RwLock;
+use prometheus_client::registry::Registry;
+
+use crate::NumericClosureMetric;
+
+pub struct ValidatorMetrics {}
+
+impl ValidatorMetrics {
+    pub fn register(registry: &mut Registry, performance: Arc<RwLock<PerformanceTracker>>) {
+        let sub_registry = registry.sub_registry_with_prefix("validator");
+
+        let metrics: [(&str, &str, fn(&ValidatorPerformance) -> i64); 10] = [
+            ("epoch_number", "Epoch of the performance statistics", |p| {
+                p.epoch_number as i64
+            }),
+            ("slots", "Number of slots owned in the current epoch", |p| {
+                p.slots as i64
+            }),
+            (
+                "micro_blocks_expected",
+                "Number of micro blocks to be produced in the current epoch",
+                |p| p.micro_blocks_expected as i64,
+            ),
+            (
+                "micro_blocks_produced",
+                "Number of micro blocks produced in the current epoch",
+                |p| p.micro_blocks_produced as i64,
+            ),
+            (
+                "skip_blocks_caused",
+                "Number of own micro blocks replaced by skip blocks in the current epoch",
+                |p| p.skip_blocks_caused as i64,
+            ),
+            (
+                "tendermint_rounds",
+                "Number of Tendermint rounds participated in during the current epoch",
+                |p| p.tendermint_rounds as i64,
+            ),
+            (
+                "handel_contributions",
+                "Number of skip and macro block signatures contributed to in the current epoch",
+                |p| p.handel_contributions as i64,
+            ),
+            (
+                "penalties",
+                "Number of penalties received in the current epoch",
+                |p| p.penalties as i64,
+            ),
+            (
+                "jail_events",
+                "Number of times jailed in the current epoch",
+                |p| p.jail_events as i64,
+            ),
+            (
+                "rewards",
+                "Rewards in Luna paid out in the current epoch",
+                |p| u64::from(p.rewards) as i64,
+            ),
+        ];
+
+        for (name, help, metric) in metrics {
+            let performance = Arc::clone(&performance);
+            let closure = NumericClosureMetric::new_gauge(Box::new(move || {
+                metric(performance.read().current())
+            }));
+            sub_registry.register(name, help, closure);
+        }
+    }
+}
@@ -177,7 +177,7 @@ pub trait Network: Send + Sync + Unpin + 'static {
     where
         K: AsRef<[u8]> + Send + Sync,
         V: Serialize + Send + Sync + TaggedSignable + Clone + Ord,
-        T: TaggedKeyPair + Send + Sync + Serialize + Deserialize;
+        T: TaggedKeyPair + Send + Sync;
 
     /// Dials a peer
     async fn dial_peer(&self, peer_id: Self::PeerId) -> Result<(), Self::Error>;
@@ -28,6 +28,9 @@ pub enum NetworkError {
     #[error("DHT PutRecord error: {0:?}")]
     DhtPutRecord(libp2p::kad::PutRecordError),
 
+    #[error("Failed to sign the DHT record")]
+    DhtSigning,
+
     #[error("Gossipsub Publish error: {0:?}")]
     GossipsubPublish(libp2p::gossipsub::PublishError),
 
@@ -654,10 +654,10 @@ impl NetworkInterface for Network {
     where
         K: AsRef<[u8]> + Send + Sync,
         V: Serialize + Send + Sync + TaggedSignable + Clone + Ord,
-        T: TaggedKeyPair + Send + Sync + Serialize + Deserialize,
+        T: TaggedKeyPair + Send + Sync,
     {
         // Sign the record before transmitting it to the swarm
-        let signature = keypair.tagged_sign(v);
+        let signature = keypair.try_tagged_sign(v).ok_or(NetworkError::DhtSigning)?;
         let signed_record = TaggedSigned::new(v.clone(), signature);
         let (output_tx, output_rx) = oneshot::channel();
 
@@ -0,0 +1,189 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::MockAddress;
+
+/// Describes the quality of a directed link between two mock networks. The default is a perfect
+/// link that delivers every message instantly and exactly once.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkConfig {
+    /// The fixed delay of every message on the link.
+    pub latency: Duration,
+    /// The maximum random delay added to the latency of every message.
+    pub jitter: Duration,
+    /// The probability (between 0 and 1) that a message is lost.
+    pub loss_probability: f64,
+    /// The probability (between 0 and 1) that a message is delivered twice.
+    pub duplicate_probability: f64,
+    /// The probability (between 0 and 1) that a message is held back by `reorder_delay`, allowing
+    /// later messages to overtake it. Otherwise, messages on a link are delivered in order.
+    pub reorder_probability: f64,
+    /// The additional delay of messages that are reordered.
+    pub reorder_delay: Duration,
+}
+
+impl LinkConfig {
+    /// Returns whether the link delivers every message instantly and exactly once.
+    pub fn is_perfect(&self) -> bool {
+        self == &LinkConfig::default()
+    }
+}
+
+/// The fate of a single message sent over a link.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Delivery {
+    /// The number of times the message is delivered. Zero if the message is lost.
+    pub copies: usize,
+    /// The delay after which the message is delivered.
+    pub delay: Duration,
+    /// Whether later messages on the link may overtake this message.
+    pub reordered: bool,
+}
+
+impl Delivery {
+    pub const IMMEDIATE: Delivery = Delivery {
+        copies: 1,
+        delay: Duration::ZERO,
+        reordered: false,
+    };
+
+    pub const LOST: Delivery = Delivery {
+        copies: 0,
+        delay: Duration::ZERO,
+        reordered: false,
+    };
+}
+
+#[derive(Debug)]
+struct NetworkConditionsInner {
+    rng: StdRng,
+    default_link: LinkConfig,
+    links: HashMap<(MockAddress, MockAddress), LinkConfig>,
+    /// The group of every address that is part of a partition. Addresses that are not part of
+    /// any group form a group of their own.
+    partition: HashMap<MockAddress, usize>,
+}
+
+/// The conditions of all links of a [`MockHub`](crate::MockHub). They can be changed at runtime
+/// to inject faults: per-link latency, jitter, message loss, duplication and reordering, as well
+/// as partitions of the network. All randomness is derived from a seed, so that a test run can be
+/// reproduced.
+///
+/// The conditions apply to gossipsub messages, requests, responses and messages. The DHT is not
+/// affected. Messages a network sends to itself are always delivered instantly. The fate of a
+/// gossipsub message is decided when the receiving network reads it from its subscription.
+#[derive(Clone, Debug)]
+pub struct NetworkConditions {
+    inner: Arc<Mutex<NetworkConditionsInner>>,
+}
+
+impl Default for NetworkConditions {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl NetworkConditions {
+    pub fn with_seed(seed: u64) -> Self {
+        NetworkConditions {
+            inner: Arc::new(Mutex::new(NetworkConditionsInner {
+                rng: StdRng::seed_from_u64(seed),
+                default_link: LinkConfig::default(),
+                links: HashMap::new(),
+                partition: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Resets the random number generator to the given seed.
+    pub fn set_seed(&self, seed: u64) {
+        self.inner.lock().rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Sets the configuration of all links that don't have their own configuration.
+    pub fn set_default_link(&self, config: LinkConfig) {
+        self.inner.lock().default_link = config;
+    }
+
+    /// Sets the configuration of the directed link from `from` to `to`.
+    pub fn set_link(&self, from: MockAddress, to: MockAddress, config: LinkConfig) {
+        self.inner.lock().links.insert((from, to), config);
+    }
+
+    /// Removes the configuration of the directed link from `from` to `to`, so that it uses the
+    /// default configuration again.
+    pub fn reset_link(&self, from: MockAddress, to: MockAddress) {
+        self.inner.lock().links.remove(&(from, to));
+    }
+
+    /// Partitions the network into the given groups. Networks can only communicate with networks
+    /// of the same group. All networks that are not part of any group form another group. This
+    /// replaces any previous partition.
+    pub fn partition(&self, groups: &[&[MockAddress]]) {
+        let mut inner = self.inner.lock();
+        inner.partition.clear();
+        for (i, group) in groups.iter().enumerate() {
+            for address in group.iter() {
+                inner.partition.insert(*address, i);
+            }
+        }
+    }
+
+    /// Heals the partition, so that all networks can communicate again.
+    pub fn heal(&self) {
+        self.inner.lock().partition.clear();
+    }
+
+    /// Returns whether the networks with the given addresses are separated by a partition.
+    pub fn is_partitioned(&self, a: MockAddress, b: MockAddress) -> bool {
+        let inner = self.inner.lock();
+        inner.partition.get(&a) != inner.partition.get(&b)
+    }
+
+    /// Decides the fate of a message sent from `from` to `to`.
+    pub(crate) fn delivery(&self, from: MockAddress, to: MockAddress) -> Delivery {
+        if from == to {
+            return Delivery::IMMEDIATE;
+        }
+
+        let mut inner = self.inner.lock();
+        if inner.partition.get(&from) != inner.partition.get(&to) {
+            return Delivery::LOST;
+        }
+
+        let link = inner
+            .links
+            .get(&(from, to))
+            .unwrap_or(&inner.default_link)
+            .clone();
+        // Perfect links don't consume any randomness, so that traffic on them doesn't change the
+        // fate of messages on faulty links.
+        if link.is_perfect() {
+            return Delivery::IMMEDIATE;
+        }
+
+        let rng = &mut inner.rng;
+        if rng.gen_bool(link.loss_probability.clamp(0.0, 1.0)) {
+            return Delivery::LOST;
+        }
+
+        let mut delay = link.latency + link.jitter.mul_f64(rng.gen::<f64>());
+        let copies = if rng.gen_bool(link.duplicate_probability.clamp(0.0, 1.0)) {
+            2
+        } else {
+            1
+        };
+        let reordered = rng.gen_bool(link.reorder_probability.clamp(0.0, 1.0));
+        if reordered {
+            delay += link.reorder_delay;
+        }
+
+        Delivery {
+            copies,
+            delay,
+            reordered,
+        }
+    }
+}
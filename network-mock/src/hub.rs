@@ -10,7 +10,7 @@ use tokio::sync::{broadcast, mpsc, oneshot};
 
 use crate::{
     network::{MockNetwork, MockRequestId},
-    MockAddress, MockPeerId, ObservableHashMap,
+    MockAddress, MockPeerId, NetworkConditions, ObservableHashMap,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -68,6 +68,9 @@ pub(crate) struct MockHubInner {
 
     /// Arcs to `AtomicBool`s for each network if they're connected.
     pub is_connected: HashMap<MockAddress, Arc<AtomicBool>>,
+
+    /// The conditions of the links between the networks.
+    pub conditions: NetworkConditions,
 }
 
 impl MockHubInner {
@@ -133,6 +136,19 @@ impl MockHub {
         Self::default()
     }
 
+    /// Creates a hub whose network conditions derive all randomness from the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        let hub = Self::default();
+        hub.conditions().set_seed(seed);
+        hub
+    }
+
+    /// Returns a handle to the conditions of the links between the networks of this hub. They
+    /// can be changed at any time to inject faults.
+    pub fn conditions(&self) -> NetworkConditions {
+        self.inner.lock().conditions.clone()
+    }
+
     pub fn new_address(&mut self) -> MockAddress {
         self.last_address += 1;
         MockAddress(self.last_address)
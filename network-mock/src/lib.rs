@@ -1,7 +1,9 @@
+mod conditions;
 mod hub;
 mod network;
 mod observable_hash_map;
 
+pub use conditions::{LinkConfig, NetworkConditions};
 use derive_more::{Display, From, Into};
 pub use hub::MockHub;
 pub use network::{MockId, MockNetwork};
@@ -70,15 +72,21 @@ pub async fn create_mock_validator_network(n: usize, dial: bool) -> Vec<MockNetw
 
 #[cfg(test)]
 pub mod tests {
+    use std::time::{Duration, Instant};
+
     use futures::{Stream, StreamExt};
     use nimiq_keys::{KeyPair, SecureGenerate};
     use nimiq_network_interface::network::{Network, NetworkEvent, SubscribeEvents, Topic};
     use nimiq_test_log::test;
     use nimiq_test_utils::test_rng::test_rng;
+    use nimiq_time::timeout;
     use nimiq_utils::{spawn::spawn, tagged_signing::TaggedSignable};
     use serde::{Deserialize, Serialize};
 
-    use super::{network::MockNetworkError, MockHub, MockPeerId};
+    use super::{
+        conditions::Delivery, network::MockNetworkError, LinkConfig, MockHub, MockPeerId,
+        NetworkConditions,
+    };
 
     pub async fn assert_peer_joined(
         events: &mut SubscribeEvents<MockPeerId>,
@@ -218,4 +226,95 @@ pub mod tests {
             net1.unsubscribe::<TestTopic>().await
         );
     }
+
+    #[test(tokio::test)]
+    async fn gossipsub_respects_partitions() {
+        let mut hub = MockHub::new();
+        let net1 = hub.new_network();
+        let net2 = hub.new_network();
+        net1.dial_mock(&net2);
+
+        let mut messages = net1.subscribe::<TestTopic>().await.unwrap();
+        consume_stream(net2.subscribe::<TestTopic>().await.unwrap());
+
+        // Messages across the partition are lost.
+        hub.conditions()
+            .partition(&[&[net1.address()], &[net2.address()]]);
+        assert!(hub
+            .conditions()
+            .is_partitioned(net1.address(), net2.address()));
+        net2.publish::<TestTopic>(TestRecord { x: 1 })
+            .await
+            .unwrap();
+        assert!(timeout(Duration::from_millis(100), messages.next())
+            .await
+            .is_err());
+
+        // Once the partition is healed, messages are delivered again.
+        hub.conditions().heal();
+        net2.publish::<TestTopic>(TestRecord { x: 2 })
+            .await
+            .unwrap();
+
+        let (received_message, _peer) = messages.next().await.unwrap();
+        assert_eq!(received_message, TestRecord { x: 2 });
+    }
+
+    #[test(tokio::test)]
+    async fn gossipsub_respects_latency() {
+        let latency = Duration::from_millis(100);
+
+        let mut hub = MockHub::with_seed(42);
+        let net1 = hub.new_network();
+        let net2 = hub.new_network();
+        net1.dial_mock(&net2);
+        hub.conditions().set_default_link(LinkConfig {
+            latency,
+            ..Default::default()
+        });
+
+        let mut messages = net1.subscribe::<TestTopic>().await.unwrap();
+        consume_stream(net2.subscribe::<TestTopic>().await.unwrap());
+
+        let start = Instant::now();
+        for x in 0..3 {
+            net2.publish::<TestTopic>(TestRecord { x }).await.unwrap();
+        }
+
+        // The messages are delayed, but still delivered in order.
+        for x in 0..3 {
+            let (received_message, _peer) = messages.next().await.unwrap();
+            assert_eq!(received_message, TestRecord { x });
+        }
+        assert!(start.elapsed() >= latency);
+    }
+
+    #[test]
+    fn link_faults_are_deterministic() {
+        let mut hub = MockHub::new();
+        let a = hub.new_address();
+        let b = hub.new_address();
+        let link = LinkConfig {
+            jitter: Duration::from_millis(50),
+            loss_probability: 0.3,
+            duplicate_probability: 0.2,
+            reorder_probability: 0.1,
+            ..Default::default()
+        };
+
+        let deliveries = |seed| {
+            let conditions = NetworkConditions::with_seed(seed);
+            conditions.set_link(a, b, link.clone());
+            (0..100)
+                .map(|_| conditions.delivery(a, b))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(deliveries(7), deliveries(7));
+        assert_ne!(deliveries(7), deliveries(8));
+
+        // Only the configured direction of the link is faulty.
+        let conditions = NetworkConditions::with_seed(7);
+        conditions.set_link(a, b, link);
+        assert!((0..100).all(|_| conditions.delivery(b, a) == Delivery::IMMEDIATE));
+    }
 }
@@ -7,7 +7,10 @@ use std::{
 };
 
 use async_trait::async_trait;
-use futures::{stream::BoxStream, StreamExt};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
 use nimiq_network_interface::{
     network::{
         CloseReason, MsgAcceptance, Network, NetworkEvent, PubsubId, SubscribeEvents, Topic,
@@ -19,16 +22,22 @@ use nimiq_network_interface::{
     },
 };
 use nimiq_serde::{Deserialize, DeserializeError, Serialize};
-use nimiq_time::timeout;
-use nimiq_utils::tagged_signing::{TaggedKeyPair, TaggedSignable};
+use nimiq_time::{sleep, timeout};
+use nimiq_utils::{
+    spawn::spawn,
+    tagged_signing::{TaggedKeyPair, TaggedSignable},
+};
 use parking_lot::{Mutex, RwLock};
 use thiserror::Error;
 use tokio::sync::{broadcast, mpsc, oneshot};
-use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream};
+use tokio_stream::wrappers::{
+    errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream, UnboundedReceiverStream,
+};
 
 use crate::{
+    conditions::Delivery,
     hub::{MockHubInner, RequestKey, ResponseSender},
-    observable_hash_map, MockAddress, MockPeerId, ObservableHashMap,
+    observable_hash_map, MockAddress, MockPeerId, NetworkConditions, ObservableHashMap,
 };
 
 #[derive(Debug, Error, Eq, PartialEq)]
@@ -42,6 +51,9 @@ pub enum MockNetworkError {
     #[error("Network is not connected")]
     NotConnected,
 
+    #[error("Failed to sign the DHT record")]
+    DhtSigning,
+
     #[error("Peer is already subscribed to topic: {0}")]
     AlreadySubscribed(String),
 
@@ -77,15 +89,18 @@ pub struct MockNetwork {
     peers: Arc<RwLock<ObservableHashMap<MockPeerId, PeerInfo>>>,
     hub: Arc<Mutex<MockHubInner>>,
     is_connected: Arc<AtomicBool>,
+    conditions: NetworkConditions,
 }
 
 impl MockNetwork {
     const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+    /// The maximum number of gossipsub messages per subscription that are delayed at the same time.
+    const MAX_DELAYED_MESSAGES: usize = 1024;
 
     pub(crate) fn new(address: MockAddress, hub: Arc<Mutex<MockHubInner>>) -> Self {
         let peers = Arc::new(RwLock::new(ObservableHashMap::new()));
 
-        let is_connected = {
+        let (is_connected, conditions) = {
             let mut hub = hub.lock();
 
             // Insert out peer map into global peer maps table
@@ -97,7 +112,7 @@ impl MockNetwork {
             let is_connected = Arc::new(AtomicBool::new(false));
             hub.is_connected.insert(address, Arc::clone(&is_connected));
 
-            is_connected
+            (is_connected, hub.conditions.clone())
         };
 
         Self {
@@ -105,9 +120,15 @@ impl MockNetwork {
             peers,
             hub,
             is_connected,
+            conditions,
         }
     }
 
+    /// Returns a handle to the conditions of the links between the networks of the hub.
+    pub fn conditions(&self) -> &NetworkConditions {
+        &self.conditions
+    }
+
     pub fn address(&self) -> MockAddress {
         self.address
     }
@@ -200,9 +221,11 @@ impl MockNetwork {
             let sender = if let Some(sender) = hub.request_senders.get(&key) {
                 sender.clone()
             } else {
+                // Like libp2p, reply with a `NoReceiver` error if the peer doesn't handle this
+                // type of request.
                 log::warn!("No request sender: {:?}", key);
-                return Err(RequestError::OutboundRequest(
-                    OutboundRequestError::SendError,
+                return Err(RequestError::InboundRequest(
+                    InboundRequestError::NoReceiver,
                 ));
             };
 
@@ -226,19 +249,33 @@ impl MockNetwork {
 
         let data = request.serialize_request();
 
-        let request = (data, request_id, sender_id);
-        if let Err(e) = sender.send(request).await {
-            log::warn!(
-                "Cannot send request {} from {} to {} - {:?}",
-                std::any::type_name::<Req>(),
-                self.address,
-                peer_id,
-                e
-            );
-            self.hub.lock().response_senders.remove(&request_id);
-            return Err(RequestError::OutboundRequest(
-                OutboundRequestError::SendError,
-            ));
+        // A lost request is never received, so the requester runs into the timeout. Only messages
+        // can be duplicated, as requests can only be responded to once.
+        let delivery = self.conditions.delivery(self.address, peer_id.into());
+        let copies = if Req::Kind::EXPECT_RESPONSE {
+            delivery.copies.min(1)
+        } else {
+            delivery.copies
+        };
+        if !delivery.delay.is_zero() {
+            sleep(delivery.delay).await;
+        }
+
+        for _ in 0..copies {
+            let request = (data.clone(), request_id, sender_id);
+            if let Err(e) = sender.send(request).await {
+                log::warn!(
+                    "Cannot send request {} from {} to {} - {:?}",
+                    std::any::type_name::<Req>(),
+                    self.address,
+                    peer_id,
+                    e
+                );
+                self.hub.lock().response_senders.remove(&request_id);
+                return Err(RequestError::OutboundRequest(
+                    OutboundRequestError::SendError,
+                ));
+            }
         }
 
         let result = timeout(MockNetwork::REQUEST_TIMEOUT, rx).await;
@@ -341,29 +378,31 @@ impl MockNetwork {
                 return Err(MockNetworkError::AlreadySubscribed(topic_name));
             };
 
-        let stream = BroadcastStream::new(sender.subscribe()).filter_map(move |r| {
-            let is_connected = Arc::clone(&is_connected);
-
-            async move {
-                if is_connected.load(Ordering::SeqCst) {
-                    match r {
-                        Ok((data, peer_id)) => match T::Item::deserialize_from_vec(&data) {
-                            Ok(item) => return Some((item, peer_id)),
-                            Err(e) => {
-                                log::warn!("Dropped item because deserialization failed: {}", e)
+        let stream = self
+            .apply_conditions(BroadcastStream::new(sender.subscribe()))
+            .filter_map(move |r| {
+                let is_connected = Arc::clone(&is_connected);
+
+                async move {
+                    if is_connected.load(Ordering::SeqCst) {
+                        match r {
+                            Ok((data, peer_id)) => match T::Item::deserialize_from_vec(&data) {
+                                Ok(item) => return Some((item, peer_id)),
+                                Err(e) => {
+                                    log::warn!("Dropped item because deserialization failed: {}", e)
+                                }
+                            },
+                            Err(BroadcastStreamRecvError::Lagged(_)) => {
+                                log::warn!("Mock gossipsub channel is lagging")
                             }
-                        },
-                        Err(BroadcastStreamRecvError::Lagged(_)) => {
-                            log::warn!("Mock gossipsub channel is lagging")
                         }
+                    } else {
+                        log::debug!("Network not connected: Dropping gossipsub message.");
                     }
-                } else {
-                    log::debug!("Network not connected: Dropping gossipsub message.");
-                }
 
-                None
-            }
-        });
+                    None
+                }
+            });
 
         Ok(Box::pin(stream.map(|(topic, peer_id)| {
             let id = MockId {
@@ -373,6 +412,58 @@ impl MockNetwork {
         })))
     }
 
+    /// Applies the link conditions to a stream of gossipsub messages received by this network.
+    /// Messages are delayed concurrently and delivered in order, unless they are reordered.
+    fn apply_conditions(
+        &self,
+        messages: BroadcastStream<(Arc<Vec<u8>>, MockPeerId)>,
+    ) -> BoxStream<'static, Result<(Arc<Vec<u8>>, MockPeerId), BroadcastStreamRecvError>> {
+        let conditions = self.conditions.clone();
+        let address = self.address;
+        // Reordered messages bypass the in-order delivery. The sender is dropped once the
+        // subscription ends, so that the stream terminates after the last reordered message.
+        let (reordered_tx, reordered_rx) = mpsc::unbounded_channel();
+        let reordered_tx = Arc::new(Mutex::new(Some(reordered_tx)));
+        let reordered_tx_end = Arc::clone(&reordered_tx);
+
+        let in_order = messages
+            .map(move |message| {
+                let delivery = match message {
+                    Ok((_, peer_id)) => conditions.delivery(peer_id.into(), address),
+                    Err(_) => Delivery::IMMEDIATE,
+                };
+                let reordered_tx = reordered_tx.lock().clone();
+
+                async move {
+                    if let (true, Some(reordered_tx)) = (delivery.reordered, reordered_tx) {
+                        spawn(async move {
+                            sleep(delivery.delay).await;
+                            for _ in 0..delivery.copies {
+                                let _ = reordered_tx.send(message.clone());
+                            }
+                        });
+                        return vec![];
+                    }
+
+                    if !delivery.delay.is_zero() {
+                        sleep(delivery.delay).await;
+                    }
+                    vec![message; delivery.copies]
+                }
+            })
+            .buffered(Self::MAX_DELAYED_MESSAGES)
+            .flat_map(stream::iter)
+            .chain(
+                stream::once(async move {
+                    reordered_tx_end.lock().take();
+                    vec![]
+                })
+                .flat_map(stream::iter),
+            );
+
+        stream::select(in_order, UnboundedReceiverStream::new(reordered_rx)).boxed()
+    }
+
     async fn unsubscribe_with_name(&self, topic_name: String) -> Result<(), MockNetworkError> {
         let mut hub = self.hub.lock();
 
@@ -553,13 +644,19 @@ impl Network for MockNetwork {
         }
     }
 
-    async fn dht_put<K, V, T>(&self, k: &K, v: &V, _keypair: &T) -> Result<(), Self::Error>
+    async fn dht_put<K, V, T>(&self, k: &K, v: &V, keypair: &T) -> Result<(), Self::Error>
     where
         K: AsRef<[u8]> + Send + Sync,
         V: Serialize + Send + Sync + TaggedSignable + Clone + Ord,
-        T: TaggedKeyPair + Send + Sync + Serialize + Deserialize,
+        T: TaggedKeyPair + Send + Sync,
     {
         if self.is_connected.load(Ordering::SeqCst) {
+            // The mock network stores records unsigned, but still fails like the real network does
+            // if the record can't be signed.
+            if keypair.try_tagged_sign(v).is_none() {
+                return Err(MockNetworkError::DhtSigning);
+            }
+
             let mut hub = self.hub.lock();
 
             let data = v.serialize_to_vec();
@@ -615,8 +712,8 @@ impl Network for MockNetwork {
         request_id: Self::RequestId,
         response: Req::Response,
     ) -> Result<(), Self::Error> {
-        let mut hub = self.hub.lock();
-        if let Some(responder) = hub.response_senders.remove(&request_id) {
+        let responder = self.hub.lock().response_senders.remove(&request_id);
+        if let Some(responder) = responder {
             if !self.peers.read().contains_key(&responder.peer) {
                 return Err(MockNetworkError::NotConnected);
             }
@@ -624,6 +721,17 @@ impl Network for MockNetwork {
             let mut data = Vec::with_capacity(response.serialized_size());
             response.serialize(&mut data).unwrap();
 
+            // A lost response is never received, so the requester runs into the timeout.
+            let delivery = self
+                .conditions
+                .delivery(self.address, responder.peer.into());
+            if delivery.copies == 0 {
+                return Ok(());
+            }
+            if !delivery.delay.is_zero() {
+                sleep(delivery.delay).await;
+            }
+
             responder
                 .sender
                 .send(data)
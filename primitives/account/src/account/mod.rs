@@ -7,7 +7,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::account::{
     basic_account::BasicAccount, htlc_contract::HashedTimeLockedContract,
-    staking_contract::StakingContract, vesting_contract::VestingContract,
+    scheduled_vesting_contract::ScheduledVestingContract, staking_contract::StakingContract,
+    vesting_contract::VestingContract,
 };
 #[cfg(feature = "interaction-traits")]
 use crate::{
@@ -22,6 +23,7 @@ use crate::{
 
 pub mod basic_account;
 pub mod htlc_contract;
+pub mod scheduled_vesting_contract;
 pub mod staking_contract;
 pub mod vesting_contract;
 
@@ -33,6 +35,7 @@ macro_rules! gen_account_match {
             Account::Vesting(account) => account.$f($( $arg ),*),
             Account::HTLC(account) => account.$f($( $arg ),*),
             Account::Staking(account) => account.$f($( $arg ),*),
+            Account::ScheduledVesting(account) => account.$f($( $arg ),*),
         }
     };
 }
@@ -45,6 +48,7 @@ macro_rules! gen_account_type_match {
             AccountType::Vesting => VestingContract::$f($( $arg ),*),
             AccountType::HTLC => HashedTimeLockedContract::$f($( $arg ),*),
             AccountType::Staking => StakingContract::$f($( $arg ),*),
+            AccountType::ScheduledVesting => ScheduledVestingContract::$f($( $arg ),*),
         }
     };
 }
@@ -56,6 +60,7 @@ pub enum Account {
     Vesting(VestingContract),
     HTLC(HashedTimeLockedContract),
     Staking(StakingContract),
+    ScheduledVesting(ScheduledVestingContract),
 }
 
 impl Account {
@@ -65,6 +70,7 @@ impl Account {
             Account::Vesting(_) => AccountType::Vesting,
             Account::HTLC(_) => AccountType::HTLC,
             Account::Staking(_) => AccountType::Staking,
+            Account::ScheduledVesting(_) => AccountType::ScheduledVesting,
         }
     }
 
@@ -74,6 +80,7 @@ impl Account {
             Account::Vesting(ref account) => account.balance,
             Account::HTLC(ref account) => account.balance,
             Account::Staking(ref account) => account.balance,
+            Account::ScheduledVesting(ref account) => account.balance,
         }
     }
 
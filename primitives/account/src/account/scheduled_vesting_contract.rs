@@ -0,0 +1,316 @@
+use nimiq_keys::Address;
+use nimiq_primitives::{account::AccountError, coin::Coin};
+#[cfg(feature = "interaction-traits")]
+use nimiq_primitives::{account::AccountType, policy::Policy};
+use nimiq_serde::{Deserialize, Serialize};
+pub use nimiq_transaction::account::scheduled_vesting_contract::{VestingSchedule, VestingTranche};
+#[cfg(feature = "interaction-traits")]
+use nimiq_transaction::{
+    account::scheduled_vesting_contract::CreationTransactionData, inherent::Inherent,
+    SignatureProof, Transaction,
+};
+
+use crate::{convert_receipt, AccountReceipt};
+#[cfg(feature = "interaction-traits")]
+use crate::{
+    data_store::{DataStoreRead, DataStoreWrite},
+    interaction_traits::{
+        AccountInherentInteraction, AccountPruningInteraction, AccountTransactionInteraction,
+    },
+    reserved_balance::ReservedBalance,
+    Account, BlockState, InherentLogger, Log, TransactionLog,
+};
+
+/// A vesting contract that releases its funds according to one or more schedules. Each schedule
+/// consists of a cliff followed by tranches with their own step sizes.
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Serialize, Deserialize)]
+pub struct ScheduledVestingContract {
+    /// Total balance of the contract.
+    pub balance: Coin,
+    /// The owner of the contract, the only address that can interact with it.
+    pub owner: Address,
+    /// The release schedules of the contract.
+    pub schedules: Vec<VestingSchedule>,
+    /// Initially locked balance, i.e. the sum of the amounts of all schedules.
+    pub total_amount: Coin,
+}
+
+impl ScheduledVestingContract {
+    /// Returns the amount that is still locked at the given time.
+    pub fn min_cap(&self, time: u64) -> Coin {
+        self.schedules
+            .iter()
+            .map(|schedule| schedule.locked_amount(time))
+            .sum()
+    }
+}
+
+#[cfg(feature = "interaction-traits")]
+impl ScheduledVestingContract {
+    fn can_change_balance(
+        &self,
+        transaction: &Transaction,
+        new_balance: Coin,
+        block_state: &BlockState,
+    ) -> Result<(), AccountError> {
+        // Check vesting min cap.
+        let min_cap = self.min_cap(block_state.time);
+
+        if new_balance < min_cap {
+            return Err(AccountError::InsufficientFunds {
+                balance: self.balance.saturating_sub(min_cap),
+                needed: self.balance - new_balance,
+            });
+        }
+
+        // Check transaction signer is contract owner.
+        let signature_proof = SignatureProof::deserialize_all(&transaction.proof)?;
+
+        if !signature_proof.is_signed_by(&self.owner) {
+            return Err(AccountError::InvalidSignature);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "interaction-traits")]
+impl AccountTransactionInteraction for ScheduledVestingContract {
+    fn create_new_contract(
+        transaction: &Transaction,
+        initial_balance: Coin,
+        block_state: &BlockState,
+        _data_store: DataStoreWrite,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<Account, AccountError> {
+        // Scheduled vesting contracts are only available from their protocol version on.
+        if block_state.version < Policy::SCHEDULED_VESTING_VERSION {
+            return Err(AccountError::InvalidForRecipient);
+        }
+
+        let data = CreationTransactionData::parse(transaction)?;
+        let total_amount = data.total_amount()?;
+
+        tx_logger.push_log(Log::ScheduledVestingCreate {
+            contract_address: transaction.recipient.clone(),
+            owner: data.owner.clone(),
+            schedules: data.schedules.clone(),
+            total_amount,
+        });
+
+        Ok(Account::ScheduledVesting(ScheduledVestingContract {
+            balance: initial_balance + transaction.value,
+            owner: data.owner,
+            schedules: data.schedules,
+            total_amount,
+        }))
+    }
+
+    fn revert_new_contract(
+        &mut self,
+        transaction: &Transaction,
+        _block_state: &BlockState,
+        _data_store: DataStoreWrite,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<(), AccountError> {
+        self.balance -= transaction.value;
+
+        tx_logger.push_log(Log::ScheduledVestingCreate {
+            contract_address: transaction.recipient.clone(),
+            owner: self.owner.clone(),
+            schedules: self.schedules.clone(),
+            total_amount: self.total_amount,
+        });
+
+        Ok(())
+    }
+
+    fn commit_incoming_transaction(
+        &mut self,
+        _transaction: &Transaction,
+        _block_state: &BlockState,
+        _data_store: DataStoreWrite,
+        _tx_logger: &mut TransactionLog,
+    ) -> Result<Option<AccountReceipt>, AccountError> {
+        Err(AccountError::InvalidForRecipient)
+    }
+
+    fn revert_incoming_transaction(
+        &mut self,
+        _transaction: &Transaction,
+        _block_state: &BlockState,
+        _receipt: Option<AccountReceipt>,
+        _data_store: DataStoreWrite,
+        _tx_logger: &mut TransactionLog,
+    ) -> Result<(), AccountError> {
+        Err(AccountError::InvalidForRecipient)
+    }
+
+    fn commit_outgoing_transaction(
+        &mut self,
+        transaction: &Transaction,
+        block_state: &BlockState,
+        _data_store: DataStoreWrite,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<Option<AccountReceipt>, AccountError> {
+        let new_balance = self.balance.safe_sub(transaction.total_value())?;
+        self.can_change_balance(transaction, new_balance, block_state)?;
+        self.balance = new_balance;
+
+        tx_logger.push_log(Log::pay_fee_log(transaction));
+        tx_logger.push_log(Log::transfer_log(transaction));
+
+        Ok(None)
+    }
+
+    fn revert_outgoing_transaction(
+        &mut self,
+        transaction: &Transaction,
+        _block_state: &BlockState,
+        _receipt: Option<AccountReceipt>,
+        _data_store: DataStoreWrite,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<(), AccountError> {
+        self.balance += transaction.total_value();
+
+        tx_logger.push_log(Log::transfer_log(transaction));
+        tx_logger.push_log(Log::pay_fee_log(transaction));
+
+        Ok(())
+    }
+
+    fn commit_failed_transaction(
+        &mut self,
+        transaction: &Transaction,
+        block_state: &BlockState,
+        _data_store: DataStoreWrite,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<Option<AccountReceipt>, AccountError> {
+        let new_balance = self.balance.safe_sub(transaction.fee)?;
+        self.can_change_balance(transaction, new_balance, block_state)?;
+        self.balance = new_balance;
+
+        tx_logger.push_log(Log::pay_fee_log(transaction));
+
+        Ok(None)
+    }
+
+    fn revert_failed_transaction(
+        &mut self,
+        transaction: &Transaction,
+        _block_state: &BlockState,
+        _receipt: Option<AccountReceipt>,
+        _data_store: DataStoreWrite,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<(), AccountError> {
+        self.balance += transaction.fee;
+
+        tx_logger.push_log(Log::pay_fee_log(transaction));
+
+        Ok(())
+    }
+
+    fn reserve_balance(
+        &self,
+        transaction: &Transaction,
+        reserved_balance: &mut ReservedBalance,
+        block_state: &BlockState,
+        _data_store: DataStoreRead,
+    ) -> Result<(), AccountError> {
+        let needed = reserved_balance
+            .balance()
+            .checked_add(transaction.total_value())
+            .ok_or(AccountError::InvalidCoinValue)?;
+        let new_balance = self.balance.safe_sub(needed)?;
+        self.can_change_balance(transaction, new_balance, block_state)?;
+
+        reserved_balance.reserve(self.balance, transaction.total_value())
+    }
+
+    fn release_balance(
+        &self,
+        transaction: &Transaction,
+        reserved_balance: &mut ReservedBalance,
+        _data_store: DataStoreRead,
+    ) -> Result<(), AccountError> {
+        reserved_balance.release(transaction.total_value());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "interaction-traits")]
+impl AccountInherentInteraction for ScheduledVestingContract {
+    fn commit_inherent(
+        &mut self,
+        _inherent: &Inherent,
+        _block_state: &BlockState,
+        _data_store: DataStoreWrite,
+        _inherent_logger: &mut InherentLogger,
+    ) -> Result<Option<AccountReceipt>, AccountError> {
+        Err(AccountError::InvalidForTarget)
+    }
+
+    fn revert_inherent(
+        &mut self,
+        _inherent: &Inherent,
+        _block_state: &BlockState,
+        _receipt: Option<AccountReceipt>,
+        _data_store: DataStoreWrite,
+        _inherent_logger: &mut InherentLogger,
+    ) -> Result<(), AccountError> {
+        Err(AccountError::InvalidForTarget)
+    }
+}
+
+#[cfg(feature = "interaction-traits")]
+impl AccountPruningInteraction for ScheduledVestingContract {
+    fn can_be_pruned(&self) -> bool {
+        self.balance.is_zero()
+    }
+
+    fn prune(self, _data_store: DataStoreRead) -> Option<AccountReceipt> {
+        Some(PrunedScheduledVestingContract::from(self).into())
+    }
+
+    fn restore(
+        _ty: AccountType,
+        pruned_account: Option<&AccountReceipt>,
+        _data_store: DataStoreWrite,
+    ) -> Result<Account, AccountError> {
+        let receipt = pruned_account.ok_or(AccountError::InvalidReceipt)?;
+        let pruned_account = PrunedScheduledVestingContract::try_from(receipt)?;
+        Ok(Account::ScheduledVesting(ScheduledVestingContract::from(
+            pruned_account,
+        )))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+struct PrunedScheduledVestingContract {
+    pub owner: Address,
+    pub schedules: Vec<VestingSchedule>,
+    pub total_amount: Coin,
+}
+
+impl From<ScheduledVestingContract> for PrunedScheduledVestingContract {
+    fn from(contract: ScheduledVestingContract) -> Self {
+        PrunedScheduledVestingContract {
+            owner: contract.owner,
+            schedules: contract.schedules,
+            total_amount: contract.total_amount,
+        }
+    }
+}
+
+impl From<PrunedScheduledVestingContract> for ScheduledVestingContract {
+    fn from(receipt: PrunedScheduledVestingContract) -> Self {
+        ScheduledVestingContract {
+            balance: Coin::ZERO,
+            owner: receipt.owner,
+            schedules: receipt.schedules,
+            total_amount: receipt.total_amount,
+        }
+    }
+}
+
+convert_receipt!(PrunedScheduledVestingContract);
@@ -1,6 +1,7 @@
 use nimiq_primitives::{
     account::{AccountError, AccountType},
     coin::Coin,
+    policy::Policy,
 };
 use nimiq_transaction::{inherent::Inherent, Transaction};
 
@@ -10,21 +11,35 @@ use crate::{
     Account, AccountReceipt, InherentLogger, TransactionLog,
 };
 
-#[derive(Default, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct BlockState {
     pub number: u32,
     pub time: u64,
+    /// The protocol version of the block.
+    pub version: u16,
 }
 
 impl BlockState {
+    /// Creates the state of a block of the current protocol version.
     pub fn new(block_number: u32, block_time: u64) -> Self {
+        Self::with_version(block_number, block_time, Policy::VERSION)
+    }
+
+    pub fn with_version(block_number: u32, block_time: u64, version: u16) -> Self {
         Self {
             number: block_number,
             time: block_time,
+            version,
         }
     }
 }
 
+impl Default for BlockState {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
 pub trait AccountTransactionInteraction: Sized {
     fn create_new_contract(
         transaction: &Transaction,
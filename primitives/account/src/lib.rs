@@ -10,8 +10,12 @@ pub use crate::data_store::{DataStore, DataStoreRead, DataStoreWrite};
 pub use crate::interaction_traits::*;
 pub use crate::{
     account::{
-        basic_account::BasicAccount, htlc_contract::HashedTimeLockedContract, staking_contract::*,
-        vesting_contract::VestingContract, Account,
+        basic_account::BasicAccount,
+        htlc_contract::HashedTimeLockedContract,
+        scheduled_vesting_contract::{ScheduledVestingContract, VestingSchedule, VestingTranche},
+        staking_contract::*,
+        vesting_contract::VestingContract,
+        Account,
     },
     data_store_ops::DataStoreReadOps,
     logs::*,
@@ -6,7 +6,10 @@ use nimiq_primitives::{
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{
-    account::htlc_contract::{AnyHash, PreImage},
+    account::{
+        htlc_contract::{AnyHash, PreImage},
+        scheduled_vesting_contract::VestingSchedule,
+    },
     Transaction,
 };
 
@@ -63,6 +66,14 @@ pub enum Log {
         total_amount: Coin,
     },
 
+    #[serde(rename_all = "camelCase")]
+    ScheduledVestingCreate {
+        contract_address: Address,
+        owner: Address,
+        schedules: Vec<VestingSchedule>,
+        total_amount: Coin,
+    },
+
     #[serde(rename_all = "camelCase")]
     CreateValidator {
         validator_address: Address,
@@ -239,6 +250,11 @@ impl Log {
                 owner,
                 ..
             } => contract_address == address || owner == address,
+            Log::ScheduledVestingCreate {
+                contract_address,
+                owner,
+                ..
+            } => contract_address == address || owner == address,
             Log::CreateValidator {
                 validator_address,
                 reward_address,
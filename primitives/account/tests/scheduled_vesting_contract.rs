@@ -0,0 +1,306 @@
+use std::convert::TryInto;
+
+use nimiq_account::{
+    Account, BasicAccount, BlockState, Log, ScheduledVestingContract, TransactionLog,
+    VestingSchedule, VestingTranche,
+};
+use nimiq_keys::{Address, KeyPair};
+use nimiq_primitives::{
+    account::{AccountError, AccountType},
+    coin::Coin,
+    networks::NetworkId,
+    policy::Policy,
+    transaction::TransactionError,
+};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_test_log::test;
+use nimiq_test_utils::{accounts_revert::TestCommitRevert, test_rng::test_rng};
+use nimiq_transaction::{
+    account::scheduled_vesting_contract::CreationTransactionData, SignatureProof, Transaction,
+};
+use nimiq_utils::key_rng::SecureGenerate;
+
+fn schedules() -> Vec<VestingSchedule> {
+    vec![
+        // 100 at time 100, then 20 every 10 until time 150, then 50 every 100 until time 550.
+        VestingSchedule {
+            cliff_time: 100,
+            cliff_amount: 100.try_into().unwrap(),
+            tranches: vec![
+                VestingTranche {
+                    time_step: 10,
+                    step_amount: 20.try_into().unwrap(),
+                    amount: 100.try_into().unwrap(),
+                },
+                VestingTranche {
+                    time_step: 100,
+                    step_amount: 50.try_into().unwrap(),
+                    amount: 200.try_into().unwrap(),
+                },
+            ],
+        },
+        // 600 at time 500.
+        VestingSchedule {
+            cliff_time: 500,
+            cliff_amount: 600.try_into().unwrap(),
+            tranches: vec![],
+        },
+    ]
+}
+
+fn init_tree() -> (TestCommitRevert, ScheduledVestingContract, KeyPair, KeyPair) {
+    let mut rng = test_rng(true);
+    let key_1 = KeyPair::generate(&mut rng);
+    let key_2 = KeyPair::generate(&mut rng);
+    let vesting_contract = ScheduledVestingContract {
+        balance: 1000.try_into().unwrap(),
+        owner: Address::from(&key_1.public),
+        schedules: schedules(),
+        total_amount: 1000.try_into().unwrap(),
+    };
+
+    let accounts = TestCommitRevert::with_initial_state(&[
+        (
+            Address::from(&key_1.public),
+            Account::Basic(BasicAccount {
+                balance: Coin::from_u64_unchecked(1000),
+            }),
+        ),
+        (
+            Address([1u8; 20]),
+            Account::ScheduledVesting(vesting_contract.clone()),
+        ),
+    ]);
+
+    (accounts, vesting_contract, key_1, key_2)
+}
+
+fn make_signed_transaction(key_1: &KeyPair, key_2: &KeyPair, value: u64) -> Transaction {
+    let mut tx = Transaction::new_basic(
+        Address::from(key_1),
+        Address::from(key_2),
+        Coin::from_u64_unchecked(value),
+        Coin::ZERO,
+        1,
+        NetworkId::UnitAlbatross,
+    );
+    tx.sender_type = AccountType::ScheduledVesting;
+    let signature = key_1.sign(&tx.serialize_content());
+    let signature_proof = SignatureProof::from_ed25519(key_1.public, signature);
+    tx.proof = signature_proof.serialize_to_vec();
+
+    tx
+}
+
+#[test]
+fn it_can_serialize_and_deserialize_a_scheduled_vesting_contract() {
+    let (_accounts, vesting_contract, _key_1, _key_2) = init_tree();
+    let bytes = vesting_contract.serialize_to_vec();
+    assert_eq!(bytes.len(), vesting_contract.serialized_size());
+
+    let contract = ScheduledVestingContract::deserialize_from_vec(&bytes).unwrap();
+    assert_eq!(contract, vesting_contract);
+}
+
+#[test]
+fn it_computes_the_locked_amount_of_all_schedules() {
+    let (_accounts, vesting_contract, _key_1, _key_2) = init_tree();
+
+    // Nothing is released before the cliff.
+    assert_eq!(vesting_contract.min_cap(0), 1000.try_into().unwrap());
+    assert_eq!(vesting_contract.min_cap(99), 1000.try_into().unwrap());
+    // The cliff amount is released at once.
+    assert_eq!(vesting_contract.min_cap(100), 900.try_into().unwrap());
+    // First tranche.
+    assert_eq!(vesting_contract.min_cap(109), 900.try_into().unwrap());
+    assert_eq!(vesting_contract.min_cap(125), 860.try_into().unwrap());
+    assert_eq!(vesting_contract.min_cap(150), 800.try_into().unwrap());
+    // Second tranche, starting when the first one is released completely.
+    assert_eq!(vesting_contract.min_cap(249), 800.try_into().unwrap());
+    assert_eq!(vesting_contract.min_cap(250), 750.try_into().unwrap());
+    // Second schedule.
+    assert_eq!(vesting_contract.min_cap(500), 50.try_into().unwrap());
+    assert_eq!(vesting_contract.min_cap(550), Coin::ZERO);
+    assert_eq!(vesting_contract.min_cap(u64::MAX), Coin::ZERO);
+}
+
+#[test]
+fn it_can_create_contract_from_transaction() {
+    let (accounts, _vesting_contract, key_1, _key_2) = init_tree();
+
+    let owner = Address::from(&key_1);
+    let data = CreationTransactionData {
+        owner: owner.clone(),
+        schedules: schedules(),
+    };
+    let mut tx = Transaction::new_contract_creation(
+        owner.clone(),
+        AccountType::Basic,
+        vec![],
+        AccountType::ScheduledVesting,
+        data.to_tx_data(),
+        1000.try_into().unwrap(),
+        0.try_into().unwrap(),
+        0,
+        NetworkId::UnitAlbatross,
+    );
+
+    // Scheduled vesting contracts are not available in older protocol versions.
+    let block_state = BlockState::with_version(1, 1, Policy::SCHEDULED_VESTING_VERSION - 1);
+    let mut tx_logger = TransactionLog::empty();
+    let result = accounts.test_create_new_contract::<ScheduledVestingContract>(
+        &tx,
+        Coin::ZERO,
+        &block_state,
+        &mut tx_logger,
+        true,
+    );
+    assert_eq!(result, Err(AccountError::InvalidForRecipient));
+    assert_eq!(tx_logger.logs.len(), 0);
+
+    let block_state = BlockState::with_version(1, 1, Policy::SCHEDULED_VESTING_VERSION);
+    let mut tx_logger = TransactionLog::empty();
+    let contract = accounts
+        .test_create_new_contract::<ScheduledVestingContract>(
+            &tx,
+            Coin::ZERO,
+            &block_state,
+            &mut tx_logger,
+            true,
+        )
+        .expect("Failed to create contract");
+
+    assert_eq!(
+        tx_logger.logs,
+        vec![Log::ScheduledVestingCreate {
+            contract_address: tx.contract_creation_address(),
+            owner: owner.clone(),
+            schedules: schedules(),
+            total_amount: 1000.try_into().unwrap(),
+        }]
+    );
+
+    let contract = match contract {
+        Account::ScheduledVesting(contract) => contract,
+        _ => panic!("Wrong account type created"),
+    };
+
+    assert_eq!(contract.balance, 1000.try_into().unwrap());
+    assert_eq!(contract.owner, owner);
+    assert_eq!(contract.schedules, schedules());
+    assert_eq!(contract.total_amount, 1000.try_into().unwrap());
+
+    // Invalid data: a tranche without a time step.
+    let mut data = data;
+    data.schedules[0].tranches[0].time_step = 0;
+    tx.recipient_data = data.to_tx_data();
+    tx.recipient = tx.contract_creation_address();
+
+    let mut tx_logger = TransactionLog::empty();
+    let result = accounts.test_create_new_contract::<ScheduledVestingContract>(
+        &tx,
+        Coin::ZERO,
+        &block_state,
+        &mut tx_logger,
+        true,
+    );
+
+    assert_eq!(
+        result,
+        Err(AccountError::InvalidTransaction(
+            TransactionError::InvalidData
+        ))
+    );
+}
+
+#[test]
+fn it_can_apply_and_revert_valid_transaction() {
+    let (accounts, mut vesting_contract, key_1, key_2) = init_tree();
+
+    // 140 coins have been released at time 125.
+    let block_state = BlockState::new(2, 125);
+
+    let tx = make_signed_transaction(&key_1, &key_2, 141);
+    let mut tx_logger = TransactionLog::empty();
+    let result = accounts.test_commit_outgoing_transaction(
+        &mut vesting_contract,
+        &tx,
+        &block_state,
+        &mut tx_logger,
+        true,
+    );
+    assert_eq!(
+        result,
+        Err(AccountError::InsufficientFunds {
+            needed: 141.try_into().unwrap(),
+            balance: 140.try_into().unwrap()
+        })
+    );
+    assert_eq!(tx_logger.logs.len(), 0);
+
+    let tx = make_signed_transaction(&key_1, &key_2, 140);
+    let mut tx_logger = TransactionLog::empty();
+    let _ = accounts
+        .test_commit_outgoing_transaction(
+            &mut vesting_contract,
+            &tx,
+            &block_state,
+            &mut tx_logger,
+            true,
+        )
+        .expect("Failed to commit transaction");
+
+    assert_eq!(vesting_contract.balance, 860.try_into().unwrap());
+    assert_eq!(
+        tx_logger.logs,
+        vec![
+            Log::PayFee {
+                from: tx.sender.clone(),
+                fee: tx.fee
+            },
+            Log::Transfer {
+                from: tx.sender.clone(),
+                to: tx.recipient.clone(),
+                amount: tx.value,
+                data: None
+            }
+        ]
+    );
+
+    // Everything has been released at time 550.
+    let block_state = BlockState::new(3, 550);
+
+    let tx = make_signed_transaction(&key_1, &key_2, 860);
+    let mut tx_logger = TransactionLog::empty();
+    let _ = accounts
+        .test_commit_outgoing_transaction(
+            &mut vesting_contract,
+            &tx,
+            &block_state,
+            &mut tx_logger,
+            true,
+        )
+        .expect("Failed to commit transaction");
+
+    assert_eq!(vesting_contract.balance, Coin::ZERO);
+}
+
+#[test]
+fn it_refuses_transactions_not_signed_by_the_owner() {
+    let (accounts, mut vesting_contract, _key_1, key_2) = init_tree();
+
+    let block_state = BlockState::new(1, 550);
+
+    let tx = make_signed_transaction(&key_2, &key_2, 100);
+    let mut tx_logger = TransactionLog::empty();
+    let result = accounts.test_commit_outgoing_transaction(
+        &mut vesting_contract,
+        &tx,
+        &block_state,
+        &mut tx_logger,
+        true,
+    );
+
+    assert_eq!(result, Err(AccountError::InvalidSignature));
+    assert_eq!(tx_logger.logs.len(), 0);
+}
@@ -1,6 +1,6 @@
 use std::{fmt, io};
 
-use nimiq_bls::cache::PublicKeyCache;
+use nimiq_bls::{batch::BatchVerifier, cache::PublicKeyCache, pool::VerificationPool};
 use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
 use nimiq_keys::Ed25519PublicKey;
@@ -407,7 +407,9 @@ impl Block {
 
             // Perform block type specific body verification.
             match body {
-                BlockBody::Micro(body) => body.verify(self.is_skip(), self.block_number())?,
+                BlockBody::Micro(body) => {
+                    body.verify(self.is_skip(), self.block_number(), self.version())?
+                }
                 BlockBody::Macro(body) => body.verify(self.is_election())?,
             };
         }
@@ -611,11 +613,32 @@ impl Block {
         }
     }
 
-    /// Verifies that the block is valid for the given validators.
+    /// Verifies that the block is valid for the given validators. The signatures are verified in
+    /// the global [`VerificationPool`].
     pub fn verify_validators(&self, validators: &Validators) -> Result<(), BlockError> {
+        let mut batch = BatchVerifier::new();
+        self.verify_validators_batched(validators, &mut batch)?;
+        if !VerificationPool::global().verify(batch) {
+            // A failed batch doesn't tell which signature is invalid, so verify them one by one
+            // to return the right error.
+            match self {
+                Block::Micro(block) => block.verify_validators(validators)?,
+                Block::Macro(block) => block.verify_validators(validators)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies that the block is valid for the given validators, but only adds the signatures
+    /// to `batch` instead of verifying them.
+    pub fn verify_validators_batched(
+        &self,
+        validators: &Validators,
+        batch: &mut BatchVerifier,
+    ) -> Result<(), BlockError> {
         match self {
-            Block::Micro(block) => block.verify_validators(validators),
-            Block::Macro(block) => block.verify_validators(validators),
+            Block::Micro(block) => block.verify_validators_batched(validators, batch),
+            Block::Macro(block) => block.verify_validators_batched(validators, batch),
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, hash::Hasher, io, mem, ops::Range};
 
-use nimiq_bls::{AggregatePublicKey, AggregateSignature};
+use nimiq_bls::{batch::BatchVerifier, AggregatePublicKey, AggregateSignature};
 use nimiq_collections::BitSet;
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash as _, HashOutput, SerializeContent};
 use nimiq_keys::{
@@ -40,6 +40,13 @@ fn get_validator<'a>(
         .ok_or(EquivocationProofError::InvalidValidatorAddress)
 }
 
+fn verify_batch(batch: BatchVerifier) -> Result<(), EquivocationProofError> {
+    if !batch.verify() {
+        return Err(EquivocationProofError::InvalidJustification);
+    }
+    Ok(())
+}
+
 impl EquivocationProof {
     /// Locator of this proof.
     ///
@@ -92,13 +99,27 @@ impl EquivocationProof {
         &self,
         network: NetworkId,
         validators: &Validators,
+    ) -> Result<(), EquivocationProofError> {
+        let mut batch = BatchVerifier::new();
+        self.verify_batched(network, validators, &mut batch)?;
+        verify_batch(batch)
+    }
+
+    /// Check if an equivocation proof contains a valid offense like [`verify`](Self::verify),
+    /// but only add its BLS signatures to `batch` instead of verifying them.
+    pub fn verify_batched(
+        &self,
+        network: NetworkId,
+        validators: &Validators,
+        batch: &mut BatchVerifier,
     ) -> Result<(), EquivocationProofError> {
         if network != self.network() {
             return Err(EquivocationProofError::NetworkMismatch);
         }
-        self.verify_excluding_address_and_network(
+        self.verify_excluding_address_and_network_batched(
             validators,
             get_validator(validators, self.validator_address())?,
+            batch,
         )
     }
 
@@ -108,6 +129,17 @@ impl EquivocationProof {
         &self,
         validators: &Validators,
         validator: &Validator,
+    ) -> Result<(), EquivocationProofError> {
+        let mut batch = BatchVerifier::new();
+        self.verify_excluding_address_and_network_batched(validators, validator, &mut batch)?;
+        verify_batch(batch)
+    }
+
+    fn verify_excluding_address_and_network_batched(
+        &self,
+        validators: &Validators,
+        validator: &Validator,
+        batch: &mut BatchVerifier,
     ) -> Result<(), EquivocationProofError> {
         use self::EquivocationProof::*;
         match self {
@@ -115,9 +147,11 @@ impl EquivocationProof {
             DoubleProposal(proof) => {
                 proof.verify_excluding_address_and_network(&validator.signing_key)
             }
-            DoubleVote(proof) => {
-                proof.verify_excluding_address_and_network(validators, validator.slots.clone())
-            }
+            DoubleVote(proof) => proof.verify_excluding_address_and_network_batched(
+                validators,
+                validator.slots.clone(),
+                batch,
+            ),
         }
     }
 
@@ -526,6 +560,20 @@ impl DoubleVoteProof {
         &self,
         validators: &Validators,
         validator_slots: Range<u16>,
+    ) -> Result<(), EquivocationProofError> {
+        let mut batch = BatchVerifier::new();
+        self.verify_excluding_address_and_network_batched(validators, validator_slots, &mut batch)?;
+        verify_batch(batch)
+    }
+
+    /// Verify the validity of a double vote proof like
+    /// [`verify_excluding_address_and_network`](Self::verify_excluding_address_and_network), but
+    /// only add the signatures to `batch` instead of verifying them.
+    pub fn verify_excluding_address_and_network_batched(
+        &self,
+        validators: &Validators,
+        validator_slots: Range<u16>,
+        batch: &mut BatchVerifier,
     ) -> Result<(), EquivocationProofError> {
         // Check that the proposals are not equal and in the right order:
         match self.proposal_hash1.cmp(&self.proposal_hash2) {
@@ -543,36 +591,32 @@ impl DoubleVoteProof {
             return Err(EquivocationProofError::NoOverlap);
         }
 
-        let verify =
-            |proposal_hash, signers: &BitSet, signature| -> Result<(), EquivocationProofError> {
-                // Calculate the message that was actually signed by the validators.
-                let message = TendermintVote {
-                    proposal_hash,
-                    id: self.tendermint_id.clone(),
-                };
-                // Verify the signatures.
-                let mut agg_pk = AggregatePublicKey::new();
-                for (i, pk) in validators.voting_keys().iter().enumerate() {
-                    if signers.contains(i) {
-                        agg_pk.aggregate(pk);
-                    }
-                }
-                if !agg_pk.verify(&message, signature) {
-                    return Err(EquivocationProofError::InvalidJustification);
-                }
-                Ok(())
+        let mut verify = |proposal_hash, signers: &BitSet, signature| {
+            // Calculate the message that was actually signed by the validators.
+            let message = TendermintVote {
+                proposal_hash,
+                id: self.tendermint_id.clone(),
             };
+            // Verify the signatures.
+            let mut agg_pk = AggregatePublicKey::new();
+            for (i, pk) in validators.voting_keys().iter().enumerate() {
+                if signers.contains(i) {
+                    agg_pk.aggregate(pk);
+                }
+            }
+            batch.add_aggregate(&agg_pk, &message, signature);
+        };
 
         verify(
             self.proposal_hash1.clone(),
             &self.signers1,
             &self.signature1,
-        )?;
+        );
         verify(
             self.proposal_hash2.clone(),
             &self.signers2,
             &self.signature2,
-        )?;
+        );
         Ok(())
     }
 }
@@ -8,6 +8,7 @@ pub use macro_block::*;
 pub use micro_block::*;
 pub use multisig::*;
 use nimiq_primitives::transaction::TransactionError;
+pub use receipt_proof::*;
 pub use skip_block::*;
 pub use tendermint::*;
 use thiserror::Error;
@@ -18,6 +19,7 @@ mod equivocation_proof;
 mod macro_block;
 mod micro_block;
 mod multisig;
+mod receipt_proof;
 mod skip_block;
 mod tendermint;
 
@@ -1,7 +1,7 @@
 use std::{fmt, io};
 
 use ark_ec::Group;
-use nimiq_bls::{G2Projective, PublicKey as BlsPublicKey};
+use nimiq_bls::{batch::BatchVerifier, G2Projective, PublicKey as BlsPublicKey};
 use nimiq_collections::bitset::BitSet;
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash, HashOutput, Hasher, SerializeContent};
 use nimiq_keys::{Address, Ed25519PublicKey as SchnorrPublicKey};
@@ -129,6 +129,25 @@ impl MacroBlock {
         Ok(())
     }
 
+    /// Verifies the block like `verify_validators`, but only adds the signature of the Tendermint
+    /// proof to `batch` instead of verifying it.
+    pub(crate) fn verify_validators_batched(
+        &self,
+        validators: &Validators,
+        batch: &mut BatchVerifier,
+    ) -> Result<(), BlockError> {
+        if !TendermintProof::verify_batched(self, validators, batch) {
+            warn!(
+                %self,
+                reason = "Macro block with bad justification",
+                "Rejecting block"
+            );
+            return Err(BlockError::InvalidJustification);
+        }
+
+        Ok(())
+    }
+
     /// Creates a default block that has body and justification.
     pub fn non_empty_default() -> Self {
         let mut validators = ValidatorsBuilder::new();
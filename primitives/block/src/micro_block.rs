@@ -1,5 +1,6 @@
 use std::{cmp::Ordering, collections::HashSet, fmt, fmt::Debug, io};
 
+use nimiq_bls::batch::BatchVerifier;
 use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
 use nimiq_hash_derive::SerializeContent;
@@ -118,6 +119,37 @@ impl MicroBlock {
 
         Ok(())
     }
+
+    /// Verifies the block like `verify_validators`, but only adds the signature of the skip
+    /// block proof to `batch` instead of verifying it.
+    pub(crate) fn verify_validators_batched(
+        &self,
+        validators: &Validators,
+        batch: &mut BatchVerifier,
+    ) -> Result<(), BlockError> {
+        let justification = self
+            .justification
+            .as_ref()
+            .ok_or(BlockError::MissingJustification)?;
+
+        if let MicroJustification::Skip(proof) = justification {
+            let skip_block = SkipBlockInfo {
+                block_number: self.header.block_number,
+                vrf_entropy: self.header.seed.entropy(),
+            };
+
+            if !proof.verify_batched(&skip_block, validators, batch) {
+                debug!(
+                    block = %self,
+                    reason = "Bad skip block proof",
+                    "Rejecting block"
+                );
+                return Err(BlockError::InvalidSkipBlockProof);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl IntoDatabaseValue for MicroBlock {
@@ -248,7 +280,12 @@ impl MicroBody {
     }
 
     /// Verifies the micro block: size, proofs, transactions, etc.
-    pub(crate) fn verify(&self, is_skip: bool, block_number: u32) -> Result<(), BlockError> {
+    pub(crate) fn verify(
+        &self,
+        is_skip: bool,
+        block_number: u32,
+        version: u16,
+    ) -> Result<(), BlockError> {
         // Check that the maximum body size is not exceeded.
         let body_size = self.serialized_size();
         if body_size > Policy::MAX_SIZE_MICRO_BODY {
@@ -295,7 +332,8 @@ impl MicroBody {
             previous_proof = Some(proof);
         }
 
-        // Ensure transactions are unique and within their validity window.
+        // Ensure transactions are unique, within their validity window and supported by the
+        // block's protocol version.
         let mut uniq = HashSet::new();
         for tx in &self.get_raw_transactions() {
             // Check validity window.
@@ -303,6 +341,9 @@ impl MicroBody {
                 return Err(BlockError::ExpiredTransaction);
             }
 
+            // Check protocol version.
+            tx.verify_version(version)?;
+
             // Check uniqueness.
             if !uniq.insert(tx.hash::<Blake2bHash>()) {
                 return Err(BlockError::DuplicateTransaction);
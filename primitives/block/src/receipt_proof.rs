@@ -0,0 +1,127 @@
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_transaction::{
+    historic_transaction::HistoricTransaction, history_proof::HistoryTreeProof,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Block, BlockError, MacroBlock};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ReceiptProofError {
+    #[error("The proof doesn't start at the trusted election block")]
+    UntrustedBlock,
+    #[error("Block {0} is not an election block")]
+    NotAnElectionBlock(u32),
+    #[error("Election block {0} is missing its validators")]
+    MissingValidators(u32),
+    #[error("Block {0} is invalid: {1}")]
+    InvalidBlock(u32, BlockError),
+    #[error("The history proof is invalid")]
+    InvalidHistoryProof,
+    #[error("Transaction from block {0} is not committed to by block {1}")]
+    TransactionOutOfRange(u32, u32),
+}
+
+/// A self-contained proof that transactions were included in the chain. It can be verified offline
+/// by anyone who trusts a specific election block, without access to a node.
+///
+/// The proof consists of a chain of election blocks, starting at the trusted election block, in
+/// which every block is justified by the validators elected in the previous one. The last block of
+/// the chain justifies the macro block whose history root commits to the transactions, which is
+/// shown by an inclusion proof in the history tree.
+#[derive(Serialize, Deserialize)]
+pub struct TransactionReceiptProof {
+    /// The trusted election block. It contains the validators that justify the first block of
+    /// the chain.
+    pub trusted_block: MacroBlock,
+    /// The election blocks between the trusted election block and the proving block.
+    pub election_blocks: Vec<MacroBlock>,
+    /// The macro block whose history root commits to the transactions.
+    pub block: MacroBlock,
+    /// The inclusion proof of the transactions in the history tree of the proving block.
+    pub history_proof: HistoryTreeProof,
+}
+
+impl TransactionReceiptProof {
+    /// Verifies the proof against the election block with the given hash and returns the proven
+    /// transactions.
+    pub fn verify(
+        &self,
+        trusted_hash: &Blake2bHash,
+        network_id: NetworkId,
+    ) -> Result<&[HistoricTransaction], ReceiptProofError> {
+        if self.trusted_block.hash() != *trusted_hash {
+            return Err(ReceiptProofError::UntrustedBlock);
+        }
+        if !self.trusted_block.is_election() {
+            return Err(ReceiptProofError::NotAnElectionBlock(
+                self.trusted_block.block_number(),
+            ));
+        }
+
+        // Verify the chain of election blocks.
+        let mut predecessor = &self.trusted_block;
+        for block in &self.election_blocks {
+            if !block.is_election() {
+                return Err(ReceiptProofError::NotAnElectionBlock(block.block_number()));
+            }
+            Self::verify_successor(block, predecessor, network_id)?;
+            predecessor = block;
+        }
+
+        // The proving block might be the trusted election block itself.
+        if self.block.hash() != predecessor.hash() {
+            Self::verify_successor(&self.block, predecessor, network_id)?;
+        }
+
+        // Verify that the transactions are part of the history tree of the proving block.
+        if self.history_proof.history.len() != self.history_proof.positions.len()
+            || self
+                .history_proof
+                .verify(self.block.header.history_root.clone())
+                != Some(true)
+        {
+            return Err(ReceiptProofError::InvalidHistoryProof);
+        }
+
+        // The history tree only covers the epoch of the proving block up to that block.
+        let block_number = self.block.block_number();
+        for transaction in &self.history_proof.history {
+            if transaction.block_number > block_number
+                || Policy::epoch_at(transaction.block_number) != Policy::epoch_at(block_number)
+            {
+                return Err(ReceiptProofError::TransactionOutOfRange(
+                    transaction.block_number,
+                    block_number,
+                ));
+            }
+        }
+
+        Ok(&self.history_proof.history)
+    }
+
+    /// Verifies that `block` is a valid macro successor of `predecessor`, justified by the
+    /// validators elected in the election block preceding it.
+    fn verify_successor(
+        block: &MacroBlock,
+        predecessor: &MacroBlock,
+        network_id: NetworkId,
+    ) -> Result<(), ReceiptProofError> {
+        let validators =
+            predecessor
+                .get_validators()
+                .ok_or(ReceiptProofError::MissingValidators(
+                    predecessor.block_number(),
+                ))?;
+
+        let invalid = |e| ReceiptProofError::InvalidBlock(block.block_number(), e);
+        let wrapped = Block::Macro(block.clone());
+        wrapped.verify(network_id).map_err(invalid)?;
+        wrapped
+            .verify_macro_successor(predecessor)
+            .map_err(invalid)?;
+        block.verify_validators(&validators).map_err(invalid)
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use nimiq_bls::AggregatePublicKey;
+use nimiq_bls::{batch::BatchVerifier, AggregatePublicKey};
 use nimiq_hash_derive::SerializeContent;
 use nimiq_primitives::{
     policy::Policy, slots_allocation::Validators, Message, SignedMessage, PREFIX_SKIP_BLOCK_INFO,
@@ -54,6 +54,18 @@ impl SkipBlockProof {
     /// Verifies the proof. This only checks that the proof is valid for this skip block, not that
     /// the skip block itself is valid.
     pub fn verify(&self, skip_block: &SkipBlockInfo, validators: &Validators) -> bool {
+        let mut batch = BatchVerifier::new();
+        self.verify_batched(skip_block, validators, &mut batch) && batch.verify()
+    }
+
+    /// Verifies the proof like [`verify`](Self::verify), but only adds the signature to `batch`
+    /// instead of verifying it. Returns false if the proof is invalid regardless of the signature.
+    pub fn verify_batched(
+        &self,
+        skip_block: &SkipBlockInfo,
+        validators: &Validators,
+        batch: &mut BatchVerifier,
+    ) -> bool {
         // Check if there are enough votes.
         if self.sig.signers.len() < Policy::TWO_F_PLUS_ONE as usize {
             error!(
@@ -79,6 +91,7 @@ impl SkipBlockProof {
                 });
 
         // Verify the aggregated signature against our aggregated public key.
-        agg_pk.verify_hash(skip_block.hash_with_prefix(), &self.sig.signature)
+        batch.add_aggregate_hash(&agg_pk, skip_block.hash_with_prefix(), &self.sig.signature);
+        true
     }
 }
@@ -1,5 +1,5 @@
 use log::error;
-use nimiq_bls::AggregatePublicKey;
+use nimiq_bls::{batch::BatchVerifier, AggregatePublicKey};
 use nimiq_primitives::{
     policy::Policy, slots_allocation::Validators, TendermintIdentifier, TendermintStep,
     TendermintVote,
@@ -26,6 +26,17 @@ impl TendermintProof {
     /// Verifies the proof. This only checks that the proof is valid for this block, not that the
     /// block itself is valid.
     pub fn verify(block: &MacroBlock, current_validators: &Validators) -> bool {
+        let mut batch = BatchVerifier::new();
+        Self::verify_batched(block, current_validators, &mut batch) && batch.verify()
+    }
+
+    /// Verifies the proof like [`verify`](Self::verify), but only adds the signature to `batch`
+    /// instead of verifying it. Returns false if the proof is invalid regardless of the signature.
+    pub fn verify_batched(
+        block: &MacroBlock,
+        current_validators: &Validators,
+        batch: &mut BatchVerifier,
+    ) -> bool {
         // If there's no justification then the proof is false evidently.
         let justification = match &block.justification {
             None => {
@@ -66,6 +77,7 @@ impl TendermintProof {
         }
 
         // Verify the aggregated signature against our aggregated public key.
-        agg_pk.verify(&message, &justification.sig.signature)
+        batch.add_aggregate(&agg_pk, &message, &justification.sig.signature);
+        true
     }
 }
@@ -7,7 +7,10 @@ use nimiq_bls::{AggregateSignature, G2Projective, PublicKey as BlsPublicKey};
 use nimiq_collections::BitSet;
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
 use nimiq_keys::{Address, Ed25519PublicKey as SchnorrPublicKey, Ed25519Signature, KeyPair};
-use nimiq_primitives::{networks::NetworkId, policy::Policy, slots_allocation::ValidatorsBuilder};
+use nimiq_primitives::{
+    account::AccountType, networks::NetworkId, policy::Policy, slots_allocation::ValidatorsBuilder,
+    transaction::TransactionError,
+};
 use nimiq_test_log::test;
 use nimiq_test_utils::blockchain::{generate_transactions, validator_address};
 use nimiq_transaction::ExecutedTransaction;
@@ -336,6 +339,64 @@ fn test_verify_micro_block_body_txns() {
     );
 }
 
+#[test]
+fn test_verify_micro_block_body_scheduled_vesting() {
+    let mut micro_header = MicroHeader {
+        network: NetworkId::UnitAlbatross,
+        version: Policy::VERSION,
+        block_number: 1,
+        timestamp: 0,
+        parent_hash: Blake2bHash::default(),
+        seed: VrfSeed::default(),
+        extra_data: vec![],
+        state_root: Blake2bHash::default(),
+        body_root: Blake2sHash::default(),
+        diff_root: Blake2bHash::default(),
+        history_root: Blake2bHash::default(),
+    };
+
+    let micro_justification = MicroJustification::Micro(Ed25519Signature::default());
+
+    // Scheduled vesting contracts are not supported by the current protocol version yet.
+    for (sender_type, recipient_type, error) in [
+        (
+            AccountType::Basic,
+            AccountType::ScheduledVesting,
+            TransactionError::InvalidForRecipient,
+        ),
+        (
+            AccountType::ScheduledVesting,
+            AccountType::Basic,
+            TransactionError::InvalidForSender,
+        ),
+    ] {
+        let mut tx = generate_transactions(&KeyPair::default(), 1, NetworkId::UnitAlbatross, 1, 0)
+            .pop()
+            .unwrap();
+        tx.sender_type = sender_type;
+        tx.recipient_type = recipient_type;
+
+        let micro_body = MicroBody {
+            equivocation_proofs: vec![],
+            transactions: vec![ExecutedTransaction::Ok(tx)],
+        };
+
+        // Build a block with a body that uses a scheduled vesting contract
+        micro_header.body_root = micro_body.hash();
+        let block = Block::Micro(MicroBlock {
+            header: micro_header.clone(),
+            justification: Some(micro_justification.clone()),
+            body: Some(micro_body),
+        });
+
+        // The body check should fail
+        assert_eq!(
+            block.verify(NetworkId::UnitAlbatross),
+            Err(BlockError::InvalidTransaction(error))
+        );
+    }
+}
+
 #[test]
 fn test_verify_micro_block_body_fork_proofs() {
     let genesis_block_number = Policy::genesis_block_number();
@@ -6,4 +6,5 @@ pub enum Error {
     InvalidProof,
     IncompleteProof,
     ProofOutOfOrder,
+    PrunedNodes,
 }
@@ -1,7 +1,7 @@
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::{BTreeSet, HashSet, VecDeque},
     marker::PhantomData,
-    ops::RangeBounds,
+    ops::{Range, RangeBounds},
 };
 
 use utils::BaggingInfo;
@@ -16,14 +16,14 @@ use crate::{
         proof::{Proof, RangeProof},
         utils::bagging,
     },
-    store::{memory::MemoryTransaction, LightStore, Store},
+    store::{memory::MemoryTransaction, LightStore, PrunableStore, Store},
 };
 
 pub mod partial;
 pub mod peaks;
 pub mod position;
 pub mod proof;
-mod utils;
+pub(crate) mod utils;
 
 /// This is the main struct for the Merkle Mountain Range. It simply consists of a store for the
 /// nodes and the number of leaves.
@@ -90,10 +90,41 @@ impl<H: Merge + Clone, S: Store<H>> MerkleMountainRange<H, S> {
         // Set new leaf index.
         let num_leaves = self.num_leaves();
 
-        let index = self.len();
-        let mut pos = Position::from(index);
+        let mut store = MemoryTransaction::new(&mut self.store);
+        Self::push_to_store(&mut store, elem)?;
+        store.commit();
+
+        // Update num_leaves.
+        self.num_leaves += 1;
+        let leaf_index = num_leaves;
+        Ok(leaf_index)
+    }
+
+    /// Inserts multiple elements at once and returns the range of their leaf indexes.
+    /// All new nodes are written to the store in a single batch.
+    pub fn append<T>(&mut self, elems: &[T]) -> Result<Range<usize>, Error>
+    where
+        T: Hash<H>,
+    {
+        let first_leaf_index = self.num_leaves();
 
         let mut store = MemoryTransaction::new(&mut self.store);
+        for elem in elems {
+            Self::push_to_store(&mut store, elem)?;
+        }
+        store.commit();
+
+        self.num_leaves += elems.len();
+        Ok(first_leaf_index..self.num_leaves)
+    }
+
+    /// Private function that pushes a leaf and all parent nodes it completes to the store.
+    fn push_to_store<T, St>(store: &mut St, elem: &T) -> Result<(), Error>
+    where
+        T: Hash<H>,
+        St: Store<H>,
+    {
+        let mut pos = Position::from(store.len());
         store.push(elem.hash(pos.num_leaves() as u64));
 
         // Hash up as long as possible (as long as we're the right child of the parent).
@@ -111,15 +142,13 @@ impl<H: Merge + Clone, S: Store<H>> MerkleMountainRange<H, S> {
             store.push(parent_elem);
         }
 
-        store.commit();
-
-        // Update num_leaves.
-        self.num_leaves += 1;
-        let leaf_index = num_leaves;
-        Ok(leaf_index)
+        Ok(())
     }
 
     /// Removes the most recent leaf.
+    ///
+    /// Fails without removing anything if the peaks of the remaining tree or their children have
+    /// been pruned, since the remaining tree could neither compute its root nor grow again.
     pub fn remove_back(&mut self) -> Result<(), Error> {
         if self.is_empty() {
             return Err(Error::EmptyTree);
@@ -128,6 +157,22 @@ impl<H: Merge + Clone, S: Store<H>> MerkleMountainRange<H, S> {
         // If the latest entry is at height h, there are h intermediate nodes
         // to be removed until we reach the most recent leaf (which we also want to remove).
         let height = index_to_height(self.len() - 1);
+        let remaining_len = self.len() - (height + 1);
+
+        // Removing the leaf turns interior nodes into peaks, these must not have been pruned.
+        for peak_pos in PeakIterator::new(remaining_len) {
+            let mut positions = [
+                Some(peak_pos),
+                peak_pos.left_child(),
+                peak_pos.right_child(),
+            ]
+            .into_iter()
+            .flatten();
+            if positions.any(|pos| self.store.get(pos.index).is_none()) {
+                return Err(Error::PrunedNodes);
+            }
+        }
+
         self.store.remove_back(height + 1);
         Ok(())
     }
@@ -232,7 +277,9 @@ impl<H: Merge + Clone, S: Store<H>> MerkleMountainRange<H, S> {
 
         let mut positions = positions?;
 
+        // The leaves don't need to be contiguous, but each leaf is proven only once.
         positions.sort_unstable();
+        positions.dedup();
 
         self.prove_positions(positions, length, false)
     }
@@ -464,6 +511,58 @@ impl<H: Merge + Clone, S: Store<H>> MerkleMountainRange<H, S> {
     }
 }
 
+impl<H: Merge + Clone, S: PrunableStore<H>> MerkleMountainRange<H, S> {
+    /// Prunes all nodes of the tree that are not needed to prove the given leaves.
+    ///
+    /// The peaks and their children are always retained, so the tree can still compute its root,
+    /// prove its number of leaves and grow after pruning. For each of the given leaves, the leaf
+    /// itself and the siblings along its path to the peak are retained, so that the leaves can be
+    /// proven with `prove` in any combination. Proofs for other leaves or for an earlier
+    /// `verifier_state` might fail once the tree has been pruned.
+    ///
+    /// Fails without pruning anything if one of the nodes to be retained has been pruned before.
+    pub fn prune(&mut self, leaf_indices: &[usize]) -> Result<(), Error> {
+        let retained = self.retained_positions(leaf_indices)?;
+        if retained
+            .iter()
+            .any(|&index| self.store.get(index).is_none())
+        {
+            return Err(Error::ProveInvalidLeaves);
+        }
+
+        self.store.retain(&retained);
+        Ok(())
+    }
+
+    /// Private function that returns the indices of the nodes needed to prove the given leaves.
+    fn retained_positions(&self, leaf_indices: &[usize]) -> Result<BTreeSet<usize>, Error> {
+        let peaks: HashSet<usize> = self.peaks().map(|peak_pos| peak_pos.index).collect();
+
+        let mut retained = BTreeSet::new();
+        for peak_pos in self.peaks() {
+            retained.insert(peak_pos.index);
+            retained.extend(peak_pos.left_child().map(|pos| pos.index));
+            retained.extend(peak_pos.right_child().map(|pos| pos.index));
+        }
+
+        for &leaf_index in leaf_indices {
+            let mut pos = Position::from(leaf_number_to_index(leaf_index));
+            if pos.index >= self.len() {
+                return Err(Error::ProveInvalidLeaves);
+            }
+
+            // Walk up to the peak covering the leaf.
+            retained.insert(pos.index);
+            while !peaks.contains(&pos.index) {
+                retained.insert(pos.sibling().index);
+                pos = pos.parent();
+            }
+        }
+
+        Ok(retained)
+    }
+}
+
 impl<H: Merge + Clone + PartialEq, S: Store<H>> MerkleMountainRange<H, S> {
     /// Tries to find a hash in the tree in O(n) and returns its leaf index.
     pub fn find<T>(&self, elem: T) -> Option<usize>
@@ -634,7 +733,7 @@ mod tests {
     use super::*;
     use crate::{
         mmr::utils::test_utils::{hash_mmr, TestHash},
-        store::memory::{LightMemoryStore, MemoryStore},
+        store::memory::{LightMemoryStore, MemoryStore, SparseMemoryStore},
     };
 
     #[test]
@@ -722,4 +821,75 @@ mod tests {
             assert_eq!(size_proof.size() as usize, i + 1);
         }
     }
+
+    #[test]
+    fn it_correctly_appends_batches() {
+        let nodes = vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29];
+
+        let mut mmr = MerkleMountainRange::<TestHash, _>::new(MemoryStore::new());
+        assert_eq!(mmr.append(&nodes[..3]), Ok(0..3));
+        assert_eq!(mmr.append::<usize>(&[]), Ok(3..3));
+        assert_eq!(mmr.append(&nodes[3..]), Ok(3..nodes.len()));
+        assert_eq!(mmr.get_root(), Ok(hash_mmr(&nodes)));
+
+        let mut pushed = MerkleMountainRange::<TestHash, _>::new(MemoryStore::new());
+        for v in nodes.iter() {
+            pushed.push(v).unwrap();
+        }
+        assert_eq!(mmr.store.inner, pushed.store.inner);
+    }
+
+    #[test]
+    fn it_correctly_prunes_trees() {
+        let nodes = vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31];
+        let retained = [1, 5, 6];
+
+        let mut mmr = MerkleMountainRange::<TestHash, _>::new(SparseMemoryStore::new());
+        mmr.append(&nodes).unwrap();
+        let len = mmr.len();
+        mmr.prune(&retained).unwrap();
+
+        // The pruned tree still has the same size and root.
+        assert_eq!(mmr.len(), len);
+        assert_eq!(mmr.num_leaves(), nodes.len());
+        assert_eq!(mmr.get_root(), Ok(hash_mmr(&nodes)));
+        assert!(mmr.store.inner.len() < len);
+        assert!(mmr.get_leaf(0).is_none());
+        assert!(mmr.get_leaf(1).is_some());
+
+        // Any combination of the retained leaves can be proven.
+        for to_prove in [&[1][..], &[5, 6], &[1, 6], &[6, 1, 5]] {
+            let proof = mmr.prove(to_prove, None).unwrap();
+            let leaves: Vec<_> = to_prove.iter().map(|&i| (i, &nodes[i])).collect();
+            assert_eq!(proof.verify(&mmr.get_root().unwrap(), &leaves), Ok(true));
+        }
+        assert!(mmr.prove(&[0], None).is_err());
+
+        // The size can still be proven.
+        let size_proof = mmr
+            .prove_num_leaves(|i| nodes.get(i).copied(), None)
+            .unwrap();
+        assert!(size_proof.verify(&mmr.get_root().unwrap()));
+
+        // Pruned leaves can't be retained anymore.
+        assert_eq!(mmr.prune(&[0]), Err(Error::ProveInvalidLeaves));
+        assert_eq!(mmr.prune(&[nodes.len()]), Err(Error::ProveInvalidLeaves));
+
+        // The tree can still grow and shrink.
+        let more_nodes = vec![37, 41, 43];
+        mmr.append(&more_nodes).unwrap();
+        let all_nodes = [nodes.clone(), more_nodes].concat();
+        assert_eq!(mmr.get_root(), Ok(hash_mmr(&all_nodes)));
+        mmr.prune(&[5]).unwrap();
+        mmr.remove_back().unwrap();
+        assert_eq!(
+            mmr.get_root(),
+            Ok(hash_mmr(&all_nodes[..all_nodes.len() - 1]))
+        );
+        let proof = mmr.prove(&[5], None).unwrap();
+        assert_eq!(
+            proof.verify(&mmr.get_root().unwrap(), &[(5, &nodes[5])]),
+            Ok(true)
+        );
+    }
 }
@@ -0,0 +1,296 @@
+use std::{collections::BTreeSet, fmt, marker::PhantomData};
+
+use nimiq_database::{
+    traits::{ReadCursor, ReadTransaction, WriteCursor, WriteTransaction},
+    TableProxy, TransactionProxy, WriteTransactionProxy,
+};
+use nimiq_database_value::{AsDatabaseBytes, FromDatabaseValue};
+
+use super::*;
+
+type WriteCursorProxy<'env> =
+    <WriteTransactionProxy<'env> as WriteTransaction<'env>>::WriteCursor<'env>;
+
+/// A persistent store for MMRs of any hash type in a single database table.
+/// The table can contain multiple MMRs, each identified by a `u32` id, and has one entry per node.
+/// The keys are constructed as follows:
+/// The big-endian byte representation of the id concatenated with the big-endian byte
+/// representation of the node index.
+///
+/// The store supports pruning: pruned nodes are removed from the table, but still count towards
+/// the size of the MMR. The size is derived from the last node of an MMR, which is always a peak
+/// and is therefore never pruned. `MerkleMountainRange::remove_back` refuses to remove nodes if
+/// that would turn pruned nodes into peaks, so this holds after removing nodes as well.
+pub struct DatabaseStore<'a, 'env, H> {
+    table: &'a TableProxy,
+    tx: &'a TransactionProxy<'env>,
+    cursor: Option<WriteCursorProxy<'env>>,
+    id: u32,
+    size: usize,
+    hash: PhantomData<H>,
+}
+
+impl<H> fmt::Debug for DatabaseStore<'_, '_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseStore")
+            .field("table", &self.table)
+            .field("tx", &self.tx)
+            .field("id", &self.id)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<'a, 'env, H> DatabaseStore<'a, 'env, H> {
+    /// Create a read-only store.
+    pub fn with_read_transaction(
+        table: &'a TableProxy,
+        tx: &'a TransactionProxy<'env>,
+        id: u32,
+    ) -> Self {
+        let size = get_size(table, tx, id);
+        DatabaseStore {
+            table,
+            tx,
+            cursor: None,
+            id,
+            size,
+            hash: PhantomData,
+        }
+    }
+
+    /// Create a writable store.
+    pub fn with_write_transaction(
+        table: &'a TableProxy,
+        tx: &'a mut WriteTransactionProxy<'env>,
+        id: u32,
+    ) -> Self {
+        let size = get_size(table, tx, id);
+        DatabaseStore {
+            table,
+            tx,
+            cursor: Some(WriteTransaction::cursor(tx, table)),
+            id,
+            size,
+            hash: PhantomData,
+        }
+    }
+
+    /// Removes the node at the given index if it hasn't been pruned yet.
+    fn remove(&mut self, index: usize) {
+        let key = node_key(self.id, index);
+        let cursor = self
+            .cursor
+            .as_mut()
+            .expect("Cannot remove from a read-only store");
+        if cursor.seek_key::<_, Vec<u8>>(&key).is_some() {
+            cursor.remove();
+        }
+    }
+}
+
+/// Constructs the key of a node, `id || index`.
+fn node_key(id: u32, index: usize) -> Vec<u8> {
+    let mut key = Vec::with_capacity(12);
+    key.extend_from_slice(&id.to_be_bytes());
+    key.extend_from_slice(&(index as u64).to_be_bytes());
+    key
+}
+
+/// Splits a key into the id and the node index.
+fn split_key(key: &[u8]) -> (u32, usize) {
+    let (id, index) = key.split_at(4);
+    (
+        u32::from_be_bytes(id.try_into().unwrap()),
+        u64::from_be_bytes(index.try_into().unwrap()) as usize,
+    )
+}
+
+/// Calculates the size of the MMR with the given id.
+fn get_size(table: &TableProxy, tx: &TransactionProxy, id: u32) -> usize {
+    let mut cursor = tx.cursor(table);
+
+    // Place the cursor at the last entry of the MMR. This is the entry before the first entry of
+    // the next id, or the last entry of the table if there is none.
+    let last_entry = match id.checked_add(1) {
+        Some(next_id) => {
+            if cursor
+                .seek_range_key::<_, Vec<u8>>(&node_key(next_id, 0))
+                .is_some()
+            {
+                cursor.prev::<Vec<u8>, Vec<u8>>()
+            } else {
+                cursor.last::<Vec<u8>, Vec<u8>>()
+            }
+        }
+        None => cursor.last::<Vec<u8>, Vec<u8>>(),
+    };
+
+    // If there is no such entry or it belongs to another MMR, the MMR is empty.
+    match last_entry {
+        Some((key, _)) => match split_key(&key) {
+            (entry_id, index) if entry_id == id => index + 1,
+            _ => 0,
+        },
+        None => 0,
+    }
+}
+
+impl<'a, 'env, H: AsDatabaseBytes + FromDatabaseValue> Store<H> for DatabaseStore<'a, 'env, H> {
+    fn push(&mut self, elem: H) {
+        if let Some(ref mut cursor) = self.cursor {
+            // There might be MMRs with higher ids, so we can't append to the table.
+            cursor.put(&node_key(self.id, self.size), &elem);
+            self.size += 1;
+        } else {
+            panic!("Cannot push to a read-only store");
+        }
+    }
+
+    fn remove_back(&mut self, num_elems: usize) {
+        for _ in 0..num_elems.min(self.size) {
+            self.remove(self.size - 1);
+            self.size -= 1;
+        }
+    }
+
+    fn get(&self, pos: usize) -> Option<H> {
+        if pos >= self.size {
+            return None;
+        }
+        self.tx.get(self.table, &node_key(self.id, pos))
+    }
+
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+}
+
+impl<'a, 'env, H: AsDatabaseBytes + FromDatabaseValue> PrunableStore<H>
+    for DatabaseStore<'a, 'env, H>
+{
+    fn retain(&mut self, positions: &BTreeSet<usize>) {
+        let pruned: Vec<usize> = self
+            .tx
+            .cursor(self.table)
+            .into_iter_from::<Vec<u8>, Vec<u8>>(&node_key(self.id, 0))
+            .map(|(key, _)| split_key(&key))
+            .take_while(|(id, _)| *id == self.id)
+            .map(|(_, index)| index)
+            .filter(|index| !positions.contains(index))
+            .collect();
+
+        for index in pruned {
+            self.remove(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, io};
+
+    use nimiq_database::{traits::Database, volatile::VolatileDatabase};
+    use nimiq_test_log::test;
+
+    use super::*;
+    use crate::{
+        error::Error,
+        mmr::{utils::test_utils::TestHash, MerkleMountainRange},
+    };
+
+    impl AsDatabaseBytes for TestHash {
+        fn as_database_bytes(&self) -> Cow<[u8]> {
+            Cow::Owned((self.0 as u64).to_be_bytes().to_vec())
+        }
+    }
+
+    impl FromDatabaseValue for TestHash {
+        fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+        where
+            Self: Sized,
+        {
+            let bytes = bytes
+                .try_into()
+                .map_err(|_| io::Error::from(io::ErrorKind::InvalidData))?;
+            Ok(TestHash(u64::from_be_bytes(bytes) as usize))
+        }
+    }
+
+    #[test]
+    fn it_persists_and_prunes_trees() {
+        let env = VolatileDatabase::new(1).unwrap();
+        let table = env.open_table("MMR".to_string());
+        let nodes = vec![2, 3, 5, 7, 11, 13, 17];
+
+        let mut txn = env.write_transaction();
+        let root = {
+            let mut mmr = MerkleMountainRange::new(
+                DatabaseStore::<TestHash>::with_write_transaction(&table, &mut txn, 1),
+            );
+            mmr.append(&nodes).unwrap();
+            mmr.prune(&[2]).unwrap();
+            assert!(mmr.get_leaf(2).is_some());
+            assert!(mmr.get_leaf(0).is_none());
+            mmr.get_root().unwrap()
+        };
+        {
+            // A second tree in the same table doesn't interfere with the first one.
+            let mut mmr = MerkleMountainRange::new(
+                DatabaseStore::<TestHash>::with_write_transaction(&table, &mut txn, 0),
+            );
+            mmr.push(&nodes[0]).unwrap();
+        }
+        txn.commit();
+
+        let txn = env.read_transaction();
+        let mmr = MerkleMountainRange::new(DatabaseStore::<TestHash>::with_read_transaction(
+            &table, &txn, 1,
+        ));
+        assert_eq!(mmr.num_leaves(), nodes.len());
+        assert_eq!(mmr.get_root().as_ref(), Ok(&root));
+
+        let proof = mmr.prove(&[2], None).unwrap();
+        assert_eq!(proof.verify(&root, &[(2, &nodes[2])]), Ok(true));
+        assert!(mmr.prove(&[0], None).is_err());
+
+        let mmr = MerkleMountainRange::new(DatabaseStore::<TestHash>::with_read_transaction(
+            &table, &txn, 0,
+        ));
+        assert_eq!(mmr.num_leaves(), 1);
+    }
+
+    #[test]
+    fn it_rejects_removing_pruned_nodes() {
+        let env = VolatileDatabase::new(1).unwrap();
+        let table = env.open_table("MMR".to_string());
+        let nodes = vec![2, 3, 5, 7, 11, 13, 17, 19];
+
+        let mut txn = env.write_transaction();
+        let (len, root) = {
+            let mut mmr = MerkleMountainRange::new(
+                DatabaseStore::<TestHash>::with_write_transaction(&table, &mut txn, 0),
+            );
+            mmr.append(&nodes).unwrap();
+            mmr.prune(&[]).unwrap();
+
+            // Removing the last leaf would turn pruned interior nodes into peaks.
+            assert_eq!(mmr.remove_back(), Err(Error::PrunedNodes));
+            (mmr.len(), mmr.get_root().unwrap())
+        };
+        txn.commit();
+
+        // The tree is unchanged after reopening it.
+        let txn = env.read_transaction();
+        let mmr = MerkleMountainRange::new(DatabaseStore::<TestHash>::with_read_transaction(
+            &table, &txn, 0,
+        ));
+        assert_eq!(mmr.len(), len);
+        assert_eq!(mmr.num_leaves(), nodes.len());
+        assert_eq!(mmr.get_root(), Ok(root));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use super::*;
 
@@ -48,6 +48,57 @@ impl<H: Clone> Store<H> for MemoryStore<H> {
     }
 }
 
+/// An in-memory store that supports pruning. Only the retained nodes are kept in memory.
+pub struct SparseMemoryStore<H> {
+    pub inner: BTreeMap<usize, H>,
+    len: usize,
+}
+
+impl<H: Clone> SparseMemoryStore<H> {
+    pub fn new() -> Self {
+        SparseMemoryStore {
+            inner: BTreeMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<H: Clone> Default for SparseMemoryStore<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Clone> Store<H> for SparseMemoryStore<H> {
+    fn push(&mut self, elem: H) {
+        self.inner.insert(self.len, elem);
+        self.len += 1;
+    }
+
+    fn remove_back(&mut self, num_elems: usize) {
+        self.len -= num_elems;
+        let _ = self.inner.split_off(&self.len);
+    }
+
+    fn get(&self, pos: usize) -> Option<H> {
+        self.inner.get(&pos).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<H: Clone> PrunableStore<H> for SparseMemoryStore<H> {
+    fn retain(&mut self, positions: &BTreeSet<usize>) {
+        self.inner.retain(|pos, _| positions.contains(pos));
+    }
+}
+
 pub struct MemoryTransaction<'a, H, S> {
     store: &'a mut S,
     /// The position of the transaction's data within the store.
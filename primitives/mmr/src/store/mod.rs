@@ -1,3 +1,7 @@
+use std::collections::BTreeSet;
+
+#[cfg(feature = "database-storage")]
+pub mod database;
 pub mod memory;
 
 pub trait Store<H> {
@@ -13,6 +17,14 @@ pub trait Store<H> {
     fn is_empty(&self) -> bool;
 }
 
+/// A store that can drop nodes which are no longer needed, see
+/// `MerkleMountainRange::prune`. Pruned nodes still count towards the length of the store, but
+/// `get` returns `None` for them.
+pub trait PrunableStore<H>: Store<H> {
+    /// Removes all nodes except the ones at the given positions.
+    fn retain(&mut self, positions: &BTreeSet<usize>);
+}
+
 pub trait LightStore<H> {
     fn insert(&mut self, elem: H, pos: usize);
     fn remove(&mut self, pos: usize);
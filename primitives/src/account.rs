@@ -27,6 +27,7 @@ pub enum AccountType {
     Vesting = 1,
     HTLC = 2,
     Staking = 3,
+    ScheduledVesting = 4,
     // HistoricTransaction = 0xff,
 }
 
@@ -49,6 +50,7 @@ impl TryFrom<u8> for AccountType {
             1 => Ok(AccountType::Vesting),
             2 => Ok(AccountType::HTLC),
             3 => Ok(AccountType::Staking),
+            4 => Ok(AccountType::ScheduledVesting),
             _ => Err(Error(value)),
         }
     }
@@ -61,6 +63,7 @@ impl From<AccountType> for u8 {
             AccountType::Vesting => 1,
             AccountType::HTLC => 2,
             AccountType::Staking => 3,
+            AccountType::ScheduledVesting => 4,
         }
     }
 }
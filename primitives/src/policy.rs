@@ -67,6 +67,9 @@ impl Policy {
     /// The current version number of the protocol. Changing this always results in a hard fork.
     pub const VERSION: u16 = 1;
 
+    /// The protocol version starting from which scheduled vesting contracts can be created.
+    pub const SCHEDULED_VESTING_VERSION: u16 = 2;
+
     /// Number of available validator slots. Note that a single validator may own several validator slots.
     pub const SLOTS: u16 = 512;
 
@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_serde::{Deserialize, Serialize};
+
+use crate::{
+    key_nibbles::KeyNibbles,
+    trie::{
+        trie_node::{TrieNodeChild, NO_CHILDREN},
+        trie_proof::{Error, TrieProof},
+        trie_proof_node::{ProofValue, TrieProofNode},
+    },
+};
+
+/// A [`TrieProof`] in a compact encoding, meant to be sent over the network.
+///
+/// A [`TrieProof`] contains every node on the paths to the proven keys together with all of their
+/// children. Most of that information can be derived by the verifier:
+///
+/// 1. The nodes are stored in pre-order and without their keys. The key of a node is the key of
+///    its parent followed by the suffix stored in the parent, the root always has the empty key.
+///
+/// 2. The hashes of children that are part of the proof are omitted, the verifier computes them
+///    from the child nodes. Only the hashes of the siblings that are not part of the proof are
+///    included.
+///
+/// 3. The nodes proving the absence of keys are omitted, the verifier finds them by walking down
+///    the proof.
+///
+/// Every node is included exactly once, no matter how many of the proven keys share it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CompactTrieProof {
+    nodes: Vec<CompactTrieProofNode>,
+}
+
+/// A node of a [`CompactTrieProof`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CompactTrieProofNode {
+    value: ProofValue,
+    children: [Option<CompactTrieProofChild>; 16],
+}
+
+/// A child of a [`CompactTrieProofNode`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+enum CompactTrieProofChild {
+    /// The child is not part of the proof, only its suffix and hash are known.
+    Hash(TrieNodeChild),
+    /// The child is part of the proof, its hash is computed from the child node. Contains the
+    /// suffix of the child.
+    Node(KeyNibbles),
+}
+
+/// A subtree of a [`TrieProof`], used to bring the nodes into pre-order.
+struct Subtree {
+    node: TrieProofNode,
+    children: Vec<Subtree>,
+}
+
+impl From<TrieProof> for CompactTrieProof {
+    /// Compresses a proof. If the proof is not a valid trie, the compressed proof won't be valid
+    /// either.
+    fn from(proof: TrieProof) -> Self {
+        // The nodes are in post-order, so all children of a node come right before it.
+        let mut subtrees: Vec<Subtree> = Vec::new();
+        for node in proof.nodes {
+            let mut children = Vec::new();
+            while let Some(child) = subtrees.pop() {
+                if node.key != child.node.key && node.key.is_prefix_of(&child.node.key) {
+                    children.push(child);
+                } else {
+                    subtrees.push(child);
+                    break;
+                }
+            }
+            children.reverse();
+            subtrees.push(Subtree { node, children });
+        }
+
+        let mut nodes = Vec::new();
+        for subtree in subtrees {
+            Self::push_pre_order(subtree, &mut nodes);
+        }
+        CompactTrieProof { nodes }
+    }
+}
+
+impl CompactTrieProof {
+    /// Private function that appends the nodes of a subtree to `nodes` in pre-order.
+    fn push_pre_order(subtree: Subtree, nodes: &mut Vec<CompactTrieProofNode>) {
+        let Subtree {
+            node,
+            children: mut subtree_children,
+        } = subtree;
+
+        let mut proof_children = Vec::new();
+        let mut children: [Option<CompactTrieProofChild>; 16] = Default::default();
+        for (slot, child) in node.children.into_iter().enumerate() {
+            let Some(child) = child else {
+                continue;
+            };
+            let child_key = &node.key + &child.suffix;
+            children[slot] = match subtree_children
+                .iter()
+                .position(|subtree| subtree.node.key == child_key)
+            {
+                Some(index) => {
+                    proof_children.push(subtree_children.remove(index));
+                    Some(CompactTrieProofChild::Node(child.suffix))
+                }
+                None => Some(CompactTrieProofChild::Hash(child)),
+            };
+        }
+
+        nodes.push(CompactTrieProofNode {
+            value: node.value,
+            children,
+        });
+        for child in proof_children {
+            Self::push_pre_order(child, nodes);
+        }
+    }
+
+    /// Returns the number of nodes in the proof.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns whether the proof is empty.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Expands the proof into a [`TrieProof`] for the given keys. This doesn't verify the proof,
+    /// see [`TrieProof::verify_values`].
+    pub fn into_trie_proof(self, keys: &[&KeyNibbles]) -> Result<TrieProof, Error> {
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        let mut compact_nodes = self.nodes.into_iter();
+        Self::expand_node(KeyNibbles::ROOT, &mut compact_nodes, &mut nodes)?;
+        if compact_nodes.next().is_some() {
+            return Err(Error("proof contains unreachable nodes"));
+        }
+
+        // A key that is not part of the proof is proven absent by the deepest node on its path.
+        let mut missing_proven_by = BTreeMap::new();
+        for &key in keys {
+            let proven_by = nodes
+                .iter()
+                .filter(|node| node.key.is_prefix_of(key))
+                .max_by_key(|node| node.key.len());
+            if let Some(node) = proven_by {
+                if node.key != *key {
+                    missing_proven_by.insert(key.clone(), node.key.clone());
+                }
+            }
+        }
+
+        Ok(TrieProof::new(nodes, missing_proven_by))
+    }
+
+    /// Private function that expands the next node and its subtree into `nodes` in post-order
+    /// and returns the hash of the node.
+    fn expand_node<I: Iterator<Item = CompactTrieProofNode>>(
+        key: KeyNibbles,
+        compact_nodes: &mut I,
+        nodes: &mut Vec<TrieProofNode>,
+    ) -> Result<Blake2bHash, Error> {
+        let compact_node = compact_nodes
+            .next()
+            .ok_or(Error("proof is missing nodes"))?;
+
+        let mut children = NO_CHILDREN;
+        for (slot, child) in compact_node.children.into_iter().enumerate() {
+            children[slot] = match child {
+                None => None,
+                Some(CompactTrieProofChild::Hash(child)) => Some(child),
+                Some(CompactTrieProofChild::Node(suffix)) => {
+                    // Every child must extend the key, which also bounds the depth of the proof.
+                    if suffix.is_empty() || key.len() + suffix.len() > KeyNibbles::MAX_BYTES * 2 {
+                        return Err(Error("invalid child suffix"));
+                    }
+                    let hash = Self::expand_node(&key + &suffix, compact_nodes, nodes)?;
+                    Some(TrieNodeChild { suffix, hash })
+                }
+            };
+        }
+
+        let node = TrieProofNode {
+            key,
+            value: compact_node.value,
+            children,
+        };
+        let hash = node.hash();
+        nodes.push(node);
+        Ok(hash)
+    }
+
+    /// Verifies the proof against the given root hash and returns the values of the given keys,
+    /// see [`TrieProof::verify_values`].
+    pub fn verify_values(
+        self,
+        root_hash: &Blake2bHash,
+        keys: &[&KeyNibbles],
+    ) -> Result<BTreeMap<KeyNibbles, Option<Vec<u8>>>, Error> {
+        self.into_trie_proof(keys)?.verify_values(root_hash, keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_hash::Hash;
+    use nimiq_test_log::test;
+
+    use super::*;
+    use crate::trie::trie_node::TrieNode;
+
+    // We're going to construct proofs based on this tree:
+    //
+    //        R
+    //        |
+    //        B1
+    //      /    \
+    //     L1    L2
+    //
+    #[test]
+    fn compact_proofs_work() {
+        let key_l1: KeyNibbles = "0011".parse().unwrap();
+        let l1 = TrieNode::new_leaf(key_l1.clone(), vec![1]);
+
+        let key_l2: KeyNibbles = "0033".parse().unwrap();
+        let l2 = TrieNode::new_leaf(key_l2.clone(), vec![2]);
+
+        let key_b1: KeyNibbles = "00".parse().unwrap();
+        let mut b1 = TrieNode::new_empty(key_b1.clone());
+        b1.put_child(&key_l1, l1.hash_assert()).unwrap();
+        b1.put_child(&key_l2, l2.hash_assert()).unwrap();
+
+        let mut r = TrieNode::new_empty(KeyNibbles::ROOT);
+        r.put_child(&key_b1, b1.hash_assert()).unwrap();
+        let root_hash = r.hash_assert();
+
+        let proof = TrieProof::new(
+            vec![TrieProofNode::new(true, l1), l2.into(), b1.into(), r.into()],
+            Default::default(),
+        );
+        let proof = CompactTrieProof::from(proof);
+        assert_eq!(proof.len(), 4);
+
+        let key_missing: KeyNibbles = "0012".parse().unwrap();
+        let values = proof
+            .clone()
+            .verify_values(&root_hash, &[&key_l1, &key_missing])
+            .unwrap();
+        assert_eq!(values[&key_l1], Some(vec![1]));
+        assert_eq!(values[&key_missing], None);
+
+        // The expanded proof is the original proof.
+        let expanded = proof.clone().into_trie_proof(&[&key_l1]).unwrap();
+        assert_eq!(
+            expanded
+                .nodes
+                .iter()
+                .map(|node| node.key.clone())
+                .collect::<Vec<_>>(),
+            vec![key_l1.clone(), key_l2, key_b1, KeyNibbles::ROOT]
+        );
+        assert!(expanded.verify(&root_hash));
+
+        // Wrong root hash.
+        assert!(proof
+            .clone()
+            .verify_values(&":-E".hash(), &[&key_l1])
+            .is_err());
+
+        // Missing nodes.
+        let mut truncated = proof.clone();
+        truncated.nodes.pop();
+        assert!(truncated.verify_values(&root_hash, &[&key_l1]).is_err());
+
+        // Superfluous nodes.
+        let mut extended = proof.clone();
+        extended.nodes.push(proof.nodes[0].clone());
+        assert!(extended.verify_values(&root_hash, &[&key_l1]).is_err());
+
+        // Children must extend the key of their parent.
+        let mut invalid = proof;
+        invalid.nodes[0].children[0] = Some(CompactTrieProofChild::Node(KeyNibbles::ROOT));
+        assert!(invalid.verify_values(&root_hash, &[&key_l1]).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod compact_trie_proof;
 pub mod error;
 pub mod trie_chunk;
 pub mod trie_diff;
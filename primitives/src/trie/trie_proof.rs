@@ -205,6 +205,20 @@ impl TrieProof {
         }
         Ok(result)
     }
+
+    /// Verifies a proof for a single key against the given root hash. Returns the value of the key,
+    /// or `None` if the proof shows that the key is not part of the trie.
+    ///
+    /// This allows external parties to verify e.g. an account against the state root of a block
+    /// header without having to trust the node that created the proof.
+    pub fn verify_value(
+        self,
+        root_hash: &Blake2bHash,
+        key: &KeyNibbles,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let mut values = self.verify_values(root_hash, &[key])?;
+        Ok(values.remove(key).flatten())
+    }
 }
 
 #[cfg(test)]
@@ -30,13 +30,13 @@ use crate::{
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TrieProofNode {
     pub key: KeyNibbles,
-    value: ProofValue,
+    pub(crate) value: ProofValue,
     pub children: [Option<TrieNodeChild>; 16],
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[repr(u8)]
-enum ProofValue {
+pub(crate) enum ProofValue {
     None,
     LeafValue(Vec<u8>),
     HybridHash(Blake2bHash),
@@ -3,6 +3,7 @@ use nimiq_primitives::account::AccountType;
 use crate::{
     account::{
         basic_account::BasicAccountVerifier, htlc_contract::HashedTimeLockedContractVerifier,
+        scheduled_vesting_contract::ScheduledVestingContractVerifier,
         staking_contract::StakingContractVerifier, vesting_contract::VestingContractVerifier,
     },
     Transaction, TransactionError,
@@ -10,6 +11,7 @@ use crate::{
 
 pub mod basic_account;
 pub mod htlc_contract;
+pub mod scheduled_vesting_contract;
 pub mod staking_contract;
 pub mod vesting_contract;
 
@@ -35,6 +37,9 @@ impl AccountTransactionVerification for AccountType {
             AccountType::Staking => {
                 StakingContractVerifier::verify_incoming_transaction(transaction)
             }
+            AccountType::ScheduledVesting => {
+                ScheduledVestingContractVerifier::verify_incoming_transaction(transaction)
+            }
         }
     }
 
@@ -51,6 +56,9 @@ impl AccountTransactionVerification for AccountType {
             AccountType::Staking => {
                 StakingContractVerifier::verify_outgoing_transaction(transaction)
             }
+            AccountType::ScheduledVesting => {
+                ScheduledVestingContractVerifier::verify_outgoing_transaction(transaction)
+            }
         }
     }
 }
@@ -0,0 +1,220 @@
+use nimiq_keys::Address;
+use nimiq_primitives::{account::AccountType, coin::Coin};
+use nimiq_serde::{Deserialize, Serialize};
+
+use crate::{
+    account::AccountTransactionVerification, SignatureProof, Transaction, TransactionError,
+    TransactionFlags,
+};
+
+/// The verifier trait for a scheduled vesting contract. This only uses data available in the
+/// transaction.
+pub struct ScheduledVestingContractVerifier;
+
+impl AccountTransactionVerification for ScheduledVestingContractVerifier {
+    fn verify_incoming_transaction(transaction: &Transaction) -> Result<(), TransactionError> {
+        assert_eq!(transaction.recipient_type, AccountType::ScheduledVesting);
+
+        if !transaction
+            .flags
+            .contains(TransactionFlags::CONTRACT_CREATION)
+        {
+            warn!(
+                "Only contract creation is allowed for this transaction:\n{:?}",
+                transaction
+            );
+            return Err(TransactionError::InvalidForRecipient);
+        }
+
+        if transaction.flags.contains(TransactionFlags::SIGNALING) {
+            warn!(
+                "Signaling not allowed for this transaction:\n{:?}",
+                transaction
+            );
+            return Err(TransactionError::InvalidForRecipient);
+        }
+
+        if transaction.recipient != transaction.contract_creation_address() {
+            warn!("Recipient address must match contract creation address for this transaction:\n{:?}",
+                transaction);
+            return Err(TransactionError::InvalidForRecipient);
+        }
+
+        let data = CreationTransactionData::parse(transaction)?;
+        if data.total_amount()? > transaction.value {
+            warn!(
+                "The locked amount exceeds the value of this transaction:\n{:?}",
+                transaction
+            );
+            return Err(TransactionError::InvalidValue);
+        }
+
+        Ok(())
+    }
+
+    fn verify_outgoing_transaction(transaction: &Transaction) -> Result<(), TransactionError> {
+        assert_eq!(transaction.sender_type, AccountType::ScheduledVesting);
+
+        if !transaction.sender_data.is_empty() {
+            warn!(
+                "The following transaction can't have sender data:\n{:?}",
+                transaction
+            );
+            return Err(TransactionError::Overflow);
+        }
+
+        // Verify signature.
+        let signature_proof = SignatureProof::deserialize_all(&transaction.proof)?;
+
+        if !signature_proof.verify(&transaction.serialize_content()) {
+            warn!("Invalid signature for this transaction:\n{:?}", transaction);
+            return Err(TransactionError::InvalidProof);
+        }
+
+        Ok(())
+    }
+}
+
+/// A part of a [`VestingSchedule`] that releases funds linearly.
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingTranche {
+    /// The frequency at which funds are released.
+    pub time_step: u64,
+    /// The amount released at each [`time_step`](Self::time_step).
+    pub step_amount: Coin,
+    /// The total amount released by this tranche.
+    pub amount: Coin,
+}
+
+impl VestingTranche {
+    /// The time it takes to release the whole amount of the tranche.
+    pub fn duration(&self) -> u64 {
+        if self.step_amount.is_zero() {
+            return 0;
+        }
+        let steps = u64::from(self.amount).div_ceil(u64::from(self.step_amount));
+        steps.saturating_mul(self.time_step)
+    }
+
+    /// The amount released `elapsed` time after the tranche started.
+    pub fn released_amount(&self, elapsed: u64) -> Coin {
+        if self.time_step == 0 {
+            return self.amount;
+        }
+        let steps = elapsed / self.time_step;
+        let released = u128::from(steps) * u128::from(u64::from(self.step_amount));
+        // This can't exceed the tranche amount, so it is a valid coin value.
+        Coin::from_u64_unchecked(released.min(u128::from(u64::from(self.amount))) as u64)
+    }
+
+    fn verify(&self) -> Result<(), TransactionError> {
+        if self.time_step == 0 || self.step_amount.is_zero() || self.step_amount > self.amount {
+            return Err(TransactionError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+/// A release schedule of a scheduled vesting contract. Nothing is released before the cliff.
+/// At the cliff, the cliff amount is released at once. Afterwards, the tranches release their
+/// funds one after the other.
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingSchedule {
+    /// The time at which the schedule starts releasing funds.
+    pub cliff_time: u64,
+    /// The amount released at the cliff.
+    pub cliff_amount: Coin,
+    /// The tranches of the schedule, in the order in which they release their funds.
+    pub tranches: Vec<VestingTranche>,
+}
+
+impl VestingSchedule {
+    /// The total amount released by the schedule.
+    pub fn total_amount(&self) -> Result<Coin, TransactionError> {
+        self.tranches
+            .iter()
+            .try_fold(self.cliff_amount, |total, tranche| {
+                total.checked_add(tranche.amount)
+            })
+            .ok_or(TransactionError::Overflow)
+    }
+
+    /// The amount that is still locked at the given time.
+    pub fn locked_amount(&self, time: u64) -> Coin {
+        let total_amount = self.total_amount().unwrap_or(Coin::MAX);
+        if time < self.cliff_time {
+            return total_amount;
+        }
+
+        let mut released = self.cliff_amount;
+        let mut tranche_start = self.cliff_time;
+        for tranche in &self.tranches {
+            let released_in_tranche = tranche.released_amount(time - tranche_start);
+            released += released_in_tranche;
+            if released_in_tranche < tranche.amount {
+                break;
+            }
+            // The tranche has been released completely, so it ended before `time`.
+            tranche_start = tranche_start.saturating_add(tranche.duration());
+        }
+
+        total_amount - released
+    }
+
+    fn verify(&self) -> Result<(), TransactionError> {
+        if self.tranches.is_empty() && self.cliff_amount.is_zero() {
+            return Err(TransactionError::InvalidData);
+        }
+        for tranche in &self.tranches {
+            tranche.verify()?;
+        }
+        self.total_amount().map(|_| ())
+    }
+}
+
+/// Data used to create scheduled vesting contracts.
+///
+/// Used in [`Transaction::recipient_data`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreationTransactionData {
+    /// The owner of the contract, the only address that can interact with it.
+    pub owner: Address,
+    /// The release schedules of the contract. Their locked amounts add up.
+    pub schedules: Vec<VestingSchedule>,
+}
+
+impl CreationTransactionData {
+    pub fn parse(tx: &Transaction) -> Result<Self, TransactionError> {
+        let data = CreationTransactionData::deserialize_all(&tx.recipient_data)?;
+        data.verify()?;
+        Ok(data)
+    }
+
+    pub fn to_tx_data(&self) -> Vec<u8> {
+        self.serialize_to_vec()
+    }
+
+    /// The total amount locked by all schedules.
+    pub fn total_amount(&self) -> Result<Coin, TransactionError> {
+        self.schedules
+            .iter()
+            .try_fold(Coin::ZERO, |total, schedule| {
+                total
+                    .checked_add(schedule.total_amount()?)
+                    .ok_or(TransactionError::Overflow)
+            })
+    }
+
+    /// Checks that there is at least one schedule and that all schedules are valid.
+    pub fn verify(&self) -> Result<(), TransactionError> {
+        if self.schedules.is_empty() {
+            return Err(TransactionError::InvalidData);
+        }
+        for schedule in &self.schedules {
+            schedule.verify()?;
+        }
+        self.total_amount().map(|_| ())
+    }
+}
@@ -24,6 +24,7 @@ use thiserror::Error;
 
 use crate::account::{
     htlc_contract::{CreationTransactionData as HtlcCreationData, OutgoingHTLCTransactionProof},
+    scheduled_vesting_contract::CreationTransactionData as ScheduledVestingCreationData,
     staking_contract::IncomingStakingTransactionData,
     vesting_contract::CreationTransactionData as VestingCreationData,
     AccountTransactionVerification,
@@ -378,6 +379,22 @@ impl Transaction {
         Ok(())
     }
 
+    /// Checks that the accounts involved in the transaction are supported by the given protocol
+    /// version.
+    pub fn verify_version(&self, version: u16) -> Result<(), TransactionError> {
+        // Scheduled vesting contracts are only available from their protocol version on.
+        if version < Policy::SCHEDULED_VESTING_VERSION {
+            if self.sender_type == AccountType::ScheduledVesting {
+                return Err(TransactionError::InvalidForSender);
+            }
+            if self.recipient_type == AccountType::ScheduledVesting {
+                return Err(TransactionError::InvalidForRecipient);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn is_valid_at(&self, block_height: u32) -> bool {
         let window = Policy::transaction_validity_window_blocks();
         block_height
@@ -430,7 +447,7 @@ impl Transaction {
         }
 
         match self.sender_type {
-            AccountType::Basic | AccountType::Vesting => {}
+            AccountType::Basic | AccountType::Vesting | AccountType::ScheduledVesting => {}
             AccountType::HTLC => {
                 if let Ok(proof) = OutgoingHTLCTransactionProof::deserialize_all(&self.proof) {
                     match proof {
@@ -472,6 +489,12 @@ impl Transaction {
                     addresses.insert(contract_data.owner);
                 }
             }
+            AccountType::ScheduledVesting => {
+                if let Ok(contract_data) = ScheduledVestingCreationData::parse(self) {
+                    // Add the owner of the new scheduled vesting contract
+                    addresses.insert(contract_data.owner);
+                }
+            }
             AccountType::HTLC => {
                 if let Ok(contract_data) = HtlcCreationData::parse(self) {
                     // Add both the "sender" and "recipient" of the new HTLC
@@ -0,0 +1,192 @@
+use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_primitives::{
+    account::AccountType, coin::Coin, networks::NetworkId, transaction::TransactionError,
+};
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use nimiq_transaction::{
+    account::{
+        scheduled_vesting_contract::{CreationTransactionData, VestingSchedule, VestingTranche},
+        AccountTransactionVerification,
+    },
+    SignatureProof, Transaction, TransactionFlags,
+};
+
+const OWNER_KEY: &str = "9d5bd02379e7e45cf515c788048f5cf3c454ffabd3e83bd1d7667716c325c3c0";
+
+fn key_pair() -> KeyPair {
+    KeyPair::from(PrivateKey::deserialize_from_vec(&hex::decode(OWNER_KEY).unwrap()).unwrap())
+}
+
+fn creation_data() -> CreationTransactionData {
+    CreationTransactionData {
+        owner: Address::from([0u8; 20]),
+        schedules: vec![
+            VestingSchedule {
+                cliff_time: 100,
+                cliff_amount: Coin::try_from(10).unwrap(),
+                tranches: vec![
+                    VestingTranche {
+                        time_step: 10,
+                        step_amount: Coin::try_from(5).unwrap(),
+                        amount: Coin::try_from(20).unwrap(),
+                    },
+                    VestingTranche {
+                        time_step: 100,
+                        step_amount: Coin::try_from(20).unwrap(),
+                        amount: Coin::try_from(30).unwrap(),
+                    },
+                ],
+            },
+            VestingSchedule {
+                cliff_time: 1000,
+                cliff_amount: Coin::try_from(40).unwrap(),
+                tranches: vec![],
+            },
+        ],
+    }
+}
+
+#[test]
+fn it_can_verify_creation_transaction() {
+    let owner = Address::from([0u8; 20]);
+    let mut transaction = Transaction::new_contract_creation(
+        owner,
+        AccountType::Basic,
+        vec![],
+        AccountType::ScheduledVesting,
+        vec![],
+        100.try_into().unwrap(),
+        0.try_into().unwrap(),
+        0,
+        NetworkId::UnitAlbatross,
+    );
+
+    // Invalid data
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&transaction),
+        Err(TransactionError::InvalidSerialization(
+            DeserializeError::unexpected_end()
+        ))
+    );
+    transaction.recipient_data = creation_data().to_tx_data();
+
+    // Invalid recipient
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&transaction),
+        Err(TransactionError::InvalidForRecipient)
+    );
+    transaction.recipient = transaction.contract_creation_address();
+
+    // Valid
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&transaction),
+        Ok(())
+    );
+    assert_eq!(
+        CreationTransactionData::parse(&transaction),
+        Ok(creation_data())
+    );
+
+    // Invalid transaction flags
+    transaction.flags = TransactionFlags::empty();
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&transaction),
+        Err(TransactionError::InvalidForRecipient)
+    );
+    transaction.flags = TransactionFlags::CONTRACT_CREATION;
+
+    // Locked amount exceeds the transaction value
+    transaction.value = 99.try_into().unwrap();
+    transaction.recipient = transaction.contract_creation_address();
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&transaction),
+        Err(TransactionError::InvalidValue)
+    );
+    transaction.value = 100.try_into().unwrap();
+    transaction.recipient = transaction.contract_creation_address();
+}
+
+#[test]
+fn it_refuses_invalid_schedules() {
+    let verify = |data: CreationTransactionData| {
+        let mut transaction = Transaction::new_contract_creation(
+            Address::from([0u8; 20]),
+            AccountType::Basic,
+            vec![],
+            AccountType::ScheduledVesting,
+            data.to_tx_data(),
+            100.try_into().unwrap(),
+            0.try_into().unwrap(),
+            0,
+            NetworkId::UnitAlbatross,
+        );
+        transaction.recipient = transaction.contract_creation_address();
+        AccountType::verify_incoming_transaction(&transaction)
+    };
+
+    assert_eq!(verify(creation_data()), Ok(()));
+
+    // No schedules
+    let mut data = creation_data();
+    data.schedules.clear();
+    assert_eq!(verify(data), Err(TransactionError::InvalidData));
+
+    // A schedule that doesn't release anything
+    let mut data = creation_data();
+    data.schedules[1].cliff_amount = Coin::ZERO;
+    assert_eq!(verify(data), Err(TransactionError::InvalidData));
+
+    // Zero time step
+    let mut data = creation_data();
+    data.schedules[0].tranches[1].time_step = 0;
+    assert_eq!(verify(data), Err(TransactionError::InvalidData));
+
+    // Zero step amount
+    let mut data = creation_data();
+    data.schedules[0].tranches[0].step_amount = Coin::ZERO;
+    assert_eq!(verify(data), Err(TransactionError::InvalidData));
+
+    // Step amount > tranche amount
+    let mut data = creation_data();
+    data.schedules[0].tranches[0].step_amount = Coin::try_from(21).unwrap();
+    assert_eq!(verify(data), Err(TransactionError::InvalidData));
+
+    // The amounts overflow
+    let mut data = creation_data();
+    data.schedules[1].cliff_amount = Coin::MAX;
+    assert_eq!(verify(data), Err(TransactionError::Overflow));
+}
+
+#[test]
+fn it_can_verify_outgoing_transactions() {
+    let key_pair = key_pair();
+
+    let mut tx = Transaction::new_basic(
+        Address::from([1u8; 20]),
+        Address::from([2u8; 20]),
+        1.try_into().unwrap(),
+        1000.try_into().unwrap(),
+        1,
+        NetworkId::UnitAlbatross,
+    );
+    tx.sender_type = AccountType::ScheduledVesting;
+
+    assert_eq!(
+        AccountType::verify_outgoing_transaction(&tx),
+        Err(TransactionError::InvalidSerialization(
+            DeserializeError::unexpected_end()
+        ))
+    );
+
+    let signature = key_pair.sign(&tx.serialize_content()[..]);
+    let signature_proof = SignatureProof::from_ed25519(key_pair.public, signature);
+    tx.proof = signature_proof.serialize_to_vec();
+
+    assert_eq!(AccountType::verify_outgoing_transaction(&tx), Ok(()));
+
+    tx.sender_data = vec![1];
+    assert_eq!(
+        AccountType::verify_outgoing_transaction(&tx),
+        Err(TransactionError::Overflow)
+    );
+}
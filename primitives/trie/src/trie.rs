@@ -1637,7 +1637,10 @@ impl<'txn, T: Deserialize> Iterator for TrieNodeIter<'txn, T> {
 
 #[cfg(test)]
 mod tests {
-    use nimiq_primitives::trie::trie_diff::{TrieDiffBuilder, ValueChange};
+    use nimiq_primitives::trie::{
+        compact_trie_proof::CompactTrieProof,
+        trie_diff::{TrieDiffBuilder, ValueChange},
+    };
     use nimiq_test_log::test;
 
     use super::*;
@@ -1840,6 +1843,49 @@ mod tests {
         assert_eq!(proof_values[&key_6], None);
         assert_eq!(proof_values[&key_7], None);
 
+        // Single values.
+        assert_eq!(
+            trie.get_proof(&txn, vec![&key_3])
+                .unwrap()
+                .verify_value(&trie.root_hash_assert(&txn), &key_3)
+                .unwrap(),
+            Some(vec![3])
+        );
+        assert_eq!(
+            trie.get_proof(&txn, vec![&key_5])
+                .unwrap()
+                .verify_value(&trie.root_hash_assert(&txn), &key_5)
+                .unwrap(),
+            None
+        );
+
+        // A single value is proven for the wrong key.
+        assert!(trie
+            .get_proof(&txn, vec![&key_3])
+            .unwrap()
+            .verify_value(&trie.root_hash_assert(&txn), &key_4)
+            .is_err());
+
+        // A single value is proven against the wrong root.
+        assert!(trie
+            .get_proof(&txn, vec![&key_3])
+            .unwrap()
+            .verify_value(&Blake2bHash::default(), &key_3)
+            .is_err());
+
+        // A wrong single value is proven.
+        let root_hash = trie.root_hash_assert(&txn);
+        trie.put(&mut txn, &key_3, 5u8).expect("complete trie");
+        trie.update_root(&mut txn).expect("complete trie");
+        assert!(trie
+            .get_proof(&txn, vec![&key_3])
+            .unwrap()
+            .verify_value(&root_hash, &key_3)
+            .is_err());
+        trie.put(&mut txn, &key_3, 3u8).expect("complete trie");
+        trie.update_root(&mut txn).expect("complete trie");
+        assert_eq!(trie.root_hash_assert(&txn), root_hash);
+
         // Wrong values were proven.
         assert!(trie
             .get_proof(&txn, vec![&key_1, &key_2])
@@ -1868,6 +1914,70 @@ mod tests {
             .is_err());
     }
 
+    #[test]
+    fn compact_proofs_work() {
+        //          |
+        //         cfb98
+        //        /     |
+        //       6    e0f6
+        //      /  \
+        //   ab9   f5a
+
+        let key_1 = "cfb986f5a".parse().unwrap();
+        let key_2 = "cfb98".parse().unwrap();
+        let key_3 = "cfb98e0f6".parse().unwrap();
+        let key_4 = "cfb98e0f7".parse().unwrap();
+        let key_5 = "cfb98e".parse().unwrap();
+        let key_6 = "ca".parse().unwrap();
+
+        let env = nimiq_database::volatile::VolatileDatabase::new(20).unwrap();
+        let trie = MerkleRadixTrie::new(env.clone(), "database");
+        let mut raw_txn = env.write_transaction();
+        let mut txn: WriteTransactionProxy = (&mut raw_txn).into();
+
+        trie.put(&mut txn, &key_1, 1u8).expect("complete trie");
+        trie.put(&mut txn, &key_2, 2u8).expect("complete trie");
+        trie.put(&mut txn, &key_3, 3u8).expect("complete trie");
+        trie.put(&mut txn, &key_4, 4u8).expect("complete trie");
+        trie.update_root(&mut txn).expect("complete trie");
+        let root_hash = trie.root_hash_assert(&txn);
+
+        let compact_proof = |keys: Vec<&KeyNibbles>| {
+            let proof = trie.get_proof(&txn, keys).unwrap();
+            let compact_proof = CompactTrieProof::from(proof.clone());
+            assert_eq!(compact_proof.len(), proof.nodes.len());
+            assert!(compact_proof.serialized_size() < proof.serialized_size());
+            CompactTrieProof::deserialize_from_vec(&compact_proof.serialize_to_vec()).unwrap()
+        };
+
+        let proof_values = compact_proof(vec![&key_1, &key_3, &key_4])
+            .verify_values(&root_hash, &[&key_1, &key_3, &key_4])
+            .unwrap();
+        assert_eq!(proof_values.len(), 3);
+        assert_eq!(proof_values[&key_1], Some(vec![1]));
+        assert_eq!(proof_values[&key_3], Some(vec![3]));
+        assert_eq!(proof_values[&key_4], Some(vec![4]));
+
+        // Empty nodes and hybrid nodes.
+        let proof_values = compact_proof(vec![&key_2, &key_5, &key_6])
+            .verify_values(&root_hash, &[&key_2, &key_5, &key_6])
+            .unwrap();
+        assert_eq!(proof_values.len(), 3);
+        assert_eq!(proof_values[&key_2], Some(vec![2]));
+        assert_eq!(proof_values[&key_5], None);
+        assert_eq!(proof_values[&key_6], None);
+
+        // Wrong values were proven.
+        assert!(compact_proof(vec![&key_1, &key_2])
+            .verify_values(&root_hash, &[&key_1, &key_3])
+            .is_err());
+
+        // Wrong root hash.
+        assert!(compact_proof(vec![&key_1])
+            .verify_values(&Blake2bHash::default(), &[&key_1])
+            .is_err());
+    }
+
     #[test]
     fn hybrid_nodes_work() {
         let key_1 = "413f22".parse().unwrap();
@@ -4,8 +4,9 @@ use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 
 use crate::types::{
-    Account, Block, BlockLog, BlockchainState, ExecutedTransaction, Inherent, LogType,
-    PenalizedSlots, RPCData, RPCResult, Slot, Staker, Validator,
+    Account, AccountProof, Block, BlockLog, BlockNumberOrHash, BlockchainState,
+    ExecutedTransaction, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, Rebranch, Slot,
+    Staker, Validator,
 };
 
 #[nimiq_jsonrpc_derive::proxy(name = "BlockchainProxy", rename_all = "camelCase")]
@@ -62,6 +63,15 @@ pub trait BlockchainInterface {
         hash: Blake2bHash,
     ) -> RPCResult<ExecutedTransaction, (), Self::Error>;
 
+    /// Creates a hex-encoded, self-contained proof that the transaction with the given hash was
+    /// included in the chain. The proof can be verified offline against the election block with
+    /// the given number, e.g. with the `nimiq-receipt` tool.
+    async fn get_transaction_receipt_proof(
+        &mut self,
+        hash: Blake2bHash,
+        trusted_block_number: u32,
+    ) -> RPCResult<String, (), Self::Error>;
+
     /// Returns all the transactions (including reward transactions) for the given block number. Note
     /// that this only considers blocks in the main chain.
     async fn get_transactions_by_block_number(
@@ -116,6 +126,26 @@ pub trait BlockchainInterface {
         address: Address,
     ) -> RPCResult<Account, BlockchainState, Self::Error>;
 
+    /// Returns the most recent rebranches of the main chain observed by this node, newest first.
+    /// At most `max` (default 100) rebranches are returned.
+    async fn get_recent_forks(
+        &mut self,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<Rebranch>, (), Self::Error>;
+
+    /// Fetches the account at the given address together with a proof against the state root of
+    /// a block. The block is returned with its justification, so that the proof can be verified
+    /// with `TrieProof::verify_value` using the key of the address.
+    ///
+    /// Accounts can be proven against the head and, if the node keeps a complete accounts
+    /// snapshot, against the latest macro block. If no block is given, the latest macro block is
+    /// used if possible, otherwise the head.
+    async fn get_account_proof(
+        &mut self,
+        address: Address,
+        block: Option<BlockNumberOrHash>,
+    ) -> RPCResult<AccountProof, BlockchainState, Self::Error>;
+
     /// Fetches all accounts in the accounts tree.
     /// IMPORTANT: This operation iterates over all accounts in the accounts tree
     /// and thus is extremely computationally expensive.
@@ -176,6 +206,12 @@ pub trait BlockchainInterface {
         &mut self,
     ) -> Result<BoxStream<'static, RPCData<Blake2bHash, ()>>, Self::Error>;
 
+    /// Subscribes to rebranches of the main chain.
+    #[stream]
+    async fn subscribe_for_rebranches(
+        &mut self,
+    ) -> Result<BoxStream<'static, RPCData<Rebranch, ()>>, Self::Error>;
+
     /// Subscribes to pre epoch validators events.
     #[stream]
     async fn subscribe_for_validator_election_by_address(
@@ -2,9 +2,15 @@ use async_trait::async_trait;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 use nimiq_primitives::coin::Coin;
-use nimiq_transaction::account::htlc_contract::{AnyHash, PreImage};
+use nimiq_transaction::account::{
+    htlc_contract::{AnyHash, PreImage},
+    scheduled_vesting_contract::VestingSchedule,
+};
 
-use crate::types::{RPCResult, Transaction, ValidityStartHeight};
+use crate::types::{
+    BlockchainState, RPCResult, ScheduledTransaction, SyncStatus, Transaction,
+    TransactionSimulation, ValidityStartHeight,
+};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ConsensusProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -16,6 +22,10 @@ pub trait ConsensusInterface {
     #[allow(clippy::wrong_self_convention)]
     async fn is_consensus_established(&mut self) -> RPCResult<bool, (), Self::Error>;
 
+    /// Returns the sync status of the node: the current sync phase, the sync target, the
+    /// progress towards it and the peers we are syncing with.
+    async fn get_sync_status(&mut self) -> RPCResult<SyncStatus, (), Self::Error>;
+
     /// Given a serialized transaction, it will return the corresponding transaction struct.
     async fn get_raw_transaction_info(
         &mut self,
@@ -28,6 +38,43 @@ pub trait ConsensusInterface {
         raw_tx: String,
     ) -> RPCResult<Blake2bHash, (), Self::Error>;
 
+    /// Simulates the given serialized transaction on top of the current head without sending it
+    /// to the network. Returns the logs, receipt, fee and balance changes the transaction would
+    /// produce if it was included in the next block.
+    async fn simulate_transaction(
+        &mut self,
+        raw_tx: String,
+    ) -> RPCResult<TransactionSimulation, BlockchainState, Self::Error>;
+
+    /// Holds the given serialized transaction in the node wallet and sends it to the network once
+    /// the head of the chain reaches the given block height. The block height defaults to the
+    /// validity start height of the transaction. Returns the id of the scheduled transaction.
+    async fn schedule_raw_transaction(
+        &mut self,
+        raw_tx: String,
+        block_height: Option<u32>,
+    ) -> RPCResult<u64, (), Self::Error>;
+
+    /// Schedules a transaction redeeming the whole balance of a HTLC contract, using the
+    /// `TimeoutResolve` method, as soon as the contract has timed out. The wallet must be the
+    /// sender of the contract and must be unlocked when the timeout is reached. Returns the id of
+    /// the scheduled transaction.
+    async fn schedule_redeem_timeout_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        fee: Coin,
+    ) -> RPCResult<u64, BlockchainState, Self::Error>;
+
+    /// Returns all transactions that are scheduled but were not sent yet.
+    async fn list_scheduled_transactions(
+        &mut self,
+    ) -> RPCResult<Vec<ScheduledTransaction>, (), Self::Error>;
+
+    /// Cancels the scheduled transaction with the given id. Returns whether it existed.
+    async fn cancel_scheduled_transaction(&mut self, id: u64) -> RPCResult<bool, (), Self::Error>;
+
     /// Returns a serialized basic transaction.
     async fn create_basic_transaction(
         &mut self,
@@ -118,6 +165,50 @@ pub trait ConsensusInterface {
         validity_start_height: ValidityStartHeight,
     ) -> RPCResult<Blake2bHash, (), Self::Error>;
 
+    /// Returns a serialized transaction creating a new scheduled vesting contract.
+    async fn create_new_scheduled_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        schedules: Vec<VestingSchedule>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a transaction creating a new scheduled vesting contract to the network.
+    async fn send_new_scheduled_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        schedules: Vec<VestingSchedule>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
+    /// Returns a serialized transaction redeeming a scheduled vesting contract.
+    async fn create_redeem_scheduled_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a transaction redeeming a scheduled vesting contract to the network.
+    async fn send_redeem_scheduled_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
     /// Returns a serialized transaction creating a new HTLC contract.
     async fn create_new_htlc_transaction(
         &mut self,
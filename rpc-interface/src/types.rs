@@ -8,7 +8,9 @@ use std::{
 };
 
 use clap::ValueEnum;
-use nimiq_account::{BlockLog as BBlockLog, Log, TransactionLog};
+use nimiq_account::{
+    BlockLog as BBlockLog, Log, OperationReceipt, TransactionLog, VestingSchedule,
+};
 use nimiq_block::{MicroJustification, MultiSignature};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
 use nimiq_blockchain_proxy::BlockchainReadProxy;
@@ -17,7 +19,8 @@ use nimiq_collections::BitSet;
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
 use nimiq_keys::{Address, Ed25519PublicKey, Ed25519Signature, PrivateKey};
 use nimiq_primitives::{
-    coin::Coin, networks::NetworkId, policy::Policy, slots_allocation::Validators,
+    account::FailReason, coin::Coin, networks::NetworkId, policy::Policy,
+    slots_allocation::Validators, trie::trie_proof::TrieProof,
 };
 use nimiq_serde::Serialize as NimiqSerialize;
 use nimiq_transaction::{
@@ -92,6 +95,37 @@ impl FromStr for ValidityStartHeight {
     }
 }
 
+/// A block identified either by its number or by its hash.
+#[derive(Clone, Debug, SerializeDisplay, DeserializeFromStr)]
+pub enum BlockNumberOrHash {
+    Number(u32),
+    Hash(Blake2bHash),
+}
+
+impl Display for BlockNumberOrHash {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Hash(hash) => write!(f, "{hash}"),
+        }
+    }
+}
+
+impl FromStr for BlockNumberOrHash {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(number) = s.parse() {
+            Ok(Self::Number(number))
+        } else if let Ok(hash) = s.parse() {
+            Ok(Self::Hash(hash))
+        } else {
+            Err(crate::error::Error::InvalidBlockNumberOrHash(s.to_string()))
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, ValueEnum)]
 #[serde(rename_all = "kebab-case")]
 pub enum HashAlgorithm {
@@ -690,6 +724,128 @@ impl Transaction {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub address: Address,
+    pub balance_before: Coin,
+    pub balance_after: Coin,
+}
+
+impl From<nimiq_blockchain::BalanceChange> for BalanceChange {
+    fn from(change: nimiq_blockchain::BalanceChange) -> Self {
+        BalanceChange {
+            address: change.address,
+            balance_before: change.balance_before,
+            balance_after: change.balance_after,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSimulation {
+    pub hash: Blake2bHash,
+    pub block_number: u32,
+    pub timestamp: u64,
+    pub execution_result: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<FailReason>,
+    pub fee: Coin,
+    pub logs: Vec<Log>,
+    pub balance_changes: Vec<BalanceChange>,
+    #[serde(with = "crate::serde_helpers::hex")]
+    pub receipt: Vec<u8>,
+}
+
+impl TransactionSimulation {
+    pub fn from_simulation(
+        simulation: nimiq_blockchain::TransactionSimulation,
+        transaction: &nimiq_transaction::Transaction,
+    ) -> Self {
+        let failure_reason = simulation.fail_reason();
+        let receipt = match simulation.receipt {
+            OperationReceipt::Ok(receipt) | OperationReceipt::Err(receipt, _) => {
+                receipt.serialize_to_vec()
+            }
+        };
+
+        TransactionSimulation {
+            hash: simulation.tx_hash,
+            block_number: simulation.block_number,
+            timestamp: simulation.timestamp,
+            execution_result: failure_reason.is_none(),
+            failure_reason,
+            fee: transaction.fee,
+            logs: simulation.logs,
+            balance_changes: simulation
+                .balance_changes
+                .into_iter()
+                .map(BalanceChange::from)
+                .collect(),
+            receipt,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum ScheduledAction {
+    #[serde(rename_all = "camelCase")]
+    Send { transaction: Transaction },
+    #[serde(rename_all = "camelCase")]
+    RedeemHtlcTimeout {
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        fee: Coin,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledTransaction {
+    pub id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    pub action: ScheduledAction,
+}
+
+impl From<nimiq_wallet::ScheduledTransaction> for ScheduledTransaction {
+    fn from(scheduled: nimiq_wallet::ScheduledTransaction) -> Self {
+        let (block_height, timestamp) = match scheduled.trigger {
+            nimiq_wallet::ScheduleTrigger::BlockHeight(block_height) => (Some(block_height), None),
+            nimiq_wallet::ScheduleTrigger::Timestamp(timestamp) => (None, Some(timestamp)),
+        };
+
+        let action = match scheduled.action {
+            nimiq_wallet::ScheduledAction::Send(transaction) => ScheduledAction::Send {
+                transaction: Transaction::from_transaction(transaction),
+            },
+            nimiq_wallet::ScheduledAction::RedeemHtlcTimeout {
+                wallet,
+                contract_address,
+                recipient,
+                fee,
+            } => ScheduledAction::RedeemHtlcTimeout {
+                wallet,
+                contract_address,
+                recipient,
+                fee,
+            },
+        };
+
+        ScheduledTransaction {
+            id: scheduled.id,
+            block_height,
+            timestamp,
+            action,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum Inherent {
@@ -833,6 +989,17 @@ pub enum AccountAdditionalFields {
     /// Additional account information for the staking contract.
     #[serde(rename_all = "camelCase")]
     Staking {},
+
+    /// Additional account information for scheduled vesting contracts.
+    #[serde(rename_all = "camelCase")]
+    ScheduledVesting {
+        /// User friendly address (NQ-address) of the owner of the vesting contract.
+        owner: Address,
+        /// The release schedules of the contract, each with a cliff followed by its tranches.
+        schedules: Vec<VestingSchedule>,
+        /// The total amount (in smallest unit) that was locked at the contract creation.
+        vesting_total_amount: Coin,
+    },
 }
 
 impl Account {
@@ -872,6 +1039,15 @@ impl Account {
                 balance: staking.balance,
                 account_additional_fields: AccountAdditionalFields::Staking {},
             },
+            nimiq_account::Account::ScheduledVesting(vesting) => Account {
+                address,
+                balance: vesting.balance,
+                account_additional_fields: AccountAdditionalFields::ScheduledVesting {
+                    owner: vesting.owner,
+                    schedules: vesting.schedules,
+                    vesting_total_amount: vesting.total_amount,
+                },
+            },
         }
     }
 
@@ -896,6 +1072,35 @@ impl Account {
     }
 }
 
+/// An account together with a proof of its state that can be verified without trusting the node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    pub account: Account,
+    /// The serialized block (without its body) whose state root the proof is against. It includes
+    /// the justification of the block.
+    #[serde(with = "crate::serde_helpers::hex")]
+    pub block: Vec<u8>,
+    /// The serialized Merkle radix trie proof of the account.
+    #[serde(with = "crate::serde_helpers::hex")]
+    pub proof: Vec<u8>,
+}
+
+impl AccountProof {
+    pub fn from_proof(
+        address: Address,
+        account: nimiq_account::Account,
+        block: &nimiq_block::Block,
+        proof: &TrieProof,
+    ) -> Self {
+        AccountProof {
+            account: Account::from_account(address, account),
+            block: block.serialize_to_vec(),
+            proof: proof.serialize_to_vec(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Staker {
@@ -956,6 +1161,48 @@ impl Validator {
     }
 }
 
+/// Statistics about the performance of the local validator within one epoch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorPerformance {
+    pub epoch_number: u32,
+    pub slots: u16,
+    pub micro_blocks_expected: u32,
+    pub micro_blocks_produced: u32,
+    pub skip_blocks_caused: u32,
+    pub tendermint_rounds: u32,
+    pub handel_contributions: u32,
+    pub penalties: u32,
+    pub jail_events: u32,
+    pub rewards: Coin,
+}
+
+/// The sync status of the node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    /// One of `macro-sync`, `state-sync`, `live-sync` or `synced`.
+    pub phase: String,
+    pub block_number: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_epoch: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_block: Option<u32>,
+    pub epochs_done: u32,
+    pub epochs_total: u32,
+    pub chunks_done: u64,
+    pub chunks_total: u64,
+    pub chunks_per_second: f64,
+    pub chunk_concurrency: usize,
+    /// The first key of the accounts trie that is still missing, if the trie is incomplete.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing_accounts_from: Option<String>,
+    /// The estimated number of seconds until the macro sync is finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta: Option<u64>,
+    pub peers: Vec<String>,
+}
+
 pub type RPCResult<T, S, E> = Result<RPCData<T, S>, E>;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1030,6 +1277,58 @@ impl RPCData<BlockLog, BlockchainState> {
     }
 }
 
+/// A block that was reverted or adopted during a rebranch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebranchedBlock {
+    pub hash: Blake2bHash,
+    pub block_number: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposer: Option<Address>,
+    pub is_skip: bool,
+    pub transactions: Vec<Blake2bHash>,
+}
+
+impl From<nimiq_blockchain::rebranch_log::RebranchedBlock> for RebranchedBlock {
+    fn from(block: nimiq_blockchain::rebranch_log::RebranchedBlock) -> Self {
+        RebranchedBlock {
+            hash: block.hash,
+            block_number: block.block_number,
+            proposer: block.proposer,
+            is_skip: block.is_skip,
+            transactions: block.transactions,
+        }
+    }
+}
+
+/// A rebranch of the main chain observed by the node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rebranch {
+    pub id: u64,
+    pub timestamp: u64,
+    /// The number of blocks that were reverted.
+    pub depth: u32,
+    pub common_ancestor: Blake2bHash,
+    pub common_ancestor_number: u32,
+    pub reverted_blocks: Vec<RebranchedBlock>,
+    pub adopted_blocks: Vec<RebranchedBlock>,
+}
+
+impl From<nimiq_blockchain::rebranch_log::RebranchRecord> for Rebranch {
+    fn from(record: nimiq_blockchain::rebranch_log::RebranchRecord) -> Self {
+        Rebranch {
+            id: record.id,
+            timestamp: record.timestamp,
+            depth: record.depth(),
+            common_ancestor: record.common_ancestor,
+            common_ancestor_number: record.common_ancestor_number,
+            reverted_blocks: record.reverted_blocks.into_iter().map(Into::into).collect(),
+            adopted_blocks: record.adopted_blocks.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockchainState {
@@ -1061,6 +1360,7 @@ pub enum LogType {
     HtlcRegularTransfer,
     HtlcEarlyResolve,
     VestingCreate,
+    ScheduledVestingCreate,
     CreateValidator,
     UpdateValidator,
     ValidatorFeeDeduction,
@@ -1115,6 +1415,7 @@ impl LogType {
             Log::HTLCRegularTransfer { .. } => Self::HtlcRegularTransfer,
             Log::HTLCEarlyResolve { .. } => Self::HtlcEarlyResolve,
             Log::VestingCreate { .. } => Self::VestingCreate,
+            Log::ScheduledVestingCreate { .. } => Self::ScheduledVestingCreate,
             Log::CreateValidator { .. } => Self::CreateValidator,
             Log::UpdateValidator { .. } => Self::UpdateValidator,
             Log::DeactivateValidator { .. } => Self::DeactivateValidator,
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use nimiq_keys::Address;
 
-use crate::types::RPCResult;
+use crate::types::{RPCResult, ValidatorPerformance};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ValidatorProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -28,4 +28,11 @@ pub trait ValidatorInterface {
 
     /// Returns if our validator is currently synced.
     async fn is_validator_synced(&mut self) -> RPCResult<bool, (), Self::Error>;
+
+    /// Returns the performance statistics of our validator for the given epoch, defaults to the
+    /// current epoch. Only the current and a limited number of previous epochs are tracked.
+    async fn get_validator_performance(
+        &mut self,
+        epoch_number: Option<u32>,
+    ) -> RPCResult<ValidatorPerformance, (), Self::Error>;
 }
@@ -1,7 +1,10 @@
 use async_trait::async_trait;
 use futures::{future, stream::BoxStream, StreamExt};
 use nimiq_account::{BlockLog as BBlockLog, TransactionLog};
-use nimiq_blockchain::interface::{HistoryIndexInterface, HistoryInterface};
+use nimiq_blockchain::{
+    interface::{HistoryIndexInterface, HistoryInterface},
+    Blockchain,
+};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
 use nimiq_hash::Blake2bHash;
@@ -10,11 +13,12 @@ use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
 use nimiq_rpc_interface::{
     blockchain::BlockchainInterface,
     types::{
-        is_of_log_type_and_related_to_addresses, Account, Block, BlockLog, BlockchainState,
-        ExecutedTransaction, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, Slot, Staker,
-        Validator,
+        is_of_log_type_and_related_to_addresses, Account, AccountProof, Block, BlockLog,
+        BlockNumberOrHash, BlockchainState, ExecutedTransaction, Inherent, LogType, PenalizedSlots,
+        RPCData, RPCResult, Rebranch, Slot, Staker, Validator,
     },
 };
+use nimiq_serde::Serialize;
 use tokio_stream::wrappers::BroadcastStream;
 
 use crate::error::Error;
@@ -29,6 +33,19 @@ impl BlockchainDispatcher {
     }
 }
 
+/// Fails if the history of the given block was already pruned.
+fn ensure_history_available(blockchain: &Blockchain, block_number: u32) -> Result<(), Error> {
+    match blockchain.first_epoch_with_history() {
+        Some(first_epoch)
+            if block_number > Policy::genesis_block_number()
+                && Policy::epoch_at(block_number) < first_epoch =>
+        {
+            Err(Error::HistoryPruned(block_number, first_epoch))
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Tries to fetch a block given its hash. It has an option to include the transactions in the
 /// block, which defaults to false.
 /// This function requires the read lock acquisition prior to its execution
@@ -177,11 +194,26 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_transaction_receipt_proof(
+        &mut self,
+        hash: Blake2bHash,
+        trusted_block_number: u32,
+    ) -> RPCResult<String, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let proof = blockchain.prove_transaction_receipt(&hash, trusted_block_number)?;
+            Ok(hex::encode(proof.serialize_to_vec()).into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_transactions_by_block_number(
         &mut self,
         block_number: u32,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error> {
         if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            ensure_history_available(&blockchain, block_number)?;
+
             // Get all the historic transactions that correspond to this block.
             let hist_txs = blockchain
                 .history_store
@@ -208,6 +240,8 @@ impl BlockchainInterface for BlockchainDispatcher {
         block_number: u32,
     ) -> RPCResult<Vec<Inherent>, (), Self::Error> {
         if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            ensure_history_available(&blockchain, block_number)?;
+
             // Get all the historic transactions that correspond to this block.
             let historic_tx_vec = blockchain
                 .history_store
@@ -240,6 +274,7 @@ impl BlockchainInterface for BlockchainDispatcher {
             let last_block = Policy::macro_block_of(batch_number).ok_or(Error::InvalidArgument(
                 "Batch number out of bounds".to_string(),
             ))?;
+            ensure_history_available(&blockchain, last_block)?;
 
             // Search all micro blocks of the batch to find the transactions.
             let mut transactions = vec![];
@@ -269,6 +304,7 @@ impl BlockchainInterface for BlockchainDispatcher {
             let macro_block_number = Policy::macro_block_of(batch_number).ok_or(
                 Error::InvalidArgument("Batch number out of bounds".to_string()),
             )?;
+            ensure_history_available(&blockchain, macro_block_number)?;
 
             let mut inherent_tx_vec = vec![];
 
@@ -381,6 +417,79 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_recent_forks(
+        &mut self,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<Rebranch>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let rebranches = blockchain
+                .rebranch_log
+                .recent(max.unwrap_or(100) as usize)
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<_>>();
+            Ok(rebranches.into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_account_proof(
+        &mut self,
+        address: Address,
+        block: Option<BlockNumberOrHash>,
+    ) -> RPCResult<AccountProof, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let block_hash = match block {
+                Some(BlockNumberOrHash::Number(block_number)) => Some(
+                    blockchain
+                        .get_block_at(block_number, false)
+                        .map_err(|_| Error::BlockNotFound(block_number))?
+                        .hash(),
+                ),
+                Some(BlockNumberOrHash::Hash(hash)) => Some(hash),
+                None => None,
+            };
+
+            // We prefer to prove against the latest macro block, which is final and carries the
+            // Tendermint justification. This requires a complete accounts snapshot, otherwise we
+            // prove against the head.
+            let macro_hash = blockchain.macro_head_hash();
+            let head_hash = blockchain.head_hash();
+            let snapshot_proof = if block_hash.is_none() || block_hash.as_ref() == Some(&macro_hash)
+            {
+                blockchain
+                    .get_account_snapshot_proof(&address)
+                    .map_err(|_| Error::IncompleteAccountsTrie)?
+            } else {
+                None
+            };
+            let (account, proof, block_hash) = match (snapshot_proof, block_hash) {
+                (Some((account, proof)), _) => (account, proof, macro_hash),
+                (None, Some(hash)) if hash != head_hash => {
+                    return Err(Error::AccountProofNotAvailable(hash));
+                }
+                (None, _) => {
+                    let (account, proof) = blockchain
+                        .get_account_proof(&address)
+                        .map_err(|_| Error::IncompleteAccountsTrie)?;
+                    (account, proof, head_hash)
+                }
+            };
+            let block = blockchain
+                .get_block(&block_hash, false)
+                .map_err(|_| Error::BlockNotFoundByHash(block_hash))?;
+
+            Ok(RPCData {
+                data: AccountProof::from_proof(address, account, &block, &proof),
+                metadata: BlockchainState::new(blockchain.block_number(), blockchain.head_hash()),
+            })
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_accounts(&mut self) -> RPCResult<Vec<Account>, BlockchainState, Self::Error> {
         let blockchain_proxy = self.blockchain.read();
         if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
@@ -594,6 +703,22 @@ impl BlockchainInterface for BlockchainDispatcher {
             .boxed())
     }
 
+    #[stream]
+    async fn subscribe_for_rebranches(
+        &mut self,
+    ) -> Result<BoxStream<'static, RPCData<Rebranch, ()>>, Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let stream = BroadcastStream::new(blockchain.rebranch_notifier.subscribe());
+            Ok(stream
+                .filter_map(|record| {
+                    future::ready(record.ok().map(|record| Rebranch::from(record).into()))
+                })
+                .boxed())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     #[stream]
     async fn subscribe_for_validator_election_by_address(
         &mut self,
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use nimiq_account::Account;
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainReadProxy;
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
@@ -8,37 +9,54 @@ use nimiq_consensus::ConsensusProxy;
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::{Address, Ed25519PublicKey, KeyPair, PrivateKey};
 use nimiq_network_libp2p::Network;
-use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
 use nimiq_rpc_interface::{
     consensus::ConsensusInterface,
-    types::{RPCResult, Transaction as RPCTransaction, ValidityStartHeight},
+    types::{
+        BlockchainState, RPCData, RPCResult, ScheduledTransaction as RPCScheduledTransaction,
+        SyncStatus, Transaction as RPCTransaction, TransactionSimulation, ValidityStartHeight,
+    },
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{
-    account::htlc_contract::{AnyHash, PreImage},
+    account::{
+        htlc_contract::{AnyHash, PreImage},
+        scheduled_vesting_contract::VestingSchedule,
+    },
     SignatureProof, Transaction,
 };
 use nimiq_transaction_builder::TransactionBuilder;
+use nimiq_wallet::{ScheduleTrigger, ScheduledAction, TransactionScheduler, UnlockedWallets};
 use parking_lot::RwLock;
 
-use crate::{error::Error, wallets::UnlockedWallets};
+use crate::error::Error;
 
 pub struct ConsensusDispatcher {
     consensus: ConsensusProxy<Network>,
     unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+    scheduler: Option<Arc<TransactionScheduler>>,
 }
 
 impl ConsensusDispatcher {
     pub fn new(
         consensus: ConsensusProxy<Network>,
         unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+        scheduler: Option<Arc<TransactionScheduler>>,
     ) -> Self {
         Self {
             consensus,
             unlocked_wallets,
+            scheduler,
         }
     }
 
+    /// Returns the transaction scheduler of the node wallet, if there is one.
+    fn get_scheduler(&self) -> Result<&TransactionScheduler, Error> {
+        self.scheduler
+            .as_deref()
+            .ok_or(Error::TransactionSchedulerNotAvailable)
+    }
+
     /// Tries to fetch the key pair for the wallet with the given address.
     fn get_wallet_keypair(&self, address: &Address) -> Result<KeyPair, Error> {
         Ok(self
@@ -77,6 +95,32 @@ impl ConsensusInterface for ConsensusDispatcher {
         Ok(self.consensus.is_established().into())
     }
 
+    async fn get_sync_status(&mut self) -> RPCResult<SyncStatus, (), Self::Error> {
+        let status = self.consensus.sync_status();
+        Ok(SyncStatus {
+            phase: status.phase.as_str().to_string(),
+            block_number: status.block_number,
+            target_epoch: status.target_epoch,
+            target_block: status.target_block,
+            epochs_done: status.epochs_done,
+            epochs_total: status.epochs_total,
+            chunks_done: status.chunks_done,
+            chunks_total: status.chunks_total,
+            chunks_per_second: status.chunks_per_second,
+            chunk_concurrency: status.chunk_concurrency,
+            missing_accounts_from: status
+                .missing_accounts_range
+                .map(|range| range.start.to_string()),
+            eta: status.eta.map(|eta| eta.as_secs()),
+            peers: status
+                .peers
+                .iter()
+                .map(|peer_id| peer_id.to_string())
+                .collect(),
+        }
+        .into())
+    }
+
     async fn get_raw_transaction_info(
         &mut self,
         raw_tx: String,
@@ -92,12 +136,109 @@ impl ConsensusInterface for ConsensusDispatcher {
         let tx = Transaction::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
         let txid = tx.hash::<Blake2bHash>();
 
+        // Don't broadcast transactions that the current protocol version doesn't support yet.
+        tx.verify_version(Policy::VERSION)?;
+
         match self.consensus.send_transaction(tx).await {
             Ok(_) => Ok(txid.into()),
             Err(e) => Err(Error::NetworkError(e)),
         }
     }
 
+    async fn simulate_transaction(
+        &mut self,
+        raw_tx: String,
+    ) -> RPCResult<TransactionSimulation, BlockchainState, Self::Error> {
+        let tx = Transaction::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
+
+        if let BlockchainReadProxy::Full(blockchain) = self.consensus.blockchain.read() {
+            let simulation = blockchain.simulate_transaction(&tx)?;
+            Ok(RPCData::new(
+                TransactionSimulation::from_simulation(simulation, &tx),
+                BlockchainState::new(blockchain.block_number(), blockchain.head_hash()),
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn schedule_raw_transaction(
+        &mut self,
+        raw_tx: String,
+        block_height: Option<u32>,
+    ) -> RPCResult<u64, (), Self::Error> {
+        let tx = Transaction::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
+        tx.verify(self.get_network_id())?;
+
+        let trigger =
+            ScheduleTrigger::BlockHeight(block_height.unwrap_or(tx.validity_start_height));
+        let scheduled = self
+            .get_scheduler()?
+            .schedule(trigger, ScheduledAction::Send(tx));
+
+        Ok(scheduled.id.into())
+    }
+
+    async fn schedule_redeem_timeout_htlc_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        fee: Coin,
+    ) -> RPCResult<u64, BlockchainState, Self::Error> {
+        let scheduler = self.get_scheduler()?;
+
+        if let BlockchainReadProxy::Full(blockchain) = self.consensus.blockchain.read() {
+            let contract = match blockchain
+                .get_account_if_complete(&contract_address)
+                .ok_or(Error::NoConsensus)?
+            {
+                Account::HTLC(contract) => contract,
+                _ => return Err(Error::AccountNotFound(contract_address)),
+            };
+
+            if contract.sender != wallet {
+                return Err(Error::InvalidArgument(format!(
+                    "Wallet {} is not the sender of HTLC {}",
+                    wallet, contract_address
+                )));
+            }
+
+            let scheduled = scheduler.schedule(
+                ScheduleTrigger::Timestamp(contract.timeout),
+                ScheduledAction::RedeemHtlcTimeout {
+                    wallet,
+                    contract_address,
+                    recipient,
+                    fee,
+                },
+            );
+
+            Ok(RPCData::new(
+                scheduled.id,
+                BlockchainState::new(blockchain.block_number(), blockchain.head_hash()),
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn list_scheduled_transactions(
+        &mut self,
+    ) -> RPCResult<Vec<RPCScheduledTransaction>, (), Self::Error> {
+        Ok(self
+            .get_scheduler()?
+            .list()
+            .into_iter()
+            .map(RPCScheduledTransaction::from)
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    async fn cancel_scheduled_transaction(&mut self, id: u64) -> RPCResult<bool, (), Self::Error> {
+        Ok(self.get_scheduler()?.remove(id).into())
+    }
+
     async fn create_basic_transaction(
         &mut self,
         wallet: Address,
@@ -276,6 +417,96 @@ impl ConsensusInterface for ConsensusDispatcher {
         self.send_raw_transaction(raw_tx).await
     }
 
+    async fn create_new_scheduled_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        schedules: Vec<VestingSchedule>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let transaction = TransactionBuilder::new_create_scheduled_vesting(
+            &self.get_wallet_keypair(&wallet)?,
+            owner,
+            schedules,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_new_scheduled_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        owner: Address,
+        schedules: Vec<VestingSchedule>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let raw_tx = self
+            .create_new_scheduled_vesting_transaction(
+                wallet,
+                owner,
+                schedules,
+                value,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
+    async fn create_redeem_scheduled_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let transaction = TransactionBuilder::new_redeem_scheduled_vesting(
+            &self.get_wallet_keypair(&wallet)?,
+            contract_address,
+            recipient,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_redeem_scheduled_vesting_transaction(
+        &mut self,
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let raw_tx = self
+            .create_redeem_scheduled_vesting_transaction(
+                wallet,
+                contract_address,
+                recipient,
+                value,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
     async fn create_new_htlc_transaction(
         &mut self,
         wallet: Address,
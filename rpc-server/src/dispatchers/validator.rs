@@ -4,7 +4,10 @@ use async_trait::async_trait;
 use nimiq_consensus::ConsensusProxy;
 use nimiq_keys::Address;
 use nimiq_network_libp2p::Network;
-use nimiq_rpc_interface::{types::RPCResult, validator::ValidatorInterface};
+use nimiq_rpc_interface::{
+    types::{RPCResult, ValidatorPerformance},
+    validator::ValidatorInterface,
+};
 use nimiq_serde::Serialize;
 use nimiq_validator::validator::ValidatorProxy;
 
@@ -34,18 +37,21 @@ impl ValidatorInterface for ValidatorDispatcher {
     }
 
     async fn get_signing_key(&mut self) -> RPCResult<String, (), Self::Error> {
-        Ok(hex::encode(self.validator.signing_key.read().private.serialize_to_vec()).into())
+        let signing_key = self
+            .validator
+            .signer
+            .signing_key()
+            .ok_or(Error::RemoteSignerKey)?;
+        Ok(hex::encode(signing_key.private.serialize_to_vec()).into())
     }
 
     async fn get_voting_key(&mut self) -> RPCResult<String, (), Self::Error> {
-        Ok(hex::encode(
-            self.validator
-                .voting_key
-                .read()
-                .secret_key
-                .serialize_to_vec(),
-        )
-        .into())
+        let voting_key = self
+            .validator
+            .signer
+            .voting_key()
+            .ok_or(Error::RemoteSignerKey)?;
+        Ok(hex::encode(voting_key.secret_key.serialize_to_vec()).into())
     }
 
     async fn set_automatic_reactivation(
@@ -69,4 +75,31 @@ impl ValidatorInterface for ValidatorDispatcher {
         let is_synced = self.consensus.is_ready_for_validation();
         Ok(is_synced.into())
     }
+
+    async fn get_validator_performance(
+        &mut self,
+        epoch_number: Option<u32>,
+    ) -> RPCResult<ValidatorPerformance, (), Self::Error> {
+        let tracker = self.validator.performance.read();
+        let performance = match epoch_number {
+            Some(epoch_number) => tracker
+                .get(epoch_number)
+                .ok_or(Error::NoPerformanceData(epoch_number))?,
+            None => tracker.current(),
+        };
+
+        Ok(ValidatorPerformance {
+            epoch_number: performance.epoch_number,
+            slots: performance.slots,
+            micro_blocks_expected: performance.micro_blocks_expected,
+            micro_blocks_produced: performance.micro_blocks_produced,
+            skip_blocks_caused: performance.skip_blocks_caused,
+            tendermint_rounds: performance.tendermint_rounds,
+            handel_contributions: performance.handel_contributions,
+            penalties: performance.penalties,
+            jail_events: performance.jail_events,
+            rewards: performance.rewards,
+        }
+        .into())
+    }
 }
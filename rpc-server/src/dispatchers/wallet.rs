@@ -9,10 +9,10 @@ use nimiq_rpc_interface::{
 };
 use nimiq_serde::Deserialize;
 use nimiq_utils::otp::Locked;
-use nimiq_wallet::{WalletAccount, WalletStore};
+use nimiq_wallet::{UnlockedWallets, WalletAccount, WalletStore};
 use parking_lot::RwLock;
 
-use crate::{error::Error, wallets::UnlockedWallets};
+use crate::error::Error;
 
 fn message_from_maybe_hex(s: String, is_hex: bool) -> Result<Vec<u8>, Error> {
     if is_hex {
@@ -28,10 +28,13 @@ pub struct WalletDispatcher {
 }
 
 impl WalletDispatcher {
-    pub fn new(wallet_store: Arc<WalletStore>) -> Self {
+    pub fn new(
+        wallet_store: Arc<WalletStore>,
+        unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+    ) -> Self {
         Self {
             wallet_store,
-            unlocked_wallets: Arc::new(RwLock::new(UnlockedWallets::default())),
+            unlocked_wallets,
         }
     }
 }
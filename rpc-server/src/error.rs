@@ -15,12 +15,18 @@ pub enum Error {
     #[error("Mempool rejected transaction: {0}")]
     MempoolError(VerifyErr),
 
+    #[error("Transaction simulation failed: {0}")]
+    SimulationError(#[from] nimiq_blockchain_interface::SimulationError),
+
     #[error("Block not found: {0}")]
     BlockNotFound(u32),
 
     #[error("Block not found: {0}")]
     BlockNotFoundByHash(Blake2bHash),
 
+    #[error("History of block {0} was pruned, the oldest stored history is of epoch {1}")]
+    HistoryPruned(u32, u32),
+
     #[error("Block number cannot be smaller than genesis block")]
     BlockNumberBeforeGenesis,
 
@@ -75,6 +81,9 @@ pub enum Error {
     #[error("{0}")]
     Argon2(#[from] nimiq_hash::argon2kdf::Argon2Error),
 
+    #[error("Failed to prove transaction receipt: {0}")]
+    ProveReceipt(#[from] nimiq_blockchain::ProveReceiptError),
+
     #[error("Transaction not found: {0}")]
     TransactionNotFound(Blake2bHash),
 
@@ -87,6 +96,24 @@ pub enum Error {
     #[error("No consensus")]
     NoConsensus,
 
+    #[error("Accounts can't be proven against block {0}")]
+    AccountProofNotAvailable(Blake2bHash),
+
+    #[error("The accounts trie is incomplete")]
+    IncompleteAccountsTrie,
+
+    #[error("Transaction scheduler not available")]
+    TransactionSchedulerNotAvailable,
+
+    #[error("No performance data for epoch {0}")]
+    NoPerformanceData(u32),
+
+    #[error("The validator key is held by a remote signer")]
+    RemoteSignerKey,
+
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(#[from] nimiq_primitives::transaction::TransactionError),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
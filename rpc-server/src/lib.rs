@@ -3,4 +3,3 @@ pub use nimiq_jsonrpc_server::{Config, Server};
 
 pub mod dispatchers;
 pub mod error;
-pub mod wallets;
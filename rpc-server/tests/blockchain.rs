@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use nimiq_account::Account;
+use nimiq_block::Block;
+use nimiq_blockchain::BlockchainConfig;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_hash::Hash;
+use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy, trie::trie_proof::TrieProof};
+use nimiq_rpc_interface::{blockchain::BlockchainInterface, types::BlockNumberOrHash};
+use nimiq_rpc_server::dispatchers::BlockchainDispatcher;
+use nimiq_serde::Deserialize;
+use nimiq_test_log::test;
+use nimiq_test_utils::{
+    block_production::TemporaryBlockProducer, blockchain::produce_macro_blocks,
+};
+
+#[test(tokio::test)]
+async fn it_proves_accounts_against_the_latest_macro_block() {
+    let temp_producer = TemporaryBlockProducer::new();
+    produce_macro_blocks(&temp_producer.producer, &temp_producer.blockchain, 1);
+    // The snapshot is rebuilt in the background by the consensus.
+    while temp_producer
+        .blockchain
+        .read()
+        .rebuild_accounts_snapshot_chunk()
+    {}
+    // Move the head past the macro block.
+    temp_producer.next_block(vec![], false);
+
+    let mut dispatcher =
+        BlockchainDispatcher::new(BlockchainProxy::from(Arc::clone(&temp_producer.blockchain)));
+    let address = Policy::STAKING_CONTRACT_ADDRESS;
+    let account_proof = dispatcher
+        .get_account_proof(address.clone(), None)
+        .await
+        .unwrap()
+        .data;
+
+    // The proof is against the latest macro block, which comes with its justification.
+    let block = Block::deserialize_from_vec(&account_proof.block).unwrap();
+    let macro_head = temp_producer.blockchain.read().macro_head();
+    assert_eq!(block.hash(), macro_head.hash());
+    assert!(block.unwrap_macro_ref().justification.is_some());
+
+    let proof = TrieProof::deserialize_from_vec(&account_proof.proof).unwrap();
+    let value = proof
+        .clone()
+        .verify_value(block.state_root(), &KeyNibbles::from(&address))
+        .unwrap()
+        .expect("The staking contract must be part of the proof");
+    let account = Account::deserialize_from_vec(&value).unwrap();
+    assert_eq!(account.balance(), account_proof.account.balance);
+
+    // The proof doesn't verify against any other state root.
+    assert!(proof
+        .verify_value(&"wrong root".hash(), &KeyNibbles::from(&address))
+        .is_err());
+}
+
+#[test(tokio::test)]
+async fn it_proves_accounts_against_the_head_without_snapshot() {
+    let temp_producer = TemporaryBlockProducer::with_config(BlockchainConfig {
+        accounts_snapshot: false,
+        ..Default::default()
+    });
+    produce_macro_blocks(&temp_producer.producer, &temp_producer.blockchain, 1);
+    temp_producer.next_block(vec![], false);
+
+    let mut dispatcher =
+        BlockchainDispatcher::new(BlockchainProxy::from(Arc::clone(&temp_producer.blockchain)));
+    let address = Policy::STAKING_CONTRACT_ADDRESS;
+    let (head_hash, head_number, macro_hash) = {
+        let blockchain = temp_producer.blockchain.read();
+        (
+            blockchain.head_hash(),
+            blockchain.block_number(),
+            blockchain.macro_head_hash(),
+        )
+    };
+
+    // Without a snapshot, the proof is against the head, also if the head is requested explicitly.
+    for block in [
+        None,
+        Some(BlockNumberOrHash::Hash(head_hash.clone())),
+        Some(BlockNumberOrHash::Number(head_number)),
+    ] {
+        let account_proof = dispatcher
+            .get_account_proof(address.clone(), block)
+            .await
+            .unwrap()
+            .data;
+
+        let block = Block::deserialize_from_vec(&account_proof.block).unwrap();
+        assert_eq!(block.hash(), head_hash);
+
+        let proof = TrieProof::deserialize_from_vec(&account_proof.proof).unwrap();
+        assert!(proof
+            .verify_value(block.state_root(), &KeyNibbles::from(&address))
+            .unwrap()
+            .is_some());
+    }
+
+    // Other blocks can't be proven against.
+    assert!(dispatcher
+        .get_account_proof(address.clone(), Some(BlockNumberOrHash::Hash(macro_hash)))
+        .await
+        .is_err());
+    assert!(dispatcher
+        .get_account_proof(address, Some(BlockNumberOrHash::Number(head_number + 1)))
+        .await
+        .is_err());
+}
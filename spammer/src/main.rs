@@ -212,8 +212,8 @@ async fn main_inner() -> Result<(), Error> {
     // Initialize RPC server
     if let Some(rpc_config) = rpc_config {
         use nimiq::extras::rpc_server::initialize_rpc_server;
-        let rpc_server = initialize_rpc_server(&client, rpc_config, client.wallet_store())
-            .expect("Failed to initialize RPC server");
+        let rpc_server =
+            initialize_rpc_server(&client, rpc_config).expect("Failed to initialize RPC server");
         tokio::spawn(async move { rpc_server.run().await });
     }
 
@@ -238,6 +238,7 @@ async fn main_inner() -> Result<(), Error> {
             client.mempool(),
             client.consensus_proxy(),
             client.network(),
+            client.validator_proxy(),
             &[],
         )
     }
@@ -107,11 +107,12 @@ pub trait Protocol: Clone + Send + Sync + Unpin + Sized + 'static {
         round: u32,
     ) -> Result<(ProposalMessage<Self::Proposal>, Self::Inherent), ProtocolError>;
 
-    /// Signs a given `proposal_message` for sending it over the wire
+    /// Signs a given `proposal_message` for sending it over the wire.
+    /// If the proposal cannot be signed, the node does not propose in this round.
     fn sign_proposal(
         &self,
         proposal_message: &ProposalMessage<Self::Proposal>,
-    ) -> Self::ProposalSignature;
+    ) -> Result<Self::ProposalSignature, ProtocolError>;
 
     /// Verifies a given `proposal`. Optionally a precomputed `precalculated_inherent` can be provided if the inherent has been computed before.
     /// All checks except for the signature verification can be skipped using the `signature_only` flag
@@ -117,10 +117,17 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
                 valid_round: Some(*valid_round),
             };
 
-            // Sign the proposal message
-            let signature = self.protocol.sign_proposal(&message);
+            // Sign the proposal message. Without a signature there is nothing to propose.
+            let Ok(signature) = self.protocol.sign_proposal(&message) else {
+                return Ok(self.skip_proposal());
+            };
 
             // Store the proposal for the current round.
+            let proposals = self
+                .state
+                .round_proposals
+                .entry(self.state.current_round)
+                .or_default();
             proposals.insert(proposal_hash.clone(), (Some(*valid_round), signature));
 
             // Yield the state as it has changed.
@@ -135,8 +142,10 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
             // Create a new proposal.
             let (message, inherent) = self.protocol.create_proposal(self.state.current_round)?;
 
-            // Sign the proposal message
-            let signature = self.protocol.sign_proposal(&message);
+            // Sign the proposal message. Without a signature there is nothing to propose.
+            let Ok(signature) = self.protocol.sign_proposal(&message) else {
+                return Ok(self.skip_proposal());
+            };
 
             // Hash it for identification and voting.
             let proposal_hash = message.proposal.hash();
@@ -153,10 +162,32 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
                 .insert(proposal_hash.clone(), message.proposal);
 
             // Store the proposal for the current round.
-            proposals.insert(proposal_hash, (None, signature));
+            self.state
+                .round_proposals
+                .entry(self.state.current_round)
+                .or_default()
+                .insert(proposal_hash, (None, signature));
 
             // Yield the state as it has changed.
             Ok(Return::Update(self.state.clone()))
         }
     }
+
+    /// Gives up on proposing in the current round, e.g. because the proposal could not be signed.
+    ///
+    /// Nothing was broadcast, so the node behaves as if the proposal did not arrive in time:
+    /// It votes nil and progresses to the prevote step.
+    fn skip_proposal(&mut self) -> Return<TProtocol> {
+        log::warn!(
+            current_round = self.state.current_round,
+            "Failed to sign our proposal, skipping it",
+        );
+
+        self.state
+            .votes
+            .insert((self.state.current_round, Step::Prevote), None);
+        self.state.current_step = Step::Prevote;
+
+        Return::Update(self.state.clone())
+    }
 }
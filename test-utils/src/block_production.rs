@@ -62,16 +62,14 @@ impl TemporaryBlockProducer {
     }
 
     pub fn new() -> Self {
+        Self::with_config(BlockchainConfig::default())
+    }
+
+    pub fn with_config(config: BlockchainConfig) -> Self {
         let time = Arc::new(OffsetTime::new());
         let env = VolatileDatabase::new(20).unwrap();
         let blockchain = Arc::new(RwLock::new(
-            Blockchain::new(
-                env,
-                BlockchainConfig::default(),
-                NetworkId::UnitAlbatross,
-                time,
-            )
-            .unwrap(),
+            Blockchain::new(env, config, NetworkId::UnitAlbatross, time).unwrap(),
         ));
 
         let signing_key = SchnorrKeyPair::from(
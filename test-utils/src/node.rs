@@ -97,6 +97,7 @@ impl<N: NetworkInterface + TestNetwork> Node<N> {
                 TESTING_BLS_CACHE_MAX_CAPACITY,
             ))),
             network.subscribe_events(),
+            None,
         )
         .await;
         let consensus = Consensus::<N>::new(
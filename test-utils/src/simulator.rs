@@ -0,0 +1,505 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use futures::{
+    future::{self, AbortHandle, Abortable},
+    StreamExt,
+};
+use nimiq_block::{Block, BlockTopic};
+use nimiq_blockchain::{BlockProducer, Blockchain};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_bls::KeyPair as BlsKeyPair;
+use nimiq_consensus::ConsensusEvent;
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_genesis_builder::{GenesisBuilder, GenesisInfo};
+use nimiq_keys::{Address, KeyPair as SchnorrKeyPair, SecureGenerate};
+use nimiq_network_interface::network::Network as NetworkInterface;
+use nimiq_network_mock::{LinkConfig, MockHub, MockNetwork, NetworkConditions};
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_time::{sleep, start_virtual_clock, system_time};
+use nimiq_utils::{spawn::spawn, time::systemtime_to_timestamp};
+use nimiq_validator::validator::ConsensusState;
+use parking_lot::RwLock;
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::{
+    node::Node,
+    validator::{build_validator, seeded_rng},
+};
+#[cfg(feature = "byzantine")]
+use nimiq_validator::byzantine::{Behaviour, ByzantineStrategy};
+
+/// The configuration of a [`Simulator`].
+#[derive(Clone, Debug)]
+pub struct SimulatorConfig {
+    /// The number of validators. Every validator owns the same number of slots.
+    pub num_validators: usize,
+    /// The number of history nodes that follow the chain without validating.
+    pub num_full_nodes: usize,
+    /// The seed from which the validator keys and the network faults are derived.
+    pub seed: u64,
+}
+
+impl Default for SimulatorConfig {
+    fn default() -> Self {
+        SimulatorConfig {
+            num_validators: 4,
+            num_full_nodes: 0,
+            seed: 0,
+        }
+    }
+}
+
+/// An event of a scripted scenario.
+#[derive(Clone, Debug)]
+pub enum SimulationEvent {
+    /// Stops the validator with the given index and disconnects it from the network.
+    KillValidator(usize),
+    /// Delays all messages sent by the validator with the given index, e.g. to make it miss its
+    /// proposer slots.
+    DelayValidator(usize, Duration),
+    /// Partitions the nodes into the given groups of node indices.
+    Partition(Vec<Vec<usize>>),
+    /// Heals any partition.
+    Heal,
+    /// Makes the proposer of the next micro block publish two conflicting blocks.
+    InjectEquivocation,
+}
+
+/// A validator running in a [`Simulator`].
+pub struct SimulatedValidator {
+    pub validator_address: Address,
+    pub signing_key: SchnorrKeyPair,
+    pub voting_key: BlsKeyPair,
+    pub network: Arc<MockNetwork>,
+    pub blockchain: Arc<RwLock<Blockchain>>,
+    pub consensus_state: Arc<RwLock<ConsensusState>>,
+    #[cfg(feature = "byzantine")]
+    behaviour: Behaviour,
+    abort_handle: AbortHandle,
+    is_alive: bool,
+}
+
+/// A history node running in a [`Simulator`].
+pub struct SimulatedNode {
+    pub network: Arc<MockNetwork>,
+    pub blockchain: Arc<RwLock<Blockchain>>,
+}
+
+/// A deterministic simulation of a network of validators and history nodes.
+///
+/// All nodes run on the current-thread runtime of the test over a [`MockHub`], and all timers
+/// use a virtual clock that starts at the genesis timestamp. Whenever all nodes are idle, the
+/// clock jumps to the next timer, so timeouts like the block producer timeout don't slow down
+/// the test. The network faults are derived from the seed of the configuration.
+///
+/// Nodes are identified by an index: validators come first, followed by the full nodes.
+pub struct Simulator {
+    hub: Option<MockHub>,
+    conditions: NetworkConditions,
+    pub genesis: GenesisInfo,
+    pub validators: Vec<SimulatedValidator>,
+    pub full_nodes: Vec<SimulatedNode>,
+}
+
+impl Simulator {
+    /// The interval at which the conditions of [`Simulator::run_until`] are checked.
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Starts the virtual clock and sets up all nodes of the simulation. Returns once the
+    /// validators have established consensus.
+    ///
+    /// This must be called from within a current-thread runtime.
+    pub async fn new(config: SimulatorConfig) -> Self {
+        assert!(
+            config.num_validators > 0,
+            "At least one validator is needed"
+        );
+
+        let hub = MockHub::with_seed(config.seed);
+        let conditions = hub.conditions();
+        let mut hub = Some(hub);
+
+        // Generate validator key pairs.
+        let mut rng = seeded_rng(config.seed);
+        let keys: Vec<_> = (0..config.num_validators)
+            .map(|_| {
+                (
+                    SchnorrKeyPair::generate(&mut rng),
+                    SchnorrKeyPair::generate(&mut rng),
+                    BlsKeyPair::generate(&mut rng),
+                    SchnorrKeyPair::generate(&mut rng),
+                )
+            })
+            .collect();
+
+        // Generate genesis block.
+        let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+        let mut genesis_builder = GenesisBuilder::default();
+        genesis_builder
+            .with_network(NetworkId::UnitAlbatross)
+            .with_genesis_block_number(Policy::genesis_block_number());
+        for (validator_key, signing_key, voting_key, _) in &keys {
+            genesis_builder.with_genesis_validator(
+                Address::from(validator_key),
+                signing_key.public,
+                voting_key.public_key,
+                Address::default(),
+                None,
+                None,
+                false,
+            );
+        }
+        let genesis = genesis_builder.generate(env).unwrap();
+
+        // From now on, time only advances when all nodes are idle.
+        start_virtual_clock(UNIX_EPOCH + Duration::from_millis(genesis.block.timestamp()));
+
+        // Instantiate validators.
+        let mut validators = vec![];
+        let mut consensus = vec![];
+        for (i, (validator_key, signing_key, voting_key, fee_key)) in keys.into_iter().enumerate() {
+            let (validator, c) = build_validator::<MockNetwork>(
+                i as u64 + 1,
+                Address::from(&validator_key),
+                false,
+                signing_key.clone(),
+                voting_key.clone(),
+                fee_key,
+                genesis.clone(),
+                &mut hub,
+                false,
+            )
+            .await;
+
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+            validators.push(SimulatedValidator {
+                validator_address: Address::from(&validator_key),
+                signing_key,
+                voting_key,
+                network: Arc::clone(&c.network),
+                blockchain: Arc::clone(&validator.blockchain),
+                consensus_state: validator.proxy().consensus_state,
+                #[cfg(feature = "byzantine")]
+                behaviour: validator.proxy().behaviour,
+                abort_handle,
+                is_alive: true,
+            });
+            consensus.push((validator, abort_registration, c));
+        }
+
+        // Connect the validators to each other.
+        for (i, validator) in validators.iter().enumerate() {
+            for other in &validators[i + 1..] {
+                validator.network.dial_mock(&other.network);
+            }
+        }
+
+        // Wait until the validators have established consensus, then start them.
+        let mut events: Vec<BroadcastStream<ConsensusEvent>> = consensus
+            .iter()
+            .map(|(_, _, c)| c.subscribe_events())
+            .collect();
+        let mut validator_futures = vec![];
+        for (validator, abort_registration, c) in consensus {
+            spawn(c);
+            validator_futures.push(Abortable::new(validator, abort_registration));
+        }
+        future::join_all(events.iter_mut().map(|e| e.next())).await;
+        for validator in validator_futures {
+            spawn(async move {
+                let _ = validator.await;
+            });
+        }
+
+        // Instantiate full nodes and connect them to all validators.
+        let mut full_nodes = vec![];
+        for i in 0..config.num_full_nodes {
+            let mut node = Node::<MockNetwork>::history_with_genesis_info(
+                (validators.len() + i) as u64 + 1,
+                genesis.clone(),
+                &mut hub,
+                false,
+            )
+            .await;
+            for validator in &validators {
+                node.network.dial_mock(&validator.network);
+            }
+            node.consume();
+            full_nodes.push(SimulatedNode {
+                network: node.network,
+                blockchain: node.blockchain,
+            });
+        }
+
+        Simulator {
+            hub,
+            conditions,
+            genesis,
+            validators,
+            full_nodes,
+        }
+    }
+
+    /// Returns the hub all nodes are connected to.
+    pub fn hub(&self) -> &MockHub {
+        self.hub.as_ref().unwrap()
+    }
+
+    /// Returns the virtual time that passed since the genesis block.
+    pub fn elapsed(&self) -> Duration {
+        system_time()
+            .duration_since(UNIX_EPOCH + Duration::from_millis(self.genesis.block.timestamp()))
+            .unwrap_or_default()
+    }
+
+    fn network(&self, node: usize) -> &Arc<MockNetwork> {
+        match self.validators.get(node) {
+            Some(validator) => &validator.network,
+            None => &self.full_nodes[node - self.validators.len()].network,
+        }
+    }
+
+    /// Returns the blockchains of all nodes that are still running.
+    fn live_blockchains(&self) -> impl Iterator<Item = &Arc<RwLock<Blockchain>>> {
+        self.validators
+            .iter()
+            .filter(|validator| validator.is_alive)
+            .map(|validator| &validator.blockchain)
+            .chain(self.full_nodes.iter().map(|node| &node.blockchain))
+    }
+
+    /// Returns the lowest block number of all nodes that are still running.
+    pub fn block_number(&self) -> u32 {
+        self.live_blockchains()
+            .map(|blockchain| blockchain.read().block_number())
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// Returns whether a running validator collected an equivocation proof against the validator
+    /// with the given address, which wasn't included in a block yet.
+    pub fn has_pending_equivocation_proof(&self, validator_address: &Address) -> bool {
+        self.validators
+            .iter()
+            .filter(|validator| validator.is_alive)
+            .any(|validator| {
+                validator
+                    .consensus_state
+                    .read()
+                    .equivocation_proofs()
+                    .any(|proof| proof.validator_address() == validator_address)
+            })
+    }
+
+    /// Runs the simulation until `condition` is met or `timeout` of virtual time passed. Returns
+    /// whether the condition was met.
+    pub async fn run_until<F: FnMut(&Self) -> bool>(
+        &self,
+        mut condition: F,
+        timeout: Duration,
+    ) -> bool {
+        let deadline = self.elapsed() + timeout;
+        while !condition(self) {
+            if self.elapsed() >= deadline {
+                return false;
+            }
+            sleep(Self::POLL_INTERVAL).await;
+        }
+        true
+    }
+
+    /// Runs the simulation until all running nodes reached `block_number` or `timeout` of virtual
+    /// time passed. Returns whether the block number was reached.
+    pub async fn run_until_block(&self, block_number: u32, timeout: Duration) -> bool {
+        self.run_until(
+            |simulator| simulator.block_number() >= block_number,
+            timeout,
+        )
+        .await
+    }
+
+    /// Runs a scripted scenario. Every event is applied once all running nodes reached the block
+    /// number it is scheduled at. Panics if a block number isn't reached within `timeout` of
+    /// virtual time.
+    pub async fn run_scenario<I>(&mut self, scenario: I, timeout: Duration)
+    where
+        I: IntoIterator<Item = (u32, SimulationEvent)>,
+    {
+        for (block_number, event) in scenario {
+            self.assert_liveness(block_number, timeout).await;
+            log::info!(block_number, ?event, "Applying simulation event");
+            self.apply(event).await;
+        }
+    }
+
+    /// Applies a single event to the simulation.
+    pub async fn apply(&mut self, event: SimulationEvent) {
+        match event {
+            SimulationEvent::KillValidator(index) => self.kill_validator(index),
+            SimulationEvent::DelayValidator(index, delay) => self.delay_validator(index, delay),
+            SimulationEvent::Partition(groups) => self.partition(&groups),
+            SimulationEvent::Heal => self.conditions.heal(),
+            SimulationEvent::InjectEquivocation => {
+                self.inject_equivocation().await;
+            }
+        }
+    }
+
+    /// Stops the validator with the given index and disconnects it from the network.
+    pub fn kill_validator(&mut self, index: usize) {
+        let validator = &mut self.validators[index];
+        validator.abort_handle.abort();
+        validator.network.disconnect();
+        validator.is_alive = false;
+    }
+
+    /// Delays all messages sent by the validator with the given index by `delay`.
+    pub fn delay_validator(&self, index: usize, delay: Duration) {
+        let address = self.validators[index].network.address();
+        for node in 0..self.validators.len() + self.full_nodes.len() {
+            if node != index {
+                self.conditions.set_link(
+                    address,
+                    self.network(node).address(),
+                    LinkConfig {
+                        latency: delay,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+    }
+
+    /// Makes the validator with the given index follow the given byzantine strategy, or behave
+    /// honestly again if `strategy` is `None`.
+    #[cfg(feature = "byzantine")]
+    pub fn set_byzantine_strategy(
+        &self,
+        index: usize,
+        strategy: Option<Arc<dyn ByzantineStrategy>>,
+    ) {
+        self.validators[index].behaviour.set_strategy(strategy);
+    }
+
+    /// Partitions the nodes into the given groups of node indices.
+    pub fn partition(&self, groups: &[Vec<usize>]) {
+        let groups: Vec<Vec<_>> = groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|node| self.network(*node).address())
+                    .collect()
+            })
+            .collect();
+        let groups: Vec<&[_]> = groups.iter().map(Vec::as_slice).collect();
+        self.conditions.partition(&groups);
+    }
+
+    /// Makes the proposer of the next micro block publish two conflicting blocks for its slot.
+    /// Returns the index of the equivocating validator, or `None` if the next block is a macro
+    /// block or its proposer isn't running.
+    pub async fn inject_equivocation(&self) -> Option<usize> {
+        let (index, blocks) = {
+            let blockchain = self
+                .live_blockchains()
+                .next()
+                .expect("No running node left")
+                .read();
+            let block_number = blockchain.block_number() + 1;
+            if Policy::is_macro_block_at(block_number) {
+                return None;
+            }
+
+            let slot = blockchain.get_proposer_at(block_number, 0, None).ok()?;
+            let index = self.validators.iter().position(|validator| {
+                validator.is_alive
+                    && validator.voting_key.public_key.compress()
+                        == *slot.validator.voting_key.compressed()
+            })?;
+
+            let validator = &self.validators[index];
+            let producer =
+                BlockProducer::new(validator.signing_key.clone(), validator.voting_key.clone());
+            let timestamp = u64::max(
+                blockchain.timestamp(),
+                systemtime_to_timestamp(system_time()),
+            );
+            let blocks: Vec<_> = [b"equivocation-1".to_vec(), b"equivocation-2".to_vec()]
+                .into_iter()
+                .map(|extra_data| {
+                    Block::Micro(producer.next_micro_block(
+                        &blockchain,
+                        timestamp,
+                        vec![],
+                        vec![],
+                        extra_data,
+                        None,
+                    ))
+                })
+                .collect();
+            (index, blocks)
+        };
+
+        let network = &self.validators[index].network;
+        for block in blocks {
+            if let Err(error) = network.publish::<BlockTopic>(block).await {
+                log::warn!(%error, "Failed to publish equivocating block");
+            }
+        }
+        Some(index)
+    }
+
+    /// Asserts that all running nodes reach `block_number` within `timeout` of virtual time.
+    pub async fn assert_liveness(&self, block_number: u32, timeout: Duration) {
+        assert!(
+            self.run_until_block(block_number, timeout).await,
+            "Nodes didn't reach block #{} within {:?}, lowest block number is #{}",
+            block_number,
+            timeout,
+            self.block_number(),
+        );
+    }
+
+    /// Asserts that no two nodes, including stopped validators, finalized different macro blocks
+    /// at the same block number.
+    pub fn assert_finality_safety(&self) {
+        let blockchains = self
+            .validators
+            .iter()
+            .map(|validator| &validator.blockchain)
+            .chain(self.full_nodes.iter().map(|node| &node.blockchain));
+
+        let mut finalized = BTreeMap::new();
+        for (node, blockchain) in blockchains.enumerate() {
+            let blockchain = blockchain.read();
+            let macro_head = blockchain.macro_head().block_number();
+
+            let mut block_number = Policy::genesis_block_number();
+            while block_number <= macro_head {
+                let hash = blockchain
+                    .get_block_at(block_number, false, None)
+                    .expect("Finalized macro block is missing")
+                    .hash();
+                match finalized.entry(block_number) {
+                    Entry::Vacant(entry) => {
+                        entry.insert((node, hash));
+                    }
+                    Entry::Occupied(entry) => {
+                        let (other_node, other_hash) = entry.get();
+                        assert_eq!(
+                            &hash, other_hash,
+                            "Nodes {} and {} finalized different macro blocks at #{}",
+                            other_node, node, block_number,
+                        );
+                    }
+                }
+                block_number = Policy::macro_block_after(block_number);
+            }
+        }
+    }
+}
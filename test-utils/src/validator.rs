@@ -13,7 +13,7 @@ use nimiq_network_mock::MockHub;
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_utils::spawn::spawn;
-use nimiq_validator::validator::Validator;
+use nimiq_validator::{signer::LocalSigner, validator::Validator};
 use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
 use rand::{rngs::StdRng, SeedableRng};
 use tokio_stream::wrappers::BroadcastStream;
@@ -52,9 +52,10 @@ where
             validator_network,
             validator_address,
             automatic_reactivate,
-            signing_key,
-            voting_key,
+            None,
+            Arc::new(LocalSigner::new(signing_key, voting_key)),
             fee_key,
+            false,
             MempoolConfig::default(),
         ),
         consensus,
@@ -166,7 +167,7 @@ where
     validators
         .iter()
         .find(|validator| {
-            &validator.voting_key().public_key.compress() == slot.validator.voting_key.compressed()
+            &validator.voting_public_key().compress() == slot.validator.voting_key.compressed()
         })
         .unwrap()
 }
@@ -192,7 +193,7 @@ where
     let index = validators
         .iter()
         .position(|validator| {
-            &validator.voting_key().public_key.compress() == slot.validator.voting_key.compressed()
+            &validator.voting_public_key().compress() == slot.validator.voting_key.compressed()
         })
         .unwrap();
     validators.remove(index)
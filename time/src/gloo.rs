@@ -1,4 +1,9 @@
-use std::{convert::TryInto, future::Future, pin::pin, time::Duration};
+use std::{
+    convert::TryInto,
+    future::Future,
+    pin::pin,
+    time::{Duration, SystemTime},
+};
 
 use futures::future::{select, Either};
 use gloo_timers::future::{IntervalStream, TimeoutFuture};
@@ -41,3 +46,8 @@ pub fn sleep(duration: Duration) -> impl Future<Output = ()> {
         .expect("Duration as millis must fit in u32");
     SendWrapper::new(TimeoutFuture::new(millis))
 }
+
+/// Returns the current wall-clock time. Virtual clocks are not supported in WASM.
+pub fn system_time() -> SystemTime {
+    SystemTime::now()
+}
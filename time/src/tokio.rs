@@ -1,4 +1,8 @@
-use std::{future::Future, time::Duration};
+use std::{
+    cell::Cell,
+    future::Future,
+    time::{Duration, SystemTime},
+};
 
 use tokio::time::{
     interval_at, sleep as tokio_sleep, timeout as tokio_timeout, Instant, Sleep, Timeout,
@@ -22,3 +26,44 @@ pub fn timeout<F: Future>(duration: Duration, future: F) -> Timeout<F> {
 pub fn sleep(duration: Duration) -> Sleep {
     tokio_sleep(duration)
 }
+
+thread_local! {
+    /// The wall-clock time at which the virtual clock of this thread was started, together with
+    /// the (paused) tokio instant at that moment.
+    static VIRTUAL_CLOCK: Cell<Option<(SystemTime, Instant)>> = const { Cell::new(None) };
+}
+
+/// Returns the current wall-clock time. If a virtual clock is running on the current thread, the
+/// time advances with the virtual clock instead.
+pub fn system_time() -> SystemTime {
+    match VIRTUAL_CLOCK.with(Cell::get) {
+        Some((start, instant)) => start + instant.elapsed(),
+        None => SystemTime::now(),
+    }
+}
+
+/// Starts a virtual clock at the wall-clock time `start`. This pauses the tokio clock, so that
+/// timers only fire once the runtime is idle or the clock is advanced, and makes [`system_time`]
+/// follow it.
+///
+/// The virtual clock is tied to the current thread, so it must be started from within a
+/// current-thread runtime.
+#[cfg(feature = "virtual-time")]
+pub fn start_virtual_clock(start: SystemTime) {
+    tokio::time::pause();
+    VIRTUAL_CLOCK.with(|clock| clock.set(Some((start, Instant::now()))));
+}
+
+/// Stops the virtual clock of the current thread and resumes the tokio clock.
+#[cfg(feature = "virtual-time")]
+pub fn stop_virtual_clock() {
+    VIRTUAL_CLOCK.with(|clock| clock.set(None));
+    tokio::time::resume();
+}
+
+/// Advances the virtual clock of the current thread by `duration`, firing all timers that
+/// expire in between.
+#[cfg(feature = "virtual-time")]
+pub async fn advance(duration: Duration) {
+    tokio::time::advance(duration).await
+}
@@ -0,0 +1,90 @@
+use std::{
+    fs,
+    io::{stdin, Read},
+    process::exit,
+    str::FromStr,
+};
+
+use anyhow::Error;
+use clap::{crate_authors, crate_description, crate_version, Arg, Command};
+use nimiq_block::TransactionReceiptProof;
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::networks::NetworkId;
+use nimiq_serde::Deserialize;
+use thiserror::Error;
+
+fn run_app() -> Result<(), Error> {
+    let matches = Command::new("Verify transaction receipt")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about(crate_description!())
+        .arg(
+            Arg::new("proof")
+                .value_name("FILE")
+                .help("Read the hex-encoded receipt proof from FILE instead of STDIN."),
+        )
+        .arg(
+            Arg::new("trusted_block")
+                .short('t')
+                .long("trusted-block")
+                .value_name("HASH")
+                .help("Hash of the trusted election block the proof is verified against."),
+        )
+        .arg(
+            Arg::new("network_id")
+                .short('N')
+                .long("network")
+                .value_name("NETWORK")
+                .help("Set network ID"),
+        )
+        .get_matches();
+
+    let trusted_hash = Blake2bHash::from_str(
+        matches
+            .get_one::<String>("trusted_block")
+            .ok_or(AppError::TrustedBlock)?,
+    )?;
+    let network_id = match matches.get_one::<String>("network_id") {
+        Some(s) => NetworkId::from_str(s)?,
+        None => NetworkId::Main,
+    };
+
+    // read proof either from file or stdin
+    let hex_proof = match matches.get_one::<String>("proof") {
+        Some(path) => fs::read_to_string(path)?,
+        None => {
+            let mut hex_proof = String::new();
+            stdin().read_to_string(&mut hex_proof)?;
+            hex_proof
+        }
+    };
+    let proof = TransactionReceiptProof::deserialize_from_vec(&hex::decode(hex_proof.trim())?)?;
+
+    let transactions = proof.verify(&trusted_hash, network_id)?;
+    println!(
+        "Proof is valid for block #{} ({})",
+        proof.block.block_number(),
+        proof.block.hash()
+    );
+    for transaction in transactions {
+        let hash: Blake2bHash = transaction.tx_hash().into();
+        println!("{} in block #{}", hash, transaction.block_number);
+    }
+    Ok(())
+}
+
+fn main() {
+    exit(match run_app() {
+        Ok(_) => 0,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    });
+}
+
+#[derive(Debug, Error)]
+enum AppError {
+    #[error("Trusted block hash is missing")]
+    TrustedBlock,
+}
@@ -0,0 +1,136 @@
+use std::{fs, path::PathBuf, process::exit, sync::Arc};
+
+use anyhow::Error;
+use clap::{crate_authors, crate_version, value_parser, Arg, Command};
+use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
+use nimiq_keys::{KeyPair, PrivateKey};
+use nimiq_serde::Deserialize;
+use nimiq_utils::file_store::FileStore;
+use nimiq_validator::signer::{
+    LocalSigner, ProtectedSigner, SignerAddress, SignerServer, SlashingProtection,
+};
+use thiserror::Error;
+
+fn run_app() -> Result<(), Error> {
+    let matches = Command::new("Validator signer")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about("Holds the signing key and the voting key of a validator and signs on its behalf, refusing to sign conflicting messages.")
+        .arg(
+            Arg::new("listen")
+                .short('l')
+                .long("listen")
+                .value_name("ADDRESS")
+                .required(true)
+                .value_parser(value_parser!(SignerAddress))
+                .help("Listen on ADDRESS, either unix:<path> or <host>:<port>."),
+        )
+        .arg(
+            Arg::new("auth_token_file")
+                .short('t')
+                .long("auth-token-file")
+                .value_name("FILE")
+                .required(true)
+                .help("Read the token shared with the validator from FILE."),
+        )
+        .arg(
+            Arg::new("protection_file")
+                .short('p')
+                .long("protection-file")
+                .value_name("FILE")
+                .required(true)
+                .value_parser(value_parser!(PathBuf))
+                .help("Keep the slashing protection records in FILE."),
+        )
+        .arg(
+            Arg::new("signing_key_file")
+                .long("signing-key-file")
+                .value_name("FILE")
+                .help("Load the signing key from FILE, as written by the client."),
+        )
+        .arg(
+            Arg::new("signing_key")
+                .long("signing-key")
+                .value_name("SECRET_KEY")
+                .conflicts_with("signing_key_file")
+                .help("Use the hex encoded signing key SECRET_KEY."),
+        )
+        .arg(
+            Arg::new("voting_key_file")
+                .long("voting-key-file")
+                .value_name("FILE")
+                .help("Load the voting key from FILE, as written by the client."),
+        )
+        .arg(
+            Arg::new("voting_key")
+                .long("voting-key")
+                .value_name("SECRET_KEY")
+                .conflicts_with("voting_key_file")
+                .help("Use the hex encoded voting key SECRET_KEY."),
+        )
+        .get_matches();
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::builder()
+                .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        .init();
+
+    let signing_key: KeyPair = if let Some(file) = matches.get_one::<String>("signing_key_file") {
+        FileStore::new(file).load()?
+    } else if let Some(hex_key) = matches.get_one::<String>("signing_key") {
+        PrivateKey::deserialize_from_vec(&hex::decode(hex_key)?)?.into()
+    } else {
+        return Err(AppError::SigningKey.into());
+    };
+
+    let voting_key: BlsKeyPair = if let Some(file) = matches.get_one::<String>("voting_key_file") {
+        FileStore::new(file).load()?
+    } else if let Some(hex_key) = matches.get_one::<String>("voting_key") {
+        BlsSecretKey::deserialize_from_vec(&hex::decode(hex_key)?)?.into()
+    } else {
+        return Err(AppError::VotingKey.into());
+    };
+
+    let auth_token = fs::read_to_string(matches.get_one::<String>("auth_token_file").unwrap())?
+        .trim()
+        .to_string();
+    if auth_token.is_empty() {
+        return Err(AppError::AuthToken.into());
+    }
+
+    let protection =
+        SlashingProtection::open(matches.get_one::<PathBuf>("protection_file").unwrap())?;
+    let signer = ProtectedSigner::new(LocalSigner::new(signing_key, voting_key), protection);
+
+    let server = SignerServer::bind(
+        matches.get_one::<SignerAddress>("listen").unwrap(),
+        auth_token,
+        Arc::new(signer),
+    )?;
+    log::info!(address = %server.local_address()?, "Signer listening");
+    server.run()?;
+    Ok(())
+}
+
+fn main() {
+    exit(match run_app() {
+        Ok(_) => 0,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    });
+}
+
+#[derive(Debug, Error)]
+enum AppError {
+    #[error("Signing key is missing")]
+    SigningKey,
+    #[error("Voting key is missing")]
+    VotingKey,
+    #[error("Authentication token is empty")]
+    AuthToken,
+}
@@ -3,11 +3,15 @@ use nimiq_hash::Blake2bHash;
 use nimiq_keys::{Address, Ed25519PublicKey, KeyPair};
 use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
 use nimiq_transaction::{
-    account::htlc_contract::{AnyHash, PreImage},
+    account::{
+        htlc_contract::{AnyHash, PreImage},
+        scheduled_vesting_contract::VestingSchedule,
+    },
     SignatureProof, Transaction,
 };
 use thiserror::Error;
 
+use crate::recipient::scheduled_vesting_contract::ScheduledVestingRecipientBuilderError;
 pub use crate::{proof::TransactionProofBuilder, recipient::Recipient, sender::Sender};
 
 pub mod proof;
@@ -68,6 +72,9 @@ pub enum TransactionBuilderError {
     /// [`signaling transaction`]: struct.TransactionBuilder.html#method.with_value
     #[error("The value must be zero for signaling transactions and cannot be zero for others.")]
     InvalidValue,
+    /// The release schedules of a new scheduled vesting contract are invalid.
+    #[error("Invalid vesting schedules: {0}")]
+    InvalidVestingSchedules(#[from] ScheduledVestingRecipientBuilderError),
 }
 
 /// A helper to build arbitrary transactions.
@@ -607,6 +614,105 @@ impl TransactionBuilder {
         }
     }
 
+    /// Creates a transaction that creates a new scheduled vesting contract.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The key pair used to sign the outgoing transaction. The vesting
+    ///                             contract value is sent from the basic account belonging to this
+    ///                             key pair.
+    ///  - `owner`:                 The address of the owner of the vesting contract.
+    ///  - `schedules`:             The release schedules of the contract, each consisting of a
+    ///                             cliff followed by tranches. Their amounts must not exceed
+    ///                             `value`.
+    ///  - `value`:                 The value for the vesting contract. This is sent from the
+    ///                             account belonging to `key_pair`.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction.
+    ///
+    pub fn new_create_scheduled_vesting(
+        key_pair: &KeyPair,
+        owner: Address,
+        schedules: Vec<VestingSchedule>,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let mut recipient = Recipient::new_scheduled_vesting_builder(owner);
+        for schedule in schedules {
+            recipient.with_schedule(schedule);
+        }
+
+        let mut builder = Self::new();
+        builder
+            .with_sender(Sender::new_basic(Address::from(key_pair)))
+            .with_recipient(recipient.generate()?)
+            .with_value(value)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        let proof_builder = builder.generate()?;
+        match proof_builder {
+            TransactionProofBuilder::Basic(mut builder) => {
+                builder.sign_with_key_pair(key_pair);
+                Ok(builder.generate().unwrap())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Creates a transaction that redeems funds from a scheduled vesting contract.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The key pair used to sign the transaction. This key pair
+    ///                             corresponds to the owner of the vesting contract
+    ///  - `contract_address`:      The address of the scheduled vesting contract.
+    ///  - `recipient`:             The address of the basic account that will receive the funds.
+    ///  - `value`:                 The value that will be sent to the recipient account.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction.
+    ///
+    pub fn new_redeem_scheduled_vesting(
+        key_pair: &KeyPair,
+        contract_address: Address,
+        recipient: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let mut builder = Self::new();
+        builder
+            .with_sender(Sender::new_scheduled_vesting(contract_address))
+            .with_recipient(Recipient::new_basic(recipient))
+            .with_value(value)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        let proof_builder = builder.generate()?;
+        match proof_builder {
+            TransactionProofBuilder::Vesting(mut builder) => {
+                builder.sign_with_key_pair(key_pair);
+                Ok(builder.generate().unwrap())
+            }
+            _ => unreachable!(),
+        }
+    }
+
     /// Creates a transaction that creates a new HTLC contract.
     ///
     /// # Arguments
@@ -19,7 +19,7 @@ pub mod staking_contract;
 /// The proof mostly depends on the sender account (with the exception of incoming staking transactions).
 ///
 /// Thus, there exist four different types of proof builders:
-/// - [`BasicProofBuilder`] (for basic and vesting sender accounts)
+/// - [`BasicProofBuilder`] (for basic and (scheduled) vesting sender accounts)
 /// - [`HtlcProofBuilder`] (for HTLC sender accounts)
 /// - [`StakingProofBuilder`] (for outgoing staking transactions)
 /// - [`StakingDataBuilder`] (that build the staking data and return a normal proof builder)
@@ -44,7 +44,7 @@ impl TransactionProofBuilder {
             AccountType::Basic => {
                 TransactionProofBuilder::Basic(BasicProofBuilder::new(transaction))
             }
-            AccountType::Vesting => {
+            AccountType::Vesting | AccountType::ScheduledVesting => {
                 TransactionProofBuilder::Vesting(BasicProofBuilder::new(transaction))
             }
             AccountType::HTLC => TransactionProofBuilder::Htlc(HtlcProofBuilder::new(transaction)),
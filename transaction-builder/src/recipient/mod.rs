@@ -3,16 +3,19 @@ use nimiq_primitives::{account::AccountType, policy::Policy};
 use nimiq_serde::Serialize;
 use nimiq_transaction::account::{
     htlc_contract::CreationTransactionData as HtlcCreationData,
+    scheduled_vesting_contract::CreationTransactionData as ScheduledVestingCreationData,
     staking_contract::IncomingStakingTransactionData,
     vesting_contract::CreationTransactionData as VestingCreationData,
 };
 
 use crate::recipient::{
-    htlc_contract::HtlcRecipientBuilder, staking_contract::StakingRecipientBuilder,
-    vesting_contract::VestingRecipientBuilder,
+    htlc_contract::HtlcRecipientBuilder,
+    scheduled_vesting_contract::ScheduledVestingRecipientBuilder,
+    staking_contract::StakingRecipientBuilder, vesting_contract::VestingRecipientBuilder,
 };
 
 pub mod htlc_contract;
+pub mod scheduled_vesting_contract;
 pub mod staking_contract;
 pub mod vesting_contract;
 
@@ -21,15 +24,18 @@ pub mod vesting_contract;
 ///
 /// New contracts can be created using dedicated builders as described below.
 ///
-/// There are four types of recipients:
+/// There are five types of recipients:
 /// - basic recipients that can be built with [`new_basic`]
 /// - HTLC contracts that can be set up with a builder using [`new_htlc_builder`]
 /// - vesting contracts that can be set up with a builder using [`new_vesting_builder`]
+/// - scheduled vesting contracts that can be set up with a builder using
+///   [`new_scheduled_vesting_builder`]
 /// - actions on the staking contract that built with [`new_staking_builder`]
 ///
 /// [`new_basic`]: enum.Recipient.html#method.new_basic
 /// [`new_htlc_builder`]: enum.Recipient.html#method.new_htlc_builder
 /// [`new_vesting_builder`]: enum.Recipient.html#method.new_vesting_builder
+/// [`new_scheduled_vesting_builder`]: enum.Recipient.html#method.new_scheduled_vesting_builder
 /// [`new_staking_builder`]: enum.Recipient.html#method.new_staking_builder
 #[derive(Clone, Debug)]
 pub enum Recipient {
@@ -43,6 +49,9 @@ pub enum Recipient {
     VestingCreation {
         data: VestingCreationData,
     },
+    ScheduledVestingCreation {
+        data: ScheduledVestingCreationData,
+    },
     Staking {
         data: IncomingStakingTransactionData,
     },
@@ -142,6 +151,32 @@ impl Recipient {
         VestingRecipientBuilder::new(owner)
     }
 
+    /// Initiates a [`ScheduledVestingRecipientBuilder`] that can be used to create new scheduled
+    /// vesting contracts owned by the `owner` address.
+    /// The [`generate`] method of the builder will then return a `Recipient`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nimiq_transaction_builder::Recipient;
+    /// use nimiq_keys::Address;
+    /// use nimiq_primitives::coin::Coin;
+    ///
+    /// let owner = Address::from_any_str("NQ25 B7NR A1HC V4R2 YRKD 20PR RPGS MNV7 D812").unwrap();
+    /// let mut recipient_builder = Recipient::new_scheduled_vesting_builder(owner);
+    /// recipient_builder
+    ///     .with_cliff(10_000, Coin::from_u64_unchecked(1_000))
+    ///     .with_tranche(100, Coin::from_u64_unchecked(500), Coin::from_u64_unchecked(2_000));
+    /// let recipient = recipient_builder.generate();
+    /// assert!(recipient.is_ok());
+    /// ```
+    ///
+    /// [`ScheduledVestingRecipientBuilder`]: scheduled_vesting_contract/struct.ScheduledVestingRecipientBuilder.html
+    /// [`generate`]: scheduled_vesting_contract/struct.ScheduledVestingRecipientBuilder.html#method.generate
+    pub fn new_scheduled_vesting_builder(owner: Address) -> ScheduledVestingRecipientBuilder {
+        ScheduledVestingRecipientBuilder::new(owner)
+    }
+
     /// Initiates a [`StakingRecipientBuilder`] that can be used to interact with the staking
     /// contract at address `staking_contract`.
     /// The [`generate`] method of the builder will then return a `Recipient`.
@@ -169,12 +204,14 @@ impl Recipient {
     }
 
     /// This method checks whether the transaction is a contract creation.
-    /// Vesting and HTLC recipients do create new contracts.
+    /// Vesting, scheduled vesting and HTLC recipients do create new contracts.
     /// Basic recipients and the staking contract do not create new contracts.
     pub fn is_creation(&self) -> bool {
         matches!(
             self,
-            Recipient::HtlcCreation { .. } | Recipient::VestingCreation { .. }
+            Recipient::HtlcCreation { .. }
+                | Recipient::VestingCreation { .. }
+                | Recipient::ScheduledVestingCreation { .. }
         )
     }
 
@@ -200,6 +237,7 @@ impl Recipient {
             Recipient::Basic { .. } => AccountType::Basic,
             Recipient::HtlcCreation { .. } => AccountType::HTLC,
             Recipient::VestingCreation { .. } => AccountType::Vesting,
+            Recipient::ScheduledVestingCreation { .. } => AccountType::ScheduledVesting,
             Recipient::Staking { .. } => AccountType::Staking,
         }
     }
@@ -219,6 +257,7 @@ impl Recipient {
             Recipient::Basic { data, .. } => data.clone(),
             Recipient::HtlcCreation { data } => data.serialize_to_vec(),
             Recipient::VestingCreation { data } => data.to_tx_data(),
+            Recipient::ScheduledVestingCreation { data } => data.to_tx_data(),
             Recipient::Staking { data } => data.serialize_to_vec(),
         }
     }
@@ -0,0 +1,129 @@
+use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
+use nimiq_transaction::account::scheduled_vesting_contract::{
+    CreationTransactionData as ScheduledVestingCreationData, VestingSchedule, VestingTranche,
+};
+use thiserror::Error;
+
+use crate::recipient::Recipient;
+
+/// Building a scheduled vesting recipient can fail if the release schedules are invalid.
+/// In these cases, a `ScheduledVestingRecipientBuilderError` is returned.
+#[derive(Debug, Error)]
+pub enum ScheduledVestingRecipientBuilderError {
+    /// No release schedule has been added to the [`ScheduledVestingRecipientBuilder`].
+    /// Call [`with_cliff`] or [`with_schedule`] to add one.
+    ///
+    /// [`ScheduledVestingRecipientBuilder`]: struct.ScheduledVestingRecipientBuilder.html
+    /// [`with_cliff`]: struct.ScheduledVestingRecipientBuilder.html#method.with_cliff
+    /// [`with_schedule`]: struct.ScheduledVestingRecipientBuilder.html#method.with_schedule
+    #[error("The vesting schedules are missing.")]
+    NoSchedules,
+    /// One of the release schedules doesn't release anything, has a tranche with a zero time
+    /// step or step amount, a step amount larger than the tranche amount, or the amounts overflow.
+    #[error("The vesting schedules are invalid.")]
+    InvalidSchedules,
+}
+
+/// A `ScheduledVestingRecipientBuilder` can be used to create new scheduled vesting contracts.
+/// A scheduled vesting contract locks funds of a single `owner`.
+/// The owner does not necessarily needs to coincide with the transaction's sender.
+///
+/// The funds are released according to one or more schedules whose locked amounts add up.
+/// Nothing is released before the cliff of a schedule. At the cliff time, the cliff amount is
+/// released at once. Afterwards, the tranches of the schedule release their funds one after the
+/// other, each with its own `time_step` and `step_amount`.
+pub struct ScheduledVestingRecipientBuilder {
+    owner: Address,
+    schedules: Vec<VestingSchedule>,
+}
+
+impl ScheduledVestingRecipientBuilder {
+    /// Creates a new scheduled vesting contract with funds owned by `owner`.
+    pub fn new(owner: Address) -> Self {
+        ScheduledVestingRecipientBuilder {
+            owner,
+            schedules: vec![],
+        }
+    }
+
+    /// Sets the `owner` of the funds in the contract.
+    pub fn with_owner(&mut self, owner: Address) -> &mut Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Adds a complete release `schedule`.
+    pub fn with_schedule(&mut self, schedule: VestingSchedule) -> &mut Self {
+        self.schedules.push(schedule);
+        self
+    }
+
+    /// Starts a new release schedule that releases `cliff_amount` at time `cliff_time`.
+    /// Tranches added afterwards belong to this schedule.
+    pub fn with_cliff(&mut self, cliff_time: u64, cliff_amount: Coin) -> &mut Self {
+        self.with_schedule(VestingSchedule {
+            cliff_time,
+            cliff_amount,
+            tranches: vec![],
+        })
+    }
+
+    /// Adds a tranche releasing `amount` in steps of `step_amount` every `time_step` to the
+    /// latest schedule. The tranche starts when the previous tranche of the schedule (or the
+    /// cliff) has been released completely.
+    /// If no schedule has been started yet, this starts one with a cliff at time 0.
+    pub fn with_tranche(&mut self, time_step: u64, step_amount: Coin, amount: Coin) -> &mut Self {
+        if self.schedules.is_empty() {
+            self.with_cliff(0, Coin::ZERO);
+        }
+        let schedule = self.schedules.last_mut().unwrap();
+        schedule.tranches.push(VestingTranche {
+            time_step,
+            step_amount,
+            amount,
+        });
+        self
+    }
+
+    /// This method tries putting together the contract creation,
+    /// returning a [`Recipient`] in case of success.
+    /// In case of a failure, it returns a [`ScheduledVestingRecipientBuilderError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nimiq_transaction_builder::Recipient;
+    /// use nimiq_keys::Address;
+    /// use nimiq_primitives::coin::Coin;
+    ///
+    /// let owner = Address::from_any_str("NQ25 B7NR A1HC V4R2 YRKD 20PR RPGS MNV7 D812").unwrap();
+    /// let mut recipient_builder = Recipient::new_scheduled_vesting_builder(owner);
+    /// recipient_builder
+    ///     // Release 1000 at time 10000,
+    ///     .with_cliff(10_000, Coin::from_u64_unchecked(1_000))
+    ///     // then 500 every 100 until 2000 have been released,
+    ///     .with_tranche(100, Coin::from_u64_unchecked(500), Coin::from_u64_unchecked(2_000))
+    ///     // then 1000 every 1000 until another 3000 have been released.
+    ///     .with_tranche(1_000, Coin::from_u64_unchecked(1_000), Coin::from_u64_unchecked(3_000));
+    /// let recipient = recipient_builder.generate();
+    /// assert!(recipient.is_ok());
+    /// ```
+    ///
+    /// [`Recipient`]: ../enum.Recipient.html
+    /// [`ScheduledVestingRecipientBuilderError`]: enum.ScheduledVestingRecipientBuilderError.html
+    pub fn generate(self) -> Result<Recipient, ScheduledVestingRecipientBuilderError> {
+        if self.schedules.is_empty() {
+            return Err(ScheduledVestingRecipientBuilderError::NoSchedules);
+        }
+
+        let data = ScheduledVestingCreationData {
+            owner: self.owner,
+            schedules: self.schedules,
+        };
+        data.verify()
+            .map_err(|_| ScheduledVestingRecipientBuilderError::InvalidSchedules)?;
+
+        Ok(Recipient::ScheduledVestingCreation { data })
+    }
+}
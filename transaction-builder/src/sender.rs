@@ -15,6 +15,9 @@ pub enum Sender {
     Vesting {
         address: Address,
     },
+    ScheduledVesting {
+        address: Address,
+    },
     Staking {
         data: OutgoingStakingTransactionData,
     },
@@ -33,6 +36,10 @@ impl Sender {
         Sender::Vesting { address }
     }
 
+    pub fn new_scheduled_vesting(address: Address) -> Self {
+        Sender::ScheduledVesting { address }
+    }
+
     pub fn new_staking_builder() -> StakingSenderBuilder {
         StakingSenderBuilder::new()
     }
@@ -42,6 +49,7 @@ impl Sender {
             Sender::Basic { .. } => AccountType::Basic,
             Sender::Htlc { .. } => AccountType::HTLC,
             Sender::Vesting { .. } => AccountType::Vesting,
+            Sender::ScheduledVesting { .. } => AccountType::ScheduledVesting,
             Sender::Staking { .. } => AccountType::Staking,
         }
     }
@@ -49,9 +57,10 @@ impl Sender {
     /// Returns the recipient address if this is not a contract creation.
     pub fn address(&self) -> Address {
         match self {
-            Sender::Basic { address } | Sender::Htlc { address } | Sender::Vesting { address } => {
-                address.clone()
-            }
+            Sender::Basic { address }
+            | Sender::Htlc { address }
+            | Sender::Vesting { address }
+            | Sender::ScheduledVesting { address } => address.clone(),
             Sender::Staking { .. } => Policy::STAKING_CONTRACT_ADDRESS,
         }
     }
@@ -59,9 +68,10 @@ impl Sender {
     /// Returns the data field for the transaction.
     pub fn data(&self) -> Vec<u8> {
         match self {
-            Sender::Basic { .. } | Sender::Htlc { .. } | Sender::Vesting { .. } => {
-                vec![]
-            }
+            Sender::Basic { .. }
+            | Sender::Htlc { .. }
+            | Sender::Vesting { .. }
+            | Sender::ScheduledVesting { .. } => vec![],
             Sender::Staking { data } => data.serialize_to_vec(),
         }
     }
@@ -117,6 +117,13 @@ pub trait TaggedKeyPair: Sized {
 
     fn sign(&self, message: &[u8]) -> Vec<u8>;
 
+    /// Signs the message, returning `None` if no signature could be created, e.g. because the key
+    /// is held by a remote signer that is not available. Key pairs that can always sign don't need
+    /// to implement this.
+    fn try_sign(&self, message: &[u8]) -> Option<Vec<u8>> {
+        Some(self.sign(message))
+    }
+
     fn tagged_sign<TSignable>(&self, message: &TSignable) -> TaggedSignature<TSignable, Self>
     where
         TSignable: TaggedSignable,
@@ -125,6 +132,18 @@ pub trait TaggedKeyPair: Sized {
 
         TaggedSignature::from_bytes(signature)
     }
+
+    fn try_tagged_sign<TSignable>(
+        &self,
+        message: &TSignable,
+    ) -> Option<TaggedSignature<TSignable, Self>>
+    where
+        TSignable: TaggedSignable,
+    {
+        let signature = self.try_sign(&message.message_data())?;
+
+        Some(TaggedSignature::from_bytes(signature))
+    }
 }
 
 pub trait TaggedPublicKey {
@@ -169,6 +188,10 @@ impl<'de, TSignable: TaggedSignable> serde::Deserialize<'de> for Tag<TSignable>
 }
 
 #[derive(Clone, Deserialize, Serialize)]
+#[serde(bound(
+    serialize = "TSignable: serde::Serialize",
+    deserialize = "TSignable: serde::Deserialize<'de>"
+))]
 pub struct TaggedSigned<TSignable, TScheme>
 where
     TSignable: TaggedSignable,
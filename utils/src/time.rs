@@ -28,9 +28,9 @@ impl OffsetTime {
         let offset = self.offset.load(Ordering::Relaxed);
         let abs_offset = offset.unsigned_abs();
         let system_time = if offset > 0 {
-            SystemTime::now() + Duration::from_millis(abs_offset)
+            nimiq_time::system_time() + Duration::from_millis(abs_offset)
         } else {
-            SystemTime::now() - Duration::from_millis(abs_offset)
+            nimiq_time::system_time() - Duration::from_millis(abs_offset)
         };
 
         systemtime_to_timestamp(system_time)
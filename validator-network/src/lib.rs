@@ -5,11 +5,12 @@ pub mod validator_record;
 
 use async_trait::async_trait;
 use futures::stream::BoxStream;
-use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, SecretKey};
+use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, PublicKey};
 use nimiq_network_interface::{
     network::{CloseReason, MsgAcceptance, Network, SubscribeEvents, Topic},
     request::{Message, Request, RequestCommon},
 };
+use nimiq_utils::tagged_signing::TaggedKeyPair;
 
 pub use crate::error::NetworkError;
 
@@ -63,12 +64,14 @@ pub trait ValidatorNetwork: Send + Sync {
     /// Subscribes to network events
     fn subscribe_events(&self) -> SubscribeEvents<<Self::NetworkType as Network>::PeerId>;
 
-    /// Sets this node peer ID using its secret key and public key.
-    async fn set_public_key(
+    /// Sets this node peer ID using its public key and a key pair able to sign for it.
+    async fn set_public_key<K>(
         &self,
         public_key: &CompressedPublicKey,
-        secret_key: &SecretKey,
-    ) -> Result<(), Self::Error>;
+        key_pair: &K,
+    ) -> Result<(), Self::Error>
+    where
+        K: TaggedKeyPair<PublicKey = PublicKey> + Send + Sync;
 
     /// Closes the connection to the peer with `peer_id` with the given `close_reason`.
     async fn disconnect_peer(
@@ -3,13 +3,13 @@ use std::{collections::BTreeMap, error::Error, fmt::Debug, sync::Arc};
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt, TryFutureExt};
 use log::warn;
-use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, KeyPair, SecretKey};
+use nimiq_bls::{lazy::LazyPublicKey, CompressedPublicKey, KeyPair, PublicKey};
 use nimiq_network_interface::{
     network::{CloseReason, MsgAcceptance, Network, SubscribeEvents, Topic},
     request::{InboundRequestError, Message, Request, RequestCommon, RequestError},
 };
 use nimiq_serde::{Deserialize, Serialize};
-use nimiq_utils::spawn::spawn;
+use nimiq_utils::{spawn::spawn, tagged_signing::TaggedKeyPair};
 use parking_lot::RwLock;
 use time::OffsetDateTime;
 
@@ -437,19 +437,20 @@ where
         self.network.subscribe_events()
     }
 
-    async fn set_public_key(
+    async fn set_public_key<K>(
         &self,
         public_key: &CompressedPublicKey,
-        secret_key: &SecretKey,
-    ) -> Result<(), Self::Error> {
+        key_pair: &K,
+    ) -> Result<(), Self::Error>
+    where
+        K: TaggedKeyPair<PublicKey = PublicKey> + Send + Sync,
+    {
         let peer_id = self.network.get_local_peer_id();
         let record = ValidatorRecord::new(
             peer_id,
             (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as u64,
         );
-        self.network
-            .dht_put(public_key, &record, &KeyPair::from(*secret_key))
-            .await?;
+        self.network.dht_put(public_key, &record, key_pair).await?;
 
         Ok(())
     }
@@ -7,12 +7,12 @@ use std::{
 };
 
 use futures::{
-    future::FutureExt,
+    future::{self, FutureExt},
     ready,
     stream::{select, BoxStream, Stream, StreamExt},
 };
-use nimiq_block::{MultiSignature, SignedSkipBlockInfo, SkipBlockInfo, SkipBlockProof};
-use nimiq_bls::{AggregateSignature, KeyPair};
+use nimiq_block::{MultiSignature, SkipBlockInfo, SkipBlockProof};
+use nimiq_bls::AggregateSignature;
 use nimiq_collections::BitSet;
 use nimiq_handel::{
     aggregation::Aggregation,
@@ -21,6 +21,7 @@ use nimiq_handel::{
     evaluator::WeightedVote,
     identity::WeightRegistry,
     partitioner::BinomialPartitioner,
+    peer_quality::PeerQualityTracker,
     protocol::Protocol,
     store::ReplaceStore,
     update::LevelUpdate,
@@ -28,6 +29,7 @@ use nimiq_handel::{
 use nimiq_hash::Blake2sHash;
 use nimiq_network_interface::request::{MessageMarker, RequestCommon};
 use nimiq_primitives::{policy, slots_allocation::Validators, Message};
+use nimiq_time::sleep;
 use nimiq_validator_network::ValidatorNetwork;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -35,6 +37,9 @@ use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use super::{registry::ValidatorRegistry, verifier::MultithreadedVerifier};
+#[cfg(feature = "byzantine")]
+use crate::byzantine::Behaviour;
+use crate::signer::ValidatorSigner;
 
 enum SkipBlockResult {
     SkipBlock(SignedSkipBlockMessage),
@@ -88,11 +93,22 @@ impl Stream for InputStreamSwitch {
 struct NetworkWrapper<TValidatorNetwork: ValidatorNetwork> {
     network: Arc<TValidatorNetwork>,
     tag: SkipBlockInfo,
+    #[cfg(feature = "byzantine")]
+    behaviour: Behaviour,
 }
 
 impl<TValidatorNetwork: ValidatorNetwork> NetworkWrapper<TValidatorNetwork> {
-    fn new(tag: SkipBlockInfo, network: Arc<TValidatorNetwork>) -> Self {
-        Self { network, tag }
+    fn new(
+        tag: SkipBlockInfo,
+        network: Arc<TValidatorNetwork>,
+        #[cfg(feature = "byzantine")] behaviour: Behaviour,
+    ) -> Self {
+        Self {
+            network,
+            tag,
+            #[cfg(feature = "byzantine")]
+            behaviour,
+        }
     }
 }
 impl<TValidatorNetwork: ValidatorNetwork + 'static> nimiq_handel::network::Network
@@ -104,6 +120,14 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> nimiq_handel::network::Netwo
         &self,
         (msg, recipient): (LevelUpdate<Self::Contribution>, u16),
     ) -> futures::future::BoxFuture<'static, ()> {
+        #[cfg(feature = "byzantine")]
+        if self
+            .behaviour
+            .withholds_contributions(self.tag.block_number)
+        {
+            return future::ready(()).boxed();
+        }
+
         // Create the update.
         let update_message = SkipBlockUpdate {
             level_update: msg,
@@ -247,13 +271,17 @@ impl Protocol<u32> for SkipBlockAggregationProtocol {
 pub struct SkipBlockAggregation {}
 
 impl SkipBlockAggregation {
+    const SIGNING_RETRY_DELAY: Duration = Duration::from_secs(1);
+
     pub async fn start<N: ValidatorNetwork + 'static>(
         skip_block_info: SkipBlockInfo,
-        voting_key: KeyPair,
+        signer: Arc<dyn ValidatorSigner>,
         // TODO: This seems to be a SlotBand. Change this to a proper Validator ID.
         validator_id: u16,
         active_validators: Validators,
         network: Arc<N>,
+        #[cfg(feature = "byzantine")] behaviour: Behaviour,
+        peer_quality: Arc<PeerQualityTracker>,
     ) -> (SkipBlockInfo, SkipBlockProof) {
         // TODO expose this somewehere else so we don't need to clone here.
         let weights = Arc::new(ValidatorRegistry::new(active_validators.clone()));
@@ -262,6 +290,21 @@ impl SkipBlockAggregation {
             .slots
             .clone();
 
+        // Our signature stays the same, so it only needs to be obtained once.
+        let own_signature = loop {
+            match signer.sign_skip_block(&skip_block_info) {
+                Ok(signature) => break signature,
+                Err(error) => {
+                    error!(
+                        block_number = skip_block_info.block_number,
+                        %error,
+                        "Failed to sign skip block, retrying"
+                    );
+                    sleep(Self::SIGNING_RETRY_DELAY).await;
+                }
+            }
+        };
+
         loop {
             let message_hash = skip_block_info.hash_with_prefix();
             trace!(
@@ -269,15 +312,9 @@ impl SkipBlockAggregation {
                 &skip_block_info,
                 message_hash
             );
-            let signed_skip_block_info = SignedSkipBlockInfo::from_message(
-                skip_block_info.clone(),
-                &voting_key.secret_key,
-                validator_id,
-            );
 
-            let signature = AggregateSignature::from_signatures(&[signed_skip_block_info
-                .signature
-                .multiply(slots.len() as u16)]);
+            let signature =
+                AggregateSignature::from_signatures(&[own_signature.multiply(slots.len() as u16)]);
 
             let mut signers = BitSet::new();
             for slot in slots.clone() {
@@ -302,16 +339,28 @@ impl SkipBlockAggregation {
             );
 
             let (input_switch, receiver) = InputStreamSwitch::new(
-                Box::pin(network.receive::<SkipBlockUpdate>().map(|item| item.0)),
+                network
+                    .receive::<SkipBlockUpdate>()
+                    .filter_map(|(item, validator_id)| {
+                        // Check that the level update specifies the correct sender.
+                        future::ready((item.level_update.origin() == validator_id).then_some(item))
+                    })
+                    .boxed(),
                 skip_block_info.clone(),
             );
 
-            let aggregation = Aggregation::new(
+            let aggregation = Aggregation::with_peer_quality(
                 protocol,
                 Config::default(),
                 own_contribution,
                 Box::pin(input_switch),
-                NetworkWrapper::new(skip_block_info.clone(), Arc::clone(&network)),
+                NetworkWrapper::new(
+                    skip_block_info.clone(),
+                    Arc::clone(&network),
+                    #[cfg(feature = "byzantine")]
+                    behaviour.clone(),
+                ),
+                Arc::clone(&peer_quality),
             );
 
             let mut stream = select(
@@ -1,14 +1,17 @@
-use std::{collections::BTreeMap, ops};
+use std::{
+    collections::{BTreeMap, HashSet},
+    ops,
+};
 
-use nimiq_block::MultiSignature;
-use nimiq_bls::{AggregateSignature, SecretKey};
+use nimiq_block::{DoubleVoteProof, MultiSignature};
+use nimiq_bls::{AggregateSignature, Signature};
 use nimiq_collections::bitset::BitSet;
 use nimiq_handel::{
     contribution::{AggregatableContribution, ContributionError},
     update::LevelUpdate,
 };
 use nimiq_hash::Blake2sHash;
-use nimiq_primitives::TendermintVote;
+use nimiq_primitives::{slots_allocation::Validators, TendermintIdentifier};
 use nimiq_tendermint::{Aggregation, AggregationMessage};
 use serde::{Deserialize, Serialize};
 
@@ -26,16 +29,19 @@ impl std::fmt::Debug for TendermintContribution {
 }
 
 impl TendermintContribution {
-    pub(crate) fn from_vote(
-        vote: TendermintVote,
-        secret_key: &SecretKey,
+    /// Creates the contribution of a single validator from its `signature` of the vote for
+    /// `proposal_hash`.
+    pub(crate) fn from_signature(
+        proposal_hash: Option<Blake2sHash>,
+        signature: Signature,
         validator_slots: ops::Range<u16>,
     ) -> Self {
         assert!(!validator_slots.is_empty());
-        // sign the hash
-        let signature = AggregateSignature::from_signatures(&[secret_key
-            .sign(&vote)
-            .multiply(validator_slots.len() as u16)]);
+        // weigh the signature by the number of slots
+        let signature =
+            AggregateSignature::from_signatures(
+                &[signature.multiply(validator_slots.len() as u16)],
+            );
 
         // get the slots of the validator and insert them into the bitset
         let mut signers = BitSet::new();
@@ -47,9 +53,45 @@ impl TendermintContribution {
         let multi_signature = MultiSignature::new(signature, signers);
 
         let mut contributions = BTreeMap::new();
-        contributions.insert(vote.proposal_hash, multi_signature);
+        contributions.insert(proposal_hash, multi_signature);
         Self { contributions }
     }
+
+    /// Returns a proof for every validator that has slots among the signers of votes for two
+    /// different proposals in this contribution. `validators` must be the validators the votes
+    /// identified by `id` were cast by.
+    pub(crate) fn double_votes(
+        &self,
+        id: &TendermintIdentifier,
+        validators: &Validators,
+    ) -> Vec<DoubleVoteProof> {
+        let mut offenders = HashSet::new();
+        let mut proofs = Vec::new();
+
+        for (i, (hash1, multi_sig1)) in self.contributions.iter().enumerate() {
+            for (hash2, multi_sig2) in self.contributions.iter().skip(i + 1) {
+                let overlap = &multi_sig1.signers & &multi_sig2.signers;
+                for slot in overlap.iter() {
+                    let validator = validators.get_validator_by_slot_number(slot as u16);
+                    if !offenders.insert(validator.address.clone()) {
+                        continue;
+                    }
+                    proofs.push(DoubleVoteProof::new(
+                        id.clone(),
+                        validator.address.clone(),
+                        hash1.clone(),
+                        multi_sig1.signature.clone(),
+                        multi_sig1.signers.clone(),
+                        hash2.clone(),
+                        multi_sig2.signature.clone(),
+                        multi_sig2.signers.clone(),
+                    ));
+                }
+            }
+        }
+
+        proofs
+    }
 }
 
 impl AggregatableContribution for TendermintContribution {
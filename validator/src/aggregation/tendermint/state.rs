@@ -74,6 +74,11 @@ impl MacroState {
         }
     }
 
+    /// The Tendermint round this state is in.
+    pub(crate) fn round_number(&self) -> u32 {
+        self.round_number
+    }
+
     pub fn into_tendermint_state<TValidatorNetwork>(
         self,
         reference_height: u32,
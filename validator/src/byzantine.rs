@@ -0,0 +1,213 @@
+//! Byzantine behaviour of our validator, for adversarial testing.
+//!
+//! This module is only compiled with the `byzantine` feature. A validator behaves honestly unless
+//! a [`ByzantineStrategy`] is installed in its [`Behaviour`]. Strategies hook into block production,
+//! Tendermint and the Handel aggregations to make the validator deviate from the protocol in a
+//! specific way. Conflicting messages are signed with the local keys directly, bypassing the
+//! slashing protection.
+
+use std::{convert::Infallible, sync::Arc};
+
+use nimiq_block::{Block, BlockTopic, MacroBlock, MacroBody, MicroBlock};
+use nimiq_blockchain::{BlockProducer, Blockchain};
+use nimiq_bls::Signature as BlsSignature;
+use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
+use nimiq_primitives::TendermintVote;
+use nimiq_utils::spawn::spawn;
+use nimiq_validator_network::ValidatorNetwork;
+use parking_lot::RwLock;
+
+use crate::signer::ValidatorSigner;
+
+/// A way for our validator to deviate from the protocol. All hooks default to honest behaviour.
+pub trait ByzantineStrategy: Send + Sync {
+    /// Whether to publish a second, conflicting micro block for the given block number.
+    fn double_propose(&self, _block_number: u32) -> bool {
+        false
+    }
+
+    /// Returns a vote conflicting with the given Tendermint vote, which is cast in addition to it.
+    fn conflicting_vote(&self, _vote: &TendermintVote) -> Option<TendermintVote> {
+        None
+    }
+
+    /// Whether to withhold our contributions to the aggregations of the given block number.
+    fn withhold_contributions(&self, _block_number: u32) -> bool {
+        false
+    }
+
+    /// Whether to withhold the micro block we are supposed to produce at the given block number.
+    fn withhold_micro_block(&self, _block_number: u32) -> bool {
+        false
+    }
+
+    /// Modifies the body of the macro block we propose. Returns whether the body was changed.
+    fn tamper_macro_body(&self, _block_number: u32, _body: &mut MacroBody) -> bool {
+        false
+    }
+}
+
+/// Publishes a second micro block with different content for every micro block we produce.
+pub struct DoublePropose;
+
+impl ByzantineStrategy for DoublePropose {
+    fn double_propose(&self, _block_number: u32) -> bool {
+        true
+    }
+}
+
+/// Casts a nil vote alongside every vote for a proposal, and a vote for a non-existent proposal
+/// alongside every nil vote.
+pub struct ConflictingVotes;
+
+impl ByzantineStrategy for ConflictingVotes {
+    fn conflicting_vote(&self, vote: &TendermintVote) -> Option<TendermintVote> {
+        let proposal_hash = match vote.proposal_hash {
+            Some(_) => None,
+            None => Some(Blake2sHash::default()),
+        };
+        Some(TendermintVote {
+            proposal_hash,
+            id: vote.id.clone(),
+        })
+    }
+}
+
+/// Takes part in aggregations without ever sending our contribution to anyone, and never produces
+/// the micro blocks of our slots.
+pub struct WithholdContributions;
+
+impl ByzantineStrategy for WithholdContributions {
+    fn withhold_contributions(&self, _block_number: u32) -> bool {
+        true
+    }
+
+    fn withhold_micro_block(&self, _block_number: u32) -> bool {
+        true
+    }
+}
+
+/// Proposes macro blocks with a body that doesn't match the state of the chain.
+pub struct InvalidMacroBody;
+
+impl ByzantineStrategy for InvalidMacroBody {
+    fn tamper_macro_body(&self, _block_number: u32, body: &mut MacroBody) -> bool {
+        let punished_set = &mut body.next_batch_initial_punished_set;
+        if punished_set.contains(0) {
+            punished_set.remove(0);
+        } else {
+            punished_set.insert(0);
+        }
+        true
+    }
+}
+
+/// The behaviour of our validator. It is honest unless a [`ByzantineStrategy`] is set.
+#[derive(Clone, Default)]
+pub struct Behaviour {
+    strategy: Arc<RwLock<Option<Arc<dyn ByzantineStrategy>>>>,
+}
+
+impl Behaviour {
+    /// Replaces the strategy of our validator. `None` makes the validator honest again.
+    pub fn set_strategy(&self, strategy: Option<Arc<dyn ByzantineStrategy>>) {
+        *self.strategy.write() = strategy;
+    }
+
+    fn strategy(&self) -> Option<Arc<dyn ByzantineStrategy>> {
+        self.strategy.read().clone()
+    }
+
+    /// Publishes a micro block conflicting with `block`, if our strategy asks for one. `block`
+    /// must have been produced on top of the head of `blockchain`.
+    pub(crate) fn publish_conflicting_micro_block<TValidatorNetwork>(
+        &self,
+        blockchain: &Blockchain,
+        block: &MicroBlock,
+        signer: &dyn ValidatorSigner,
+        network: &Arc<TValidatorNetwork>,
+    ) where
+        TValidatorNetwork: ValidatorNetwork + 'static,
+    {
+        let Some(strategy) = self.strategy() else {
+            return;
+        };
+        if !strategy.double_propose(block.header.block_number) {
+            return;
+        }
+        let Some(signing_key) = signer.signing_key() else {
+            return;
+        };
+
+        let result: Result<_, Infallible> = BlockProducer::next_micro_block_with_signer(
+            blockchain,
+            block.header.timestamp,
+            vec![],
+            vec![],
+            b"byzantine".to_vec(),
+            None,
+            |prev_seed| Ok(prev_seed.sign_next(&signing_key)),
+            |header| Ok(signing_key.sign(header.hash::<Blake2bHash>().as_slice())),
+        );
+        let Ok(conflicting_block) = result else {
+            return;
+        };
+
+        warn!(
+            block_number = block.header.block_number,
+            "Byzantine: publishing a conflicting micro block"
+        );
+        let network = Arc::clone(network);
+        spawn(async move {
+            if let Err(error) = network
+                .publish::<BlockTopic>(Block::Micro(conflicting_block))
+                .await
+            {
+                warn!(?error, "Failed to publish conflicting micro block");
+            }
+        });
+    }
+
+    /// Signs a vote conflicting with `vote`, if our strategy asks for one. Returns the proposal
+    /// hash of the conflicting vote together with its signature.
+    pub(crate) fn conflicting_vote(
+        &self,
+        vote: &TendermintVote,
+        signer: &dyn ValidatorSigner,
+    ) -> Option<(Option<Blake2sHash>, BlsSignature)> {
+        let conflicting_vote = self.strategy()?.conflicting_vote(vote)?;
+        let voting_key = signer.voting_key()?;
+        let signature = voting_key.secret_key.sign(&conflicting_vote);
+        Some((conflicting_vote.proposal_hash, signature))
+    }
+
+    /// Whether to withhold our contributions to the aggregations of the given block number.
+    pub(crate) fn withholds_contributions(&self, block_number: u32) -> bool {
+        self.strategy()
+            .map(|strategy| strategy.withhold_contributions(block_number))
+            .unwrap_or(false)
+    }
+
+    /// Whether to withhold the micro block we are supposed to produce at the given block number.
+    pub(crate) fn withholds_micro_block(&self, block_number: u32) -> bool {
+        self.strategy()
+            .map(|strategy| strategy.withhold_micro_block(block_number))
+            .unwrap_or(false)
+    }
+
+    /// Tampers with the body of our macro block proposal, if our strategy asks for it. The body
+    /// root in the header is updated to match the new body. Returns whether the block was changed.
+    pub(crate) fn tamper_macro_block(&self, block: &mut MacroBlock) -> bool {
+        let Some(strategy) = self.strategy() else {
+            return false;
+        };
+        let Some(body) = block.body.as_mut() else {
+            return false;
+        };
+        if !strategy.tamper_macro_body(block.header.block_number, body) {
+            return false;
+        }
+        block.header.body_root = body.hash();
+        true
+    }
+}
@@ -20,6 +20,11 @@ impl EquivocationProofPool {
         self.equivocation_proofs.insert(equivocation_proof)
     }
 
+    /// Returns an iterator over the equivocation proofs in the pool.
+    pub fn iter(&self) -> impl Iterator<Item = &EquivocationProof> {
+        self.equivocation_proofs.iter()
+    }
+
     /// Applies a block to the pool, removing processed equivocation proofs.
     pub fn apply_block(&mut self, block: &Block) {
         match block {
@@ -2,9 +2,14 @@
 extern crate log;
 
 pub mod aggregation;
+#[cfg(feature = "byzantine")]
+pub mod byzantine;
 mod jail;
 mod r#macro;
 mod micro;
+pub mod performance;
 mod proposal_buffer;
+pub mod signer;
 pub mod tendermint;
+pub mod treasury;
 pub mod validator;
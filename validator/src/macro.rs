@@ -7,23 +7,29 @@ use std::{
 
 use futures::{
     future,
-    stream::{BoxStream, Stream, StreamExt},
+    stream::{self, BoxStream, Stream, StreamExt},
 };
-use nimiq_block::MacroBlock;
-use nimiq_blockchain::{BlockProducer, Blockchain};
+use nimiq_block::{EquivocationProof, MacroBlock};
+use nimiq_blockchain::Blockchain;
+use nimiq_handel::peer_quality::PeerQualityTracker;
 use nimiq_keys::Ed25519Signature as SchnorrSignature;
 use nimiq_network_interface::network::Topic;
 use nimiq_primitives::{networks::NetworkId, slots_allocation::Validators};
 use nimiq_tendermint::{Return as TendermintReturn, SignedProposalMessage, Tendermint};
 use nimiq_validator_network::{PubsubId, ValidatorNetwork};
 use parking_lot::RwLock;
+use tokio::sync::mpsc::unbounded_channel;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
+#[cfg(feature = "byzantine")]
+use crate::byzantine::Behaviour;
 use crate::{
     aggregation::tendermint::{
         proposal::{Header, SignedProposal},
         state::MacroState,
         update_message::TendermintUpdate,
     },
+    signer::ValidatorSigner,
     tendermint::TendermintProtocol,
 };
 
@@ -42,6 +48,7 @@ where
     ProposalRejected(
         SignedProposalMessage<Header<PubsubId<TValidatorNetwork>>, (SchnorrSignature, u16)>,
     ),
+    EquivocationProof(EquivocationProof),
 }
 
 pub struct ProposalTopic<TValidatorNetwork> {
@@ -74,7 +81,7 @@ where
     pub fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn ValidatorSigner>,
         validator_slot_band: u16,
         current_validators: Validators,
         network_id: NetworkId,
@@ -84,6 +91,8 @@ where
             'static,
             SignedProposalMessage<Header<PubsubId<TValidatorNetwork>>, (SchnorrSignature, u16)>,
         >,
+        #[cfg(feature = "byzantine")] behaviour: Behaviour,
+        peer_quality: Arc<PeerQualityTracker>,
     ) -> Self {
         let input = network
             .receive::<TendermintUpdate>()
@@ -100,15 +109,21 @@ where
             })
             .boxed();
 
+        let (equivocation_proof_tx, equivocation_proof_rx) = unbounded_channel();
+
         let dependencies = TendermintProtocol::new(
             blockchain,
             network,
-            block_producer,
+            signer,
             current_validators,
             validator_slot_band,
             network_id,
             block_height,
-        );
+        )
+        .with_peer_quality(peer_quality)
+        .with_equivocation_proofs(equivocation_proof_tx);
+        #[cfg(feature = "byzantine")]
+        let dependencies = dependencies.with_behaviour(behaviour);
 
         // create the Tendermint instance, which implements Stream
         let tendermint = Tendermint::new(
@@ -132,6 +147,11 @@ where
             }
         });
 
+        // Also report the double votes found in the aggregations.
+        let equivocation_proofs = UnboundedReceiverStream::new(equivocation_proof_rx)
+            .map(MappedReturn::EquivocationProof);
+        let tendermint = stream::select(tendermint, equivocation_proofs);
+
         // Create the instance and return it.
         Self {
             tendermint: Box::pin(tendermint),
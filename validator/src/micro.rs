@@ -3,21 +3,27 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::{Duration, SystemTime},
+    time::Duration,
 };
 
 use futures::{future::BoxFuture, ready, FutureExt, Stream};
 use nimiq_block::{Block, EquivocationProof, MicroBlock, SkipBlockInfo};
 use nimiq_blockchain::{BlockProducer, Blockchain};
 use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+use nimiq_handel::peer_quality::PeerQualityTracker;
 use nimiq_mempool::mempool::Mempool;
-use nimiq_time::sleep;
+use nimiq_time::{sleep, system_time};
 use nimiq_utils::time::systemtime_to_timestamp;
 use nimiq_validator_network::ValidatorNetwork;
 use nimiq_vrf::VrfSeed;
 use parking_lot::RwLock;
 
-use crate::aggregation::skip_block::SkipBlockAggregation;
+#[cfg(feature = "byzantine")]
+use crate::byzantine::Behaviour;
+use crate::{
+    aggregation::skip_block::SkipBlockAggregation,
+    signer::{SignerError, ValidatorSigner},
+};
 
 // Ignoring this clippy warning since size difference is not that much (320
 // bytes) and we probably don't want the performance penalty of the allocation.
@@ -31,13 +37,16 @@ struct NextProduceMicroBlockEvent<TValidatorNetwork> {
     blockchain: Arc<RwLock<Blockchain>>,
     mempool: Arc<Mempool>,
     network: Arc<TValidatorNetwork>,
-    block_producer: BlockProducer,
+    signer: Arc<dyn ValidatorSigner>,
     validator_slot_band: u16,
     equivocation_proofs: Vec<EquivocationProof>,
     prev_seed: VrfSeed,
     block_number: u32,
     producer_timeout: Duration,
     block_separation_time: Duration,
+    #[cfg(feature = "byzantine")]
+    behaviour: Behaviour,
+    peer_quality: Arc<PeerQualityTracker>,
 }
 
 impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<TValidatorNetwork> {
@@ -48,25 +57,30 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
         blockchain: Arc<RwLock<Blockchain>>,
         mempool: Arc<Mempool>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn ValidatorSigner>,
         validator_slot_band: u16,
         equivocation_proofs: Vec<EquivocationProof>,
         prev_seed: VrfSeed,
         block_number: u32,
         producer_timeout: Duration,
         block_separation_time: Duration,
+        #[cfg(feature = "byzantine")] behaviour: Behaviour,
+        peer_quality: Arc<PeerQualityTracker>,
     ) -> Self {
         Self {
             blockchain,
             mempool,
             network,
-            block_producer,
+            signer,
             validator_slot_band,
             equivocation_proofs,
             prev_seed,
             block_number,
             producer_timeout,
             block_separation_time,
+            #[cfg(feature = "byzantine")]
+            behaviour,
+            peer_quality,
         }
     }
 
@@ -95,9 +109,18 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
                 if !in_current_state(&blockchain.head()) {
                     break Some(None);
                 } else if self.is_our_turn(&blockchain) {
+                    #[cfg(feature = "byzantine")]
+                    if self.behaviour.withholds_micro_block(self.block_number) {
+                        warn!(
+                            block_number = self.block_number,
+                            "Byzantine: withholding our micro block"
+                        );
+                        break None;
+                    }
+
                     // We want to produce a block at the expected timestamp for this block in this batch
                     // as it is calculated by the reward function and set the producer timeout accordingly
-                    let now = systemtime_to_timestamp(SystemTime::now());
+                    let now = systemtime_to_timestamp(system_time());
 
                     // If the expected timestamp is already in the past, produce a block immediately.
                     // If the timestamp hasn't passed, wait until the expected block timestamp
@@ -110,7 +133,17 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
                             self.block_number,
                         );
 
-                        let block = self.produce_micro_block(&blockchain);
+                        let block = match self.produce_micro_block(&blockchain) {
+                            Ok(block) => block,
+                            Err(error) => {
+                                error!(
+                                    block_number = self.block_number,
+                                    %error,
+                                    "Failed to sign our micro block"
+                                );
+                                break Some(None);
+                            }
+                        };
                         let num_transactions = block
                             .body
                             .as_ref()
@@ -126,6 +159,14 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
                             num_transactions
                         );
 
+                        #[cfg(feature = "byzantine")]
+                        self.behaviour.publish_conflicting_micro_block(
+                            &blockchain,
+                            &block,
+                            self.signer.as_ref(),
+                            &self.network,
+                        );
+
                         let block1 = block.clone();
 
                         // Use a trusted push since these blocks were generated by this validator
@@ -168,7 +209,7 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
 
         // Wait for the block to be produced. We wait for at least `producer_timeout` here, but can
         // wait longer if the expected timestamp of the block is further in the future.
-        let now = systemtime_to_timestamp(SystemTime::now());
+        let now = systemtime_to_timestamp(system_time());
         let wait_until_min = now + self.producer_timeout.as_millis() as u64;
         let wait_until_expected = expected_next_ts
             + (self.producer_timeout - self.block_separation_time).as_millis() as u64;
@@ -200,10 +241,13 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
 
         let (_, skip_block_proof) = SkipBlockAggregation::start(
             skip_block_info.clone(),
-            self.block_producer.voting_key.clone(),
+            Arc::clone(&self.signer),
             self.validator_slot_band,
             active_validators.unwrap(),
             Arc::clone(&self.network),
+            #[cfg(feature = "byzantine")]
+            self.behaviour.clone(),
+            Arc::clone(&self.peer_quality),
         )
         .await;
 
@@ -218,14 +262,19 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             } else {
                 let timestamp = head.timestamp() + self.producer_timeout.as_millis() as u64;
 
-                let block = self.block_producer.next_micro_block(
+                // Skip blocks carry over the previous seed and are justified by the skip block
+                // proof, so nothing needs to be signed here.
+                let block = BlockProducer::next_micro_block_with_signer(
                     &blockchain,
                     timestamp,
                     vec![],
                     vec![],
                     vec![], // TODO: Allow validators to set extra data field.
                     Some(skip_block_proof),
-                );
+                    |prev_seed| self.signer.sign_vrf_seed(prev_seed),
+                    |header| self.signer.sign_micro_header(header),
+                )
+                .expect("Skip blocks are not signed by the block producer");
 
                 let block1 = block.clone();
 
@@ -274,10 +323,10 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
         }
     }
 
-    fn produce_micro_block(&self, blockchain: &Blockchain) -> MicroBlock {
+    fn produce_micro_block(&self, blockchain: &Blockchain) -> Result<MicroBlock, SignerError> {
         let timestamp = u64::max(
             blockchain.timestamp(),
-            systemtime_to_timestamp(SystemTime::now()),
+            systemtime_to_timestamp(system_time()),
         );
 
         // First we try to fill the block with control transactions
@@ -296,13 +345,15 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
 
         transactions.append(&mut regular_transactions);
 
-        self.block_producer.next_micro_block(
+        BlockProducer::next_micro_block_with_signer(
             blockchain,
             timestamp,
             self.equivocation_proofs.clone(),
             transactions,
             vec![], // TODO: Allow validators to set extra data field.
             None,
+            |prev_seed| self.signer.sign_vrf_seed(prev_seed),
+            |header| self.signer.sign_micro_header(header),
         )
     }
 
@@ -335,25 +386,30 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
         blockchain: Arc<RwLock<Blockchain>>,
         mempool: Arc<Mempool>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn ValidatorSigner>,
         validator_slot_band: u16,
         equivocation_proofs: Vec<EquivocationProof>,
         prev_seed: VrfSeed,
         block_number: u32,
         producer_timeout: Duration,
         block_separation_time: Duration,
+        #[cfg(feature = "byzantine")] behaviour: Behaviour,
+        peer_quality: Arc<PeerQualityTracker>,
     ) -> Self {
         let next_event = NextProduceMicroBlockEvent::new(
             blockchain,
             mempool,
             network,
-            block_producer,
+            signer,
             validator_slot_band,
             equivocation_proofs,
             prev_seed,
             block_number,
             producer_timeout,
             block_separation_time,
+            #[cfg(feature = "byzantine")]
+            behaviour,
+            peer_quality,
         )
         .next()
         .boxed();
@@ -0,0 +1,347 @@
+use std::{collections::VecDeque, ops::Range};
+
+use nimiq_account::{BlockLog, Log, TransactionLog};
+use nimiq_block::{Block, BlockJustification, MicroJustification};
+use nimiq_blockchain::Blockchain;
+use nimiq_collections::BitSet;
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use tokio::sync::watch;
+
+/// Statistics about the performance of our validator within one epoch.
+///
+/// The statistics only cover the blocks the node processed while it was running, so they can be
+/// incomplete for the epoch in which the node was started.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidatorPerformance {
+    /// The epoch these statistics belong to.
+    pub epoch_number: u32,
+    /// The number of slots our validator owns in this epoch.
+    pub slots: u16,
+    /// The number of micro blocks our validator was supposed to produce.
+    pub micro_blocks_expected: u32,
+    /// The number of micro blocks our validator produced.
+    pub micro_blocks_produced: u32,
+    /// The number of skip blocks that replaced a micro block of our validator.
+    pub skip_blocks_caused: u32,
+    /// The number of Tendermint rounds our validator participated in.
+    pub tendermint_rounds: u32,
+    /// The number of skip block and macro block signatures our validator contributed to.
+    pub handel_contributions: u32,
+    /// The number of times our validator was penalized.
+    pub penalties: u32,
+    /// The number of times our validator was jailed.
+    pub jail_events: u32,
+    /// The rewards paid out to the reward address of our validator.
+    pub rewards: Coin,
+}
+
+impl ValidatorPerformance {
+    /// The share of expected micro blocks that were actually produced, `None` if no micro block was
+    /// expected yet.
+    pub fn production_rate(&self) -> Option<f64> {
+        if self.micro_blocks_expected == 0 {
+            return None;
+        }
+        Some(self.micro_blocks_produced as f64 / self.micro_blocks_expected as f64)
+    }
+}
+
+/// The contribution of a single block to the [`ValidatorPerformance`].
+#[derive(Default)]
+struct BlockPerformance {
+    micro_blocks_expected: u32,
+    micro_blocks_produced: u32,
+    skip_blocks_caused: u32,
+    handel_contributions: u32,
+    penalties: u32,
+    jail_events: u32,
+    rewards: Coin,
+}
+
+/// Keeps track of the [`ValidatorPerformance`] of the current and the previous epochs.
+pub struct PerformanceTracker {
+    current: ValidatorPerformance,
+    current_slots: Option<Range<u16>>,
+    previous: VecDeque<ValidatorPerformance>,
+    last_tendermint_round: Option<(u32, u32)>,
+    /// The reward address of our validator, looked up once and kept until it is updated.
+    reward_address: Option<Option<Address>>,
+    /// The number of the last block the statistics were updated with.
+    last_block_number: watch::Sender<u32>,
+}
+
+impl Default for PerformanceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PerformanceTracker {
+    /// The number of previous epochs for which the statistics are kept.
+    pub const MAX_PREVIOUS_EPOCHS: usize = 16;
+
+    pub fn new() -> Self {
+        Self {
+            current: ValidatorPerformance::default(),
+            current_slots: None,
+            previous: VecDeque::new(),
+            last_tendermint_round: None,
+            reward_address: None,
+            last_block_number: watch::Sender::new(0),
+        }
+    }
+
+    /// Subscribes to the number of the last block the statistics were updated with.
+    pub fn subscribe(&self) -> watch::Receiver<u32> {
+        self.last_block_number.subscribe()
+    }
+
+    /// Returns the statistics of the current epoch.
+    pub fn current(&self) -> &ValidatorPerformance {
+        &self.current
+    }
+
+    /// Returns the statistics of the given epoch, if it is still tracked.
+    pub fn get(&self, epoch_number: u32) -> Option<&ValidatorPerformance> {
+        if self.current.epoch_number == epoch_number {
+            return Some(&self.current);
+        }
+        self.previous
+            .iter()
+            .find(|performance| performance.epoch_number == epoch_number)
+    }
+
+    fn start_epoch(&mut self, epoch_number: u32, slots: Option<Range<u16>>) {
+        let previous = std::mem::take(&mut self.current);
+        if previous.epoch_number != 0 {
+            if self.previous.len() == Self::MAX_PREVIOUS_EPOCHS {
+                self.previous.pop_front();
+            }
+            self.previous.push_back(previous);
+        }
+
+        self.current = ValidatorPerformance {
+            epoch_number,
+            slots: slots.as_ref().map_or(0, |slots| slots.len() as u16),
+            ..Default::default()
+        };
+        self.current_slots = slots;
+        self.reward_address = None;
+    }
+
+    /// Records a Tendermint round our validator took part in.
+    pub(crate) fn on_tendermint_round(&mut self, block_number: u32, round: u32) {
+        if Policy::epoch_at(block_number) != self.current.epoch_number {
+            return;
+        }
+
+        let is_new_round = match self.last_tendermint_round {
+            Some((last_block_number, last_round)) => {
+                block_number != last_block_number || round > last_round
+            }
+            None => true,
+        };
+        if is_new_round {
+            self.current.tendermint_rounds += 1;
+            self.last_tendermint_round = Some((block_number, round));
+        }
+    }
+
+    /// Updates the statistics with an applied or reverted block.
+    ///
+    /// The blockchain is only accessed if the block is relevant for our validator, i.e. if we own
+    /// slots in its epoch or if it pays out rewards.
+    pub(crate) fn on_block_log(
+        &mut self,
+        blockchain: &Blockchain,
+        validator_address: &Address,
+        block_log: &BlockLog,
+    ) {
+        let (block_hash, block_number, inherent_logs, tx_logs, is_revert) = match block_log {
+            BlockLog::AppliedBlock {
+                block_hash,
+                block_number,
+                inherent_logs,
+                tx_logs,
+                ..
+            } => (block_hash, *block_number, inherent_logs, tx_logs, false),
+            BlockLog::RevertedBlock {
+                block_hash,
+                block_number,
+                inherent_logs,
+                tx_logs,
+                ..
+            } => (block_hash, *block_number, inherent_logs, tx_logs, true),
+        };
+
+        let epoch_number = Policy::epoch_at(block_number);
+        if epoch_number != self.current.epoch_number {
+            // Election blocks are final, so blocks of previous epochs are never reverted.
+            if is_revert {
+                return;
+            }
+            let slots = blockchain
+                .get_validators_for_epoch(epoch_number, None)
+                .ok()
+                .and_then(|validators| {
+                    validators
+                        .get_validator_by_address(validator_address)
+                        .map(|validator| validator.slots.clone())
+                });
+            self.start_epoch(epoch_number, slots);
+        }
+
+        let performance =
+            self.block_performance(blockchain, validator_address, block_hash, inherent_logs);
+
+        if is_revert {
+            self.revert(performance);
+        } else {
+            self.apply(block_number, performance);
+        }
+
+        // Look the reward address up again if it changed in this block.
+        if Self::updates_validator(tx_logs, validator_address) {
+            self.reward_address = None;
+        }
+
+        self.last_block_number.send_replace(block_number);
+    }
+
+    fn block_performance(
+        &mut self,
+        blockchain: &Blockchain,
+        validator_address: &Address,
+        block_hash: &Blake2bHash,
+        inherent_logs: &[Log],
+    ) -> BlockPerformance {
+        let mut performance = BlockPerformance::default();
+
+        if let Some(slots) = &self.current_slots {
+            match blockchain.get_block(block_hash, false, None) {
+                Ok(block) => {
+                    if block.is_micro() {
+                        let is_ours = blockchain
+                            .get_proposer_of(block_hash, None)
+                            .map(|slot| slot.validator.address == *validator_address)
+                            .unwrap_or(false);
+                        if is_ours {
+                            performance.micro_blocks_expected = 1;
+                            if block.is_skip() {
+                                performance.skip_blocks_caused = 1;
+                            } else {
+                                performance.micro_blocks_produced = 1;
+                            }
+                        }
+                    }
+
+                    if Self::block_signers(&block)
+                        .map_or(false, |signers| Self::has_signed(&signers, slots))
+                    {
+                        performance.handel_contributions = 1;
+                    }
+                }
+                Err(error) => {
+                    debug!(%block_hash, %error, "Block for performance statistics not found");
+                }
+            }
+        }
+
+        for log in inherent_logs {
+            match log {
+                Log::Penalize {
+                    validator_address: address,
+                    ..
+                } if address == validator_address => performance.penalties += 1,
+                Log::Jail {
+                    validator_address: address,
+                    ..
+                } if address == validator_address => performance.jail_events += 1,
+                Log::PayoutReward { to, value } => {
+                    let reward_address = self
+                        .reward_address
+                        .get_or_insert_with(|| Self::reward_address(blockchain, validator_address));
+                    if reward_address.as_ref() == Some(to) {
+                        performance.rewards += *value;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        performance
+    }
+
+    fn block_signers(block: &Block) -> Option<BitSet> {
+        match block.justification()? {
+            BlockJustification::Micro(MicroJustification::Skip(proof)) => Some(proof.sig.signers),
+            BlockJustification::Macro(proof) => Some(proof.sig.signers),
+            _ => None,
+        }
+    }
+
+    fn updates_validator(tx_logs: &[TransactionLog], validator_address: &Address) -> bool {
+        tx_logs.iter().flat_map(|tx_log| &tx_log.logs).any(|log| {
+            matches!(log, Log::UpdateValidator { validator_address: address, .. } if address == validator_address)
+        })
+    }
+
+    fn has_signed(signers: &BitSet, slots: &Range<u16>) -> bool {
+        slots.clone().any(|slot| signers.contains(slot as usize))
+    }
+
+    fn reward_address(blockchain: &Blockchain, validator_address: &Address) -> Option<Address> {
+        let staking_contract = blockchain.get_staking_contract_if_complete(None)?;
+        let data_store = blockchain.get_staking_contract_store();
+        let txn = blockchain.read_transaction();
+        staking_contract
+            .get_validator(&data_store.read(&txn), validator_address)
+            .map(|validator| validator.reward_address)
+    }
+
+    fn apply(&mut self, block_number: u32, performance: BlockPerformance) {
+        if performance.skip_blocks_caused > 0 {
+            warn!(
+                block_number,
+                "A skip block replaced our micro block, the validator will be penalized"
+            );
+        }
+        if performance.penalties > 0 {
+            warn!(block_number, "Our validator was penalized");
+        }
+        if performance.jail_events > 0 {
+            warn!(block_number, "Our validator was jailed");
+        }
+
+        let current = &mut self.current;
+        current.micro_blocks_expected += performance.micro_blocks_expected;
+        current.micro_blocks_produced += performance.micro_blocks_produced;
+        current.skip_blocks_caused += performance.skip_blocks_caused;
+        current.handel_contributions += performance.handel_contributions;
+        current.penalties += performance.penalties;
+        current.jail_events += performance.jail_events;
+        current.rewards += performance.rewards;
+    }
+
+    fn revert(&mut self, performance: BlockPerformance) {
+        // Saturate, the block might have been applied before we started tracking.
+        let current = &mut self.current;
+        current.micro_blocks_expected = current
+            .micro_blocks_expected
+            .saturating_sub(performance.micro_blocks_expected);
+        current.micro_blocks_produced = current
+            .micro_blocks_produced
+            .saturating_sub(performance.micro_blocks_produced);
+        current.skip_blocks_caused = current
+            .skip_blocks_caused
+            .saturating_sub(performance.skip_blocks_caused);
+        current.handel_contributions = current
+            .handel_contributions
+            .saturating_sub(performance.handel_contributions);
+        current.penalties = current.penalties.saturating_sub(performance.penalties);
+        current.jail_events = current.jail_events.saturating_sub(performance.jail_events);
+        current.rewards = current.rewards.saturating_sub(performance.rewards);
+    }
+}
@@ -620,6 +620,7 @@ mod test {
             Arc::clone(&net),
             Arc::new(Mutex::new(PublicKeyCache::new(10))),
             net.subscribe_events(),
+            None,
         )
         .await;
 
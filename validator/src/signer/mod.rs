@@ -0,0 +1,245 @@
+//! Signing of consensus messages with the validator's signing key and voting key.
+//!
+//! The [`ValidatorSigner`] trait abstracts over where the keys are kept. The [`LocalSigner`] holds
+//! the keys in memory, while the [`RemoteSigner`] forwards all signing requests to a separate signer
+//! process (see [`SignerServer`]), so that the keys never have to be stored on the networked host.
+//! Signers can be wrapped in a [`ProtectedSigner`] to enforce slashing protection.
+
+use std::{io, sync::Arc};
+
+use nimiq_block::{MacroHeader, MicroHeader, SkipBlockInfo};
+use nimiq_bls::{KeyPair as BlsKeyPair, PublicKey as BlsPublicKey, Signature as BlsSignature};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::{Ed25519PublicKey, Ed25519Signature, KeyPair as SchnorrKeyPair};
+use nimiq_primitives::{Message, TendermintVote};
+use nimiq_serde::{DeserializeError, Serialize};
+use nimiq_utils::tagged_signing::TaggedKeyPair;
+use nimiq_vrf::VrfSeed;
+use thiserror::Error;
+
+use crate::aggregation::tendermint::proposal::SignedProposal;
+
+mod protection;
+mod protocol;
+mod remote;
+mod server;
+
+pub use self::{
+    protection::{ProtectedSigner, SlashingProtection},
+    protocol::SignerAddress,
+    remote::{RemoteSigner, RemoteSignerConfig},
+    server::SignerServer,
+};
+
+/// The tag of the validator records stored in the DHT, see
+/// [`ValidatorRecord`](nimiq_validator_network::validator_record::ValidatorRecord).
+const VALIDATOR_RECORD_TAG: u8 = 0x03;
+
+#[derive(Debug, Error)]
+pub enum SignerError {
+    #[error("Refusing to sign: {0}")]
+    Refused(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid message: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Authentication with the signer failed")]
+    Authentication,
+    #[error("Unexpected response from the signer")]
+    UnexpectedResponse,
+    #[error("Signer error: {0}")]
+    Remote(String),
+    #[error("The live head has not been observed yet")]
+    NotLive,
+}
+
+/// Creates all signatures the validator needs to take part in consensus.
+///
+/// Implementations may block while waiting for a signature, so they should answer quickly.
+pub trait ValidatorSigner: Send + Sync {
+    /// The public key of the signing key, which signs blocks and proposals.
+    fn signing_public_key(&self) -> Ed25519PublicKey;
+
+    /// The public key of the voting key, which signs Tendermint votes and skip blocks.
+    fn voting_public_key(&self) -> BlsPublicKey;
+
+    /// Signs the seed of the previous block with the signing key, producing the next seed.
+    fn sign_vrf_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError>;
+
+    /// Signs the header of a micro block we produced.
+    fn sign_micro_header(&self, header: &MicroHeader) -> Result<Ed25519Signature, SignerError>;
+
+    /// Signs a macro block proposal for the given Tendermint round.
+    fn sign_proposal(
+        &self,
+        header: &MacroHeader,
+        round: u32,
+        valid_round: Option<u32>,
+    ) -> Result<Ed25519Signature, SignerError>;
+
+    /// Signs a Tendermint prevote or precommit.
+    fn sign_tendermint_vote(&self, vote: &TendermintVote) -> Result<BlsSignature, SignerError>;
+
+    /// Signs our contribution to a skip block.
+    fn sign_skip_block(&self, info: &SkipBlockInfo) -> Result<BlsSignature, SignerError>;
+
+    /// Signs the tagged message data of our validator record in the DHT.
+    fn sign_validator_record(&self, message: &[u8]) -> Result<BlsSignature, SignerError>;
+
+    /// Informs the signer about the block number of the current head of the chain, which bounds
+    /// the block numbers messages are signed for.
+    fn observe_head(&self, _block_number: u32) {}
+
+    /// Returns the signing key if it is available on this host.
+    fn signing_key(&self) -> Option<SchnorrKeyPair> {
+        None
+    }
+
+    /// Returns the voting key if it is available on this host.
+    fn voting_key(&self) -> Option<BlsKeyPair> {
+        None
+    }
+}
+
+/// A signer keeping the validator keys in memory.
+#[derive(Clone)]
+pub struct LocalSigner {
+    signing_key: SchnorrKeyPair,
+    voting_key: BlsKeyPair,
+}
+
+impl LocalSigner {
+    pub fn new(signing_key: SchnorrKeyPair, voting_key: BlsKeyPair) -> Self {
+        Self {
+            signing_key,
+            voting_key,
+        }
+    }
+}
+
+impl ValidatorSigner for LocalSigner {
+    fn signing_public_key(&self) -> Ed25519PublicKey {
+        self.signing_key.public
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        self.voting_key.public_key
+    }
+
+    fn sign_vrf_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError> {
+        Ok(prev_seed.sign_next(&self.signing_key))
+    }
+
+    fn sign_micro_header(&self, header: &MicroHeader) -> Result<Ed25519Signature, SignerError> {
+        let hash = header.hash::<Blake2bHash>();
+        Ok(self.signing_key.sign(hash.as_slice()))
+    }
+
+    fn sign_proposal(
+        &self,
+        header: &MacroHeader,
+        round: u32,
+        valid_round: Option<u32>,
+    ) -> Result<Ed25519Signature, SignerError> {
+        let data = SignedProposal::hash(header, round, valid_round).serialize_to_vec();
+        Ok(self.signing_key.sign(&data))
+    }
+
+    fn sign_tendermint_vote(&self, vote: &TendermintVote) -> Result<BlsSignature, SignerError> {
+        Ok(self.voting_key.secret_key.sign(vote))
+    }
+
+    fn sign_skip_block(&self, info: &SkipBlockInfo) -> Result<BlsSignature, SignerError> {
+        Ok(info.sign(&self.voting_key.secret_key))
+    }
+
+    fn sign_validator_record(&self, message: &[u8]) -> Result<BlsSignature, SignerError> {
+        if message.first() != Some(&VALIDATOR_RECORD_TAG) {
+            return Err(SignerError::Refused(
+                "message is not a validator record".to_string(),
+            ));
+        }
+        Ok(self.voting_key.sign(&message))
+    }
+
+    fn signing_key(&self) -> Option<SchnorrKeyPair> {
+        Some(self.signing_key.clone())
+    }
+
+    fn voting_key(&self) -> Option<BlsKeyPair> {
+        Some(self.voting_key.clone())
+    }
+}
+
+impl<S: ValidatorSigner + ?Sized> ValidatorSigner for Arc<S> {
+    fn signing_public_key(&self) -> Ed25519PublicKey {
+        (**self).signing_public_key()
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        (**self).voting_public_key()
+    }
+
+    fn sign_vrf_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError> {
+        (**self).sign_vrf_seed(prev_seed)
+    }
+
+    fn sign_micro_header(&self, header: &MicroHeader) -> Result<Ed25519Signature, SignerError> {
+        (**self).sign_micro_header(header)
+    }
+
+    fn sign_proposal(
+        &self,
+        header: &MacroHeader,
+        round: u32,
+        valid_round: Option<u32>,
+    ) -> Result<Ed25519Signature, SignerError> {
+        (**self).sign_proposal(header, round, valid_round)
+    }
+
+    fn sign_tendermint_vote(&self, vote: &TendermintVote) -> Result<BlsSignature, SignerError> {
+        (**self).sign_tendermint_vote(vote)
+    }
+
+    fn sign_skip_block(&self, info: &SkipBlockInfo) -> Result<BlsSignature, SignerError> {
+        (**self).sign_skip_block(info)
+    }
+
+    fn sign_validator_record(&self, message: &[u8]) -> Result<BlsSignature, SignerError> {
+        (**self).sign_validator_record(message)
+    }
+
+    fn observe_head(&self, block_number: u32) {
+        (**self).observe_head(block_number)
+    }
+
+    fn signing_key(&self) -> Option<SchnorrKeyPair> {
+        (**self).signing_key()
+    }
+
+    fn voting_key(&self) -> Option<BlsKeyPair> {
+        (**self).voting_key()
+    }
+}
+
+/// Adapts a [`ValidatorSigner`] to the [`TaggedKeyPair`] interface used to sign DHT records.
+pub struct VotingKeySigner<'a>(pub &'a dyn ValidatorSigner);
+
+impl<'a> TaggedKeyPair for VotingKeySigner<'a> {
+    type PublicKey = BlsPublicKey;
+
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        // The network signs DHT records with `try_sign`. An empty signature never verifies.
+        self.try_sign(message).unwrap_or_default()
+    }
+
+    fn try_sign(&self, message: &[u8]) -> Option<Vec<u8>> {
+        match self.0.sign_validator_record(message) {
+            Ok(signature) => Some(signature.compress().as_ref().to_vec()),
+            Err(error) => {
+                error!(%error, "Failed to sign validator record");
+                None
+            }
+        }
+    }
+}
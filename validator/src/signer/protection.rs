@@ -0,0 +1,369 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use nimiq_block::{MacroHeader, MicroHeader, SkipBlockInfo};
+use nimiq_bls::{KeyPair as BlsKeyPair, PublicKey as BlsPublicKey, Signature as BlsSignature};
+use nimiq_database::{
+    traits::{Database, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableProxy,
+};
+use nimiq_database_value::FromDatabaseValue;
+use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
+use nimiq_keys::{Ed25519PublicKey, Ed25519Signature, KeyPair as SchnorrKeyPair};
+use nimiq_primitives::{policy::Policy, TendermintStep, TendermintVote};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_vrf::VrfSeed;
+use parking_lot::Mutex;
+
+use super::{SignerError, ValidatorSigner};
+
+/// The messages signed so far, indexed by block number and Tendermint round and step.
+#[derive(Default, Serialize, Deserialize)]
+struct ProtectionState {
+    /// Requests for blocks below this block number are refused, as the records about them have
+    /// been pruned.
+    low_watermark: u32,
+    micro_headers: BTreeMap<u32, Blake2bHash>,
+    proposals: BTreeMap<(u32, u32), Blake2bHash>,
+    votes: BTreeMap<(u32, u32, TendermintStep), Option<Blake2sHash>>,
+}
+
+impl ProtectionState {
+    /// The highest block number a message was signed for.
+    fn highest_block_number(&self) -> Option<u32> {
+        let micro_header = self.micro_headers.keys().next_back().copied();
+        let proposal = self
+            .proposals
+            .keys()
+            .next_back()
+            .map(|&(block_number, _)| block_number);
+        let vote = self
+            .votes
+            .keys()
+            .map(|&(block_number, ..)| block_number)
+            .max();
+        micro_header.into_iter().chain(proposal).chain(vote).max()
+    }
+}
+
+impl FromDatabaseValue for ProtectionState {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::deserialize_from_vec(bytes).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+/// Where the [`SlashingProtection`] is persisted.
+enum Storage {
+    Memory,
+    File(PathBuf),
+    Database {
+        env: DatabaseProxy,
+        table: TableProxy,
+    },
+}
+
+/// Refuses to sign messages that conflict with messages signed before, which would make our
+/// validator punishable by an equivocation proof:
+///
+/// - two different micro block headers for the same block number,
+/// - two different proposals for the same block number and Tendermint round,
+/// - two different votes for the same block number, Tendermint round and step.
+///
+/// Signing the exact same message again is allowed, as is signing messages at lower positions,
+/// e.g. on a new branch after a rebranch. The records are kept for one epoch and can be persisted
+/// to a file or to the database, which is written before any signature is handed out.
+///
+/// Messages are only signed up to [`MAX_BLOCKS_AHEAD`](Self::MAX_BLOCKS_AHEAD) blocks after the
+/// head of the chain reported with [`observe_head`](Self::observe_head). Otherwise a single
+/// request for a far-future block would raise the watermark and make the validator unable to sign
+/// anything for the current blocks.
+///
+/// In safe startup mode, nothing is signed until the observed head reached the highest block number
+/// signed before, which protects against double signing if the validator is restarted mid-round.
+pub struct SlashingProtection {
+    storage: Storage,
+    state: ProtectionState,
+    head: Option<u32>,
+    safe_startup: bool,
+    live: bool,
+}
+
+impl SlashingProtection {
+    /// The number of blocks after the head messages can be signed for.
+    pub const MAX_BLOCKS_AHEAD: u32 = 1;
+
+    const TABLE: &'static str = "SlashingProtection";
+    const STATE_KEY: &'static str = "state";
+
+    fn new(storage: Storage, state: ProtectionState) -> Self {
+        Self {
+            storage,
+            state,
+            head: None,
+            safe_startup: false,
+            live: false,
+        }
+    }
+
+    /// Creates a slashing protection that is not persisted.
+    pub fn in_memory() -> Self {
+        Self::new(Storage::Memory, ProtectionState::default())
+    }
+
+    /// Opens the slashing protection persisted at `path`, or creates a new one if the file does not
+    /// exist yet.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, SignerError> {
+        let path = path.as_ref().to_path_buf();
+        let state = match fs::read(&path) {
+            Ok(data) => ProtectionState::deserialize_all(&data)?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                ProtectionState::default()
+            }
+            Err(error) => return Err(error.into()),
+        };
+        Ok(Self::new(Storage::File(path), state))
+    }
+
+    /// Opens the slashing protection persisted in its own table of the database.
+    pub fn open_database(env: DatabaseProxy) -> Self {
+        let table = env.open_table(Self::TABLE.to_string());
+        let state = env
+            .read_transaction()
+            .get(&table, Self::STATE_KEY)
+            .unwrap_or_default();
+        Self::new(Storage::Database { env, table }, state)
+    }
+
+    /// Enables or disables the safe startup mode.
+    pub fn with_safe_startup(mut self, safe_startup: bool) -> Self {
+        self.safe_startup = safe_startup;
+        self
+    }
+
+    /// The lowest block number messages can still be signed for.
+    pub fn low_watermark(&self) -> u32 {
+        self.state.low_watermark
+    }
+
+    /// Whether messages can be signed, i.e. the head was observed and, in safe startup mode, it
+    /// reached the highest block number signed before.
+    pub fn is_live(&self) -> bool {
+        self.live
+    }
+
+    /// Sets the block number of the current head of the chain.
+    pub fn observe_head(&mut self, block_number: u32) {
+        self.head = Some(block_number);
+
+        if !self.live
+            && (!self.safe_startup
+                || self
+                    .state
+                    .highest_block_number()
+                    .map_or(true, |highest| block_number >= highest))
+        {
+            info!(block_number, "Observed the live head, signing is enabled");
+            self.live = true;
+        }
+    }
+
+    /// Records the signature of a micro block header, failing if it conflicts with an earlier one.
+    pub fn register_micro_header(
+        &mut self,
+        block_number: u32,
+        hash: Blake2bHash,
+    ) -> Result<(), SignerError> {
+        self.check_bounds(block_number)?;
+        match self.state.micro_headers.get(&block_number) {
+            Some(signed) if *signed == hash => return Ok(()),
+            Some(_) => {
+                return Err(SignerError::Refused(format!(
+                    "a different micro block header was already signed at block {}",
+                    block_number
+                )))
+            }
+            None => {}
+        }
+        self.state.micro_headers.insert(block_number, hash);
+        self.commit(block_number)
+    }
+
+    /// Records the signature of a proposal, failing if it conflicts with an earlier one.
+    pub fn register_proposal(
+        &mut self,
+        block_number: u32,
+        round: u32,
+        hash: Blake2bHash,
+    ) -> Result<(), SignerError> {
+        self.check_bounds(block_number)?;
+        match self.state.proposals.get(&(block_number, round)) {
+            Some(signed) if *signed == hash => return Ok(()),
+            Some(_) => {
+                return Err(SignerError::Refused(format!(
+                    "a different proposal was already signed at block {} round {}",
+                    block_number, round
+                )))
+            }
+            None => {}
+        }
+        self.state.proposals.insert((block_number, round), hash);
+        self.commit(block_number)
+    }
+
+    /// Records the signature of a Tendermint vote, failing if it conflicts with an earlier one.
+    pub fn register_vote(&mut self, vote: &TendermintVote) -> Result<(), SignerError> {
+        let id = &vote.id;
+        self.check_bounds(id.block_number)?;
+        let key = (id.block_number, id.round_number, id.step);
+        match self.state.votes.get(&key) {
+            Some(signed) if *signed == vote.proposal_hash => return Ok(()),
+            Some(_) => {
+                return Err(SignerError::Refused(format!(
+                    "a different {:?} vote was already signed at block {} round {}",
+                    id.step, id.block_number, id.round_number
+                )))
+            }
+            None => {}
+        }
+        self.state.votes.insert(key, vote.proposal_hash.clone());
+        self.commit(id.block_number)
+    }
+
+    fn check_bounds(&self, block_number: u32) -> Result<(), SignerError> {
+        if block_number < self.state.low_watermark {
+            return Err(SignerError::Refused(format!(
+                "block {} is below the slashing protection watermark {}",
+                block_number, self.state.low_watermark
+            )));
+        }
+        let Some(head) = self.head.filter(|_| self.live) else {
+            return Err(SignerError::NotLive);
+        };
+        if block_number > head.saturating_add(Self::MAX_BLOCKS_AHEAD) {
+            return Err(SignerError::Refused(format!(
+                "block {} is too far ahead of the head {}",
+                block_number, head
+            )));
+        }
+        Ok(())
+    }
+
+    /// Prunes the records older than one epoch and persists the state.
+    fn commit(&mut self, block_number: u32) -> Result<(), SignerError> {
+        let low_watermark = block_number.saturating_sub(Policy::blocks_per_epoch());
+        if low_watermark > self.state.low_watermark {
+            let state = &mut self.state;
+            state.low_watermark = low_watermark;
+            state.micro_headers = state.micro_headers.split_off(&low_watermark);
+            state.proposals = state.proposals.split_off(&(low_watermark, 0));
+            state
+                .votes
+                .retain(|&(block_number, ..), _| block_number >= low_watermark);
+        }
+
+        match &self.storage {
+            Storage::Memory => {}
+            Storage::File(path) => {
+                // Write to a temporary file first so that a crash never leaves a truncated file
+                // behind.
+                let tmp_path = path.with_extension("tmp");
+                fs::write(&tmp_path, self.state.serialize_to_vec())?;
+                fs::rename(&tmp_path, path)?;
+            }
+            Storage::Database { env, table } => {
+                let mut write_transaction = env.write_transaction();
+                write_transaction.put::<str, Vec<u8>>(
+                    table,
+                    Self::STATE_KEY,
+                    &self.state.serialize_to_vec(),
+                );
+                write_transaction.commit();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wraps a [`ValidatorSigner`], refusing to sign any message that the [`SlashingProtection`]
+/// considers conflicting.
+pub struct ProtectedSigner<S> {
+    inner: S,
+    protection: Mutex<SlashingProtection>,
+}
+
+impl<S: ValidatorSigner> ProtectedSigner<S> {
+    pub fn new(inner: S, protection: SlashingProtection) -> Self {
+        Self {
+            inner,
+            protection: Mutex::new(protection),
+        }
+    }
+}
+
+impl<S: ValidatorSigner> ValidatorSigner for ProtectedSigner<S> {
+    fn signing_public_key(&self) -> Ed25519PublicKey {
+        self.inner.signing_public_key()
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        self.inner.voting_public_key()
+    }
+
+    fn sign_vrf_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError> {
+        // A VRF seed can only be used in a block that is signed as well.
+        self.inner.sign_vrf_seed(prev_seed)
+    }
+
+    fn sign_micro_header(&self, header: &MicroHeader) -> Result<Ed25519Signature, SignerError> {
+        self.protection
+            .lock()
+            .register_micro_header(header.block_number, header.hash())?;
+        self.inner.sign_micro_header(header)
+    }
+
+    fn sign_proposal(
+        &self,
+        header: &MacroHeader,
+        round: u32,
+        valid_round: Option<u32>,
+    ) -> Result<Ed25519Signature, SignerError> {
+        // Only proposals with different headers are punishable, the valid round does not matter.
+        self.protection
+            .lock()
+            .register_proposal(header.block_number, round, header.hash())?;
+        self.inner.sign_proposal(header, round, valid_round)
+    }
+
+    fn sign_tendermint_vote(&self, vote: &TendermintVote) -> Result<BlsSignature, SignerError> {
+        self.protection.lock().register_vote(vote)?;
+        self.inner.sign_tendermint_vote(vote)
+    }
+
+    fn sign_skip_block(&self, info: &SkipBlockInfo) -> Result<BlsSignature, SignerError> {
+        // Skip block contributions are not punishable.
+        self.inner.sign_skip_block(info)
+    }
+
+    fn sign_validator_record(&self, message: &[u8]) -> Result<BlsSignature, SignerError> {
+        self.inner.sign_validator_record(message)
+    }
+
+    fn observe_head(&self, block_number: u32) {
+        self.protection.lock().observe_head(block_number);
+        self.inner.observe_head(block_number);
+    }
+
+    fn signing_key(&self) -> Option<SchnorrKeyPair> {
+        self.inner.signing_key()
+    }
+
+    fn voting_key(&self) -> Option<BlsKeyPair> {
+        self.inner.voting_key()
+    }
+}
@@ -0,0 +1,447 @@
+//! The protocol spoken between the [`RemoteSigner`](super::RemoteSigner) and the
+//! [`SignerServer`](super::SignerServer).
+//!
+//! Every message is a frame consisting of its length as a big endian `u32` followed by the message.
+//! After connecting, client and server authenticate each other: The server sends a random
+//! challenge, which the client answers with a challenge of its own and an HMAC-SHA512 of both
+//! challenges keyed with the shared authentication token. The server verifies it and proves its
+//! own knowledge of the token the same way. Both sides then derive a session key from the token and
+//! the challenges, and append an HMAC of every following message and its sequence number to the
+//! frame, so that messages can neither be forged, replayed nor reordered. The client sends
+//! [`SignerMessage`]s, each of which is answered with a [`SignerResponse`].
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use nimiq_block::{MacroHeader, MicroHeader, SkipBlockInfo};
+use nimiq_bls::{PublicKey as BlsPublicKey, Signature as BlsSignature};
+use nimiq_hash::{hmac::compute_hmac_sha512, Blake2sHash};
+use nimiq_keys::{Ed25519PublicKey, Ed25519Signature};
+use nimiq_primitives::TendermintIdentifier;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_vrf::VrfSeed;
+
+use super::SignerError;
+
+/// The maximum size of a single frame.
+const MAX_FRAME_SIZE: usize = 64 * 1024;
+
+/// The size of the authentication challenge.
+pub(super) const CHALLENGE_SIZE: usize = 32;
+
+/// The size of the HMAC authenticating the handshake and the messages.
+const MAC_SIZE: usize = 64;
+
+/// The domain separators of the HMACs computed with the authentication token.
+const CLIENT_PROOF: &[u8] = b"nimiq-signer-client";
+const SERVER_PROOF: &[u8] = b"nimiq-signer-server";
+const SESSION_KEY: &[u8] = b"nimiq-signer-session";
+
+/// The domain separators of the message HMACs, one for each direction.
+const REQUEST: &[u8] = b"request";
+const RESPONSE: &[u8] = b"response";
+
+/// The address of a signer, either `unix:<path>` for a Unix socket or `[tcp://]<host>:<port>`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SignerAddress {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for SignerAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("unix:") {
+            #[cfg(unix)]
+            return Ok(SignerAddress::Unix(PathBuf::from(path)));
+            #[cfg(not(unix))]
+            return Err(format!(
+                "Unix sockets are not supported on this platform: {}",
+                path
+            ));
+        }
+        let address = s.strip_prefix("tcp://").unwrap_or(s);
+        if address.is_empty() {
+            return Err("Empty signer address".to_string());
+        }
+        Ok(SignerAddress::Tcp(address.to_string()))
+    }
+}
+
+impl fmt::Display for SignerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignerAddress::Tcp(address) => write!(f, "tcp://{}", address),
+            #[cfg(unix)]
+            SignerAddress::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// The byte stream underlying a [`Connection`].
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => {
+                stream.set_read_timeout(timeout)?;
+                stream.set_write_timeout(timeout)
+            }
+            #[cfg(unix)]
+            Stream::Unix(stream) => {
+                stream.set_read_timeout(timeout)?;
+                stream.set_write_timeout(timeout)
+            }
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// The state of an authenticated connection.
+struct Session {
+    key: [u8; MAC_SIZE],
+    /// The domain separator of the messages we send.
+    outgoing: &'static [u8],
+    /// The domain separator of the messages we receive.
+    incoming: &'static [u8],
+    sent: u64,
+    received: u64,
+}
+
+impl Session {
+    fn new(
+        auth_token: &str,
+        server_challenge: &[u8],
+        client_challenge: &[u8],
+        is_client: bool,
+    ) -> Self {
+        let (outgoing, incoming) = if is_client {
+            (REQUEST, RESPONSE)
+        } else {
+            (RESPONSE, REQUEST)
+        };
+        Self {
+            key: hmac(
+                auth_token.as_bytes(),
+                &[SESSION_KEY, server_challenge, client_challenge],
+            ),
+            outgoing,
+            incoming,
+            sent: 0,
+            received: 0,
+        }
+    }
+
+    fn mac(&self, direction: &[u8], sequence_number: u64, payload: &[u8]) -> [u8; MAC_SIZE] {
+        hmac(
+            &self.key,
+            &[direction, &sequence_number.to_be_bytes(), payload],
+        )
+    }
+
+    /// Appends the HMAC of the next outgoing message to `payload`.
+    fn seal(&mut self, mut payload: Vec<u8>) -> Vec<u8> {
+        let mac = self.mac(self.outgoing, self.sent, &payload);
+        self.sent += 1;
+        payload.extend_from_slice(&mac);
+        payload
+    }
+
+    /// Verifies and strips the HMAC of the next incoming message.
+    fn open(&mut self, mut frame: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        if frame.len() < MAC_SIZE {
+            return Err(SignerError::Authentication);
+        }
+        let mac = frame.split_off(frame.len() - MAC_SIZE);
+        if !constant_time_eq(&mac, &self.mac(self.incoming, self.received, &frame)) {
+            return Err(SignerError::Authentication);
+        }
+        self.received += 1;
+        Ok(frame)
+    }
+}
+
+/// A connection between the remote signer and the signer server.
+///
+/// Messages can only be exchanged after both sides authenticated each other, see
+/// [`authenticate`](Self::authenticate) and [`challenge`](Self::challenge).
+pub(super) struct Connection {
+    stream: Stream,
+    session: Option<Session>,
+}
+
+impl Connection {
+    fn new(stream: Stream) -> Self {
+        Self {
+            stream,
+            session: None,
+        }
+    }
+
+    pub(super) fn connect(address: &SignerAddress, timeout: Duration) -> io::Result<Self> {
+        let stream = match address {
+            SignerAddress::Tcp(address) => {
+                let stream = TcpStream::connect(address)?;
+                stream.set_nodelay(true)?;
+                Stream::Tcp(stream)
+            }
+            #[cfg(unix)]
+            SignerAddress::Unix(path) => Stream::Unix(UnixStream::connect(path)?),
+        };
+        stream.set_timeout(Some(timeout))?;
+        Ok(Self::new(stream))
+    }
+
+    pub(super) fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.stream.set_timeout(timeout)
+    }
+
+    fn write_frame(&mut self, data: &[u8]) -> io::Result<()> {
+        if data.len() > MAX_FRAME_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Frame too large",
+            ));
+        }
+        self.stream.write_u32::<BigEndian>(data.len() as u32)?;
+        self.stream.write_all(data)?;
+        self.stream.flush()
+    }
+
+    fn read_frame(&mut self) -> io::Result<Vec<u8>> {
+        let len = self.stream.read_u32::<BigEndian>()? as usize;
+        if len > MAX_FRAME_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Frame too large",
+            ));
+        }
+        let mut data = vec![0u8; len];
+        self.stream.read_exact(&mut data)?;
+        Ok(data)
+    }
+
+    pub(super) fn send<T: Serialize>(&mut self, message: &T) -> Result<(), SignerError> {
+        let session = self.session.as_mut().ok_or(SignerError::Authentication)?;
+        let frame = session.seal(message.serialize_to_vec());
+        Ok(self.write_frame(&frame)?)
+    }
+
+    pub(super) fn receive<T: Deserialize>(&mut self) -> Result<T, SignerError> {
+        let frame = self.read_frame()?;
+        let session = self.session.as_mut().ok_or(SignerError::Authentication)?;
+        let data = session.open(frame)?;
+        Ok(T::deserialize_all(&data)?)
+    }
+
+    /// Answers the challenge of the server and verifies that the server knows the authentication
+    /// token as well.
+    pub(super) fn authenticate(&mut self, auth_token: &str) -> Result<(), SignerError> {
+        let server_challenge = self.read_frame()?;
+        if server_challenge.len() != CHALLENGE_SIZE {
+            return Err(SignerError::Authentication);
+        }
+        let client_challenge: [u8; CHALLENGE_SIZE] = rand::random();
+
+        let mut answer = client_challenge.to_vec();
+        answer.extend_from_slice(&hmac(
+            auth_token.as_bytes(),
+            &[CLIENT_PROOF, &server_challenge, &client_challenge],
+        ));
+        self.write_frame(&answer)?;
+
+        // The server closes the connection if it rejects our answer.
+        let server_proof = self.read_frame().map_err(|_| SignerError::Authentication)?;
+        let expected = hmac(
+            auth_token.as_bytes(),
+            &[SERVER_PROOF, &server_challenge, &client_challenge],
+        );
+        if !constant_time_eq(&server_proof, &expected) {
+            return Err(SignerError::Authentication);
+        }
+
+        self.session = Some(Session::new(
+            auth_token,
+            &server_challenge,
+            &client_challenge,
+            true,
+        ));
+        Ok(())
+    }
+
+    /// Challenges the client to prove that it knows the authentication token, and proves the same
+    /// to the client.
+    pub(super) fn challenge(&mut self, auth_token: &str) -> Result<(), SignerError> {
+        let server_challenge: [u8; CHALLENGE_SIZE] = rand::random();
+        self.write_frame(&server_challenge)?;
+
+        let answer = self.read_frame()?;
+        if answer.len() != CHALLENGE_SIZE + MAC_SIZE {
+            return Err(SignerError::Authentication);
+        }
+        let (client_challenge, client_proof) = answer.split_at(CHALLENGE_SIZE);
+        let expected = hmac(
+            auth_token.as_bytes(),
+            &[CLIENT_PROOF, &server_challenge, client_challenge],
+        );
+        if !constant_time_eq(client_proof, &expected) {
+            return Err(SignerError::Authentication);
+        }
+
+        self.write_frame(&hmac(
+            auth_token.as_bytes(),
+            &[SERVER_PROOF, &server_challenge, client_challenge],
+        ))?;
+
+        self.session = Some(Session::new(
+            auth_token,
+            &server_challenge,
+            client_challenge,
+            false,
+        ));
+        Ok(())
+    }
+}
+
+/// Accepts connections on a [`SignerAddress`].
+pub(super) enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    pub(super) fn bind(address: &SignerAddress) -> io::Result<Self> {
+        Ok(match address {
+            SignerAddress::Tcp(address) => Listener::Tcp(TcpListener::bind(address)?),
+            #[cfg(unix)]
+            SignerAddress::Unix(path) => Listener::Unix(UnixListener::bind(path)?),
+        })
+    }
+
+    pub(super) fn accept(&self) -> io::Result<Connection> {
+        Ok(match self {
+            Listener::Tcp(listener) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_nodelay(true)?;
+                Connection::new(Stream::Tcp(stream))
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener) => Connection::new(Stream::Unix(listener.accept()?.0)),
+        })
+    }
+
+    pub(super) fn local_address(&self) -> io::Result<SignerAddress> {
+        Ok(match self {
+            Listener::Tcp(listener) => SignerAddress::Tcp(listener.local_addr()?.to_string()),
+            #[cfg(unix)]
+            Listener::Unix(listener) => SignerAddress::Unix(
+                listener
+                    .local_addr()?
+                    .as_pathname()
+                    .map(|path| path.to_path_buf())
+                    .unwrap_or_default(),
+            ),
+        })
+    }
+}
+
+/// Computes the HMAC-SHA512 of the concatenation of `parts`.
+fn hmac(key: &[u8], parts: &[&[u8]]) -> [u8; MAC_SIZE] {
+    compute_hmac_sha512(key, &parts.concat()).into()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// A request together with the head of the chain the client knows about, see
+/// [`ValidatorSigner::observe_head`](super::ValidatorSigner::observe_head).
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct SignerMessage {
+    pub head: Option<u32>,
+    pub request: SignerRequest,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) enum SignerRequest {
+    PublicKeys,
+    VrfSeed(VrfSeed),
+    MicroHeader(MicroHeader),
+    Proposal {
+        header: MacroHeader,
+        round: u32,
+        valid_round: Option<u32>,
+    },
+    TendermintVote {
+        id: TendermintIdentifier,
+        proposal_hash: Option<Blake2sHash>,
+    },
+    SkipBlock(SkipBlockInfo),
+    ValidatorRecord(Vec<u8>),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) enum SignerResponse {
+    PublicKeys {
+        signing_key: Ed25519PublicKey,
+        voting_key: BlsPublicKey,
+    },
+    VrfSeed(VrfSeed),
+    SchnorrSignature(Ed25519Signature),
+    BlsSignature(BlsSignature),
+    Refused(String),
+    Error(String),
+}
+
+impl From<SignerError> for SignerResponse {
+    fn from(error: SignerError) -> Self {
+        match error {
+            SignerError::Refused(reason) => SignerResponse::Refused(reason),
+            error => SignerResponse::Error(error.to_string()),
+        }
+    }
+}
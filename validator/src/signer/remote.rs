@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use nimiq_block::{MacroHeader, MicroHeader, SkipBlockInfo};
+use nimiq_bls::{PublicKey as BlsPublicKey, Signature as BlsSignature};
+use nimiq_keys::{Ed25519PublicKey, Ed25519Signature};
+use nimiq_primitives::TendermintVote;
+use nimiq_utils::Sensitive;
+use nimiq_vrf::VrfSeed;
+use parking_lot::Mutex;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+use super::{
+    protocol::{Connection, SignerAddress, SignerMessage, SignerRequest, SignerResponse},
+    SignerError, ValidatorSigner,
+};
+
+/// Configuration of a [`RemoteSigner`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RemoteSignerConfig {
+    /// The address the signer server listens on.
+    pub address: SignerAddress,
+    /// The token shared with the signer server to authenticate.
+    pub auth_token: Sensitive<String>,
+    /// How long to wait for the signer server before a request fails.
+    pub timeout: Duration,
+}
+
+impl RemoteSignerConfig {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+}
+
+/// A signer forwarding all requests to a [`SignerServer`](super::SignerServer).
+///
+/// The connection is established lazily and re-established if it breaks. Every request carries
+/// the head of the chain last passed to [`observe_head`](ValidatorSigner::observe_head).
+pub struct RemoteSigner {
+    config: RemoteSignerConfig,
+    signing_public_key: Ed25519PublicKey,
+    voting_public_key: BlsPublicKey,
+    connection: Mutex<Option<Connection>>,
+    head: Mutex<Option<u32>>,
+}
+
+impl RemoteSigner {
+    /// Connects to the signer server and fetches the public keys of the validator.
+    pub fn connect(config: RemoteSignerConfig) -> Result<Self, SignerError> {
+        let mut connection = Self::open(&config)?;
+        connection.send(&SignerMessage {
+            head: None,
+            request: SignerRequest::PublicKeys,
+        })?;
+        let (signing_public_key, voting_public_key) = match connection.receive()? {
+            SignerResponse::PublicKeys {
+                signing_key,
+                voting_key,
+            } => (signing_key, voting_key),
+            response => return Err(Self::unexpected(response)),
+        };
+
+        info!(address = %config.address, "Connected to remote signer");
+
+        Ok(Self {
+            config,
+            signing_public_key,
+            voting_public_key,
+            connection: Mutex::new(Some(connection)),
+            head: Mutex::new(None),
+        })
+    }
+
+    fn open(config: &RemoteSignerConfig) -> Result<Connection, SignerError> {
+        let mut connection = Connection::connect(&config.address, config.timeout)?;
+        connection.authenticate(&config.auth_token)?;
+        Ok(connection)
+    }
+
+    /// Runs blocking I/O with the signer server.
+    ///
+    /// The signer is called from synchronous code running on the Tokio runtime, e.g. the Tendermint
+    /// protocol and the block producer. On a multi-threaded runtime, the other tasks of the current
+    /// worker are handed off to another thread before blocking, so they are not stalled by a slow
+    /// signer.
+    fn blocking<T>(f: impl FnOnce() -> T) -> T {
+        match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(f)
+            }
+            _ => f(),
+        }
+    }
+
+    fn request(&self, request: SignerRequest) -> Result<SignerResponse, SignerError> {
+        let message = SignerMessage {
+            head: *self.head.lock(),
+            request,
+        };
+        Self::blocking(|| self.send_request(&message))
+    }
+
+    fn send_request(&self, message: &SignerMessage) -> Result<SignerResponse, SignerError> {
+        let mut guard = self.connection.lock();
+
+        // Retry once with a new connection, the server might have been restarted.
+        for attempt in 0..2 {
+            if guard.is_none() {
+                *guard = Some(Self::open(&self.config)?);
+            }
+            let connection = guard.as_mut().unwrap();
+
+            let result = connection.send(message).and_then(|_| connection.receive());
+            match result {
+                Ok(response) => return Ok(response),
+                Err(error) => {
+                    *guard = None;
+                    if attempt > 0 {
+                        return Err(error);
+                    }
+                    debug!(%error, "Request to remote signer failed, reconnecting");
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    fn unexpected(response: SignerResponse) -> SignerError {
+        match response {
+            SignerResponse::Refused(reason) => SignerError::Refused(reason),
+            SignerResponse::Error(error) => SignerError::Remote(error),
+            _ => SignerError::UnexpectedResponse,
+        }
+    }
+
+    fn schnorr_signature(&self, request: SignerRequest) -> Result<Ed25519Signature, SignerError> {
+        match self.request(request)? {
+            SignerResponse::SchnorrSignature(signature) => Ok(signature),
+            response => Err(Self::unexpected(response)),
+        }
+    }
+
+    fn bls_signature(&self, request: SignerRequest) -> Result<BlsSignature, SignerError> {
+        match self.request(request)? {
+            SignerResponse::BlsSignature(signature) => Ok(signature),
+            response => Err(Self::unexpected(response)),
+        }
+    }
+}
+
+impl ValidatorSigner for RemoteSigner {
+    fn signing_public_key(&self) -> Ed25519PublicKey {
+        self.signing_public_key
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        self.voting_public_key
+    }
+
+    fn sign_vrf_seed(&self, prev_seed: &VrfSeed) -> Result<VrfSeed, SignerError> {
+        match self.request(SignerRequest::VrfSeed(prev_seed.clone()))? {
+            SignerResponse::VrfSeed(seed) => Ok(seed),
+            response => Err(Self::unexpected(response)),
+        }
+    }
+
+    fn sign_micro_header(&self, header: &MicroHeader) -> Result<Ed25519Signature, SignerError> {
+        self.schnorr_signature(SignerRequest::MicroHeader(header.clone()))
+    }
+
+    fn sign_proposal(
+        &self,
+        header: &MacroHeader,
+        round: u32,
+        valid_round: Option<u32>,
+    ) -> Result<Ed25519Signature, SignerError> {
+        self.schnorr_signature(SignerRequest::Proposal {
+            header: header.clone(),
+            round,
+            valid_round,
+        })
+    }
+
+    fn sign_tendermint_vote(&self, vote: &TendermintVote) -> Result<BlsSignature, SignerError> {
+        self.bls_signature(SignerRequest::TendermintVote {
+            id: vote.id.clone(),
+            proposal_hash: vote.proposal_hash.clone(),
+        })
+    }
+
+    fn sign_skip_block(&self, info: &SkipBlockInfo) -> Result<BlsSignature, SignerError> {
+        self.bls_signature(SignerRequest::SkipBlock(info.clone()))
+    }
+
+    fn sign_validator_record(&self, message: &[u8]) -> Result<BlsSignature, SignerError> {
+        self.bls_signature(SignerRequest::ValidatorRecord(message.to_vec()))
+    }
+
+    fn observe_head(&self, block_number: u32) {
+        *self.head.lock() = Some(block_number);
+    }
+}
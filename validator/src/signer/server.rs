@@ -0,0 +1,132 @@
+use std::{io, sync::Arc, thread};
+
+use nimiq_primitives::TendermintVote;
+
+use super::{
+    protocol::{Connection, Listener, SignerAddress, SignerMessage, SignerRequest, SignerResponse},
+    SignerError, ValidatorSigner,
+};
+
+/// Serves signing requests of [`RemoteSigner`](super::RemoteSigner)s.
+///
+/// The signer it serves from should be a [`ProtectedSigner`](super::ProtectedSigner), as the
+/// server does not check the requests it answers itself.
+pub struct SignerServer {
+    listener: Listener,
+    auth_token: Arc<String>,
+    signer: Arc<dyn ValidatorSigner>,
+}
+
+impl SignerServer {
+    pub fn bind(
+        address: &SignerAddress,
+        auth_token: String,
+        signer: Arc<dyn ValidatorSigner>,
+    ) -> Result<Self, SignerError> {
+        Ok(Self {
+            listener: Listener::bind(address)?,
+            auth_token: Arc::new(auth_token),
+            signer,
+        })
+    }
+
+    /// The address the server is listening on.
+    pub fn local_address(&self) -> io::Result<SignerAddress> {
+        self.listener.local_address()
+    }
+
+    /// Accepts connections until the listener fails, serving each connection on its own thread.
+    pub fn run(self) -> io::Result<()> {
+        loop {
+            let connection = self.listener.accept()?;
+            let auth_token = Arc::clone(&self.auth_token);
+            let signer = Arc::clone(&self.signer);
+            thread::spawn(move || {
+                if let Err(error) = Self::serve(connection, &auth_token, signer.as_ref()) {
+                    debug!(%error, "Signer connection closed");
+                }
+            });
+        }
+    }
+
+    fn serve(
+        mut connection: Connection,
+        auth_token: &str,
+        signer: &dyn ValidatorSigner,
+    ) -> Result<(), SignerError> {
+        if let Err(error) = connection.challenge(auth_token) {
+            warn!(%error, "Rejected signer connection");
+            return Err(error);
+        }
+
+        // Authenticated clients may stay idle for as long as they want.
+        connection.set_timeout(None)?;
+        loop {
+            let SignerMessage { head, request } = connection.receive()?;
+            if let Some(head) = head {
+                signer.observe_head(head);
+            }
+            let response = Self::handle(signer, request);
+            connection.send(&response)?;
+        }
+    }
+
+    fn handle(signer: &dyn ValidatorSigner, request: SignerRequest) -> SignerResponse {
+        let result = match request {
+            SignerRequest::PublicKeys => Ok(SignerResponse::PublicKeys {
+                signing_key: signer.signing_public_key(),
+                voting_key: signer.voting_public_key(),
+            }),
+            SignerRequest::VrfSeed(prev_seed) => signer
+                .sign_vrf_seed(&prev_seed)
+                .map(SignerResponse::VrfSeed),
+            SignerRequest::MicroHeader(header) => {
+                debug!(
+                    block_number = header.block_number,
+                    "Signing micro block header"
+                );
+                signer
+                    .sign_micro_header(&header)
+                    .map(SignerResponse::SchnorrSignature)
+            }
+            SignerRequest::Proposal {
+                header,
+                round,
+                valid_round,
+            } => {
+                debug!(
+                    block_number = header.block_number,
+                    round, "Signing macro block proposal"
+                );
+                signer
+                    .sign_proposal(&header, round, valid_round)
+                    .map(SignerResponse::SchnorrSignature)
+            }
+            SignerRequest::TendermintVote { id, proposal_hash } => {
+                debug!(
+                    block_number = id.block_number,
+                    round = id.round_number,
+                    step = ?id.step,
+                    "Signing Tendermint vote"
+                );
+                signer
+                    .sign_tendermint_vote(&TendermintVote { proposal_hash, id })
+                    .map(SignerResponse::BlsSignature)
+            }
+            SignerRequest::SkipBlock(info) => {
+                debug!(block_number = info.block_number, "Signing skip block");
+                signer
+                    .sign_skip_block(&info)
+                    .map(SignerResponse::BlsSignature)
+            }
+            SignerRequest::ValidatorRecord(message) => signer
+                .sign_validator_record(&message)
+                .map(SignerResponse::BlsSignature),
+        };
+
+        result.unwrap_or_else(|error| {
+            warn!(%error, "Signing request failed");
+            error.into()
+        })
+    }
+}
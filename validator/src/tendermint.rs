@@ -2,15 +2,15 @@ use std::sync::Arc;
 
 use futures::{
     future::{self, BoxFuture, FutureExt},
-    stream::{BoxStream, StreamExt},
+    stream::{self, BoxStream, StreamExt},
 };
-use nimiq_block::{Block, MacroBlock, TendermintProof};
+use nimiq_block::{Block, EquivocationProof, MacroBlock, TendermintProof};
 use nimiq_blockchain::{BlockProducer, Blockchain};
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_collections::BitSet;
 use nimiq_handel::{
-    aggregation::Aggregation, identity::IdentityRegistry, protocol::Protocol as _,
-    verifier::VerificationResult,
+    aggregation::Aggregation, identity::IdentityRegistry, peer_quality::PeerQualityTracker,
+    protocol::Protocol as _, verifier::VerificationResult,
 };
 use nimiq_hash::{Blake2sHash, Hash};
 use nimiq_keys::Ed25519Signature as SchnorrSignature;
@@ -28,7 +28,10 @@ use nimiq_validator_network::{
     single_response_requester::SingleResponseRequester, PubsubId, ValidatorNetwork,
 };
 use parking_lot::RwLock;
+use tokio::sync::mpsc::UnboundedSender;
 
+#[cfg(feature = "byzantine")]
+use crate::byzantine::Behaviour;
 use crate::{
     aggregation::{
         registry::ValidatorRegistry,
@@ -40,6 +43,7 @@ use crate::{
         },
     },
     r#macro::ProposalTopic,
+    signer::ValidatorSigner,
 };
 
 // A note for the signing of the proposal:
@@ -53,14 +57,23 @@ struct NetworkWrapper<TValidatorNetwork: ValidatorNetwork> {
     network: Arc<TValidatorNetwork>,
     tag: (u32, Step),
     height: u32,
+    #[cfg(feature = "byzantine")]
+    behaviour: Behaviour,
 }
 
 impl<TValidatorNetwork: ValidatorNetwork> NetworkWrapper<TValidatorNetwork> {
-    fn new(height: u32, tag: (u32, Step), network: Arc<TValidatorNetwork>) -> Self {
+    fn new(
+        height: u32,
+        tag: (u32, Step),
+        network: Arc<TValidatorNetwork>,
+        #[cfg(feature = "byzantine")] behaviour: Behaviour,
+    ) -> Self {
         Self {
             height,
             network,
             tag,
+            #[cfg(feature = "byzantine")]
+            behaviour,
         }
     }
 }
@@ -73,6 +86,11 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> nimiq_handel::network::Netwo
         &self,
         (msg, recipient): (nimiq_handel::update::LevelUpdate<Self::Contribution>, u16),
     ) -> BoxFuture<'static, ()> {
+        #[cfg(feature = "byzantine")]
+        if self.behaviour.withholds_contributions(self.height) {
+            return future::ready(()).boxed();
+        }
+
         // wrap the level update in the AggregateMessage
         let aggregation = AggregateMessage(msg);
         // tag it
@@ -108,14 +126,21 @@ pub struct TendermintProtocol<TValidatorNetwork: ValidatorNetwork> {
     pub network_id: NetworkId,
     // The block number of the macro block to produce.
     pub block_height: u32,
-    // Information relative to our validator that is necessary to produce blocks.
-    pub block_producer: BlockProducer,
+    // Signs proposals and votes on behalf of our validator.
+    pub signer: Arc<dyn ValidatorSigner>,
     // The validators for the current epoch.
     pub current_validators: Validators,
     // The main blockchain struct. Contains all of this validator information about the current chain.
     pub blockchain: Arc<RwLock<Blockchain>>,
     // Validator registry on the heap for easy cloning into handel protocol.
     validator_registry: Arc<ValidatorRegistry>,
+    // Whether our validator deviates from the protocol.
+    #[cfg(feature = "byzantine")]
+    behaviour: Behaviour,
+    // The quality of the other validators in past aggregations.
+    peer_quality: Arc<PeerQualityTracker>,
+    // Receives the proofs of the double votes found in our aggregations.
+    equivocation_proofs: Option<UnboundedSender<EquivocationProof>>,
 }
 
 impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidatorNetwork> {
@@ -125,10 +150,14 @@ impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidato
             validator_slot_band: self.validator_slot_band,
             network_id: self.network_id,
             block_height: self.block_height,
-            block_producer: self.block_producer.clone(),
+            signer: Arc::clone(&self.signer),
             current_validators: self.current_validators.clone(),
             blockchain: Arc::clone(&self.blockchain),
             validator_registry: Arc::clone(&self.validator_registry),
+            #[cfg(feature = "byzantine")]
+            behaviour: self.behaviour.clone(),
+            peer_quality: Arc::clone(&self.peer_quality),
+            equivocation_proofs: self.equivocation_proofs.clone(),
         }
     }
 }
@@ -140,14 +169,14 @@ where
     pub fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn ValidatorSigner>,
         current_validators: Validators,
         validator_slot_band: u16,
         network_id: NetworkId,
         block_height: u32,
     ) -> Self {
         Self {
-            block_producer,
+            signer,
             blockchain,
             network_id,
             block_height,
@@ -155,7 +184,73 @@ where
             validator_registry: Arc::new(ValidatorRegistry::new(current_validators.clone())),
             current_validators,
             network,
+            #[cfg(feature = "byzantine")]
+            behaviour: Behaviour::default(),
+            peer_quality: Arc::new(PeerQualityTracker::new()),
+            equivocation_proofs: None,
+        }
+    }
+
+    /// Sets the behaviour of our validator, which is honest by default.
+    #[cfg(feature = "byzantine")]
+    pub(crate) fn with_behaviour(mut self, behaviour: Behaviour) -> Self {
+        self.behaviour = behaviour;
+        self
+    }
+
+    /// Shares the quality of the other validators with other aggregations.
+    pub(crate) fn with_peer_quality(mut self, peer_quality: Arc<PeerQualityTracker>) -> Self {
+        self.peer_quality = peer_quality;
+        self
+    }
+
+    /// Sends the proofs of the double votes found in our aggregations to `equivocation_proofs`.
+    pub(crate) fn with_equivocation_proofs(
+        mut self,
+        equivocation_proofs: UnboundedSender<EquivocationProof>,
+    ) -> Self {
+        self.equivocation_proofs = Some(equivocation_proofs);
+        self
+    }
+
+    /// Tampers with the body of our proposal if our validator is byzantine.
+    #[cfg(feature = "byzantine")]
+    fn tamper_proposal(&self, mut block: MacroBlock, round: u32) -> MacroBlock {
+        if self.behaviour.tamper_macro_block(&mut block) {
+            warn!(
+                block_number = self.block_height,
+                round, "Byzantine: proposing a macro block with an invalid body"
+            );
+        }
+        block
+    }
+
+    /// Adds a vote conflicting with `vote` to our contribution if our validator is byzantine.
+    #[cfg(feature = "byzantine")]
+    fn add_conflicting_vote(
+        &self,
+        mut contribution: TendermintContribution,
+        vote: &TendermintVote,
+    ) -> TendermintContribution {
+        if let Some((proposal_hash, signature)) =
+            self.behaviour.conflicting_vote(vote, self.signer.as_ref())
+        {
+            warn!(
+                block_number = self.block_height,
+                round = vote.id.round_number,
+                step = ?vote.id.step,
+                "Byzantine: casting a conflicting Tendermint vote"
+            );
+            contribution.contributions.extend(
+                TendermintContribution::from_signature(
+                    proposal_hash,
+                    signature,
+                    self.validator_registry.get_slots(self.validator_slot_band),
+                )
+                .contributions,
+            );
         }
+        contribution
     }
 }
 
@@ -217,9 +312,25 @@ where
 
         // Create the proposal.
         let time = blockchain.time.now();
-        let block = self
-            .block_producer
-            .next_macro_block_proposal(&blockchain, time, round, vec![]);
+        let block = BlockProducer::next_macro_block_proposal_with_signer(
+            &blockchain,
+            time,
+            round,
+            vec![],
+            |prev_seed| self.signer.sign_vrf_seed(prev_seed),
+        )
+        .map_err(|error| {
+            error!(
+                block_number = self.block_height,
+                round,
+                %error,
+                "Failed to sign the seed of our proposal"
+            );
+            ProtocolError::Abort
+        })?;
+
+        #[cfg(feature = "byzantine")]
+        let block = self.tamper_proposal(block, round);
 
         // Always `Some(…)` because the above function always sets it to `Some(…)`.
         let body = block.body.expect("produced blocks always have a body");
@@ -349,17 +460,24 @@ where
     fn sign_proposal(
         &self,
         proposal_message: &ProposalMessage<Self::Proposal>,
-    ) -> Self::ProposalSignature {
-        let data = SignedProposal::hash(
-            &proposal_message.proposal.0,
-            proposal_message.round,
-            proposal_message.valid_round,
-        )
-        .serialize_to_vec();
-        (
-            self.block_producer.signing_key.sign(&data),
-            self.validator_slot_band,
-        )
+    ) -> Result<Self::ProposalSignature, ProtocolError> {
+        let signature = self
+            .signer
+            .sign_proposal(
+                &proposal_message.proposal.0,
+                proposal_message.round,
+                proposal_message.valid_round,
+            )
+            .map_err(|error| {
+                error!(
+                    block_number = self.block_height,
+                    round = proposal_message.round,
+                    %error,
+                    "Failed to sign our proposal"
+                );
+                ProtocolError::Abort
+            })?;
+        Ok((signature, self.validator_slot_band))
     }
 
     fn create_aggregation(
@@ -370,8 +488,13 @@ where
         update_stream: BoxStream<'static, Self::AggregationMessage>,
     ) -> BoxStream<'static, Self::Aggregation> {
         // Wrap the network
-        let network =
-            NetworkWrapper::new(self.block_height, (round, step), Arc::clone(&self.network));
+        let network = NetworkWrapper::new(
+            self.block_height,
+            (round, step),
+            Arc::clone(&self.network),
+            #[cfg(feature = "byzantine")]
+            self.behaviour.clone(),
+        );
 
         let step = match step {
             Step::Precommit => TendermintStep::PreCommit,
@@ -391,27 +514,67 @@ where
             id: id.clone(),
         };
 
-        let own_contribution = TendermintContribution::from_vote(
-            tendermint_vote,
-            &self.block_producer.voting_key.secret_key,
+        let signature = match self.signer.sign_tendermint_vote(&tendermint_vote) {
+            Ok(signature) => signature,
+            Err(error) => {
+                // Without our own contribution we cannot take part in this aggregation.
+                error!(
+                    block_number = self.block_height,
+                    round,
+                    ?step,
+                    %error,
+                    "Failed to sign our Tendermint vote"
+                );
+                return stream::empty().boxed();
+            }
+        };
+
+        let own_contribution = TendermintContribution::from_signature(
+            tendermint_vote.proposal_hash,
+            signature,
             self.validator_registry.get_slots(self.validator_slot_band),
         );
+        #[cfg(feature = "byzantine")]
+        let own_contribution = self.add_conflicting_vote(own_contribution, &tendermint_vote);
 
         let protocol = TendermintAggregationProtocol::new(
             Arc::clone(&self.validator_registry),
             self.validator_slot_band as usize,
             1, // to be removed
-            id,
+            id.clone(),
         );
 
-        Aggregation::new(
+        let aggregation = Aggregation::with_peer_quality(
             protocol,
             nimiq_handel::config::Config::default(),
             own_contribution,
             update_stream.map(|item| item.0).boxed(),
             network,
-        )
-        .boxed()
+            Arc::clone(&self.peer_quality),
+        );
+
+        let Some(equivocation_proofs) = self.equivocation_proofs.clone() else {
+            return aggregation.boxed();
+        };
+
+        // Votes of a validator for different proposals in the same aggregation prove that the
+        // validator equivocated.
+        let validators = self.current_validators.clone();
+        aggregation
+            .inspect(move |contribution| {
+                for proof in contribution.double_votes(&id, &validators) {
+                    debug!(
+                        validator_address = %proof.validator_address(),
+                        block_number = id.block_number,
+                        round = id.round_number,
+                        step = ?id.step,
+                        "Found a double vote in our aggregation"
+                    );
+                    // The validator stops listening once the macro block is produced.
+                    let _ = equivocation_proofs.send(proof.into());
+                }
+            })
+            .boxed()
     }
 
     fn verify_aggregation_message(
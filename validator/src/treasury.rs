@@ -0,0 +1,173 @@
+use std::cmp;
+
+use nimiq_account::Log;
+use nimiq_keys::{Address, KeyPair as SchnorrKeyPair};
+use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
+use nimiq_transaction::Transaction;
+use nimiq_transaction_builder::{TransactionBuilder, TransactionBuilderError};
+
+/// Configuration of the validator treasury.
+///
+/// The treasury manages the funds of the validator's fee key account. It reacts to rewards paid out
+/// to the fee key address, so the validator's reward address needs to be set to it. Each reward is
+/// first used to refill the fee reserve, then partially added as stake (compounding) and finally
+/// swept to cold storage.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TreasuryConfig {
+    /// Balance that is always kept in the fee key account to pay for validator transactions.
+    pub fee_reserve: Coin,
+    /// The staker that receives the compounded part of the rewards.
+    pub compound_staker: Option<Address>,
+    /// Percentage (0 to 100) of each reward that is added as stake to the `compound_staker`.
+    pub compound_percentage: u8,
+    /// The address that receives the part of the rewards that is not compounded.
+    pub sweep_address: Option<Address>,
+    /// Transfers with a value below this amount are not sent, the funds stay in the fee key account.
+    pub min_transfer: Coin,
+    /// The maximum amount (including fees) moved out of the fee key account per epoch.
+    pub max_transfer_per_epoch: Option<Coin>,
+    /// The fee attached to every transaction sent by the treasury.
+    pub transaction_fee: Coin,
+    /// If set, the treasury only logs the transactions it would send.
+    pub dry_run: bool,
+}
+
+/// A transfer out of the fee key account planned by the [`Treasury`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TreasuryAction {
+    /// Add `value` as stake to the given staker.
+    Compound {
+        staker_address: Address,
+        value: Coin,
+    },
+    /// Send `value` to the given address.
+    Sweep { recipient: Address, value: Coin },
+}
+
+impl TreasuryAction {
+    pub fn value(&self) -> Coin {
+        match self {
+            TreasuryAction::Compound { value, .. } | TreasuryAction::Sweep { value, .. } => *value,
+        }
+    }
+
+    /// Builds the transaction for this action, sent from the account belonging to `key_pair`.
+    pub fn to_transaction(
+        &self,
+        key_pair: &SchnorrKeyPair,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        match self {
+            TreasuryAction::Compound {
+                staker_address,
+                value,
+            } => TransactionBuilder::new_add_stake(
+                key_pair,
+                staker_address.clone(),
+                *value,
+                fee,
+                validity_start_height,
+                network_id,
+            ),
+            TreasuryAction::Sweep { recipient, value } => TransactionBuilder::new_basic(
+                key_pair,
+                recipient.clone(),
+                *value,
+                fee,
+                validity_start_height,
+                network_id,
+            ),
+        }
+    }
+}
+
+/// Plans the transfers of rewards out of the fee key account, keeping track of the per epoch limit.
+pub struct Treasury {
+    config: TreasuryConfig,
+    epoch: u32,
+    transferred_in_epoch: Coin,
+}
+
+impl Treasury {
+    pub fn new(config: TreasuryConfig) -> Self {
+        Self {
+            config,
+            epoch: 0,
+            transferred_in_epoch: Coin::ZERO,
+        }
+    }
+
+    pub fn config(&self) -> &TreasuryConfig {
+        &self.config
+    }
+
+    /// Sums up the rewards paid out to `address` in the given inherent logs.
+    pub fn rewards_for(address: &Address, inherent_logs: &[Log]) -> Coin {
+        inherent_logs
+            .iter()
+            .filter_map(|log| match log {
+                Log::PayoutReward { to, value } if to == address => Some(*value),
+                _ => None,
+            })
+            .fold(Coin::ZERO, Coin::saturating_add)
+    }
+
+    /// Plans the transfers for a `reward` paid out at `block_number` to the fee key account, which
+    /// holds `balance` after the reward was paid.
+    pub fn plan(&mut self, block_number: u32, reward: Coin, balance: Coin) -> Vec<TreasuryAction> {
+        let epoch = Policy::epoch_at(block_number);
+        if epoch != self.epoch {
+            self.epoch = epoch;
+            self.transferred_in_epoch = Coin::ZERO;
+        }
+
+        // Only the balance exceeding the fee reserve can be moved, which refills the reserve first.
+        let mut available = cmp::min(reward, balance.saturating_sub(self.config.fee_reserve));
+        if let Some(max_transfer) = self.config.max_transfer_per_epoch {
+            available = cmp::min(
+                available,
+                max_transfer.saturating_sub(self.transferred_in_epoch),
+            );
+        }
+
+        let compound = match self.config.compound_staker {
+            Some(_) => Coin::from_u64_unchecked(
+                u64::from(available) * u64::from(cmp::min(self.config.compound_percentage, 100))
+                    / 100,
+            ),
+            None => Coin::ZERO,
+        };
+        let sweep = match self.config.sweep_address {
+            Some(_) => available - compound,
+            None => Coin::ZERO,
+        };
+
+        let mut actions = vec![];
+        if let Some(value) = self.transfer_value(compound) {
+            actions.push(TreasuryAction::Compound {
+                staker_address: self.config.compound_staker.clone().unwrap(),
+                value,
+            });
+        }
+        if let Some(value) = self.transfer_value(sweep) {
+            actions.push(TreasuryAction::Sweep {
+                recipient: self.config.sweep_address.clone().unwrap(),
+                value,
+            });
+        }
+        actions
+    }
+
+    /// Returns the value of a transfer of `amount` (including the fee) if it is worth sending and
+    /// accounts it towards the epoch limit.
+    fn transfer_value(&mut self, amount: Coin) -> Option<Coin> {
+        let value = amount.checked_sub(self.config.transaction_fee)?;
+        if value.is_zero() || value < self.config.min_transfer {
+            return None;
+        }
+        self.transferred_in_epoch += amount;
+        Some(value)
+    }
+}
@@ -11,17 +11,19 @@ use std::{
 };
 
 use futures::stream::StreamExt;
+use nimiq_account::{BlockLog, Log};
 use nimiq_block::{Block, BlockHeaderTopic, BlockTopic, BlockType, EquivocationProof};
-use nimiq_blockchain::{interface::HistoryInterface, BlockProducer, Blockchain};
+use nimiq_blockchain::{interface::HistoryInterface, Blockchain};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent, ForkEvent, PushResult};
-use nimiq_bls::{lazy::LazyPublicKey, KeyPair as BlsKeyPair};
+use nimiq_bls::{lazy::LazyPublicKey, PublicKey as BlsPublicKey};
 use nimiq_consensus::{Consensus, ConsensusEvent, ConsensusProxy};
 use nimiq_database::{
     traits::{Database, ReadTransaction, WriteTransaction},
     DatabaseProxy, TableProxy,
 };
+use nimiq_handel::peer_quality::PeerQualityTracker;
 use nimiq_hash::{Blake2bHash, Hash};
-use nimiq_keys::{Address, KeyPair as SchnorrKeyPair};
+use nimiq_keys::{Address, Ed25519PublicKey, KeyPair as SchnorrKeyPair};
 use nimiq_mempool::config::MempoolConfig;
 use nimiq_mempool_task::MempoolTask;
 use nimiq_network_interface::{
@@ -37,12 +39,17 @@ use parking_lot::RwLock;
 use tokio_metrics::TaskMonitor;
 use tokio_stream::wrappers::BroadcastStream;
 
+#[cfg(feature = "byzantine")]
+use crate::byzantine::Behaviour;
 use crate::{
     aggregation::tendermint::{proposal::RequestProposal, state::MacroState},
     jail::EquivocationProofPool,
     micro::{ProduceMicroBlock, ProduceMicroBlockEvent},
+    performance::PerformanceTracker,
     proposal_buffer::{ProposalBuffer, ProposalReceiver},
     r#macro::{MappedReturn, ProduceMacroBlock, ProposalTopic},
+    signer::{ProtectedSigner, SlashingProtection, ValidatorSigner, VotingKeySigner},
+    treasury::{Treasury, TreasuryConfig},
 };
 
 #[derive(PartialEq)]
@@ -57,6 +64,14 @@ pub struct ConsensusState {
     equivocation_proofs: EquivocationProofPool,
 }
 
+impl ConsensusState {
+    /// Returns the equivocation proofs collected by our validator that weren't included in a block
+    /// yet.
+    pub fn equivocation_proofs(&self) -> impl Iterator<Item = &EquivocationProof> {
+        self.equivocation_proofs.iter()
+    }
+}
+
 /// Validator inactivity
 struct InactivityState {
     inactive_tx_hash: Blake2bHash,
@@ -65,24 +80,30 @@ struct InactivityState {
 
 pub struct ValidatorProxy {
     pub validator_address: Arc<RwLock<Address>>,
-    pub signing_key: Arc<RwLock<SchnorrKeyPair>>,
-    pub voting_key: Arc<RwLock<BlsKeyPair>>,
+    pub signer: Arc<dyn ValidatorSigner>,
     pub fee_key: Arc<RwLock<SchnorrKeyPair>>,
     pub automatic_reactivate: Arc<AtomicBool>,
     pub slot_band: Arc<RwLock<Option<u16>>>,
     pub consensus_state: Arc<RwLock<ConsensusState>>,
+    pub performance: Arc<RwLock<PerformanceTracker>>,
+    #[cfg(feature = "byzantine")]
+    pub behaviour: Behaviour,
+    pub handel_peer_quality: Arc<PeerQualityTracker>,
 }
 
 impl Clone for ValidatorProxy {
     fn clone(&self) -> Self {
         Self {
             validator_address: Arc::clone(&self.validator_address),
-            signing_key: Arc::clone(&self.signing_key),
-            voting_key: Arc::clone(&self.voting_key),
+            signer: Arc::clone(&self.signer),
             fee_key: Arc::clone(&self.fee_key),
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
+            performance: Arc::clone(&self.performance),
+            #[cfg(feature = "byzantine")]
+            behaviour: self.behaviour.clone(),
+            handel_peer_quality: Arc::clone(&self.handel_peer_quality),
         }
     }
 }
@@ -99,8 +120,7 @@ where
     env: DatabaseProxy,
 
     validator_address: Arc<RwLock<Address>>,
-    signing_key: Arc<RwLock<SchnorrKeyPair>>,
-    voting_key: Arc<RwLock<BlsKeyPair>>,
+    signer: Arc<dyn ValidatorSigner>,
     fee_key: Arc<RwLock<SchnorrKeyPair>>,
 
     proposal_receiver: ProposalReceiver<TValidatorNetwork>,
@@ -114,6 +134,13 @@ where
     validator_state: Option<InactivityState>,
     automatic_reactivate: Arc<AtomicBool>,
 
+    treasury: Option<Treasury>,
+    performance: Arc<RwLock<PerformanceTracker>>,
+    #[cfg(feature = "byzantine")]
+    behaviour: Behaviour,
+    handel_peer_quality: Arc<PeerQualityTracker>,
+    block_log_rx: BroadcastStream<BlockLog>,
+
     macro_producer: Option<ProduceMacroBlock<TValidatorNetwork>>,
     macro_state: Arc<RwLock<Option<MacroState>>>,
 
@@ -139,15 +166,17 @@ where
         network: Arc<TValidatorNetwork>,
         validator_address: Address,
         automatic_reactivate: bool,
-        signing_key: SchnorrKeyPair,
-        voting_key: BlsKeyPair,
+        treasury: Option<TreasuryConfig>,
+        signer: Arc<dyn ValidatorSigner>,
         fee_key: SchnorrKeyPair,
+        safe_startup: bool,
         mempool_config: MempoolConfig,
     ) -> Self {
         let consensus_event_rx = consensus.subscribe_events();
 
         let blockchain_rg = blockchain.read();
         let fork_event_rx = BroadcastStream::new(blockchain_rg.fork_notifier.subscribe());
+        let block_log_rx = BroadcastStream::new(blockchain_rg.log_notifier.subscribe());
         drop(blockchain_rg);
 
         let network_event_rx = network.subscribe_events();
@@ -164,6 +193,11 @@ where
         };
         let macro_state = Arc::new(RwLock::new(macro_state));
 
+        // Everything we sign goes through the slashing protection first.
+        let protection =
+            SlashingProtection::open_database(env.clone()).with_safe_startup(safe_startup);
+        let signer: Arc<dyn ValidatorSigner> = Arc::new(ProtectedSigner::new(signer, protection));
+
         let (proposal_sender, proposal_receiver) = ProposalBuffer::new(
             Arc::clone(&blockchain),
             Arc::clone(&network),
@@ -195,8 +229,7 @@ where
             env,
 
             validator_address: Arc::new(RwLock::new(validator_address)),
-            signing_key: Arc::new(RwLock::new(signing_key)),
-            voting_key: Arc::new(RwLock::new(voting_key)),
+            signer,
             fee_key: Arc::new(RwLock::new(fee_key)),
 
             proposal_receiver,
@@ -210,6 +243,13 @@ where
             validator_state: None,
             automatic_reactivate,
 
+            treasury: treasury.map(Treasury::new),
+            performance: Arc::new(RwLock::new(PerformanceTracker::new())),
+            #[cfg(feature = "byzantine")]
+            behaviour: Behaviour::default(),
+            handel_peer_quality: Arc::new(PeerQualityTracker::new()),
+            block_log_rx,
+
             macro_producer: None,
             macro_state: Arc::clone(&macro_state),
 
@@ -269,6 +309,9 @@ where
 
         *self.slot_band.write() = validators.get_slot_band_by_address(&self.validator_address());
 
+        // Handel identifies peers by their slot band, which changes with the validator set.
+        self.handel_peer_quality.reset_peers();
+
         if let Some(slot_band) = *self.slot_band.read() {
             log::info!(
                 validator_address = %self.validator_address(),
@@ -276,6 +319,16 @@ where
                 epoch_number = blockchain.epoch_number(),
                 "We are ACTIVE in this epoch"
             );
+
+            let validator = validators.get_validator_by_slot_band(slot_band);
+            if validator.signing_key != self.signer.signing_public_key()
+                || *validator.voting_key.compressed() != self.signer.voting_public_key().compress()
+            {
+                log::error!(
+                    validator_address = %self.validator_address(),
+                    "The keys of our signer do not match the keys of our validator"
+                );
+            }
         } else {
             log::debug!(
                 validator_address = %self.validator_address(),
@@ -319,7 +372,9 @@ where
         let head = blockchain.head();
         let next_block_number = head.block_number() + 1;
         let network_id = head.network();
-        let block_producer = BlockProducer::new(self.signing_key(), self.voting_key());
+
+        // The signer only signs messages for the blocks following the head it knows about.
+        self.signer.observe_head(head.block_number());
 
         debug!(
             next_block_number = next_block_number,
@@ -336,13 +391,16 @@ where
                 self.macro_producer = Some(ProduceMacroBlock::new(
                     Arc::clone(&self.blockchain),
                     Arc::clone(&self.network),
-                    block_producer,
+                    Arc::clone(&self.signer),
                     self.validator_slot_band(),
                     active_validators,
                     network_id,
                     next_block_number,
                     self.macro_state.read().clone(),
                     proposal_stream,
+                    #[cfg(feature = "byzantine")]
+                    self.behaviour.clone(),
+                    Arc::clone(&self.handel_peer_quality),
                 ));
             }
             BlockType::Micro => {
@@ -359,13 +417,16 @@ where
                     Arc::clone(&self.blockchain),
                     Arc::clone(&self.mempool_task.mempool),
                     Arc::clone(&self.network),
-                    block_producer,
+                    Arc::clone(&self.signer),
                     self.validator_slot_band(),
                     equivocation_proofs,
                     prev_seed,
                     next_block_number,
                     Self::PRODUCER_TIMEOUT,
                     Self::BLOCK_SEPARATION_TIME,
+                    #[cfg(feature = "byzantine")]
+                    self.behaviour.clone(),
+                    Arc::clone(&self.handel_peer_quality),
                 ));
             }
         }
@@ -432,7 +493,7 @@ where
         }
         drop(consensus_state);
 
-        let head_hash = &new_chain.last().expect("new_chain must not be empty").0;
+        let (head_hash, _) = new_chain.last().expect("new_chain must not be empty");
         self.init_block_producer(Some(head_hash));
     }
 
@@ -530,6 +591,8 @@ where
                     }
                 }
 
+                MappedReturn::EquivocationProof(proof) => self.on_equivocation_proof(proof),
+
                 // In case of a new state update we need to store the new version of it disregarding
                 // any old state which potentially still lingers.
                 MappedReturn::Update(update) => {
@@ -553,6 +616,9 @@ where
                     );
                     write_transaction.commit();
 
+                    self.performance
+                        .write()
+                        .on_tendermint_round(update.block_number, update.round_number());
                     *self.macro_state.write() = Some(update);
                 }
             }
@@ -575,12 +641,15 @@ where
 
     /// Publish our own validator record to the DHT.
     fn publish_dht(&self) {
-        let key = self.voting_key();
+        let signer = Arc::clone(&self.signer);
         let network = Arc::clone(&self.network);
 
         spawn(async move {
             if let Err(err) = network
-                .set_public_key(&key.public_key.compress(), &key.secret_key)
+                .set_public_key(
+                    &signer.voting_public_key().compress(),
+                    &VotingKeySigner(signer.as_ref()),
+                )
                 .await
             {
                 error!("could not set up DHT record: {:?}", err);
@@ -653,13 +722,25 @@ where
             )
     }
 
-    fn reactivate(&self, blockchain: &Blockchain) -> InactivityState {
+    fn reactivate(&self, blockchain: &Blockchain) -> Option<InactivityState> {
+        // The reactivate transaction must be signed with the signing key.
+        let signing_key = match self.signer.signing_key() {
+            Some(signing_key) => signing_key,
+            None => {
+                error!(
+                    "Cannot reactivate the validator, the signing key is held by a remote signer"
+                );
+                self.automatic_reactivate.store(false, Ordering::Release);
+                return None;
+            }
+        };
+
         let validity_start_height = blockchain.block_number();
 
         let reactivate_transaction = TransactionBuilder::new_reactivate_validator(
             &self.fee_key(),
             self.validator_address(),
-            &self.signing_key(),
+            &signing_key,
             Coin::ZERO,
             validity_start_height,
             blockchain.network_id(),
@@ -678,9 +759,71 @@ where
             }
         });
 
-        InactivityState {
+        Some(InactivityState {
             inactive_tx_hash: tx_hash,
             inactive_tx_validity_window_start: validity_start_height,
+        })
+    }
+
+    /// Lets the treasury move the rewards paid out to the fee key at `block_number`.
+    fn manage_rewards(&mut self, block_number: u32, inherent_logs: &[Log]) {
+        let treasury = match self.treasury.as_mut() {
+            Some(treasury) => treasury,
+            None => return,
+        };
+
+        let fee_key = self.fee_key.read().clone();
+        let fee_address = Address::from(&fee_key);
+        let reward = Treasury::rewards_for(&fee_address, inherent_logs);
+        if reward.is_zero() {
+            return;
+        }
+
+        let blockchain = self.blockchain.read();
+        let balance = match blockchain.get_account_if_complete(&fee_address) {
+            Some(account) => account.balance(),
+            None => return,
+        };
+        let validity_start_height = blockchain.block_number();
+        let network_id = blockchain.network_id();
+        drop(blockchain);
+
+        let fee = treasury.config().transaction_fee;
+        let dry_run = treasury.config().dry_run;
+        for action in treasury.plan(block_number, reward, balance) {
+            let transaction =
+                match action.to_transaction(&fee_key, fee, validity_start_height, network_id) {
+                    Ok(transaction) => transaction,
+                    Err(error) => {
+                        error!(?action, %error, "Failed to build treasury transaction");
+                        continue;
+                    }
+                };
+
+            if dry_run {
+                let tx_hash: Blake2bHash = transaction.hash();
+                info!(?action, %tx_hash, "Treasury dry run, not sending transaction");
+                continue;
+            }
+
+            // Submit through our own mempool first, so the transaction is verified and considered
+            // for the blocks we produce, then broadcast it to the network.
+            let mempool = Arc::clone(&self.mempool_task.mempool);
+            let consensus = self.consensus.clone();
+            spawn(async move {
+                if let Err(error) = mempool.add_transaction(transaction.clone(), None).await {
+                    error!(
+                        ?action,
+                        ?error,
+                        "Treasury transaction rejected by the mempool"
+                    );
+                    return;
+                }
+                debug!(?action, "Sending treasury transaction to the network");
+                if consensus.send_transaction(transaction).await.is_err() {
+                    error!(?action, "Failed to send treasury transaction");
+                }
+            });
         }
     }
 
@@ -692,12 +835,12 @@ where
         self.validator_address.read().clone()
     }
 
-    pub fn voting_key(&self) -> BlsKeyPair {
-        self.voting_key.read().clone()
+    pub fn voting_public_key(&self) -> BlsPublicKey {
+        self.signer.voting_public_key()
     }
 
-    pub fn signing_key(&self) -> SchnorrKeyPair {
-        self.signing_key.read().clone()
+    pub fn signing_public_key(&self) -> Ed25519PublicKey {
+        self.signer.signing_public_key()
     }
 
     pub fn fee_key(&self) -> SchnorrKeyPair {
@@ -707,12 +850,15 @@ where
     pub fn proxy(&self) -> ValidatorProxy {
         ValidatorProxy {
             validator_address: Arc::clone(&self.validator_address),
-            signing_key: Arc::clone(&self.signing_key),
-            voting_key: Arc::clone(&self.voting_key),
+            signer: Arc::clone(&self.signer),
             fee_key: Arc::clone(&self.fee_key),
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
+            performance: Arc::clone(&self.performance),
+            #[cfg(feature = "byzantine")]
+            behaviour: self.behaviour.clone(),
+            handel_peer_quality: Arc::clone(&self.handel_peer_quality),
         }
     }
 
@@ -768,6 +914,32 @@ where
             }
         }
 
+        // Update the performance statistics and let the treasury react to rewards paid out in
+        // applied blocks.
+        while let Poll::Ready(Some(block_log)) = self.block_log_rx.poll_next_unpin(cx) {
+            let block_log = match block_log {
+                Ok(block_log) => block_log,
+                Err(_) => continue,
+            };
+
+            self.performance.write().on_block_log(
+                &self.blockchain.read(),
+                &self.validator_address(),
+                &block_log,
+            );
+
+            if let BlockLog::AppliedBlock {
+                inherent_logs,
+                block_number,
+                ..
+            } = block_log
+            {
+                if self.is_synced() {
+                    self.manage_rewards(block_number, &inherent_logs);
+                }
+            }
+        }
+
         // If we are an active validator, participate in block production.
         if self.is_synced() && self.is_elected() {
             if self.macro_producer.is_some() {
@@ -800,7 +972,7 @@ where
                     {
                         let inactivity_state = self.reactivate(&blockchain);
                         drop(blockchain);
-                        self.validator_state = Some(inactivity_state);
+                        self.validator_state = inactivity_state;
                     }
                 }
                 ValidatorStakingState::NoStake | ValidatorStakingState::Unknown => {}
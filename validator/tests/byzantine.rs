@@ -0,0 +1,164 @@
+use std::{sync::Arc, time::Duration};
+
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_keys::Address;
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::simulator::{Simulator, SimulatorConfig};
+use nimiq_validator::byzantine::{
+    ConflictingVotes, DoublePropose, InvalidMacroBody, WithholdContributions,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(600);
+
+async fn simulator(seed: u64) -> Simulator {
+    Simulator::new(SimulatorConfig {
+        num_validators: 4,
+        num_full_nodes: 0,
+        seed,
+    })
+    .await
+}
+
+/// Whether the validator with the given address is jailed on the chain of the first validator.
+fn is_jailed(simulator: &Simulator, validator_address: &Address) -> bool {
+    let blockchain = simulator.validators[0].blockchain.read();
+    let staking_contract = blockchain.get_staking_contract();
+    let data_store = blockchain.get_staking_contract_store();
+    let txn = blockchain.read_transaction();
+    staking_contract
+        .get_validator(&data_store.read(&txn), validator_address)
+        .map_or(false, |validator| validator.jailed_from.is_some())
+}
+
+/// Whether slots of the validator with the given address were penalized in the current batch on
+/// the chain of the first validator.
+fn is_penalized(simulator: &Simulator, validator_address: &Address) -> bool {
+    simulator.validators[0]
+        .blockchain
+        .read()
+        .get_staking_contract()
+        .punished_slots
+        .current_batch_punished_slots_map()
+        .contains_key(validator_address)
+}
+
+#[test(tokio::test)]
+async fn double_proposer_gets_jailed() {
+    let simulator = simulator(1).await;
+    simulator.set_byzantine_strategy(3, Some(Arc::new(DoublePropose)));
+
+    let byzantine_address = simulator.validators[3].validator_address.clone();
+    assert!(
+        simulator
+            .run_until(
+                |simulator| simulator.has_pending_equivocation_proof(&byzantine_address),
+                TIMEOUT
+            )
+            .await
+    );
+    assert!(
+        simulator
+            .run_until(
+                |simulator| is_jailed(simulator, &byzantine_address),
+                TIMEOUT
+            )
+            .await
+    );
+
+    // The remaining validators keep finalizing batches.
+    simulator
+        .assert_liveness(Policy::macro_block_after(simulator.block_number()), TIMEOUT)
+        .await;
+    simulator.assert_finality_safety();
+}
+
+#[test(tokio::test)]
+async fn double_voter_gets_jailed() {
+    let simulator = simulator(2).await;
+    simulator.set_byzantine_strategy(1, Some(Arc::new(ConflictingVotes)));
+
+    // The conflicting votes end up in the Tendermint aggregations of the honest validators.
+    let byzantine_address = simulator.validators[1].validator_address.clone();
+    assert!(
+        simulator
+            .run_until(
+                |simulator| simulator.has_pending_equivocation_proof(&byzantine_address),
+                TIMEOUT
+            )
+            .await
+    );
+    assert!(
+        simulator
+            .run_until(
+                |simulator| is_jailed(simulator, &byzantine_address),
+                TIMEOUT
+            )
+            .await
+    );
+
+    simulator
+        .assert_liveness(Policy::macro_block_after(simulator.block_number()), TIMEOUT)
+        .await;
+    simulator.assert_finality_safety();
+}
+
+#[test(tokio::test)]
+async fn withholding_validator_gets_penalized() {
+    let simulator = simulator(3).await;
+    simulator.set_byzantine_strategy(2, Some(Arc::new(WithholdContributions)));
+
+    // The micro blocks of the withholding validator are skipped.
+    let byzantine_address = simulator.validators[2].validator_address.clone();
+    assert!(
+        simulator
+            .run_until(
+                |simulator| is_penalized(simulator, &byzantine_address),
+                TIMEOUT
+            )
+            .await
+    );
+
+    // Withholding is no equivocation.
+    assert!(!simulator.has_pending_equivocation_proof(&byzantine_address));
+    assert!(!is_jailed(&simulator, &byzantine_address));
+
+    simulator
+        .assert_liveness(
+            Policy::genesis_block_number() + 2 * Policy::blocks_per_batch(),
+            TIMEOUT,
+        )
+        .await;
+    simulator.assert_finality_safety();
+}
+
+#[test(tokio::test)]
+async fn invalid_macro_proposals_are_rejected() {
+    let simulator = simulator(4).await;
+    simulator.set_byzantine_strategy(0, Some(Arc::new(InvalidMacroBody)));
+
+    simulator
+        .assert_liveness(
+            Policy::genesis_block_number() + 2 * Policy::blocks_per_batch(),
+            TIMEOUT,
+        )
+        .await;
+    simulator.assert_finality_safety();
+
+    // The tampered bodies punish a slot nobody lost, so none of them was finalized.
+    let blockchain = simulator.validators[1].blockchain.read();
+    let macro_head = blockchain
+        .get_block(&blockchain.macro_head_hash(), true, None)
+        .expect("Macro head must exist")
+        .unwrap_macro();
+    let body = macro_head.body.expect("Macro head must have a body");
+    assert!(body.next_batch_initial_punished_set.is_empty());
+    drop(blockchain);
+
+    // Proposing an invalid block is no equivocation, nobody is punished for it.
+    for validator in &simulator.validators {
+        assert!(!simulator.has_pending_equivocation_proof(&validator.validator_address));
+        assert!(!is_jailed(&simulator, &validator.validator_address));
+        assert!(!is_penalized(&simulator, &validator.validator_address));
+    }
+}
@@ -91,6 +91,69 @@ async fn one_validator_can_create_micro_blocks() {
     assert!(consensus1.blockchain.read().block_number() >= 10 + Policy::genesis_block_number());
 }
 
+#[test(tokio::test)]
+async fn validator_tracks_its_performance() {
+    let hub = MockHub::default();
+    let env = VolatileDatabase::new(20).expect("Could not open a volatile database");
+
+    let voting_key = BlsKeyPair::generate(&mut seeded_rng(0));
+    let validator_key = KeyPair::generate(&mut seeded_rng(0));
+    let fee_key = KeyPair::generate(&mut seeded_rng(0));
+    let signing_key = KeyPair::generate(&mut seeded_rng(0));
+    let genesis = GenesisBuilder::default()
+        .with_network(NetworkId::UnitAlbatross)
+        .with_genesis_block_number(Policy::genesis_block_number())
+        .with_genesis_validator(
+            Address::from(&validator_key),
+            signing_key.public,
+            voting_key.public_key,
+            Address::default(),
+            None,
+            None,
+            false,
+        )
+        .generate(env)
+        .unwrap();
+
+    let (validator, mut consensus1) = build_validator::<Network>(
+        0,
+        Address::from(&validator_key),
+        false,
+        signing_key,
+        voting_key,
+        fee_key,
+        genesis.clone(),
+        &mut Some(hub),
+        false,
+    )
+    .await;
+
+    consensus1.force_established();
+
+    let validator_proxy = validator.proxy();
+    let mut performance_updates = validator_proxy.performance.read().subscribe();
+    spawn(validator);
+
+    // Wait until the validator processed the first ten blocks.
+    let block_number = Policy::genesis_block_number() + 10;
+    performance_updates
+        .wait_for(|last_block_number| *last_block_number >= block_number)
+        .await
+        .unwrap();
+
+    // As the only validator, we own all slots and produce all micro blocks.
+    let performance = validator_proxy.performance.read().current().clone();
+    assert_eq!(performance.epoch_number, 1);
+    assert_eq!(performance.slots, Policy::SLOTS);
+    assert!(performance.micro_blocks_produced > 0);
+    assert_eq!(
+        performance.micro_blocks_produced,
+        performance.micro_blocks_expected
+    );
+    assert_eq!(performance.skip_blocks_caused, 0);
+    assert_eq!(performance.penalties, 0);
+}
+
 #[test(tokio::test)]
 async fn four_validators_can_create_micro_blocks() {
     let hub = MockHub::default();
@@ -285,7 +348,7 @@ async fn validator_can_catch_up() {
     // Manually construct a skip block for the validator
     let vc = create_skip_block_update(
         skip_block_info,
-        validator.voting_key(),
+        validator.proxy().signer.voting_key().unwrap(),
         validator.validator_slot_band(),
         &slots,
     );
@@ -0,0 +1,316 @@
+use std::{sync::Arc, thread, time::Duration};
+
+use nimiq_block::MicroHeader;
+use nimiq_bls::KeyPair as BlsKeyPair;
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
+use nimiq_keys::{KeyPair, SecureGenerate};
+use nimiq_primitives::{
+    networks::NetworkId, policy::Policy, TendermintIdentifier, TendermintStep, TendermintVote,
+};
+use nimiq_test_log::test;
+use nimiq_test_utils::validator::seeded_rng;
+use nimiq_utils::Sensitive;
+use nimiq_validator::signer::{
+    LocalSigner, ProtectedSigner, RemoteSigner, RemoteSignerConfig, SignerAddress, SignerError,
+    SignerServer, SlashingProtection, ValidatorSigner,
+};
+use nimiq_vrf::VrfSeed;
+
+fn local_signer() -> LocalSigner {
+    LocalSigner::new(
+        KeyPair::generate(&mut seeded_rng(0)),
+        BlsKeyPair::generate(&mut seeded_rng(0)),
+    )
+}
+
+fn micro_header(block_number: u32, timestamp: u64) -> MicroHeader {
+    MicroHeader {
+        network: NetworkId::UnitAlbatross,
+        version: Policy::VERSION,
+        block_number,
+        timestamp,
+        parent_hash: Blake2bHash::default(),
+        seed: VrfSeed::default(),
+        extra_data: vec![],
+        state_root: Blake2bHash::default(),
+        body_root: Blake2sHash::default(),
+        diff_root: Blake2bHash::default(),
+        history_root: Blake2bHash::default(),
+    }
+}
+
+fn vote(block_number: u32, round_number: u32, proposal: Option<u8>) -> TendermintVote {
+    TendermintVote {
+        proposal_hash: proposal.map(|byte| Blake2sHash::from([byte; 32])),
+        id: TendermintIdentifier {
+            network: NetworkId::UnitAlbatross,
+            block_number,
+            round_number,
+            step: TendermintStep::PreVote,
+        },
+    }
+}
+
+#[test]
+fn it_refuses_conflicting_micro_headers() {
+    let signer = ProtectedSigner::new(local_signer(), SlashingProtection::in_memory());
+    let block_number = Policy::genesis_block_number() + 1;
+    signer.observe_head(block_number);
+
+    assert!(signer
+        .sign_micro_header(&micro_header(block_number, 1))
+        .is_ok());
+    // Signing the same header again is fine.
+    assert!(signer
+        .sign_micro_header(&micro_header(block_number, 1))
+        .is_ok());
+    assert!(matches!(
+        signer.sign_micro_header(&micro_header(block_number, 2)),
+        Err(SignerError::Refused(_))
+    ));
+    assert!(signer
+        .sign_micro_header(&micro_header(block_number + 1, 2))
+        .is_ok());
+}
+
+#[test]
+fn it_refuses_conflicting_votes_and_proposals() {
+    let signer = ProtectedSigner::new(local_signer(), SlashingProtection::in_memory());
+    let block_number = Policy::genesis_block_number() + Policy::blocks_per_batch();
+    signer.observe_head(block_number - 1);
+
+    assert!(signer
+        .sign_tendermint_vote(&vote(block_number, 0, Some(1)))
+        .is_ok());
+    assert!(signer
+        .sign_tendermint_vote(&vote(block_number, 0, Some(1)))
+        .is_ok());
+    assert!(matches!(
+        signer.sign_tendermint_vote(&vote(block_number, 0, None)),
+        Err(SignerError::Refused(_))
+    ));
+    // A new round allows a new vote.
+    assert!(signer
+        .sign_tendermint_vote(&vote(block_number, 1, None))
+        .is_ok());
+
+    let mut protection = SlashingProtection::in_memory();
+    protection.observe_head(block_number - 1);
+    assert!(protection
+        .register_proposal(block_number, 0, Blake2bHash::from([1; 32]))
+        .is_ok());
+    assert!(matches!(
+        protection.register_proposal(block_number, 0, Blake2bHash::from([2; 32])),
+        Err(SignerError::Refused(_))
+    ));
+    assert!(protection
+        .register_proposal(block_number, 1, Blake2bHash::from([2; 32]))
+        .is_ok());
+}
+
+#[test]
+fn it_refuses_blocks_below_the_watermark() {
+    let mut protection = SlashingProtection::in_memory();
+    let block_number = Policy::genesis_block_number() + 2 * Policy::blocks_per_epoch();
+    protection.observe_head(block_number);
+
+    protection
+        .register_micro_header(block_number, Blake2bHash::from([1; 32]))
+        .unwrap();
+    assert_eq!(
+        protection.low_watermark(),
+        block_number - Policy::blocks_per_epoch()
+    );
+    assert!(matches!(
+        protection.register_micro_header(
+            block_number - Policy::blocks_per_epoch() - 1,
+            Blake2bHash::from([1; 32])
+        ),
+        Err(SignerError::Refused(_))
+    ));
+}
+
+#[test]
+fn it_refuses_blocks_far_ahead_of_the_head() {
+    let mut protection = SlashingProtection::in_memory();
+    let block_number = Policy::genesis_block_number() + 1;
+
+    // Nothing is signed before the head is known.
+    assert!(matches!(
+        protection.register_micro_header(block_number, Blake2bHash::from([1; 32])),
+        Err(SignerError::NotLive)
+    ));
+
+    protection.observe_head(block_number - 1);
+    let far_ahead = block_number + 2 * Policy::blocks_per_epoch();
+    assert!(matches!(
+        protection.register_micro_header(far_ahead, Blake2bHash::from([1; 32])),
+        Err(SignerError::Refused(_))
+    ));
+
+    // The refused request did not raise the watermark.
+    assert_eq!(protection.low_watermark(), 0);
+    assert!(protection
+        .register_micro_header(block_number, Blake2bHash::from([1; 32]))
+        .is_ok());
+}
+
+#[test]
+fn it_persists_the_slashing_protection() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("protection.bin");
+    let block_number = Policy::genesis_block_number() + 1;
+
+    let mut protection = SlashingProtection::open(&path).unwrap();
+    protection.observe_head(block_number);
+    protection
+        .register_micro_header(block_number, Blake2bHash::from([1; 32]))
+        .unwrap();
+    drop(protection);
+
+    let mut protection = SlashingProtection::open(&path).unwrap();
+    protection.observe_head(block_number);
+    assert!(matches!(
+        protection.register_micro_header(block_number, Blake2bHash::from([2; 32])),
+        Err(SignerError::Refused(_))
+    ));
+}
+
+#[test]
+fn it_allows_signing_on_a_new_branch_after_a_rebranch() {
+    let signer = ProtectedSigner::new(local_signer(), SlashingProtection::in_memory());
+    let block_number = Policy::genesis_block_number() + 3;
+    signer.observe_head(block_number);
+
+    assert!(signer
+        .sign_micro_header(&micro_header(block_number + 1, 1))
+        .is_ok());
+
+    // The chain was rebranched to a branch on which we produce an earlier block.
+    signer.observe_head(block_number - 2);
+    assert!(signer
+        .sign_micro_header(&micro_header(block_number - 1, 2))
+        .is_ok());
+
+    // Going back to a lower round is fine as well, as long as nothing conflicts.
+    let block_number = Policy::genesis_block_number() + Policy::blocks_per_batch();
+    signer.observe_head(block_number - 1);
+    assert!(signer
+        .sign_tendermint_vote(&vote(block_number, 1, Some(1)))
+        .is_ok());
+    assert!(signer
+        .sign_tendermint_vote(&vote(block_number, 0, Some(2)))
+        .is_ok());
+}
+
+#[test]
+fn it_persists_the_slashing_protection_in_the_database() {
+    let env = VolatileDatabase::new(20).unwrap();
+    let block_number = Policy::genesis_block_number() + 2;
+
+    let mut protection = SlashingProtection::open_database(env.clone());
+    protection.observe_head(block_number);
+    protection
+        .register_micro_header(block_number, Blake2bHash::from([1; 32]))
+        .unwrap();
+    drop(protection);
+
+    // A restarted validator still knows what it signed.
+    let mut protection = SlashingProtection::open_database(env);
+    protection.observe_head(block_number);
+    assert!(matches!(
+        protection.register_micro_header(block_number, Blake2bHash::from([2; 32])),
+        Err(SignerError::Refused(_))
+    ));
+}
+
+#[test]
+fn it_waits_for_the_live_head_in_safe_startup_mode() {
+    let env = VolatileDatabase::new(20).unwrap();
+    let block_number = Policy::genesis_block_number() + 5;
+
+    let mut protection = SlashingProtection::open_database(env.clone());
+    protection.observe_head(block_number - 1);
+    protection
+        .register_micro_header(block_number, Blake2bHash::from([1; 32]))
+        .unwrap();
+    drop(protection);
+
+    let mut protection = SlashingProtection::open_database(env).with_safe_startup(true);
+    assert!(!protection.is_live());
+
+    // A head below what we signed before is not enough.
+    protection.observe_head(block_number - 1);
+    assert!(!protection.is_live());
+    assert!(matches!(
+        protection.register_micro_header(block_number, Blake2bHash::from([2; 32])),
+        Err(SignerError::NotLive)
+    ));
+
+    protection.observe_head(block_number);
+    assert!(protection.is_live());
+    assert!(protection
+        .register_micro_header(block_number + 1, Blake2bHash::from([1; 32]))
+        .is_ok());
+}
+
+fn start_server(auth_token: &str) -> SignerAddress {
+    let signer = ProtectedSigner::new(local_signer(), SlashingProtection::in_memory());
+    let server = SignerServer::bind(
+        &SignerAddress::Tcp("127.0.0.1:0".to_string()),
+        auth_token.to_string(),
+        Arc::new(signer),
+    )
+    .unwrap();
+    let address = server.local_address().unwrap();
+    thread::spawn(move || server.run());
+    address
+}
+
+fn remote_config(address: SignerAddress, auth_token: &str) -> RemoteSignerConfig {
+    RemoteSignerConfig {
+        address,
+        auth_token: Sensitive(auth_token.to_string()),
+        timeout: Duration::from_secs(5),
+    }
+}
+
+#[test]
+fn it_signs_through_the_remote_signer() {
+    let address = start_server("secret");
+    let remote = RemoteSigner::connect(remote_config(address, "secret")).unwrap();
+    let local = local_signer();
+
+    assert_eq!(remote.signing_public_key(), local.signing_public_key());
+    assert_eq!(remote.voting_public_key(), local.voting_public_key());
+    assert!(remote.signing_key().is_none());
+    assert!(remote.voting_key().is_none());
+
+    let block_number = Policy::genesis_block_number() + 1;
+    remote.observe_head(block_number);
+    let header = micro_header(block_number, 1);
+    let signature = remote.sign_micro_header(&header).unwrap();
+    assert!(local
+        .signing_public_key()
+        .verify(&signature, header.hash::<Blake2bHash>().as_slice()));
+
+    let vote = vote(block_number, 0, Some(1));
+    let signature = remote.sign_tendermint_vote(&vote).unwrap();
+    assert!(local.voting_public_key().verify(&vote, &signature));
+
+    // The slashing protection of the server applies.
+    assert!(matches!(
+        remote.sign_micro_header(&micro_header(block_number, 2)),
+        Err(SignerError::Refused(_))
+    ));
+}
+
+#[test]
+fn it_rejects_a_wrong_auth_token() {
+    let address = start_server("secret");
+    assert!(matches!(
+        RemoteSigner::connect(remote_config(address, "wrong")),
+        Err(SignerError::Authentication)
+    ));
+}
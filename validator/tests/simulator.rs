@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::simulator::{SimulationEvent, Simulator, SimulatorConfig};
+
+const TIMEOUT: Duration = Duration::from_secs(600);
+
+#[test(tokio::test)]
+async fn validators_survive_a_dead_and_a_slow_validator() {
+    let mut simulator = Simulator::new(SimulatorConfig {
+        num_validators: 4,
+        num_full_nodes: 1,
+        seed: 1,
+    })
+    .await;
+
+    let genesis_block = Policy::genesis_block_number();
+    simulator
+        .run_scenario(
+            [
+                (genesis_block + 3, SimulationEvent::KillValidator(3)),
+                (
+                    genesis_block + 6,
+                    SimulationEvent::DelayValidator(2, Duration::from_secs(2)),
+                ),
+            ],
+            TIMEOUT,
+        )
+        .await;
+
+    // The remaining validators still own enough slots to finalize batches.
+    simulator
+        .assert_liveness(genesis_block + 2 * Policy::blocks_per_batch(), TIMEOUT)
+        .await;
+    simulator.assert_finality_safety();
+}
+
+#[test(tokio::test)]
+async fn validators_recover_from_a_partition_and_an_equivocation() {
+    let mut simulator = Simulator::new(SimulatorConfig {
+        num_validators: 4,
+        num_full_nodes: 0,
+        seed: 2,
+    })
+    .await;
+
+    let genesis_block = Policy::genesis_block_number();
+    simulator.assert_liveness(genesis_block + 2, TIMEOUT).await;
+
+    // Neither side of the partition can finalize a macro block on its own.
+    simulator.partition(&[vec![0, 1], vec![2, 3]]);
+    let macro_block = Policy::macro_block_after(simulator.block_number());
+    assert!(
+        !simulator
+            .run_until_block(macro_block, Duration::from_secs(60))
+            .await
+    );
+    simulator.apply(SimulationEvent::Heal).await;
+    simulator.assert_liveness(macro_block, TIMEOUT).await;
+
+    // An equivocating proposer doesn't break finality.
+    while simulator.inject_equivocation().await.is_none() {
+        let block_number = simulator.block_number();
+        simulator.assert_liveness(block_number + 1, TIMEOUT).await;
+    }
+    simulator
+        .assert_liveness(Policy::macro_block_after(simulator.block_number()), TIMEOUT)
+        .await;
+    simulator.assert_finality_safety();
+}
@@ -133,7 +133,7 @@ async fn it_verifies_inferior_chain_proposals() {
         valid_round: None,
         proposal: Header(main_chain_proposal.header, None),
     };
-    let main_chain_sig = interface.sign_proposal(&main_chain_msg);
+    let main_chain_sig = interface.sign_proposal(&main_chain_msg).unwrap();
     let message = SignedProposalMessage {
         message: main_chain_msg,
         signature: main_chain_sig,
@@ -147,7 +147,7 @@ async fn it_verifies_inferior_chain_proposals() {
         valid_round: None,
         proposal: Header(inf_proposal2.header, None),
     };
-    let inf_chain2_sig = interface.sign_proposal(&inf_chain2);
+    let inf_chain2_sig = interface.sign_proposal(&inf_chain2).unwrap();
     let message: SignedProposalMessage<Header<_>, _> = SignedProposalMessage {
         message: inf_chain2,
         signature: inf_chain2_sig,
@@ -170,7 +170,7 @@ async fn it_verifies_inferior_chain_proposals() {
         valid_round: None,
         proposal: Header(inf_proposal1.header.clone(), None),
     };
-    let inf_chain1_sig = interface.sign_proposal(&inf_chain1);
+    let inf_chain1_sig = interface.sign_proposal(&inf_chain1).unwrap();
     let message: SignedProposalMessage<Header<_>, _> = SignedProposalMessage {
         message: inf_chain1.clone(),
         signature: inf_chain1_sig,
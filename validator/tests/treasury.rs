@@ -0,0 +1,97 @@
+use nimiq_account::Log;
+use nimiq_keys::Address;
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_test_log::test;
+use nimiq_validator::treasury::{Treasury, TreasuryAction, TreasuryConfig};
+
+fn coin(value: u64) -> Coin {
+    Coin::from_u64_unchecked(value)
+}
+
+fn config() -> TreasuryConfig {
+    TreasuryConfig {
+        fee_reserve: coin(1_000),
+        compound_staker: Some(Address::from([1u8; Address::SIZE])),
+        compound_percentage: 25,
+        sweep_address: Some(Address::from([2u8; Address::SIZE])),
+        min_transfer: coin(10),
+        max_transfer_per_epoch: Some(coin(1_500)),
+        transaction_fee: coin(1),
+        dry_run: false,
+    }
+}
+
+#[test]
+fn it_sums_rewards_for_the_fee_key() {
+    let fee_address = Address::from([3u8; Address::SIZE]);
+    let logs = vec![
+        Log::PayoutReward {
+            to: fee_address.clone(),
+            value: coin(100),
+        },
+        Log::PayoutReward {
+            to: Address::from([4u8; Address::SIZE]),
+            value: coin(1_000),
+        },
+        Log::PayoutReward {
+            to: fee_address.clone(),
+            value: coin(50),
+        },
+    ];
+    assert_eq!(Treasury::rewards_for(&fee_address, &logs), coin(150));
+}
+
+#[test]
+fn it_refills_the_fee_reserve_first() {
+    let mut treasury = Treasury::new(config());
+    let block_number = Policy::genesis_block_number() + 1;
+
+    // The whole reward is needed to refill the reserve.
+    assert!(treasury.plan(block_number, coin(400), coin(900)).is_empty());
+
+    // Only the part exceeding the reserve is moved.
+    assert_eq!(
+        treasury.plan(block_number, coin(400), coin(1_200)),
+        vec![
+            TreasuryAction::Compound {
+                staker_address: config().compound_staker.unwrap(),
+                value: coin(49),
+            },
+            TreasuryAction::Sweep {
+                recipient: config().sweep_address.unwrap(),
+                value: coin(149),
+            },
+        ]
+    );
+}
+
+#[test]
+fn it_respects_the_epoch_limit_and_minimum_transfer() {
+    let mut treasury = Treasury::new(TreasuryConfig {
+        compound_staker: None,
+        ..config()
+    });
+    let block_number = Policy::genesis_block_number() + 1;
+
+    let actions = treasury.plan(block_number, coin(1_000), coin(10_000));
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].value(), coin(999));
+
+    // Only 500 are left for this epoch.
+    let actions = treasury.plan(block_number + 1, coin(1_000), coin(10_000));
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].value(), coin(499));
+
+    // The limit is exhausted.
+    assert!(treasury
+        .plan(block_number + 2, coin(1_000), coin(10_000))
+        .is_empty());
+
+    // The limit is reset in the next epoch, but transfers below the minimum are not sent.
+    let next_epoch = block_number + Policy::blocks_per_epoch();
+    assert!(treasury.plan(next_epoch, coin(5), coin(10_000)).is_empty());
+    assert_eq!(
+        treasury.plan(next_epoch, coin(1_000), coin(10_000))[0].value(),
+        coin(999)
+    );
+}
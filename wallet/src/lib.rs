@@ -1,9 +1,15 @@
 pub use multisig_account::MultiSigAccount;
+pub use transaction_scheduler::{ScheduleTrigger, ScheduledAction, ScheduledTransaction};
+#[cfg(feature = "store")]
+pub use transaction_scheduler::{ScheduledTransactionSubmitter, TransactionScheduler};
+pub use unlocked_wallets::UnlockedWallets;
 pub use wallet_account::WalletAccount;
 #[cfg(feature = "store")]
 pub use wallet_store::WalletStore;
 
 mod multisig_account;
+mod transaction_scheduler;
+mod unlocked_wallets;
 mod wallet_account;
 #[cfg(feature = "store")]
 mod wallet_store;
@@ -0,0 +1,323 @@
+use std::io;
+
+#[cfg(feature = "store")]
+use futures::{future::BoxFuture, Stream, StreamExt};
+#[cfg(feature = "store")]
+use nimiq_database::{
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableFlags, TableProxy,
+};
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
+#[cfg(feature = "store")]
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
+#[cfg(feature = "store")]
+use nimiq_keys::KeyPair;
+use nimiq_primitives::coin::Coin;
+#[cfg(feature = "store")]
+use nimiq_primitives::networks::NetworkId;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::Transaction;
+#[cfg(feature = "store")]
+use nimiq_transaction_builder::TransactionBuilder;
+
+/// The condition that has to be met before a scheduled transaction is submitted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ScheduleTrigger {
+    /// The transaction is due once the head of the chain reached the given block height.
+    BlockHeight(u32),
+    /// The transaction is due once the head of the chain has a timestamp (in milliseconds)
+    /// greater or equal to the given one.
+    Timestamp(u64),
+}
+
+impl ScheduleTrigger {
+    /// Returns whether the trigger fires for a head with the given block number and timestamp.
+    pub fn is_met(&self, block_number: u32, timestamp: u64) -> bool {
+        match self {
+            ScheduleTrigger::BlockHeight(height) => block_number >= *height,
+            ScheduleTrigger::Timestamp(time) => timestamp >= *time,
+        }
+    }
+}
+
+/// What to do once a scheduled transaction is due.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ScheduledAction {
+    /// Submit the given, already signed transaction.
+    Send(Transaction),
+    /// Build, sign and submit a transaction that redeems the whole balance of the HTLC at
+    /// `contract_address` to `recipient` after its timeout. The transaction is signed with the
+    /// key of `wallet`, which therefore needs to be unlocked when the transaction is due.
+    RedeemHtlcTimeout {
+        wallet: Address,
+        contract_address: Address,
+        recipient: Address,
+        fee: Coin,
+    },
+}
+
+/// A transaction that is held by the node wallet until its trigger fires.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledTransaction {
+    pub id: u64,
+    pub trigger: ScheduleTrigger,
+    pub action: ScheduledAction,
+}
+
+impl IntoDatabaseValue for ScheduledTransaction {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize_to_writer(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for ScheduledTransaction {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::deserialize_from_vec(bytes).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+/// The access to the chain and the network that is needed to submit due scheduled transactions.
+#[cfg(feature = "store")]
+pub trait ScheduledTransactionSubmitter {
+    /// The network for which transactions are built.
+    fn network_id(&self) -> NetworkId;
+
+    /// Returns the balance of the HTLC at `address`. Returns `Ok(None)` if the account is not
+    /// known (yet) and an error if there is no HTLC at the given address.
+    fn htlc_balance(&self, address: &Address) -> Result<Option<Coin>, String>;
+
+    /// Returns the key pair of `wallet` if it is currently unlocked.
+    fn key_pair(&self, wallet: &Address) -> Option<KeyPair>;
+
+    /// Submits the transaction to the network.
+    fn send_transaction(&self, transaction: Transaction) -> BoxFuture<'_, Result<(), String>>;
+}
+
+/// The result of trying to turn a due scheduled transaction into a transaction to send.
+#[cfg(feature = "store")]
+enum Prepared {
+    /// The transaction is ready to be sent.
+    Ready(Transaction),
+    /// The transaction can't be sent right now (e.g. the wallet is locked), try again later.
+    Postponed,
+    /// The transaction can never be sent (e.g. the HTLC was already redeemed), drop it.
+    Obsolete(String),
+}
+
+/// Persistent store for transactions that should be submitted in the future.
+///
+/// [`TransactionScheduler::run`] follows the chain head and submits the transactions once they
+/// are due.
+#[cfg(feature = "store")]
+#[derive(Debug)]
+pub struct TransactionScheduler {
+    env: DatabaseProxy,
+    scheduled_db: TableProxy,
+    meta_db: TableProxy,
+}
+
+#[cfg(feature = "store")]
+impl TransactionScheduler {
+    const SCHEDULED_DB_NAME: &'static str = "ScheduledTransactions";
+    const META_DB_NAME: &'static str = "ScheduledTransactionsMeta";
+    const NEXT_ID_KEY: &'static str = "nextId";
+
+    pub fn new(env: DatabaseProxy) -> Self {
+        let scheduled_db =
+            env.open_table_with_flags(Self::SCHEDULED_DB_NAME.to_string(), TableFlags::UINT_KEYS);
+        let meta_db = env.open_table(Self::META_DB_NAME.to_string());
+        TransactionScheduler {
+            env,
+            scheduled_db,
+            meta_db,
+        }
+    }
+
+    /// Persists a new scheduled transaction and returns it together with its assigned id.
+    /// Ids are never reused, not even after the scheduled transaction has been removed.
+    pub fn schedule(
+        &self,
+        trigger: ScheduleTrigger,
+        action: ScheduledAction,
+    ) -> ScheduledTransaction {
+        let mut txn = self.env.write_transaction();
+
+        let id: u64 = txn.get(&self.meta_db, Self::NEXT_ID_KEY).unwrap_or(0);
+        txn.put(&self.meta_db, Self::NEXT_ID_KEY, &(id + 1));
+
+        let scheduled = ScheduledTransaction {
+            id,
+            trigger,
+            action,
+        };
+        txn.put_reserve(&self.scheduled_db, &id, &scheduled);
+        txn.commit();
+
+        scheduled
+    }
+    /// Returns all scheduled transactions ordered by their id.
+    pub fn list(&self) -> Vec<ScheduledTransaction> {
+        let txn = self.env.read_transaction();
+        let cursor = txn.cursor(&self.scheduled_db);
+        cursor
+            .into_iter_start::<u64, ScheduledTransaction>()
+            .map(|(_, scheduled)| scheduled)
+            .collect()
+    }
+
+    pub fn get(&self, id: u64) -> Option<ScheduledTransaction> {
+        self.env.read_transaction().get(&self.scheduled_db, &id)
+    }
+
+    /// Returns all scheduled transactions whose trigger fires for a head with the given block
+    /// number and timestamp.
+    pub fn due(&self, block_number: u32, timestamp: u64) -> Vec<ScheduledTransaction> {
+        self.list()
+            .into_iter()
+            .filter(|scheduled| scheduled.trigger.is_met(block_number, timestamp))
+            .collect()
+    }
+
+    /// Removes the scheduled transaction with the given id. Returns whether it existed.
+    pub fn remove(&self, id: u64) -> bool {
+        let mut txn = self.env.write_transaction();
+        let exists = txn
+            .get::<u64, ScheduledTransaction>(&self.scheduled_db, &id)
+            .is_some();
+        if exists {
+            txn.remove(&self.scheduled_db, &id);
+            txn.commit();
+        }
+        exists
+    }
+
+    /// Submits the scheduled transactions that are due for each head yielded by `heads`, given
+    /// as block number and timestamp. Handled transactions are removed, transactions that can't
+    /// be submitted yet are retried with the next head. Runs until `heads` ends.
+    ///
+    /// `heads` should only yield heads while the node is in consensus, to avoid submitting
+    /// transactions based on an outdated head.
+    pub async fn run<H, S>(&self, mut heads: H, submitter: &S)
+    where
+        H: Stream<Item = (u32, u64)> + Unpin,
+        S: ScheduledTransactionSubmitter,
+    {
+        while let Some((block_number, timestamp)) = heads.next().await {
+            for scheduled in self.due(block_number, timestamp) {
+                self.submit(&scheduled, block_number, submitter).await;
+            }
+        }
+    }
+
+    async fn submit<S: ScheduledTransactionSubmitter>(
+        &self,
+        scheduled: &ScheduledTransaction,
+        block_number: u32,
+        submitter: &S,
+    ) {
+        let transaction = match Self::prepare(scheduled, block_number, submitter) {
+            Prepared::Ready(transaction) => transaction,
+            Prepared::Postponed => return,
+            Prepared::Obsolete(reason) => {
+                log::warn!(id = scheduled.id, %reason, "Dropping scheduled transaction");
+                self.remove(scheduled.id);
+                return;
+            }
+        };
+
+        // The transaction will be included in the next block at the earliest.
+        let next_block_number = block_number + 1;
+        if !transaction.is_valid_at(next_block_number) {
+            if transaction.validity_start_height > next_block_number {
+                return;
+            }
+            log::warn!(
+                id = scheduled.id,
+                "Dropping scheduled transaction, its validity window expired"
+            );
+            self.remove(scheduled.id);
+            return;
+        }
+
+        let tx_hash: Blake2bHash = transaction.hash();
+        match submitter.send_transaction(transaction).await {
+            Ok(()) => {
+                log::info!(id = scheduled.id, %tx_hash, "Sent scheduled transaction");
+                self.remove(scheduled.id);
+            }
+            Err(error) => {
+                // Keep the transaction and retry with the next head.
+                log::warn!(
+                    id = scheduled.id,
+                    %tx_hash,
+                    %error,
+                    "Failed to send scheduled transaction"
+                );
+            }
+        }
+    }
+
+    fn prepare<S: ScheduledTransactionSubmitter>(
+        scheduled: &ScheduledTransaction,
+        block_number: u32,
+        submitter: &S,
+    ) -> Prepared {
+        match &scheduled.action {
+            ScheduledAction::Send(transaction) => Prepared::Ready(transaction.clone()),
+            ScheduledAction::RedeemHtlcTimeout {
+                wallet,
+                contract_address,
+                recipient,
+                fee,
+            } => {
+                let balance = match submitter.htlc_balance(contract_address) {
+                    Ok(Some(balance)) => balance,
+                    Ok(None) => return Prepared::Postponed,
+                    Err(reason) => return Prepared::Obsolete(reason),
+                };
+
+                let value = match balance.checked_sub(*fee) {
+                    Some(value) if !value.is_zero() => value,
+                    _ => {
+                        return Prepared::Obsolete(format!(
+                            "Balance of HTLC {} does not cover the fee",
+                            contract_address
+                        ))
+                    }
+                };
+
+                let Some(key_pair) = submitter.key_pair(wallet) else {
+                    log::debug!(
+                        id = scheduled.id,
+                        %wallet,
+                        "Wallet for scheduled HTLC redemption is locked"
+                    );
+                    return Prepared::Postponed;
+                };
+
+                match TransactionBuilder::new_redeem_htlc_timeout(
+                    &key_pair,
+                    contract_address.clone(),
+                    recipient.clone(),
+                    value,
+                    *fee,
+                    block_number,
+                    submitter.network_id(),
+                ) {
+                    Ok(transaction) => Prepared::Ready(transaction),
+                    Err(error) => Prepared::Obsolete(error.to_string()),
+                }
+            }
+        }
+    }
+}
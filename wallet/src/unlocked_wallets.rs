@@ -2,8 +2,10 @@ use std::collections::HashMap;
 
 use nimiq_keys::Address;
 use nimiq_utils::otp::Unlocked;
-use nimiq_wallet::WalletAccount;
 
+use crate::WalletAccount;
+
+/// The wallet accounts that are currently unlocked and can be used to sign transactions.
 #[derive(Default)]
 pub struct UnlockedWallets {
     pub unlocked_wallets: HashMap<Address, Unlocked<WalletAccount>>,
@@ -0,0 +1,131 @@
+use futures::{future::BoxFuture, stream, FutureExt};
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
+use nimiq_serde::Deserialize;
+use nimiq_test_log::test;
+use nimiq_transaction::Transaction;
+use nimiq_wallet::{
+    ScheduleTrigger, ScheduledAction, ScheduledTransactionSubmitter, TransactionScheduler,
+    WalletAccount,
+};
+use parking_lot::Mutex;
+
+fn wallet() -> WalletAccount {
+    let raw_private_key =
+        hex::decode("b410a7a583cbc13ef4f1cbddace30928bcb4f9c13722414bc4a2faaba3f4e187").unwrap();
+    let private_key = PrivateKey::deserialize_from_vec(&raw_private_key).unwrap();
+    WalletAccount::from(KeyPair::from(private_key))
+}
+
+fn transaction(validity_start_height: u32) -> Transaction {
+    wallet().create_transaction(
+        Address::from([1u8; Address::SIZE]),
+        Coin::from_u64_unchecked(42),
+        Coin::ZERO,
+        validity_start_height,
+        NetworkId::UnitAlbatross,
+    )
+}
+
+#[test]
+fn it_can_schedule_and_cancel_transactions() {
+    let env = VolatileDatabase::new(1).unwrap();
+    let scheduler = TransactionScheduler::new(env.clone());
+
+    let first = scheduler.schedule(
+        ScheduleTrigger::BlockHeight(100),
+        ScheduledAction::Send(transaction(100)),
+    );
+    let second = scheduler.schedule(
+        ScheduleTrigger::Timestamp(5000),
+        ScheduledAction::RedeemHtlcTimeout {
+            wallet: wallet().address,
+            contract_address: Address::from([2u8; Address::SIZE]),
+            recipient: wallet().address,
+            fee: Coin::from_u64_unchecked(1),
+        },
+    );
+    assert_eq!(first.id, 0);
+    assert_eq!(second.id, 1);
+    assert_eq!(scheduler.list(), vec![first.clone(), second.clone()]);
+
+    // Scheduled transactions survive re-opening the store.
+    let scheduler = TransactionScheduler::new(env);
+    assert_eq!(scheduler.get(1), Some(second.clone()));
+
+    assert!(scheduler.due(99, 4999).is_empty());
+    assert_eq!(scheduler.due(100, 4999), vec![first.clone()]);
+    assert_eq!(scheduler.due(99, 5000), vec![second.clone()]);
+
+    assert!(scheduler.remove(0));
+    assert!(!scheduler.remove(0));
+    assert_eq!(scheduler.due(100, 5000), vec![second]);
+
+    // Ids are not reused after the last scheduled transaction was removed.
+    assert!(scheduler.remove(1));
+    let third = scheduler.schedule(
+        ScheduleTrigger::BlockHeight(100),
+        ScheduledAction::Send(transaction(100)),
+    );
+    assert_eq!(third.id, 2);
+}
+
+#[derive(Default)]
+struct TestSubmitter {
+    sent: Mutex<Vec<Transaction>>,
+}
+
+impl ScheduledTransactionSubmitter for TestSubmitter {
+    fn network_id(&self) -> NetworkId {
+        NetworkId::UnitAlbatross
+    }
+
+    fn htlc_balance(&self, _address: &Address) -> Result<Option<Coin>, String> {
+        Ok(None)
+    }
+
+    fn key_pair(&self, _wallet: &Address) -> Option<KeyPair> {
+        None
+    }
+
+    fn send_transaction(&self, transaction: Transaction) -> BoxFuture<'_, Result<(), String>> {
+        self.sent.lock().push(transaction);
+        async { Ok(()) }.boxed()
+    }
+}
+
+#[test(tokio::test)]
+async fn it_only_sends_due_transactions_within_their_validity_window() {
+    let env = VolatileDatabase::new(1).unwrap();
+    let scheduler = TransactionScheduler::new(env);
+
+    let head = Policy::transaction_validity_window_blocks() + 10;
+
+    // Due, but only valid in the future.
+    let early = scheduler.schedule(
+        ScheduleTrigger::BlockHeight(10),
+        ScheduledAction::Send(transaction(head + 2 * Policy::blocks_per_batch())),
+    );
+    // Due and valid.
+    scheduler.schedule(
+        ScheduleTrigger::BlockHeight(10),
+        ScheduledAction::Send(transaction(head)),
+    );
+    // Due, but the validity window expired.
+    scheduler.schedule(
+        ScheduleTrigger::BlockHeight(10),
+        ScheduledAction::Send(transaction(1)),
+    );
+    // Not due yet.
+    let later = scheduler.schedule(
+        ScheduleTrigger::BlockHeight(head + 1),
+        ScheduledAction::Send(transaction(head)),
+    );
+
+    let submitter = TestSubmitter::default();
+    scheduler.run(stream::iter([(head, 0)]), &submitter).await;
+
+    assert_eq!(*submitter.sent.lock(), vec![transaction(head)]);
+    assert_eq!(scheduler.list(), vec![early, later]);
+}
@@ -19,6 +19,31 @@ pub struct PlainVestingContract {
     total_amount: u64,
 }
 
+#[derive(serde::Serialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainVestingTranche {
+    time_step: u64,
+    step_amount: u64,
+    amount: u64,
+}
+
+#[derive(serde::Serialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainVestingSchedule {
+    cliff_time: u64,
+    cliff_amount: u64,
+    tranches: Vec<PlainVestingTranche>,
+}
+
+#[derive(serde::Serialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainScheduledVestingContract {
+    balance: u64,
+    owner: String,
+    schedules: Vec<PlainVestingSchedule>,
+    total_amount: u64,
+}
+
 #[derive(serde::Serialize, Tsify)]
 #[serde(rename_all = "camelCase")]
 pub struct PlainHtlcContract {
@@ -48,6 +73,7 @@ pub enum PlainAccount {
     Vesting(PlainVestingContract),
     Htlc(PlainHtlcContract),
     Staking(PlainStakingContract),
+    ScheduledVesting(PlainScheduledVestingContract),
 }
 
 impl From<&nimiq_account::Account> for PlainAccount {
@@ -124,6 +150,30 @@ impl From<&nimiq_account::Account> for PlainAccount {
                     .map(|slot| slot as u16)
                     .collect(),
             }),
+            nimiq_account::Account::ScheduledVesting(acc) => {
+                PlainAccount::ScheduledVesting(PlainScheduledVestingContract {
+                    balance: acc.balance.into(),
+                    owner: acc.owner.to_user_friendly_address(),
+                    schedules: acc
+                        .schedules
+                        .iter()
+                        .map(|schedule| PlainVestingSchedule {
+                            cliff_time: schedule.cliff_time,
+                            cliff_amount: schedule.cliff_amount.into(),
+                            tranches: schedule
+                                .tranches
+                                .iter()
+                                .map(|tranche| PlainVestingTranche {
+                                    time_step: tranche.time_step,
+                                    step_amount: tranche.step_amount.into(),
+                                    amount: tranche.amount.into(),
+                                })
+                                .collect(),
+                        })
+                        .collect(),
+                    total_amount: acc.total_amount.into(),
+                })
+            }
         }
     }
 }
@@ -42,6 +42,7 @@ pub async fn it_can_initialize_with_mock_network() {
         bls_cache,
         zkp_component.proxy(),
         network_events,
+        None,
     )
     .await;
 